@@ -1,6 +1,3 @@
-use soroban_sdk::contracterror;
-
-#[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum EventRegistryError {
@@ -76,6 +73,175 @@ pub enum EventRegistryError {
     EventNotEnded = 47,
     /// User has exceeded the maximum number of tickets allowed for this tier
     PerUserLimitExceeded = 48,
+    // ── Revenue split errors ───────────────────────────────────────────
+    /// Revenue split is malformed (too many payees, zero/duplicate entries, or shares != 10000)
+    InvalidRevenueSplit = 49,
+    /// Revenue split can no longer be changed because ticket sales have started
+    RevenueSplitLocked = 50,
+    /// No upgrade has been scheduled for the given WASM hash
+    UpgradeNotScheduled = 51,
+    /// The scheduled upgrade's timelock has not elapsed yet
+    UpgradeTimelockActive = 52,
+    /// Storage is already at the current schema version
+    AlreadyMigrated = 53,
+    // ── Loyalty points errors ──────────────────────────────────────────
+    /// The event has not opted in to loyalty point redemption
+    PointsRedemptionDisabled = 54,
+    /// The guest's redeemable point balance is too low
+    InsufficientLoyaltyPoints = 55,
+    /// Point redemption config is malformed (non-positive point value or cap above 100%)
+    InvalidPointsRedemptionConfig = 56,
+    /// Loyalty tiers, decay settings or an organizer ladder are malformed
+    InvalidLoyaltyConfig = 57,
+    /// A slash proposal against the organizer's stake is still pending
+    SlashPending = 58,
+    /// The organizer still has active events or escrow that is not yet settled
+    OrganizerHasActiveEvents = 59,
+    /// No unbonded collateral has finished its cooldown
+    UnbondingNotReady = 60,
+    /// Execution delay exceeds the allowed maximum
+    InvalidExecutionDelay = 61,
+    /// Proposal reached its threshold but its execution delay has not passed
+    ProposalTimelocked = 62,
+    /// Proposal was cancelled or rejected
+    ProposalClosed = 63,
+    /// Admin has already rejected this proposal
+    AlreadyRejected = 64,
+    /// The TicketPayment contract refused a governance-approved change
+    TicketPaymentChangeFailed = 65,
+    /// Proposal has no community vote
+    CommunityVoteNotFound = 66,
+    /// Community vote is still open
+    CommunityVoteOpen = 67,
+    /// Community vote has ended
+    CommunityVoteClosed = 68,
+    /// Community vote missed its quorum or approval threshold
+    CommunityVoteFailed = 69,
+    /// Organizer had no stake at the vote snapshot
+    NoVotingWeight = 70,
+    /// Organizer has already voted on this proposal
+    AlreadyVoted = 71,
+    /// Voting period, quorum or approval threshold is invalid
+    InvalidCommunityVoteConfig = 72,
+    /// The TicketPayment contract could not compact the event's payments
+    PaymentArchiveFailed = 73,
+    /// TTL threshold must be non-zero and below an extension within the network maximum
+    InvalidTtlPolicy = 74,
+    /// A ticket cannot be moved to the tier it already holds
+    InvalidTierChange = 75,
+    /// Tickets can only be exchanged between events of the same organizer
+    ExchangeNotAllowed = 76,
+    /// The event does not offer the requested add-on
+    AddOnNotFound = 77,
+    /// Add-on price must be positive and stock cannot drop below items sold
+    InvalidAddOn = 78,
+    /// Not enough add-on stock is left for the requested quantity
+    AddOnSoldOut = 79,
+    /// Deposits need a free tier and a positive amount
+    InvalidDeposit = 80,
+    /// Installment deadlines must be in the future and increasing, on a paid tier
+    InvalidInstallmentPlan = 81,
+}
+
+impl From<EventRegistryError> for soroban_sdk::Error {
+    fn from(err: EventRegistryError) -> soroban_sdk::Error {
+        soroban_sdk::Error::from_contract_error(err as u32)
+    }
+}
+
+impl From<&EventRegistryError> for soroban_sdk::Error {
+    fn from(err: &EventRegistryError) -> soroban_sdk::Error {
+        soroban_sdk::Error::from_contract_error(*err as u32)
+    }
+}
+
+impl From<soroban_sdk::Error> for EventRegistryError {
+    fn from(err: soroban_sdk::Error) -> EventRegistryError {
+        match err.get_code() {
+            1 => EventRegistryError::EventAlreadyExists,
+            2 => EventRegistryError::EventNotFound,
+            3 => EventRegistryError::Unauthorized,
+            4 => EventRegistryError::InvalidAddress,
+            5 => EventRegistryError::InvalidFeePercent,
+            6 => EventRegistryError::EventInactive,
+            7 => EventRegistryError::NotInitialized,
+            8 => EventRegistryError::AlreadyInitialized,
+            9 => EventRegistryError::InvalidMetadataCid,
+            10 => EventRegistryError::MaxSupplyExceeded,
+            11 => EventRegistryError::SupplyOverflow,
+            12 => EventRegistryError::UnauthorizedCaller,
+            13 => EventRegistryError::TierLimitExceedsMaxSupply,
+            14 => EventRegistryError::TierNotFound,
+            15 => EventRegistryError::TierSupplyExceeded,
+            16 => EventRegistryError::SupplyUnderflow,
+            17 => EventRegistryError::InvalidQuantity,
+            18 => EventRegistryError::OrganizerBlacklisted,
+            19 => EventRegistryError::OrganizerNotBlacklisted,
+            20 => EventRegistryError::InvalidResaleCapBps,
+            21 => EventRegistryError::InvalidPromoBps,
+            22 => EventRegistryError::EventCancelled,
+            23 => EventRegistryError::EventAlreadyCancelled,
+            24 => EventRegistryError::InvalidGracePeriodEnd,
+            25 => EventRegistryError::EventIsActive,
+            26 => EventRegistryError::AlreadyStaked,
+            27 => EventRegistryError::NotStaked,
+            28 => EventRegistryError::InsufficientStakeAmount,
+            29 => EventRegistryError::InvalidStakeAmount,
+            30 => EventRegistryError::StakingNotConfigured,
+            31 => EventRegistryError::NoRewardsAvailable,
+            32 => EventRegistryError::InvalidRewardAmount,
+            33 => EventRegistryError::AdminAlreadyExists,
+            34 => EventRegistryError::AdminNotFound,
+            35 => EventRegistryError::CannotRemoveLastAdmin,
+            36 => EventRegistryError::InvalidThreshold,
+            37 => EventRegistryError::ProposalNotFound,
+            38 => EventRegistryError::ProposalAlreadyExecuted,
+            39 => EventRegistryError::ProposalExpired,
+            40 => EventRegistryError::InsufficientApprovals,
+            41 => EventRegistryError::InvalidMilestonePlan,
+            42 => EventRegistryError::RestockingFeeExceedsTicketPrice,
+            43 => EventRegistryError::InvalidTags,
+            44 => EventRegistryError::InvalidTargetDeadline,
+            45 => EventRegistryError::AlreadyApproved,
+            46 => EventRegistryError::InvalidFeeCalculation,
+            47 => EventRegistryError::EventNotEnded,
+            48 => EventRegistryError::PerUserLimitExceeded,
+            49 => EventRegistryError::InvalidRevenueSplit,
+            50 => EventRegistryError::RevenueSplitLocked,
+            51 => EventRegistryError::UpgradeNotScheduled,
+            52 => EventRegistryError::UpgradeTimelockActive,
+            53 => EventRegistryError::AlreadyMigrated,
+            54 => EventRegistryError::PointsRedemptionDisabled,
+            55 => EventRegistryError::InsufficientLoyaltyPoints,
+            56 => EventRegistryError::InvalidPointsRedemptionConfig,
+            57 => EventRegistryError::InvalidLoyaltyConfig,
+            58 => EventRegistryError::SlashPending,
+            59 => EventRegistryError::OrganizerHasActiveEvents,
+            60 => EventRegistryError::UnbondingNotReady,
+            61 => EventRegistryError::InvalidExecutionDelay,
+            62 => EventRegistryError::ProposalTimelocked,
+            63 => EventRegistryError::ProposalClosed,
+            64 => EventRegistryError::AlreadyRejected,
+            65 => EventRegistryError::TicketPaymentChangeFailed,
+            66 => EventRegistryError::CommunityVoteNotFound,
+            67 => EventRegistryError::CommunityVoteOpen,
+            68 => EventRegistryError::CommunityVoteClosed,
+            69 => EventRegistryError::CommunityVoteFailed,
+            70 => EventRegistryError::NoVotingWeight,
+            71 => EventRegistryError::AlreadyVoted,
+            72 => EventRegistryError::InvalidCommunityVoteConfig,
+            73 => EventRegistryError::PaymentArchiveFailed,
+            74 => EventRegistryError::InvalidTtlPolicy,
+            75 => EventRegistryError::InvalidTierChange,
+            76 => EventRegistryError::ExchangeNotAllowed,
+            77 => EventRegistryError::AddOnNotFound,
+            78 => EventRegistryError::InvalidAddOn,
+            79 => EventRegistryError::AddOnSoldOut,
+            80 => EventRegistryError::InvalidDeposit,
+            81 => EventRegistryError::InvalidInstallmentPlan,
+            _ => EventRegistryError::InvalidFeeCalculation,
+        }
+    }
 }
 
 impl core::fmt::Display for EventRegistryError {
@@ -232,6 +398,111 @@ impl core::fmt::Display for EventRegistryError {
                     "User has exceeded the maximum number of tickets allowed for this tier"
                 )
             }
+            EventRegistryError::InvalidRevenueSplit => {
+                write!(
+                    f,
+                    "Revenue split is invalid: max 10 unique payees with shares summing to 10000 bps"
+                )
+            }
+            EventRegistryError::RevenueSplitLocked => {
+                write!(f, "Revenue split is locked once ticket sales have started")
+            }
+            EventRegistryError::UpgradeNotScheduled => {
+                write!(f, "No upgrade is scheduled for this WASM hash")
+            }
+            EventRegistryError::UpgradeTimelockActive => {
+                write!(f, "The upgrade timelock has not elapsed yet")
+            }
+            EventRegistryError::AlreadyMigrated => {
+                write!(f, "Storage is already at the current schema version")
+            }
+            EventRegistryError::PointsRedemptionDisabled => {
+                write!(f, "Loyalty point redemption is not enabled for this event")
+            }
+            EventRegistryError::InsufficientLoyaltyPoints => {
+                write!(f, "Insufficient redeemable loyalty points")
+            }
+            EventRegistryError::InvalidPointsRedemptionConfig => {
+                write!(f, "Invalid loyalty point redemption configuration")
+            }
+            EventRegistryError::InvalidLoyaltyConfig => {
+                write!(
+                    f,
+                    "Invalid loyalty tiers, decay settings or organizer ladder"
+                )
+            }
+            EventRegistryError::SlashPending => {
+                write!(f, "A slash proposal against this stake is pending")
+            }
+            EventRegistryError::OrganizerHasActiveEvents => {
+                write!(f, "Organizer has active or unsettled events")
+            }
+            EventRegistryError::UnbondingNotReady => {
+                write!(f, "No unbonded collateral is ready to withdraw")
+            }
+            EventRegistryError::InvalidExecutionDelay => {
+                write!(f, "Execution delay exceeds the allowed maximum")
+            }
+            EventRegistryError::ProposalTimelocked => {
+                write!(f, "Proposal execution delay has not passed")
+            }
+            EventRegistryError::ProposalClosed => {
+                write!(f, "Proposal was cancelled or rejected")
+            }
+            EventRegistryError::AlreadyRejected => {
+                write!(f, "Admin has already rejected this proposal")
+            }
+            EventRegistryError::TicketPaymentChangeFailed => {
+                write!(f, "TicketPayment contract rejected the change")
+            }
+            EventRegistryError::CommunityVoteNotFound => {
+                write!(f, "The proposal has no community vote")
+            }
+            EventRegistryError::CommunityVoteOpen => {
+                write!(f, "Community vote has not ended yet")
+            }
+            EventRegistryError::CommunityVoteClosed => {
+                write!(f, "Community vote has ended")
+            }
+            EventRegistryError::CommunityVoteFailed => {
+                write!(f, "Community vote missed its quorum or approval threshold")
+            }
+            EventRegistryError::NoVotingWeight => {
+                write!(f, "Organizer had no stake at the vote snapshot")
+            }
+            EventRegistryError::AlreadyVoted => {
+                write!(f, "Organizer has already voted on this proposal")
+            }
+            EventRegistryError::InvalidCommunityVoteConfig => {
+                write!(f, "Invalid voting period, quorum or approval threshold")
+            }
+            EventRegistryError::PaymentArchiveFailed => {
+                write!(f, "Event payments could not be archived")
+            }
+            EventRegistryError::InvalidTtlPolicy => {
+                write!(f, "Invalid TTL threshold or extension")
+            }
+            EventRegistryError::InvalidTierChange => {
+                write!(f, "Ticket is already in the requested tier")
+            }
+            EventRegistryError::ExchangeNotAllowed => {
+                write!(f, "Events do not share an organizer")
+            }
+            EventRegistryError::AddOnNotFound => {
+                write!(f, "Add-on not found for this event")
+            }
+            EventRegistryError::InvalidAddOn => {
+                write!(f, "Invalid add-on price or stock")
+            }
+            EventRegistryError::AddOnSoldOut => {
+                write!(f, "Add-on is sold out")
+            }
+            EventRegistryError::InvalidDeposit => {
+                write!(f, "Deposits need a free tier and a positive amount")
+            }
+            EventRegistryError::InvalidInstallmentPlan => {
+                write!(f, "Invalid installment deadlines or penalty")
+            }
         }
     }
 }
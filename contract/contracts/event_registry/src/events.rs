@@ -1,4 +1,5 @@
-use soroban_sdk::{contracttype, Address, String};
+use crate::types::{LoyaltyRung, LoyaltyTier, ParameterChange, Payee};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 /// Enum of all contract event types emitted by the Event Registry.
 ///
@@ -59,6 +60,61 @@ pub enum AgoraEvent {
     FeedbackCidSet,
     /// An event's token whitelist has been updated (token added or removed).
     TokenWhitelistUpdated,
+    /// An event's revenue split between payees has been configured or cleared.
+    RevenueSplitUpdated,
+    /// A governance-approved upgrade has been scheduled behind the timelock.
+    UpgradeScheduled,
+    /// A scheduled upgrade has been cancelled by the admin.
+    UpgradeCancelled,
+    /// Stored records have been migrated to a new schema version.
+    ContractMigrated,
+    /// Loyalty points and spend have been taken back after a refund.
+    LoyaltyScoreReversed,
+    /// A guest has spent redeemable loyalty points on a ticket purchase.
+    LoyaltyPointsRedeemed,
+    /// An organizer has changed an event's loyalty point redemption settings.
+    PointsRedemptionConfigured,
+    /// Governance has replaced the platform loyalty tiers or decay settings.
+    LoyaltyConfigUpdated,
+    /// An organizer has set or cleared their own loyalty ladder.
+    OrganizerLoyaltyProgramUpdated,
+    /// A guest's attendance has been counted towards an organizer's ladder.
+    AttendanceRecorded,
+    StakeSlashed,
+    ProtectionPoolDrawn,
+    UnbondingStarted,
+    StakeVerificationUpdated,
+    ProposalCreated,
+    ProposalApproved,
+    ProposalRejected,
+    ProposalQueued,
+    ProposalExecuted,
+    ProposalCancelled,
+    CommunityVoteCast,
+    /// An organizer has set, rotated or cleared an event or tier allowlist.
+    AllowlistUpdated,
+    /// An organizer has set or cleared a tier's attestation requirement.
+    TierAttestationUpdated,
+    /// A ticket has been moved from one tier to another by an upgrade or downgrade.
+    InventoryMoved,
+}
+
+/// Event topics added after `AgoraEvent` reached Soroban's enum variant limit.
+///
+/// Used the same way, as the first element of the event topic tuple.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AgoraEventExt {
+    /// A ticket has been exchanged for one at another event of the same organizer.
+    InventoryExchanged,
+    /// An organizer has switched a tier between fixed and pay-what-you-want pricing.
+    PayWhatYouWantUpdated,
+    /// An organizer has created or updated an add-on sold alongside tickets.
+    AddOnUpdated,
+    /// An organizer has set or cleared the RSVP deposit of a free tier.
+    TierDepositUpdated,
+    /// An organizer has set or cleared the installment plan of a tier.
+    InstallmentPlanUpdated,
 }
 
 /// Emitted when an event is permanently cancelled.
@@ -86,6 +142,10 @@ pub struct EventArchivedEvent {
     pub event_id: String,
     /// The address of the event organizer.
     pub organizer_address: Address,
+    /// Merkle root over the event's payments, if TicketPayment is linked.
+    pub payments_root: Option<BytesN<32>>,
+    /// Number of payments committed to by the root.
+    pub payment_count: u32,
     /// The ledger timestamp when the archival occurred.
     pub timestamp: u64,
 }
@@ -289,7 +349,7 @@ pub struct EventPostponedEvent {
 
 /// Emitted when a governance proposal is created by an admin.
 ///
-/// Published with topic `(AgoraEvent::ProposalCreated,)` (used internally).
+/// Published with topic `(AgoraEvent::ProposalCreated,)`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalCreatedEvent {
@@ -297,13 +357,17 @@ pub struct ProposalCreatedEvent {
     pub proposal_id: u64,
     /// The admin address that created the proposal.
     pub proposer: Address,
+    /// The proposed change.
+    pub change: ParameterChange,
+    /// Timestamp after which the proposal can no longer be executed.
+    pub expires_at: u64,
     /// The ledger timestamp when the proposal was created.
     pub timestamp: u64,
 }
 
 /// Emitted when an admin approves a governance proposal.
 ///
-/// Published with topic `(AgoraEvent::ProposalApproved,)` (used internally).
+/// Published with topic `(AgoraEvent::ProposalApproved,)`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalApprovedEvent {
@@ -311,13 +375,15 @@ pub struct ProposalApprovedEvent {
     pub proposal_id: u64,
     /// The admin address that approved the proposal.
     pub approver: Address,
+    /// Number of approvals after this one.
+    pub approvals: u32,
     /// The ledger timestamp when the approval occurred.
     pub timestamp: u64,
 }
 
 /// Emitted when a governance proposal is executed after meeting the approval threshold.
 ///
-/// Published with topic `(AgoraEvent::ProposalExecuted,)` (used internally).
+/// Published with topic `(AgoraEvent::ProposalExecuted,)`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalExecutedEvent {
@@ -421,10 +487,47 @@ pub struct CollateralStakedEvent {
     pub timestamp: u64,
 }
 
-/// Emitted when an organizer withdraws their staked collateral tokens.
+/// Emitted when an organizer moves part of their stake into the unbonding queue.
 ///
-/// After unstaking, the organizer loses Verified status and is removed from
-/// the stakers list. Published with topic `(AgoraEvent::CollateralUnstaked,)`.
+/// Published with topic `(AgoraEvent::UnbondingStarted,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnbondingStartedEvent {
+    /// The organizer who requested the unstake.
+    pub organizer: Address,
+    /// The token contract address of the stake.
+    pub token: Address,
+    /// The amount moved into the unbonding queue (in stroops).
+    pub amount: i128,
+    /// The organizer's stake left after the request.
+    pub remaining_stake: i128,
+    /// Earliest timestamp at which the amount can be withdrawn.
+    pub release_at: u64,
+    /// The ledger timestamp of the request.
+    pub timestamp: u64,
+}
+
+/// Emitted when an organizer gains or loses Verified status because the
+/// minimum stake changed.
+///
+/// Published with topic `(AgoraEvent::StakeVerificationUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeVerificationUpdatedEvent {
+    /// The organizer whose status changed.
+    pub organizer: Address,
+    /// The organizer's current stake.
+    pub amount: i128,
+    /// The new minimum stake.
+    pub min_stake_amount: i128,
+    /// Whether the organizer now holds Verified status.
+    pub is_verified: bool,
+}
+
+/// Emitted when an organizer withdraws unbonded collateral tokens.
+///
+/// Only amounts whose unbonding period has elapsed are paid out. Published
+/// with topic `(AgoraEvent::CollateralUnstaked,)`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CollateralUnstakedEvent {
@@ -434,7 +537,49 @@ pub struct CollateralUnstakedEvent {
     pub token: Address,
     /// The amount of tokens returned to the organizer (in stroops).
     pub amount: i128,
-    /// The ledger timestamp when the unstake occurred.
+    /// The ledger timestamp when the withdrawal occurred.
+    pub timestamp: u64,
+}
+
+/// Emitted when a governance proposal slashes an organizer's stake.
+///
+/// The slashed tokens move into the guest protection pool. Published with
+/// topic `(AgoraEvent::StakeSlashed,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeSlashedEvent {
+    /// The executed slash proposal.
+    pub proposal_id: u64,
+    /// The organizer whose stake was slashed.
+    pub organizer: Address,
+    /// The token contract address of the slashed stake.
+    pub token: Address,
+    /// The amount moved into the protection pool (in stroops).
+    pub amount: i128,
+    /// The organizer's stake left after the slash.
+    pub remaining_stake: i128,
+    /// Whether the organizer still holds Verified status.
+    pub is_verified: bool,
+    /// The ledger timestamp when the slash was executed.
+    pub timestamp: u64,
+}
+
+/// Emitted when the protection pool covers part of a refund the event's
+/// escrow could not pay.
+///
+/// Published with topic `(AgoraEvent::ProtectionPoolDrawn,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtectionPoolDrawnEvent {
+    /// The contract that received the funds.
+    pub recipient: Address,
+    /// The token drawn from the pool.
+    pub token: Address,
+    /// The amount paid out of the pool (in stroops).
+    pub amount: i128,
+    /// The pool balance left for this token.
+    pub remaining_pool: i128,
+    /// The ledger timestamp when the pool was drawn.
     pub timestamp: u64,
 }
 
@@ -478,6 +623,10 @@ pub struct LoyaltyScoreUpdatedEvent {
     pub guest: Address,
     /// The guest's new cumulative loyalty score.
     pub new_score: u64,
+    /// The points awarded by this purchase.
+    pub points_earned: u64,
+    /// The guest's spendable point balance after the award.
+    pub redeemable_points: u64,
     /// The number of tickets purchased in this transaction.
     pub tickets_purchased: u32,
     /// The ledger timestamp when the score was updated.
@@ -542,3 +691,374 @@ pub struct TokenWhitelistUpdatedEvent {
     /// The ledger timestamp when the whitelist was updated.
     pub timestamp: u64,
 }
+
+/// Emitted when an organizer configures or clears the revenue split for an event.
+///
+/// Published with topic `(AgoraEvent::RevenueSplitUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueSplitUpdatedEvent {
+    /// The unique identifier of the event.
+    pub event_id: String,
+    /// The new list of payees and their basis-point shares (empty = cleared).
+    pub payees: Vec<Payee>,
+    /// The organizer address that performed the update.
+    pub updated_by: Address,
+    /// The ledger timestamp when the split was updated.
+    pub timestamp: u64,
+}
+
+/// Emitted when a governance proposal schedules a contract upgrade.
+///
+/// Published with topic `(AgoraEvent::UpgradeScheduled,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeScheduledEvent {
+    /// The proposal that approved the upgrade.
+    pub proposal_id: u64,
+    /// Hash of the WASM that will be installed.
+    pub wasm_hash: BytesN<32>,
+    /// Earliest timestamp at which the upgrade can be applied.
+    pub eta: u64,
+}
+
+/// Emitted when the admin cancels a scheduled upgrade.
+///
+/// Published with topic `(AgoraEvent::UpgradeCancelled,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeCancelledEvent {
+    /// Hash of the WASM that will no longer be installed.
+    pub wasm_hash: BytesN<32>,
+    /// The ledger timestamp when the upgrade was cancelled.
+    pub timestamp: u64,
+}
+
+/// Emitted when `migrate` advances the stored schema version.
+///
+/// Published with topic `(AgoraEvent::ContractMigrated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractMigratedEvent {
+    /// Schema version before the migration.
+    pub from_version: u32,
+    /// Schema version after the migration.
+    pub to_version: u32,
+    /// Number of records rewritten to the new layout.
+    pub records_migrated: u32,
+    /// The ledger timestamp when the migration ran.
+    pub timestamp: u64,
+}
+
+/// Emitted when a refund takes back the loyalty points and spend a purchase earned.
+///
+/// Published with topic `(AgoraEvent::LoyaltyScoreReversed,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyScoreReversedEvent {
+    /// The wallet address of the refunded guest.
+    pub guest: Address,
+    /// The points removed from the loyalty score.
+    pub points_removed: u64,
+    /// The guest's loyalty score after the reversal.
+    pub new_score: u64,
+    /// The guest's spendable point balance after the reversal.
+    pub redeemable_points: u64,
+    /// The refunded amount removed from the guest's total spend.
+    pub amount_refunded: i128,
+    /// The ledger timestamp when the reversal occurred.
+    pub timestamp: u64,
+}
+
+/// Emitted when a guest spends redeemable points on a ticket purchase.
+///
+/// Published with topic `(AgoraEvent::LoyaltyPointsRedeemed,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyPointsRedeemedEvent {
+    /// The wallet address of the guest.
+    pub guest: Address,
+    /// The event the points were spent on.
+    pub event_id: String,
+    /// The number of points spent.
+    pub points: u64,
+    /// The guest's spendable point balance after the redemption.
+    pub remaining_points: u64,
+    /// The ledger timestamp when the redemption occurred.
+    pub timestamp: u64,
+}
+
+/// Emitted when an organizer changes an event's point redemption settings.
+///
+/// Published with topic `(AgoraEvent::PointsRedemptionConfigured,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointsRedemptionConfiguredEvent {
+    /// The event whose settings changed.
+    pub event_id: String,
+    /// Whether redemption is enabled.
+    pub enabled: bool,
+    /// Price reduction per point (in token stroops).
+    pub point_value: i128,
+    /// Maximum share of the ticket total points may cover (in basis points).
+    pub max_discount_bps: u32,
+    /// The ledger timestamp when the settings changed.
+    pub timestamp: u64,
+}
+
+/// Emitted when an executed proposal replaces the platform loyalty settings.
+///
+/// Published with topic `(AgoraEvent::LoyaltyConfigUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyConfigUpdatedEvent {
+    /// The proposal that applied the change.
+    pub proposal_id: u64,
+    /// Length of one decay period in seconds (0 = no decay).
+    pub decay_period_secs: u64,
+    /// Share of the score lost per decay period (in basis points).
+    pub decay_bps: u32,
+    /// The new discount tiers.
+    pub tiers: Vec<LoyaltyTier>,
+}
+
+/// Emitted when an organizer sets or clears their loyalty ladder.
+///
+/// Published with topic `(AgoraEvent::OrganizerLoyaltyProgramUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrganizerLoyaltyProgramUpdatedEvent {
+    /// The organizer running the ladder.
+    pub organizer: Address,
+    /// The new rungs (empty when the program was cleared).
+    pub rungs: Vec<LoyaltyRung>,
+    /// The ledger timestamp when the ladder changed.
+    pub timestamp: u64,
+}
+
+/// Emitted when a guest's attendance is counted towards an organizer's ladder.
+///
+/// Published with topic `(AgoraEvent::AttendanceRecorded,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttendanceRecordedEvent {
+    /// The attending guest.
+    pub guest: Address,
+    /// The event that was attended.
+    pub event_id: String,
+    /// The organizer of the event.
+    pub organizer: Address,
+    /// Number of this organizer's events the guest has now attended.
+    pub events_attended: u32,
+    /// The ledger timestamp when attendance was recorded.
+    pub timestamp: u64,
+}
+
+/// Emitted when an admin votes against a proposal.
+///
+/// Published with topic `(AgoraEvent::ProposalRejected,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalRejectedEvent {
+    /// The rejected proposal.
+    pub proposal_id: u64,
+    /// The rejecting admin.
+    pub admin: Address,
+    /// Number of rejections after this one.
+    pub rejections: u32,
+    /// Whether the threshold can no longer be reached, closing the proposal.
+    pub defeated: bool,
+}
+
+/// Emitted when a proposal reaches its approval threshold and starts its
+/// execution delay.
+///
+/// Published with topic `(AgoraEvent::ProposalQueued,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalQueuedEvent {
+    /// The queued proposal.
+    pub proposal_id: u64,
+    /// Earliest timestamp at which it can be executed.
+    pub executable_at: u64,
+}
+
+/// Emitted when a proposal is withdrawn before execution.
+///
+/// Published with topic `(AgoraEvent::ProposalCancelled,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCancelledEvent {
+    /// The cancelled proposal.
+    pub proposal_id: u64,
+    /// The proposer or admin who cancelled it.
+    pub cancelled_by: Address,
+    /// The ledger timestamp of the cancellation.
+    pub timestamp: u64,
+}
+
+/// Emitted when a staked organizer votes on a proposal's community vote.
+///
+/// Published with topic `(AgoraEvent::CommunityVoteCast,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommunityVoteCastEvent {
+    /// The proposal voted on.
+    pub proposal_id: u64,
+    /// The voting organizer.
+    pub voter: Address,
+    /// Whether the vote supports the proposal.
+    pub support: bool,
+    /// Stake weight of the vote at the snapshot.
+    pub weight: i128,
+    /// Total weight in favour after this vote.
+    pub votes_for: i128,
+    /// Total weight against after this vote.
+    pub votes_against: i128,
+}
+
+/// Emitted when an organizer sets, rotates or clears an allowlist.
+///
+/// Published with topic `(AgoraEvent::AllowlistUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowlistUpdatedEvent {
+    /// The event whose allowlist changed.
+    pub event_id: String,
+    /// The tier the allowlist applies to, or None for the event-wide root.
+    pub tier_id: Option<String>,
+    /// The new root, or None if the allowlist was cleared.
+    pub root: Option<BytesN<32>>,
+    /// Whether the tier requires a proof even when the event is public.
+    pub hidden: bool,
+    /// The ledger timestamp of the change.
+    pub timestamp: u64,
+}
+
+/// Emitted when an organizer sets or clears a tier's attestation requirement.
+///
+/// Published with topic `(AgoraEvent::TierAttestationUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierAttestationUpdatedEvent {
+    /// The event whose tier changed.
+    pub event_id: String,
+    /// The tier the requirement applies to.
+    pub tier_id: String,
+    /// The attester contract, or None if the requirement was cleared.
+    pub attester: Option<Address>,
+    /// The required claim, or None if the requirement was cleared.
+    pub claim: Option<String>,
+    /// The ledger timestamp of the change.
+    pub timestamp: u64,
+}
+
+/// Emitted when a ticket is moved between tiers of the same event.
+///
+/// Published with topic `(AgoraEvent::InventoryMoved,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InventoryMovedEvent {
+    /// The event whose inventory changed.
+    pub event_id: String,
+    /// The tier the ticket left.
+    pub from_tier_id: String,
+    /// The tier the ticket joined.
+    pub to_tier_id: String,
+    /// The ticket holder.
+    pub user: Address,
+    /// The ledger timestamp of the move.
+    pub timestamp: u64,
+}
+
+/// Emitted when a ticket is exchanged between events of the same organizer.
+///
+/// Published with topic `(AgoraEventExt::InventoryExchanged,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InventoryExchangedEvent {
+    /// The event the ticket left.
+    pub from_event_id: String,
+    /// The tier the ticket left.
+    pub from_tier_id: String,
+    /// The event the ticket joined.
+    pub to_event_id: String,
+    /// The tier the ticket joined.
+    pub to_tier_id: String,
+    /// The ticket holder.
+    pub user: Address,
+    /// The ledger timestamp of the exchange.
+    pub timestamp: u64,
+}
+
+/// Emitted when an organizer switches a tier between fixed and pay-what-you-want pricing.
+///
+/// Published with topic `(AgoraEventExt::PayWhatYouWantUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayWhatYouWantUpdatedEvent {
+    /// The event whose tier changed.
+    pub event_id: String,
+    /// The tier whose pricing changed.
+    pub tier_id: String,
+    /// Whether the tier's price is now a floor.
+    pub enabled: bool,
+    /// The ledger timestamp of the change.
+    pub timestamp: u64,
+}
+
+/// Emitted when an organizer creates or updates an add-on.
+///
+/// Published with topic `(AgoraEventExt::AddOnUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddOnUpdatedEvent {
+    /// The event offering the add-on.
+    pub event_id: String,
+    /// The add-on that changed.
+    pub addon_id: String,
+    /// Price of one item.
+    pub price: i128,
+    /// Items available in total (0 = unlimited).
+    pub stock: u32,
+    /// Tier the add-on is restricted to, if any.
+    pub tier_id: Option<String>,
+    /// The ledger timestamp of the change.
+    pub timestamp: u64,
+}
+
+/// Emitted when an organizer sets or clears the RSVP deposit of a free tier.
+///
+/// Published with topic `(AgoraEventExt::TierDepositUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierDepositUpdatedEvent {
+    /// The event whose tier changed.
+    pub event_id: String,
+    /// The tier whose deposit changed.
+    pub tier_id: String,
+    /// Deposit per ticket (0 = cleared).
+    pub amount: i128,
+    /// Recipient of forfeited deposits (`None` = the organizer).
+    pub forfeit_recipient: Option<Address>,
+    /// The ledger timestamp of the change.
+    pub timestamp: u64,
+}
+
+/// Emitted when an organizer sets or clears the installment plan of a tier.
+///
+/// Published with topic `(AgoraEventExt::InstallmentPlanUpdated,)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentPlanUpdatedEvent {
+    /// The event whose tier changed.
+    pub event_id: String,
+    /// The tier whose plan changed.
+    pub tier_id: String,
+    /// Number of installments (0 = plan cleared).
+    pub installments: u32,
+    /// Share of the amount paid kept on forfeiture, in basis points.
+    pub penalty_bps: u32,
+    /// The ledger timestamp of the change.
+    pub timestamp: u64,
+}
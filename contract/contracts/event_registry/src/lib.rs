@@ -10,7 +10,7 @@
 //!
 //! ## Architecture
 //!
-//! The crate is organised into five focused modules:
+//! The crate is organised into six focused modules:
 //!
 //! | Module | Responsibility |
 //! |--------|---------------|
@@ -19,6 +19,7 @@
 //! | [`storage`] | Thin wrappers around `env.storage()` – one function per data key |
 //! | [`events`] | Soroban event structs and the [`AgoraEvent`](crate::events::AgoraEvent) topic enum |
 //! | [`error`] | The [`EventRegistryError`](crate::error::EventRegistryError) enum returned by every fallible function |
+//! | [`migration`] | Legacy record layouts and the steps that rewrite them after an upgrade |
 //!
 //! ### Key data types
 //!
@@ -86,24 +87,38 @@
 #![no_std]
 
 use crate::events::{
-    AdminUpdatedEvent, AgoraEvent, CollateralStakedEvent, CollateralUnstakedEvent,
+    AdminUpdatedEvent, AgoraEvent, AllowlistUpdatedEvent, AttendanceRecordedEvent,
+    CollateralStakedEvent, CollateralUnstakedEvent, CommunityVoteCastEvent, ContractMigratedEvent,
     CustomFeeSetEvent, EventArchivedEvent, EventCancelledEvent, EventPostponedEvent,
     EventRegisteredEvent, EventStatusUpdatedEvent, EventsSuspendedEvent, FeeUpdatedEvent,
     FeedbackCidSetEvent, GlobalPromoUpdatedEvent, GoalMetEvent, InitializationEvent,
-    InventoryIncrementedEvent, LoyaltyScoreUpdatedEvent, MetadataUpdatedEvent,
-    OrganizerBlacklistedEvent, OrganizerRemovedFromBlacklistEvent, RegistryUpgradedEvent,
-    ScannerAuthorizedEvent, StakerRewardsClaimedEvent, StakerRewardsDistributedEvent,
-    TokenWhitelistUpdatedEvent,
+    InventoryIncrementedEvent, LoyaltyConfigUpdatedEvent, LoyaltyPointsRedeemedEvent,
+    LoyaltyScoreReversedEvent, LoyaltyScoreUpdatedEvent, MetadataUpdatedEvent,
+    OrganizerBlacklistedEvent, OrganizerLoyaltyProgramUpdatedEvent,
+    OrganizerRemovedFromBlacklistEvent, PointsRedemptionConfiguredEvent, ProposalApprovedEvent,
+    ProposalCancelledEvent, ProposalCreatedEvent, ProposalExecutedEvent, ProposalQueuedEvent,
+    ProposalRejectedEvent, ProtectionPoolDrawnEvent, RegistryUpgradedEvent,
+    RevenueSplitUpdatedEvent, ScannerAuthorizedEvent, StakeSlashedEvent,
+    StakeVerificationUpdatedEvent, StakerRewardsClaimedEvent, StakerRewardsDistributedEvent,
+    TierAttestationUpdatedEvent, TokenWhitelistUpdatedEvent, UnbondingStartedEvent,
+    UpgradeCancelledEvent, UpgradeScheduledEvent,
 };
 use crate::types::{
-    BlacklistAuditEntry, EventInfo, EventReceipt, EventRegistrationArgs, EventStatus, GuestProfile,
-    MultiSigConfig, OrganizerStake, PaymentInfo,
+    AddOn, AllowlistGate, BlacklistAuditEntry, CommunityVote, CommunityVoteConfig, EventInfo,
+    EventReceipt, EventRegistrationArgs, EventStatus, GuestProfile, InstallmentPlan, LoyaltyConfig,
+    LoyaltyRung, LoyaltyTier, MultiSigConfig, OrganizerStake, Payee, PaymentInfo,
+    PointsRedemptionConfig, ProposalRecord, TierAllowlist, TierAttestation, TierDeposit, TtlFamily,
+    TtlPolicy, UnbondingEntry,
+};
+use soroban_sdk::{
+    contract, contractimpl, token, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec,
 };
-use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, String, Vec};
 
 pub mod error;
 pub mod events;
+pub mod migration;
 pub mod storage;
+pub mod ttl;
 pub mod types;
 
 use crate::types::{SeriesPass, SeriesRegistry};
@@ -112,7 +127,115 @@ use crate::error::EventRegistryError;
 
 const MIN_METADATA_CID_LEN: u32 = 46;
 const MAX_METADATA_CID_LEN: u32 = 100;
+const MAX_PAYEES: u32 = 10;
+const MAX_LOYALTY_TIERS: u32 = 10;
+/// Decay periods applied in one pass; a score idle for longer is reset to zero.
+const MAX_DECAY_PERIODS: u64 = 256;
 pub const VERSION: u32 = 1;
+/// Delay between a governance-approved upgrade and the earliest time it can be applied.
+pub const UPGRADE_TIMELOCK_SECS: u64 = 172_800;
+/// Longest execution delay governance may configure (30 days).
+const MAX_EXECUTION_DELAY_SECS: u64 = 2_592_000;
+/// Time a queued proposal stays executable after its delay has passed.
+const PROPOSAL_EXECUTION_WINDOW_SECS: u64 = 86_400;
+/// Maximum number of records returned by `get_proposals`.
+const MAX_PROPOSAL_PAGE: u32 = 50;
+/// Fixed-point scale of the staker reward-per-share index.
+pub const REWARD_PRECISION: i128 = 1_000_000_000_000;
+
+// TicketPayment interface, used to check whether an organizer's escrow is settled
+// and to apply governance-approved payment parameter changes
+pub mod ticket_payment {
+    use soroban_sdk::{contractclient, Address, BytesN, Env, String, Vec};
+
+    /// Treasury wallet mirrored from the ticket_payment contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct TreasuryWallet {
+        pub address: Address,
+        pub share_bps: u32,
+    }
+
+    /// Withdrawal rate limit settings mirrored from the ticket_payment contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct WithdrawalLimitConfig {
+        pub window_secs: u64,
+        pub queue_delay_secs: u64,
+    }
+
+    /// Organizer withdrawal cap policy mirrored from the ticket_payment contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct OrganizerCapPolicy {
+        pub default_cap: i128,
+        pub verified_cap: i128,
+    }
+
+    /// Mirror of TicketPayment's `ParameterChange`; its parameters are governed by
+    /// this contract's multisig proposals.
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum PaymentParameterChange {
+        AddTokenToWhitelist(Address),
+        RemoveTokenFromWhitelist(Address),
+        UpdateWithdrawalCap(Address, i128),
+        UpdateSlippage(u32),
+        UpdateTransferFee(String, u32),
+        UpgradeContract(BytesN<32>),
+        SetTreasuryWallets(Vec<TreasuryWallet>),
+        SetWithdrawalLimitConfig(WithdrawalLimitConfig),
+        SetOrganizerCapPolicy(Address, OrganizerCapPolicy),
+        SetOrganizerWithdrawalCap(Address, Address, Option<i128>),
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct EventBalance {
+        pub organizer_amount: i128,
+        pub total_withdrawn: i128,
+        pub platform_fee: i128,
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum PaymentStatus {
+        Pending,
+        Confirmed,
+        Refunded,
+        Failed,
+        CheckedIn,
+        PartiallyPaid,
+    }
+
+    /// Mirror of TicketPayment's archived payment leaf; it must encode to the same
+    /// XDR for its hash to match the committed leaf.
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct ArchivedPayment {
+        pub payment_id: String,
+        pub buyer_address: Address,
+        pub ticket_tier_id: String,
+        pub amount: i128,
+        pub refunded_amount: i128,
+        pub status: PaymentStatus,
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct PaymentsCommitment {
+        pub root: BytesN<32>,
+        pub payment_count: u32,
+    }
+
+    #[contractclient(name = "Client")]
+    pub trait TicketPaymentInterface {
+        fn get_event_escrow_balance(env: Env, event_id: String) -> EventBalance;
+        fn apply_governance_change(env: Env, proposal_id: u64, change: PaymentParameterChange);
+        fn archive_event_payments(env: Env, event_id: String) -> PaymentsCommitment;
+        fn bump_event(env: Env, event_id: String);
+    }
+}
 
 #[contract]
 pub struct EventRegistry;
@@ -254,6 +377,7 @@ impl EventRegistry {
         storage::set_platform_fee(&env, initial_fee);
         // Automatically whitelist the USDC token provided at initialization
         storage::add_to_token_whitelist(&env, &usdc_token);
+        storage::set_schema_version(&env, migration::SCHEMA_VERSION);
         storage::set_initialized(&env, true);
 
         env.events().publish(
@@ -412,6 +536,7 @@ impl EventRegistry {
             accepted_tokens: args.accepted_tokens,
             use_global_whitelist: args.use_global_whitelist,
             feedback_cid: None,
+            payees: Vec::new(&env),
         };
 
         storage::store_event(&env, event_info);
@@ -535,6 +660,19 @@ impl EventRegistry {
                     return Err(EventRegistryError::EventIsActive);
                 }
 
+                // Compact the event's payment records into a verifiable commitment.
+                let commitment = match storage::get_ticket_payment_contract(&env) {
+                    Some(ticket_payment_addr) => {
+                        let commitment = ticket_payment::Client::new(&env, &ticket_payment_addr)
+                            .try_archive_event_payments(&event_id)
+                            .map_err(|_| EventRegistryError::PaymentArchiveFailed)?
+                            .map_err(|_| EventRegistryError::PaymentArchiveFailed)?;
+                        storage::set_payments_commitment(&env, event_id.clone(), &commitment);
+                        Some(commitment)
+                    }
+                    None => None,
+                };
+
                 storage::remove_event(&env, event_id.clone());
 
                 let receipt = EventReceipt {
@@ -550,6 +688,8 @@ impl EventRegistry {
                     EventArchivedEvent {
                         event_id,
                         organizer_address: event_info.organizer_address,
+                        payments_root: commitment.as_ref().map(|c| c.root.clone()),
+                        payment_count: commitment.map(|c| c.payment_count).unwrap_or(0),
                         timestamp: env.ledger().timestamp(),
                     },
                 );
@@ -560,6 +700,98 @@ impl EventRegistry {
         }
     }
 
+    /// Extends the TTL of everything an event depends on: its record and tiers (or
+    /// receipt once archived), per-event settings, the organizer's indices and
+    /// contract-wide settings. Also extends the event's payments in TicketPayment
+    /// when one is linked. Anyone may call this to keep an idle event alive.
+    pub fn bump_event(env: Env, event_id: String) -> Result<(), EventRegistryError> {
+        let (organizer, tier_ids, accepted_tokens) =
+            match storage::get_event(&env, event_id.clone()) {
+                Some(event_info) => (
+                    event_info.organizer_address,
+                    event_info.tiers.keys(),
+                    event_info.accepted_tokens,
+                ),
+                None => {
+                    let receipt = storage::get_event_receipt(&env, event_id.clone())
+                        .ok_or(EventRegistryError::EventNotFound)?;
+                    (receipt.organizer_address, Vec::new(&env), Vec::new(&env))
+                }
+            };
+
+        ttl::extend_config(&env);
+        storage::extend_event_entries(
+            &env,
+            event_id.clone(),
+            &organizer,
+            &tier_ids,
+            &accepted_tokens,
+        );
+
+        if let Some(ticket_payment_addr) = storage::get_ticket_payment_contract(&env) {
+            // Best effort: the registry's entries are extended even if the linked
+            // contract predates `bump_event`.
+            let _ =
+                ticket_payment::Client::new(&env, &ticket_payment_addr).try_bump_event(&event_id);
+        }
+        Ok(())
+    }
+
+    /// Overrides the TTL thresholds, in ledgers, for a family of persistent entries
+    /// (admin only). `extend_to` may not exceed the network's maximum TTL.
+    pub fn set_ttl_policy(
+        env: Env,
+        family: TtlFamily,
+        threshold: u32,
+        extend_to: u32,
+    ) -> Result<(), EventRegistryError> {
+        require_admin(&env)?;
+
+        let policy = TtlPolicy {
+            threshold,
+            extend_to,
+        };
+        if !ttl::is_valid_policy(&env, &policy) {
+            return Err(EventRegistryError::InvalidTtlPolicy);
+        }
+        storage::set_ttl_policy(&env, &family, &policy);
+        Ok(())
+    }
+
+    /// Returns the TTL policy in effect for a family of persistent entries.
+    pub fn get_ttl_policy(env: Env, family: TtlFamily) -> TtlPolicy {
+        ttl::get_policy(&env, &family)
+    }
+
+    /// Returns the Merkle commitment to an archived event's payments, kept next to
+    /// its receipt.
+    pub fn get_payments_commitment(
+        env: Env,
+        event_id: String,
+    ) -> Option<ticket_payment::PaymentsCommitment> {
+        storage::get_payments_commitment(&env, event_id)
+    }
+
+    /// Verifies that `payment_leaf` was one of an archived event's payments, proving
+    /// attendance and refund status after the payment records were compacted. The
+    /// proof lists the sibling hashes from the leaf up to the root; leaves are
+    /// published as `PaymentArchived` events by TicketPayment when archiving.
+    pub fn verify_archived_payment(
+        env: Env,
+        event_id: String,
+        payment_leaf: ticket_payment::ArchivedPayment,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let Some(commitment) = storage::get_payments_commitment(&env, event_id) else {
+            return false;
+        };
+        let mut node: BytesN<32> = env.crypto().sha256(&payment_leaf.to_xdr(&env)).into();
+        for sibling in proof.iter() {
+            node = merkle_parent(&env, &node, &sibling);
+        }
+        commitment.payment_count > 0 && node == commitment.root
+    }
+
     /// Update the decentralized metadata CID for an event (only by organizer)
     pub fn update_metadata(
         env: Env,
@@ -655,119 +887,230 @@ impl EventRegistry {
         Ok(())
     }
 
-    /// Stores or updates an event (legacy function for backward compatibility).
-    pub fn store_event(env: Env, event_info: EventInfo) {
-        // Require authorization to ensure only the organizer can store/update their event directly
+    /// Configures how organizer revenue for an event is split between multiple payees
+    /// (e.g. venue, performers, co-hosts).
+    ///
+    /// Shares are expressed in basis points and must sum to exactly 10000. Passing an
+    /// empty list clears the split so that all revenue is routed to `payment_address`.
+    /// The split is locked as soon as the first ticket is sold.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `EventCancelled` - If the event has been cancelled.
+    /// * `RevenueSplitLocked` - If tickets have ever been sold for the event, even if
+    ///   they have all been refunded since.
+    /// * `InvalidRevenueSplit` - If there are too many payees, a zero or duplicate entry,
+    ///   or the shares do not sum to 10000 bps.
+    /// * `InvalidAddress` - If a payee address is invalid.
+    pub fn set_revenue_split(
+        env: Env,
+        event_id: String,
+        payees: Vec<Payee>,
+    ) -> Result<(), EventRegistryError> {
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
         event_info.organizer_address.require_auth();
-        storage::store_event(&env, event_info);
-    }
 
-    /// Retrieves an event by its ID.
-    pub fn get_event(env: Env, event_id: String) -> Option<EventInfo> {
-        storage::get_event(&env, event_id)
-    }
+        if matches!(event_info.status, EventStatus::Cancelled) {
+            return Err(EventRegistryError::EventCancelled);
+        }
 
-    /// Returns the organizer address for a given event ID, or `None` if the event does not exist.
-    pub fn get_organizer_address(env: Env, event_id: String) -> Option<Address> {
-        storage::get_event(&env, event_id).map(|e| e.organizer_address)
-    }
+        if storage::has_sales_started(&env, event_id.clone()) {
+            return Err(EventRegistryError::RevenueSplitLocked);
+        }
 
-    /// Returns the total number of tickets sold for an event across all tiers.
-    pub fn get_total_tickets_sold(env: Env, event_id: String) -> Result<i128, EventRegistryError> {
-        let event = storage::get_event(&env, event_id).ok_or(EventRegistryError::EventNotFound)?;
-        Ok(event.current_supply)
-    }
+        validate_revenue_split(&env, &payees)?;
 
-    /// Returns the total number of events that have ever been registered on the platform.
-    pub fn get_managed_events_count(env: Env) -> u32 {
-        storage::get_global_event_count(&env)
-    }
+        event_info.payees = payees.clone();
+        storage::update_event(&env, event_info.clone());
 
-    /// Returns the total number of events that are currently active.
-    pub fn get_active_events_count(env: Env) -> u32 {
-        storage::get_global_active_event_count(&env)
-    }
+        env.events().publish(
+            (AgoraEvent::RevenueSplitUpdated,),
+            RevenueSplitUpdatedEvent {
+                event_id,
+                payees,
+                updated_by: event_info.organizer_address,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-    /// Returns the total number of tickets sold across all events on the platform.
-    pub fn get_global_tickets_sold(env: Env) -> i128 {
-        storage::get_global_tickets_sold(&env)
+        Ok(())
     }
 
-    /// Checks if an event exists.
-    pub fn event_exists(env: Env, event_id: String) -> bool {
-        storage::event_exists(&env, event_id)
-    }
+    /// Returns the effective revenue split for an event.
+    ///
+    /// When no split has been configured, a single payee receiving 100% at the event's
+    /// `payment_address` is returned.
+    pub fn get_revenue_split(env: Env, event_id: String) -> Result<Vec<Payee>, EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id).ok_or(EventRegistryError::EventNotFound)?;
 
-    /// Retrieves all event IDs for an organizer.
-    pub fn get_organizer_events(env: Env, organizer: Address) -> Vec<String> {
-        storage::get_organizer_events(&env, &organizer)
+        if !event_info.payees.is_empty() {
+            return Ok(event_info.payees);
+        }
+
+        let mut payees = Vec::new(&env);
+        payees.push_back(Payee {
+            address: event_info.payment_address,
+            share_bps: 10000,
+        });
+        Ok(payees)
     }
 
-    /// Retrieves all archived event receipts for an organizer.
-    pub fn get_organizer_receipts(env: Env, organizer: Address) -> Vec<EventReceipt> {
-        storage::get_organizer_receipts(&env, &organizer)
+    /// Sets, rotates or clears (with `None`) the allowlist root covering every tier
+    /// of an event (organizer only). Leaves are SHA-256 hashes of the XDR-encoded
+    /// `(buyer, max_quantity)` entry, so each invitee carries their own allowance.
+    /// The root only gates purchases while the event is private.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    pub fn set_event_allowlist(
+        env: Env,
+        event_id: String,
+        root: Option<BytesN<32>>,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
+        event_info.organizer_address.require_auth();
+
+        storage::set_event_allowlist(&env, event_id.clone(), &root);
+
+        env.events().publish(
+            (AgoraEvent::AllowlistUpdated,),
+            AllowlistUpdatedEvent {
+                event_id,
+                tier_id: None,
+                root,
+                hidden: false,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
     }
 
-    /// Updates the platform fee percentage. Only callable by the administrator.
-    pub fn set_platform_fee(env: Env, new_fee_percent: u32) -> Result<(), EventRegistryError> {
-        require_admin(&env)?;
+    /// Sets, rotates or clears (with `None`) the allowlist of a single tier
+    /// (organizer only). On a private event it replaces the event-wide root for
+    /// that tier; a hidden tier requires a proof even on a public event.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `TierNotFound` - If the event has no tier with the given ID.
+    pub fn set_tier_allowlist(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        allowlist: Option<TierAllowlist>,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
 
-        if new_fee_percent > 10000 {
-            return Err(EventRegistryError::InvalidFeePercent);
+        event_info.organizer_address.require_auth();
+
+        if !event_info.tiers.contains_key(tier_id.clone()) {
+            return Err(EventRegistryError::TierNotFound);
         }
 
-        storage::set_platform_fee(&env, new_fee_percent);
+        storage::set_tier_allowlist(&env, event_id.clone(), tier_id.clone(), &allowlist);
 
-        // Emit fee update event using contract event type
         env.events().publish(
-            (AgoraEvent::FeeUpdated,),
-            FeeUpdatedEvent { new_fee_percent },
+            (AgoraEvent::AllowlistUpdated,),
+            AllowlistUpdatedEvent {
+                event_id,
+                tier_id: Some(tier_id),
+                root: allowlist.as_ref().map(|a| a.root.clone()),
+                hidden: allowlist.map(|a| a.hidden).unwrap_or(false),
+                timestamp: env.ledger().timestamp(),
+            },
         );
 
         Ok(())
     }
 
-    /// Returns the current platform fee percentage.
-    pub fn get_platform_fee(env: Env) -> u32 {
-        storage::get_platform_fee(&env)
+    /// Returns the allowlist root covering every tier of an event, if set.
+    pub fn get_event_allowlist(env: Env, event_id: String) -> Option<BytesN<32>> {
+        storage::get_event_allowlist(&env, event_id)
     }
 
-    /// Returns the current minimum stake amount required for Verified organizer status.
-    pub fn get_min_stake_amount(env: Env) -> i128 {
-        storage::get_min_stake_amount(&env)
+    /// Returns the allowlist of a single tier, if set.
+    pub fn get_tier_allowlist(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+    ) -> Option<TierAllowlist> {
+        storage::get_tier_allowlist(&env, event_id, tier_id)
     }
 
-    /// Sets a custom fee for a specific event. Only callable by the administrator.
-    pub fn set_custom_event_fee(
+    /// Resolves the allowlist a purchase of `tier_id` must prove membership of:
+    /// a hidden tier's own root, otherwise on a private event the tier's root or
+    /// the event-wide root. Returns `None` when purchases are open to anyone.
+    pub fn get_purchase_allowlist(
         env: Env,
         event_id: String,
-        custom_fee_bps: Option<u32>,
-    ) -> Result<(), EventRegistryError> {
-        let admin = require_admin(&env)?;
-
-        if let Some(fee) = custom_fee_bps {
-            if fee > 10000 {
-                return Err(EventRegistryError::InvalidFeePercent);
+        tier_id: String,
+    ) -> Option<AllowlistGate> {
+        let tier_allowlist = storage::get_tier_allowlist(&env, event_id.clone(), tier_id.clone());
+        if let Some(allowlist) = &tier_allowlist {
+            if allowlist.hidden {
+                return Some(AllowlistGate {
+                    root: allowlist.root.clone(),
+                    tier_id: Some(tier_id),
+                });
             }
         }
 
-        let mut event_info =
+        if !storage::get_event(&env, event_id.clone())?.is_private {
+            return None;
+        }
+        match tier_allowlist {
+            Some(allowlist) => Some(AllowlistGate {
+                root: allowlist.root,
+                tier_id: Some(tier_id),
+            }),
+            None => storage::get_event_allowlist(&env, event_id).map(|root| AllowlistGate {
+                root,
+                tier_id: None,
+            }),
+        }
+    }
+
+    /// Sets or clears (with `None`) the attestation a tier requires (organizer
+    /// only). TicketPayment asks the attester whether the buyer, or the recipient
+    /// of a transfer, holds `claim` before the ticket changes hands.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `TierNotFound` - If the event has no tier with the given ID.
+    /// * `InvalidAddress` - If the attester address is invalid.
+    pub fn set_tier_attestation(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        requirement: Option<TierAttestation>,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
             storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
 
-        if matches!(event_info.status, EventStatus::Cancelled) {
-            return Err(EventRegistryError::EventCancelled);
+        event_info.organizer_address.require_auth();
+
+        if !event_info.tiers.contains_key(tier_id.clone()) {
+            return Err(EventRegistryError::TierNotFound);
+        }
+        if let Some(requirement) = &requirement {
+            validate_address(&env, &requirement.attester)?;
         }
 
-        event_info.custom_fee_bps = custom_fee_bps;
-        storage::update_event(&env, event_info);
+        storage::set_tier_attestation(&env, event_id.clone(), tier_id.clone(), &requirement);
 
-        // Emit custom fee set event
         env.events().publish(
-            (AgoraEvent::CustomFeeSet,),
-            CustomFeeSetEvent {
+            (AgoraEvent::TierAttestationUpdated,),
+            TierAttestationUpdatedEvent {
                 event_id,
-                custom_fee_bps,
-                admin_address: admin,
+                tier_id,
+                attester: requirement.as_ref().map(|r| r.attester.clone()),
+                claim: requirement.map(|r| r.claim),
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -775,29 +1118,44 @@ impl EventRegistry {
         Ok(())
     }
 
-    /// Returns the current administrator address.
-    pub fn get_admin(env: Env) -> Result<Address, EventRegistryError> {
-        storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)
+    /// Returns the attestation a tier requires of ticket holders, if any.
+    pub fn get_tier_attestation(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+    ) -> Option<TierAttestation> {
+        storage::get_tier_attestation(&env, event_id, tier_id)
     }
 
-    /// Updates the administrator address. Only callable by the current administrator.
-    /// Emits an `AdminUpdated` event with the old and new admin addresses.
+    /// Makes a tier's price a floor that buyers may pay any amount above, or restores
+    /// fixed pricing (organizer only). Early-bird prices become the floor while active.
     ///
-    /// # Arguments
-    /// * `new_admin` - The new administrator address.
-    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), EventRegistryError> {
-        let old_admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
-        old_admin.require_auth();
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `TierNotFound` - If the event has no tier with the given ID.
+    pub fn set_pay_what_you_want(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        enabled: bool,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
 
-        validate_address(&env, &new_admin)?;
+        event_info.organizer_address.require_auth();
 
-        storage::set_admin(&env, &new_admin);
+        if !event_info.tiers.contains_key(tier_id.clone()) {
+            return Err(EventRegistryError::TierNotFound);
+        }
+
+        storage::set_pay_what_you_want(&env, event_id.clone(), tier_id.clone(), enabled);
 
         env.events().publish(
-            (AgoraEvent::AdminUpdated,),
-            AdminUpdatedEvent {
-                old_admin,
-                new_admin,
+            (crate::events::AgoraEventExt::PayWhatYouWantUpdated,),
+            crate::events::PayWhatYouWantUpdatedEvent {
+                event_id,
+                tier_id,
+                enabled,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -805,26 +1163,437 @@ impl EventRegistry {
         Ok(())
     }
 
-    /// Returns the current platform wallet address.
-    pub fn get_platform_wallet(env: Env) -> Result<Address, EventRegistryError> {
-        storage::get_platform_wallet(&env).ok_or(EventRegistryError::NotInitialized)
+    /// Returns whether a tier accepts any amount at or above its price.
+    pub fn is_pay_what_you_want(env: Env, event_id: String, tier_id: String) -> bool {
+        storage::is_pay_what_you_want(&env, event_id, tier_id)
     }
 
-    /// Sets the authorized TicketPayment contract address. Only callable by the administrator.
+    /// Creates or updates an add-on (parking, merchandise, a meal voucher) sold
+    /// alongside an event's tickets. Only the event organizer may call this.
     ///
     /// # Arguments
-    /// * `ticket_payment_address` - The address of the TicketPayment contract authorized
-    ///   to call `increment_inventory`.
-    pub fn set_ticket_payment_contract(
+    /// * `addon_id` - Identifier of the add-on within the event.
+    /// * `stock` - Items available in total (0 = unlimited).
+    /// * `tier_id` - Tier whose tickets the add-on can be bought with (`None` = any tier).
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If the event does not exist.
+    /// * `TierNotFound` - If the restricting tier does not exist.
+    /// * `InvalidAddOn` - If the price is not positive or the stock is below items sold.
+    pub fn set_add_on(
         env: Env,
-        ticket_payment_address: Address,
+        event_id: String,
+        addon_id: String,
+        name: String,
+        price: i128,
+        stock: u32,
+        tier_id: Option<String>,
     ) -> Result<(), EventRegistryError> {
-        require_admin(&env)?;
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
 
-        validate_address(&env, &ticket_payment_address)?;
+        event_info.organizer_address.require_auth();
 
-        storage::set_ticket_payment_contract(&env, &ticket_payment_address);
-        Ok(())
+        if let Some(tier_id) = &tier_id {
+            if !event_info.tiers.contains_key(tier_id.clone()) {
+                return Err(EventRegistryError::TierNotFound);
+            }
+        }
+
+        let sold = storage::get_add_on(&env, event_id.clone(), addon_id.clone())
+            .map(|a| a.sold)
+            .unwrap_or(0);
+        if price <= 0 || (stock > 0 && stock < sold) {
+            return Err(EventRegistryError::InvalidAddOn);
+        }
+
+        storage::set_add_on(
+            &env,
+            event_id.clone(),
+            addon_id.clone(),
+            &AddOn {
+                name,
+                price,
+                stock,
+                sold,
+                tier_id: tier_id.clone(),
+            },
+        );
+
+        env.events().publish(
+            (crate::events::AgoraEventExt::AddOnUpdated,),
+            crate::events::AddOnUpdatedEvent {
+                event_id,
+                addon_id,
+                price,
+                stock,
+                tier_id,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns an event's add-on, if offered.
+    pub fn get_add_on(env: Env, event_id: String, addon_id: String) -> Option<AddOn> {
+        storage::get_add_on(&env, event_id, addon_id)
+    }
+
+    /// Returns the IDs of every add-on an event offers.
+    pub fn get_event_add_ons(env: Env, event_id: String) -> Vec<String> {
+        storage::get_add_on_ids(&env, event_id)
+    }
+
+    /// Takes add-on items out of stock for a purchase. Only the TicketPayment
+    /// contract may call this; it returns the add-on as priced at the sale.
+    ///
+    /// # Errors
+    /// * `EventInactive` - If the event is inactive or cancelled.
+    /// * `InvalidQuantity` - If `quantity` is zero.
+    /// * `AddOnNotFound` - If the event does not offer the add-on.
+    /// * `AddOnSoldOut` - If fewer than `quantity` items are left.
+    pub fn reserve_add_on(
+        env: Env,
+        event_id: String,
+        addon_id: String,
+        quantity: u32,
+    ) -> Result<AddOn, EventRegistryError> {
+        let ticket_payment_addr =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        ticket_payment_addr.require_auth();
+
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        if !event_info.is_active || matches!(event_info.status, EventStatus::Cancelled) {
+            return Err(EventRegistryError::EventInactive);
+        }
+        if quantity == 0 {
+            return Err(EventRegistryError::InvalidQuantity);
+        }
+
+        let mut add_on = storage::get_add_on(&env, event_id.clone(), addon_id.clone())
+            .ok_or(EventRegistryError::AddOnNotFound)?;
+        let sold = add_on
+            .sold
+            .checked_add(quantity)
+            .ok_or(EventRegistryError::AddOnSoldOut)?;
+        if add_on.stock > 0 && sold > add_on.stock {
+            return Err(EventRegistryError::AddOnSoldOut);
+        }
+        add_on.sold = sold;
+        storage::set_add_on(&env, event_id, addon_id, &add_on);
+
+        Ok(add_on)
+    }
+
+    /// Returns refunded add-on items to stock. Only the TicketPayment contract
+    /// may call this.
+    ///
+    /// # Errors
+    /// * `AddOnNotFound` - If the event does not offer the add-on.
+    /// * `SupplyUnderflow` - If fewer than `quantity` items were sold.
+    pub fn release_add_on(
+        env: Env,
+        event_id: String,
+        addon_id: String,
+        quantity: u32,
+    ) -> Result<(), EventRegistryError> {
+        let ticket_payment_addr =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        ticket_payment_addr.require_auth();
+
+        let mut add_on = storage::get_add_on(&env, event_id.clone(), addon_id.clone())
+            .ok_or(EventRegistryError::AddOnNotFound)?;
+        add_on.sold = add_on
+            .sold
+            .checked_sub(quantity)
+            .ok_or(EventRegistryError::SupplyUnderflow)?;
+        storage::set_add_on(&env, event_id, addon_id, &add_on);
+
+        Ok(())
+    }
+
+    /// Sets or clears the refundable deposit guests escrow to RSVP for a free
+    /// tier. Only the event organizer may call this.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If the event does not exist.
+    /// * `TierNotFound` - If the tier does not exist.
+    /// * `InvalidDeposit` - If the tier is not free or the amount is not positive.
+    pub fn set_tier_deposit(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        deposit: Option<TierDeposit>,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
+        event_info.organizer_address.require_auth();
+
+        let tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+        if let Some(deposit) = &deposit {
+            if tier.price != 0 || deposit.amount <= 0 {
+                return Err(EventRegistryError::InvalidDeposit);
+            }
+        }
+
+        storage::set_tier_deposit(&env, event_id.clone(), tier_id.clone(), &deposit);
+
+        env.events().publish(
+            (crate::events::AgoraEventExt::TierDepositUpdated,),
+            crate::events::TierDepositUpdatedEvent {
+                event_id,
+                tier_id,
+                amount: deposit.as_ref().map(|d| d.amount).unwrap_or(0),
+                forfeit_recipient: deposit.and_then(|d| d.forfeit_recipient),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the refundable deposit a free tier asks of guests, if any.
+    pub fn get_tier_deposit(env: Env, event_id: String, tier_id: String) -> Option<TierDeposit> {
+        storage::get_tier_deposit(&env, event_id, tier_id)
+    }
+
+    /// Sets or clears the installment plan a paid tier offers. Only the event
+    /// organizer may call this; tickets already on a plan keep their terms.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If the event does not exist.
+    /// * `TierNotFound` - If the tier does not exist.
+    /// * `InvalidInstallmentPlan` - If the tier is free, there are no deadlines, the
+    ///   deadlines are not increasing future timestamps, or the penalty exceeds 100%.
+    pub fn set_installment_plan(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        plan: Option<InstallmentPlan>,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
+        event_info.organizer_address.require_auth();
+
+        let tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+        if let Some(plan) = &plan {
+            let mut previous = env.ledger().timestamp();
+            for due_date in plan.due_dates.iter() {
+                if due_date <= previous {
+                    return Err(EventRegistryError::InvalidInstallmentPlan);
+                }
+                previous = due_date;
+            }
+            if tier.price <= 0 || plan.due_dates.is_empty() || plan.penalty_bps > 10_000 {
+                return Err(EventRegistryError::InvalidInstallmentPlan);
+            }
+        }
+
+        storage::set_installment_plan(&env, event_id.clone(), tier_id.clone(), &plan);
+
+        env.events().publish(
+            (crate::events::AgoraEventExt::InstallmentPlanUpdated,),
+            crate::events::InstallmentPlanUpdatedEvent {
+                event_id,
+                tier_id,
+                installments: plan.as_ref().map(|p| p.due_dates.len() + 1).unwrap_or(0),
+                penalty_bps: plan.map(|p| p.penalty_bps).unwrap_or(0),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the installment plan a tier offers, if any.
+    pub fn get_installment_plan(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+    ) -> Option<InstallmentPlan> {
+        storage::get_installment_plan(&env, event_id, tier_id)
+    }
+
+    /// Stores or updates an event (legacy function for backward compatibility).
+    pub fn store_event(env: Env, event_info: EventInfo) {
+        // Require authorization to ensure only the organizer can store/update their event directly
+        event_info.organizer_address.require_auth();
+        storage::store_event(&env, event_info);
+    }
+
+    /// Retrieves an event by its ID.
+    pub fn get_event(env: Env, event_id: String) -> Option<EventInfo> {
+        storage::get_event(&env, event_id)
+    }
+
+    /// Returns the organizer address for a given event ID, or `None` if the event does not exist.
+    pub fn get_organizer_address(env: Env, event_id: String) -> Option<Address> {
+        storage::get_event(&env, event_id).map(|e| e.organizer_address)
+    }
+
+    /// Returns the total number of tickets sold for an event across all tiers.
+    pub fn get_total_tickets_sold(env: Env, event_id: String) -> Result<i128, EventRegistryError> {
+        let event = storage::get_event(&env, event_id).ok_or(EventRegistryError::EventNotFound)?;
+        Ok(event.current_supply)
+    }
+
+    /// Returns the total number of events that have ever been registered on the platform.
+    pub fn get_managed_events_count(env: Env) -> u32 {
+        storage::get_global_event_count(&env)
+    }
+
+    /// Returns the total number of events that are currently active.
+    pub fn get_active_events_count(env: Env) -> u32 {
+        storage::get_global_active_event_count(&env)
+    }
+
+    /// Returns the total number of tickets sold across all events on the platform.
+    pub fn get_global_tickets_sold(env: Env) -> i128 {
+        storage::get_global_tickets_sold(&env)
+    }
+
+    /// Checks if an event exists.
+    pub fn event_exists(env: Env, event_id: String) -> bool {
+        storage::event_exists(&env, event_id)
+    }
+
+    /// Retrieves all event IDs for an organizer.
+    pub fn get_organizer_events(env: Env, organizer: Address) -> Vec<String> {
+        storage::get_organizer_events(&env, &organizer)
+    }
+
+    /// Retrieves all archived event receipts for an organizer.
+    pub fn get_organizer_receipts(env: Env, organizer: Address) -> Vec<EventReceipt> {
+        storage::get_organizer_receipts(&env, &organizer)
+    }
+
+    /// Updates the platform fee percentage. Only callable by the administrator.
+    pub fn set_platform_fee(env: Env, new_fee_percent: u32) -> Result<(), EventRegistryError> {
+        require_admin(&env)?;
+
+        if new_fee_percent > 10000 {
+            return Err(EventRegistryError::InvalidFeePercent);
+        }
+
+        storage::set_platform_fee(&env, new_fee_percent);
+
+        // Emit fee update event using contract event type
+        env.events().publish(
+            (AgoraEvent::FeeUpdated,),
+            FeeUpdatedEvent { new_fee_percent },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the current platform fee percentage.
+    pub fn get_platform_fee(env: Env) -> u32 {
+        storage::get_platform_fee(&env)
+    }
+
+    /// Returns the current minimum stake amount required for Verified organizer status.
+    pub fn get_min_stake_amount(env: Env) -> i128 {
+        storage::get_min_stake_amount(&env)
+    }
+
+    /// Sets a custom fee for a specific event. Only callable by the administrator.
+    pub fn set_custom_event_fee(
+        env: Env,
+        event_id: String,
+        custom_fee_bps: Option<u32>,
+    ) -> Result<(), EventRegistryError> {
+        let admin = require_admin(&env)?;
+
+        if let Some(fee) = custom_fee_bps {
+            if fee > 10000 {
+                return Err(EventRegistryError::InvalidFeePercent);
+            }
+        }
+
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
+        if matches!(event_info.status, EventStatus::Cancelled) {
+            return Err(EventRegistryError::EventCancelled);
+        }
+
+        event_info.custom_fee_bps = custom_fee_bps;
+        storage::update_event(&env, event_info);
+
+        // Emit custom fee set event
+        env.events().publish(
+            (AgoraEvent::CustomFeeSet,),
+            CustomFeeSetEvent {
+                event_id,
+                custom_fee_bps,
+                admin_address: admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the current administrator address.
+    pub fn get_admin(env: Env) -> Result<Address, EventRegistryError> {
+        storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)
+    }
+
+    /// Updates the administrator address. Only callable by the current administrator.
+    /// Emits an `AdminUpdated` event with the old and new admin addresses.
+    ///
+    /// # Arguments
+    /// * `new_admin` - The new administrator address.
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), EventRegistryError> {
+        let old_admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        old_admin.require_auth();
+
+        validate_address(&env, &new_admin)?;
+
+        storage::set_admin(&env, &new_admin);
+
+        env.events().publish(
+            (AgoraEvent::AdminUpdated,),
+            AdminUpdatedEvent {
+                old_admin,
+                new_admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the current platform wallet address.
+    pub fn get_platform_wallet(env: Env) -> Result<Address, EventRegistryError> {
+        storage::get_platform_wallet(&env).ok_or(EventRegistryError::NotInitialized)
+    }
+
+    /// Sets the authorized TicketPayment contract address. Only callable by the administrator.
+    ///
+    /// # Arguments
+    /// * `ticket_payment_address` - The address of the TicketPayment contract authorized
+    ///   to call `increment_inventory`.
+    pub fn set_ticket_payment_contract(
+        env: Env,
+        ticket_payment_address: Address,
+    ) -> Result<(), EventRegistryError> {
+        require_admin(&env)?;
+
+        validate_address(&env, &ticket_payment_address)?;
+
+        storage::set_ticket_payment_contract(&env, &ticket_payment_address);
+        Ok(())
     }
 
     /// Returns the authorized TicketPayment contract address.
@@ -915,6 +1684,9 @@ impl EventRegistry {
             .current_supply
             .checked_add(quantity_i128)
             .ok_or(EventRegistryError::SupplyOverflow)?;
+        if !storage::has_sales_started(&env, event_id.clone()) {
+            storage::set_sales_started(&env, event_id.clone());
+        }
 
         let new_supply = event_info.current_supply;
 
@@ -1011,19 +1783,177 @@ impl EventRegistry {
         let new_supply = event_info.current_supply;
         storage::update_event(&env, event_info.clone());
 
-        // Update user ticket count for per-user limits
-        storage::subtract_from_user_ticket_count(&env, &event_id, &tier_id, &user, 1);
+        // Update user ticket count for per-user limits
+        storage::subtract_from_user_ticket_count(&env, &event_id, &tier_id, &user, 1);
+
+        // Private events are excluded from the global tickets sold counter.
+        if !event_info.is_private {
+            storage::subtract_from_global_tickets_sold(&env, 1);
+        }
+
+        env.events().publish(
+            (crate::events::AgoraEvent::InventoryDecremented,),
+            crate::events::InventoryDecrementedEvent {
+                event_id,
+                new_supply,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Moves one ticket from `from_tier_id` to `to_tier_id` of the same event.
+    /// This function is restricted to calls from the authorized TicketPayment contract
+    /// when a guest upgrades or downgrades a ticket.
+    ///
+    /// The event's total supply is unchanged; only the two tier counters and the
+    /// holder's per-tier counts move, so both sides succeed or neither does.
+    ///
+    /// # Errors
+    /// * `UnauthorizedCaller` - If the invoker is not the registered TicketPayment contract.
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `EventInactive` - If the event is inactive or cancelled.
+    /// * `InvalidTierChange` - If both tiers are the same.
+    /// * `TierNotFound` - If either tier does not exist.
+    /// * `SupplyUnderflow` - If the source tier has nothing sold.
+    /// * `TierSupplyExceeded` - If the destination tier is sold out.
+    /// * `PerUserLimitExceeded` - If the holder is at the destination tier's per-user cap.
+    pub fn move_inventory(
+        env: Env,
+        event_id: String,
+        from_tier_id: String,
+        to_tier_id: String,
+        user: Address,
+    ) -> Result<(), EventRegistryError> {
+        let ticket_payment_addr =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        ticket_payment_addr.require_auth();
+
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
+        if !event_info.is_active || matches!(event_info.status, EventStatus::Cancelled) {
+            return Err(EventRegistryError::EventInactive);
+        }
+
+        if from_tier_id == to_tier_id {
+            return Err(EventRegistryError::InvalidTierChange);
+        }
+
+        let mut from_tier = event_info
+            .tiers
+            .get(from_tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+        let mut to_tier = event_info
+            .tiers
+            .get(to_tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+
+        if from_tier.current_sold <= 0 {
+            return Err(EventRegistryError::SupplyUnderflow);
+        }
+
+        let new_to_sold = to_tier
+            .current_sold
+            .checked_add(1)
+            .ok_or(EventRegistryError::SupplyOverflow)?;
+        if new_to_sold > to_tier.tier_limit {
+            return Err(EventRegistryError::TierSupplyExceeded);
+        }
+
+        if to_tier.max_per_user > 0 {
+            let current_user_count =
+                storage::get_user_ticket_count(&env, &event_id, &to_tier_id, &user);
+            if current_user_count.saturating_add(1) > to_tier.max_per_user {
+                return Err(EventRegistryError::PerUserLimitExceeded);
+            }
+        }
+
+        from_tier.current_sold -= 1;
+        to_tier.current_sold = new_to_sold;
+        event_info.tiers.set(from_tier_id.clone(), from_tier);
+        event_info.tiers.set(to_tier_id.clone(), to_tier);
+        storage::update_event(&env, event_info);
+
+        storage::subtract_from_user_ticket_count(&env, &event_id, &from_tier_id, &user, 1);
+        storage::add_to_user_ticket_count(&env, &event_id, &to_tier_id, &user, 1);
+
+        env.events().publish(
+            (AgoraEvent::InventoryMoved,),
+            crate::events::InventoryMovedEvent {
+                event_id,
+                from_tier_id,
+                to_tier_id,
+                user,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Exchanges one ticket at `from_event_id` for one at `to_event_id`.
+    /// This function is restricted to calls from the authorized TicketPayment contract
+    /// when a guest swaps to a rescheduled show or another date of a series.
+    ///
+    /// Series only group events of one organizer, so requiring a shared organizer
+    /// covers both cases. The destination is checked like a purchase and the source
+    /// is released like a refund, in one call.
+    ///
+    /// # Errors
+    /// * `UnauthorizedCaller` - If the invoker is not the registered TicketPayment contract.
+    /// * `InvalidTierChange` - If both events are the same; use `move_inventory` instead.
+    /// * `EventNotFound` - If either event does not exist.
+    /// * `ExchangeNotAllowed` - If the events have different organizers.
+    /// * Any error of `increment_inventory` for the destination or `decrement_inventory`
+    ///   for the source.
+    pub fn exchange_inventory(
+        env: Env,
+        from_event_id: String,
+        from_tier_id: String,
+        to_event_id: String,
+        to_tier_id: String,
+        user: Address,
+    ) -> Result<(), EventRegistryError> {
+        let ticket_payment_addr =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        ticket_payment_addr.require_auth();
+
+        if from_event_id == to_event_id {
+            return Err(EventRegistryError::InvalidTierChange);
+        }
 
-        // Private events are excluded from the global tickets sold counter.
-        if !event_info.is_private {
-            storage::subtract_from_global_tickets_sold(&env, 1);
+        let from_event = storage::get_event(&env, from_event_id.clone())
+            .ok_or(EventRegistryError::EventNotFound)?;
+        let to_event = storage::get_event(&env, to_event_id.clone())
+            .ok_or(EventRegistryError::EventNotFound)?;
+        if from_event.organizer_address != to_event.organizer_address {
+            return Err(EventRegistryError::ExchangeNotAllowed);
         }
 
+        Self::increment_inventory(
+            env.clone(),
+            to_event_id.clone(),
+            to_tier_id.clone(),
+            user.clone(),
+            1,
+        )?;
+        Self::decrement_inventory(
+            env.clone(),
+            from_event_id.clone(),
+            from_tier_id.clone(),
+            user.clone(),
+        )?;
+
         env.events().publish(
-            (crate::events::AgoraEvent::InventoryDecremented,),
-            crate::events::InventoryDecrementedEvent {
-                event_id,
-                new_supply,
+            (crate::events::AgoraEventExt::InventoryExchanged,),
+            crate::events::InventoryExchangedEvent {
+                from_event_id,
+                from_tier_id,
+                to_event_id,
+                to_tier_id,
+                user,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -1032,10 +1962,24 @@ impl EventRegistry {
     }
 
     /// Upgrades the contract to a new WASM hash. Only callable by the administrator.
+    ///
+    /// The hash must have been scheduled by an executed `UpgradeContract` proposal and
+    /// `UPGRADE_TIMELOCK_SECS` must have passed since. Run `migrate` afterwards if the
+    /// new WASM bumps the schema version.
     /// Performs post-upgrade state verification to ensure critical storage is intact.
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), EventRegistryError> {
         require_admin(&env)?;
 
+        let pending =
+            storage::get_pending_upgrade(&env).ok_or(EventRegistryError::UpgradeNotScheduled)?;
+        if pending.wasm_hash != new_wasm_hash {
+            return Err(EventRegistryError::UpgradeNotScheduled);
+        }
+        if env.ledger().timestamp() < pending.eta {
+            return Err(EventRegistryError::UpgradeTimelockActive);
+        }
+        storage::remove_pending_upgrade(&env);
+
         env.deployer().update_current_contract_wasm(new_wasm_hash);
 
         // Post-upgrade state verification
@@ -1053,6 +1997,67 @@ impl EventRegistry {
         Ok(())
     }
 
+    /// Cancels the scheduled upgrade. Only callable by the administrator.
+    pub fn cancel_upgrade(env: Env) -> Result<(), EventRegistryError> {
+        require_admin(&env)?;
+
+        let pending =
+            storage::get_pending_upgrade(&env).ok_or(EventRegistryError::UpgradeNotScheduled)?;
+        storage::remove_pending_upgrade(&env);
+
+        env.events().publish(
+            (AgoraEvent::UpgradeCancelled,),
+            UpgradeCancelledEvent {
+                wasm_hash: pending.wasm_hash,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the upgrade awaiting its timelock, if any.
+    pub fn get_pending_upgrade(env: Env) -> Option<types::PendingUpgrade> {
+        storage::get_pending_upgrade(&env)
+    }
+
+    /// Migrates stored records to the schema version of the running WASM.
+    ///
+    /// Runs every pending step once, rewriting the given `event_ids` from their old
+    /// layout, and returns the new schema version. Fails with `AlreadyMigrated` when
+    /// storage is already current. Only callable by the administrator.
+    pub fn migrate(env: Env, event_ids: Vec<String>) -> Result<u32, EventRegistryError> {
+        require_admin(&env)?;
+
+        let from_version = storage::get_schema_version(&env);
+        if from_version >= migration::SCHEMA_VERSION {
+            return Err(EventRegistryError::AlreadyMigrated);
+        }
+
+        let mut records_migrated = 0u32;
+        for version in from_version..migration::SCHEMA_VERSION {
+            records_migrated += migration::migrate_step(&env, version, &event_ids);
+        }
+        storage::set_schema_version(&env, migration::SCHEMA_VERSION);
+
+        env.events().publish(
+            (AgoraEvent::ContractMigrated,),
+            ContractMigratedEvent {
+                from_version,
+                to_version: migration::SCHEMA_VERSION,
+                records_migrated,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(migration::SCHEMA_VERSION)
+    }
+
+    /// Returns the layout version of the stored records.
+    pub fn get_schema_version(env: Env) -> u32 {
+        storage::get_schema_version(&env)
+    }
+
     /// Adds an organizer to the blacklist with mandatory audit logging.
     /// Only callable by the administrator.
     pub fn blacklist_organizer(
@@ -1285,10 +2290,12 @@ impl EventRegistry {
 
         storage::set_staking_token(&env, &token);
         storage::set_min_stake_amount(&env, min_amount);
+        refresh_verified_status(&env, min_amount);
         Ok(())
     }
 
     /// Allows an organizer to stake collateral tokens to unlock Verified status.
+    /// Calling it again tops up the existing stake, e.g. after the minimum was raised.
     /// The organizer must approve this contract to spend `amount` of the staking token
     /// before calling this function.
     ///
@@ -1306,16 +2313,26 @@ impl EventRegistry {
             return Err(EventRegistryError::InvalidStakeAmount);
         }
 
-        if storage::get_organizer_stake(&env, &organizer).is_some() {
-            return Err(EventRegistryError::AlreadyStaked);
-        }
-
-        let token =
+        let staking_token =
             storage::get_staking_token(&env).ok_or(EventRegistryError::StakingNotConfigured)?;
         let min_amount = storage::get_min_stake_amount(&env);
 
+        // Top-ups stay in the token the stake was opened with.
+        let mut stake =
+            storage::get_organizer_stake(&env, &organizer).unwrap_or_else(|| OrganizerStake {
+                organizer: organizer.clone(),
+                token: staking_token,
+                amount: 0,
+                staked_at: env.ledger().timestamp(),
+                is_verified: false,
+                reward_balance: 0,
+                total_rewards_claimed: 0,
+            });
+
+        settle_staker_rewards(&env, &mut stake);
+
         // Transfer tokens from organizer to this contract
-        let token_client = token::Client::new(&env, &token);
+        let token_client = token::Client::new(&env, &stake.token);
         token_client.transfer_from(
             &env.current_contract_address(),
             &organizer,
@@ -1323,17 +2340,11 @@ impl EventRegistry {
             &amount,
         );
 
-        let is_verified = amount >= min_amount;
-
-        let stake = OrganizerStake {
-            organizer: organizer.clone(),
-            token: token.clone(),
-            amount,
-            staked_at: env.ledger().timestamp(),
-            is_verified,
-            reward_balance: 0,
-            total_rewards_claimed: 0,
-        };
+        stake.amount = stake
+            .amount
+            .checked_add(amount)
+            .ok_or(EventRegistryError::InvalidStakeAmount)?;
+        stake.is_verified = stake.amount >= min_amount;
 
         storage::set_organizer_stake(&env, &stake);
         storage::add_to_total_staked(&env, amount);
@@ -1343,9 +2354,9 @@ impl EventRegistry {
             (AgoraEvent::CollateralStaked,),
             CollateralStakedEvent {
                 organizer,
-                token,
+                token: stake.token,
                 amount,
-                is_verified,
+                is_verified: stake.is_verified,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -1353,48 +2364,146 @@ impl EventRegistry {
         Ok(())
     }
 
-    /// Allows an organizer to unstake their collateral and reclaim their tokens.
-    /// All accrued rewards must be claimed before unstaking.
+    /// Moves `amount` of the organizer's stake into the unbonding queue. The amount
+    /// stops counting towards Verified status and rewards immediately and can be
+    /// withdrawn with `withdraw_unbonded` once the unbonding period has elapsed.
+    ///
+    /// Returns the timestamp at which the amount becomes withdrawable.
     ///
     /// # Arguments
     /// * `organizer` - The organizer's wallet address (must sign)
-    pub fn unstake_collateral(env: Env, organizer: Address) -> Result<(), EventRegistryError> {
+    /// * `amount` - Amount of the stake to unbond
+    ///
+    /// # Errors
+    /// * `InvalidStakeAmount` - If `amount` is not positive or exceeds the stake.
+    /// * `OrganizerBlacklisted` - Blacklisted organizers keep their collateral locked so
+    ///   governance can slash it.
+    /// * `SlashPending` - An open slash proposal targets this organizer.
+    pub fn unstake_collateral(
+        env: Env,
+        organizer: Address,
+        amount: i128,
+    ) -> Result<u64, EventRegistryError> {
+        organizer.require_auth();
+
+        let mut stake =
+            storage::get_organizer_stake(&env, &organizer).ok_or(EventRegistryError::NotStaked)?;
+        require_stake_unlocked(&env, &organizer)?;
+
+        if amount <= 0 || amount > stake.amount {
+            return Err(EventRegistryError::InvalidStakeAmount);
+        }
+
+        settle_staker_rewards(&env, &mut stake);
+        stake.amount -= amount;
+        stake.is_verified = stake.amount > 0 && stake.amount >= storage::get_min_stake_amount(&env);
+        storage::set_organizer_stake(&env, &stake);
+        storage::subtract_from_total_staked(&env, amount);
+        if stake.amount == 0 {
+            storage::remove_from_stakers_list(&env, &organizer);
+        }
+
+        let release_at = env.ledger().timestamp() + storage::get_unbonding_period(&env);
+        let mut entries = storage::get_unbonding_entries(&env, &organizer);
+        entries.push_back(UnbondingEntry { amount, release_at });
+        storage::set_unbonding_entries(&env, &organizer, &entries);
+
+        env.events().publish(
+            (AgoraEvent::UnbondingStarted,),
+            UnbondingStartedEvent {
+                organizer,
+                token: stake.token,
+                amount,
+                remaining_stake: stake.amount,
+                release_at,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(release_at)
+    }
+
+    /// Pays out all unbonded collateral whose cooldown has elapsed.
+    ///
+    /// Returns the amount transferred back to the organizer.
+    ///
+    /// # Errors
+    /// * `OrganizerHasActiveEvents` - While any of the organizer's events is active or
+    ///   still holds escrow in the TicketPayment contract.
+    /// * `UnbondingNotReady` - If no unbonding entry has matured.
+    /// * `OrganizerBlacklisted` / `SlashPending` - As for `unstake_collateral`.
+    pub fn withdraw_unbonded(env: Env, organizer: Address) -> Result<i128, EventRegistryError> {
         organizer.require_auth();
 
         let stake =
             storage::get_organizer_stake(&env, &organizer).ok_or(EventRegistryError::NotStaked)?;
+        require_stake_unlocked(&env, &organizer)?;
+        if has_unsettled_events(&env, &organizer) {
+            return Err(EventRegistryError::OrganizerHasActiveEvents);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut withdrawn: i128 = 0;
+        let mut pending = Vec::new(&env);
+        for entry in storage::get_unbonding_entries(&env, &organizer).iter() {
+            if entry.release_at <= now {
+                withdrawn += entry.amount;
+            } else {
+                pending.push_back(entry);
+            }
+        }
+        if withdrawn == 0 {
+            return Err(EventRegistryError::UnbondingNotReady);
+        }
+        storage::set_unbonding_entries(&env, &organizer, &pending);
 
-        // Transfer tokens back to organizer
         let token_client = token::Client::new(&env, &stake.token);
-        token_client.transfer(&env.current_contract_address(), &organizer, &stake.amount);
+        token_client.transfer(&env.current_contract_address(), &organizer, &withdrawn);
 
-        storage::subtract_from_total_staked(&env, stake.amount);
-        storage::remove_organizer_stake(&env, &organizer);
-        storage::remove_from_stakers_list(&env, &organizer);
+        remove_stake_if_empty(&env, &stake);
 
         env.events().publish(
             (AgoraEvent::CollateralUnstaked,),
             CollateralUnstakedEvent {
                 organizer,
                 token: stake.token,
-                amount: stake.amount,
-                timestamp: env.ledger().timestamp(),
+                amount: withdrawn,
+                timestamp: now,
             },
         );
 
+        Ok(withdrawn)
+    }
+
+    /// Returns the organizer's collateral still waiting out the unbonding period.
+    pub fn get_unbonding_entries(env: Env, organizer: Address) -> Vec<UnbondingEntry> {
+        storage::get_unbonding_entries(&env, &organizer)
+    }
+
+    /// Sets the cooldown between an unstake request and its withdrawal. Admin only.
+    pub fn set_unbonding_period(env: Env, period_secs: u64) -> Result<(), EventRegistryError> {
+        require_admin(&env)?;
+        storage::set_unbonding_period(&env, period_secs);
         Ok(())
     }
 
+    /// Returns the current unbonding cooldown in seconds.
+    pub fn get_unbonding_period(env: Env) -> u64 {
+        storage::get_unbonding_period(&env)
+    }
+
     /// Distributes rewards proportionally to all active stakers based on their
     /// share of the total staked amount. The caller must approve the reward tokens
     /// to this contract before calling.
     ///
+    /// Runs in constant time: the reward is added to a global reward-per-share index
+    /// and each staker's share is settled lazily when they stake, unstake or claim.
+    ///
     /// This should be called by the admin periodically based on ticket sales volume,
     /// or by an authorized contract (e.g., TicketPayment) after settling fees.
     ///
     /// # Arguments
     /// * `caller` - Admin or authorized contract address
-    /// * `token` - Token to distribute as rewards (must match staking token)
     /// * `total_reward` - Total reward amount to distribute across all stakers
     pub fn distribute_staker_rewards(
         env: Env,
@@ -1421,6 +2530,14 @@ impl EventRegistry {
             return Err(EventRegistryError::NotStaked);
         }
 
+        let index_delta = total_reward
+            .checked_mul(REWARD_PRECISION)
+            .ok_or(EventRegistryError::InvalidRewardAmount)?
+            / total_staked;
+        let index = storage::get_reward_per_share(&env)
+            .checked_add(index_delta)
+            .ok_or(EventRegistryError::InvalidRewardAmount)?;
+
         // Transfer reward tokens from caller to this contract
         let token_client = token::Client::new(&env, &token);
         token_client.transfer_from(
@@ -1430,29 +2547,13 @@ impl EventRegistry {
             &total_reward,
         );
 
-        // Distribute proportionally to each staker
-        let stakers = storage::get_stakers_list(&env);
-        let staker_count = stakers.len();
-
-        for organizer in stakers.iter() {
-            if let Some(mut stake) = storage::get_organizer_stake(&env, &organizer) {
-                // reward = total_reward * stake.amount / total_staked
-                let reward = total_reward
-                    .checked_mul(stake.amount)
-                    .and_then(|v| v.checked_div(total_staked))
-                    .unwrap_or(0);
-                if reward > 0 {
-                    stake.reward_balance = stake.reward_balance.saturating_add(reward);
-                    storage::set_organizer_stake(&env, &stake);
-                }
-            }
-        }
+        storage::set_reward_per_share(&env, index);
 
         env.events().publish(
             (AgoraEvent::StakerRewardsDistributed,),
             StakerRewardsDistributedEvent {
                 total_reward,
-                staker_count,
+                staker_count: storage::get_staker_count(&env),
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -1469,6 +2570,7 @@ impl EventRegistry {
 
         let mut stake =
             storage::get_organizer_stake(&env, &organizer).ok_or(EventRegistryError::NotStaked)?;
+        settle_staker_rewards(&env, &mut stake);
 
         if stake.reward_balance == 0 {
             return Err(EventRegistryError::NoRewardsAvailable);
@@ -1487,6 +2589,7 @@ impl EventRegistry {
         stake.total_rewards_claimed = stake.total_rewards_claimed.saturating_add(reward_to_claim);
         stake.reward_balance = 0;
         storage::set_organizer_stake(&env, &stake);
+        remove_stake_if_empty(&env, &stake);
 
         env.events().publish(
             (AgoraEvent::StakerRewardsClaimed,),
@@ -1500,9 +2603,68 @@ impl EventRegistry {
         Ok(reward_to_claim)
     }
 
+    /// Pays up to `amount` from the guest protection pool to the TicketPayment contract
+    /// so it can finish refunds an event's escrow no longer covers.
+    /// Only callable by the authorized TicketPayment contract.
+    ///
+    /// Returns the amount actually transferred, which is capped at the pool balance.
+    pub fn cover_refund_shortfall(
+        env: Env,
+        caller: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, EventRegistryError> {
+        caller.require_auth();
+
+        let ticket_payment =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        if caller != ticket_payment {
+            return Err(EventRegistryError::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(EventRegistryError::InvalidStakeAmount);
+        }
+
+        let pool = storage::get_protection_pool(&env, &token);
+        let covered = core::cmp::min(pool, amount);
+        if covered <= 0 {
+            return Ok(0);
+        }
+
+        let remaining_pool = pool - covered;
+        storage::set_protection_pool(&env, &token, remaining_pool);
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &caller, &covered);
+
+        env.events().publish(
+            (AgoraEvent::ProtectionPoolDrawn,),
+            ProtectionPoolDrawnEvent {
+                recipient: caller,
+                token,
+                amount: covered,
+                remaining_pool,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(covered)
+    }
+
+    /// Returns the slashed collateral held in the guest protection pool for `token`.
+    pub fn get_protection_pool_balance(env: Env, token: Address) -> i128 {
+        storage::get_protection_pool(&env, &token)
+    }
+
+    /// Returns the total amount ever slashed from an organizer's stake.
+    pub fn get_total_slashed(env: Env, organizer: Address) -> i128 {
+        storage::get_total_slashed(&env, &organizer)
+    }
+
     /// Returns the stake record for an organizer, or None if not staked.
     pub fn get_organizer_stake(env: Env, organizer: Address) -> Option<OrganizerStake> {
         storage::get_organizer_stake(&env, &organizer)
+            .map(|stake| with_accrued_rewards(&env, stake))
     }
 
     /// Returns true if the organizer has staked the minimum required amount.
@@ -1512,112 +2674,375 @@ impl EventRegistry {
             .unwrap_or(false)
     }
 
-    /// Updates the loyalty score for a guest after a ticket purchase.
-    /// Callable by the admin or the authorized TicketPayment contract.
+    /// Updates the loyalty score for a guest after a ticket purchase.
+    /// Callable by the admin or the authorized TicketPayment contract.
+    ///
+    /// # Arguments
+    /// * `caller` - Admin or authorized TicketPayment contract address
+    /// * `guest` - Guest wallet address
+    /// * `tickets_purchased` - Number of tickets purchased in this transaction
+    /// * `amount_spent` - Amount spent in this transaction (in token stroops)
+    /// * `loyalty_multiplier` - Points multiplier from the purchased tier (0 or 1 = 1x, 2 = 2x, etc.)
+    pub fn update_loyalty_score(
+        env: Env,
+        caller: Address,
+        guest: Address,
+        tickets_purchased: u32,
+        amount_spent: i128,
+        loyalty_multiplier: u32,
+    ) -> Result<(), EventRegistryError> {
+        require_loyalty_updater(&env, &caller)?;
+
+        if tickets_purchased == 0 {
+            return Err(EventRegistryError::InvalidQuantity);
+        }
+
+        let now = env.ledger().timestamp();
+        let (mut profile, decayed_at) = current_guest_profile(&env, &guest).unwrap_or((
+            GuestProfile {
+                guest_address: guest.clone(),
+                loyalty_score: 0,
+                total_tickets_purchased: 0,
+                total_spent: 0,
+                last_updated: 0,
+            },
+            now,
+        ));
+        storage::set_score_decayed_at(&env, &guest, decayed_at);
+
+        let points_earned = loyalty_points_for(tickets_purchased, loyalty_multiplier);
+        profile.loyalty_score = profile.loyalty_score.saturating_add(points_earned);
+        profile.total_tickets_purchased = profile
+            .total_tickets_purchased
+            .saturating_add(tickets_purchased);
+        profile.total_spent = profile.total_spent.saturating_add(amount_spent);
+        profile.last_updated = env.ledger().timestamp();
+
+        storage::set_guest_profile(&env, &profile);
+
+        // Every point earned is also credited to the spendable balance.
+        let redeemable_points =
+            storage::get_redeemable_points(&env, &guest).saturating_add(points_earned);
+        storage::set_redeemable_points(&env, &guest, redeemable_points);
+
+        env.events().publish(
+            (AgoraEvent::LoyaltyScoreUpdated,),
+            LoyaltyScoreUpdatedEvent {
+                guest,
+                new_score: profile.loyalty_score,
+                points_earned,
+                redeemable_points,
+                tickets_purchased,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Takes back the loyalty points and spend a purchase earned after it is refunded.
+    /// Callable by the admin or the authorized TicketPayment contract.
+    ///
+    /// Points are removed pro rata to `refund_bps` of the points the tickets earned, so a
+    /// 20% partial refund removes 20% of them. Ticket counts only drop on a full refund
+    /// (`refund_bps == 10000`). Points already spent are floored at zero in the
+    /// redeemable balance.
+    ///
+    /// # Arguments
+    /// * `caller` - Admin or authorized TicketPayment contract address
+    /// * `guest` - Guest wallet address
+    /// * `tickets_refunded` - Number of tickets the refund applies to
+    /// * `amount_refunded` - Amount returned to the guest (in token stroops)
+    /// * `loyalty_multiplier` - Points multiplier of the refunded tier
+    /// * `refund_bps` - Share of each ticket's price that was refunded (10000 = full refund)
+    pub fn reverse_loyalty_score(
+        env: Env,
+        caller: Address,
+        guest: Address,
+        tickets_refunded: u32,
+        amount_refunded: i128,
+        loyalty_multiplier: u32,
+        refund_bps: u32,
+    ) -> Result<(), EventRegistryError> {
+        require_loyalty_updater(&env, &caller)?;
+
+        if tickets_refunded == 0 {
+            return Err(EventRegistryError::InvalidQuantity);
+        }
+        if refund_bps > 10_000 {
+            return Err(EventRegistryError::InvalidFeeCalculation);
+        }
+
+        let (mut profile, decayed_at) = match current_guest_profile(&env, &guest) {
+            Some(current) => current,
+            None => return Ok(()),
+        };
+        storage::set_score_decayed_at(&env, &guest, decayed_at);
+
+        let points_removed = loyalty_points_for(tickets_refunded, loyalty_multiplier)
+            .saturating_mul(refund_bps as u64)
+            / 10_000;
+        profile.loyalty_score = profile.loyalty_score.saturating_sub(points_removed);
+        if refund_bps == 10_000 {
+            profile.total_tickets_purchased = profile
+                .total_tickets_purchased
+                .saturating_sub(tickets_refunded);
+        }
+        profile.total_spent = profile
+            .total_spent
+            .saturating_sub(amount_refunded.max(0))
+            .max(0);
+        profile.last_updated = env.ledger().timestamp();
+        storage::set_guest_profile(&env, &profile);
+
+        let redeemable_points =
+            storage::get_redeemable_points(&env, &guest).saturating_sub(points_removed);
+        storage::set_redeemable_points(&env, &guest, redeemable_points);
+
+        env.events().publish(
+            (AgoraEvent::LoyaltyScoreReversed,),
+            LoyaltyScoreReversedEvent {
+                guest,
+                points_removed,
+                new_score: profile.loyalty_score,
+                redeemable_points,
+                amount_refunded,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the guest's spendable loyalty point balance.
+    pub fn get_redeemable_points(env: Env, guest: Address) -> u64 {
+        storage::get_redeemable_points(&env, &guest)
+    }
+
+    /// Lets guests pay part of an event's ticket price with redeemable loyalty points.
+    /// Only callable by the event organizer.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `InvalidPointsRedemptionConfig` - If an enabled config has a non-positive
+    ///   `point_value` or a `max_discount_bps` above 10000.
+    pub fn set_points_redemption_config(
+        env: Env,
+        event_id: String,
+        config: PointsRedemptionConfig,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
+        event_info.organizer_address.require_auth();
+
+        if config.enabled && (config.point_value <= 0 || config.max_discount_bps > 10_000) {
+            return Err(EventRegistryError::InvalidPointsRedemptionConfig);
+        }
+
+        storage::set_points_redemption_config(&env, event_id.clone(), &config);
+
+        env.events().publish(
+            (AgoraEvent::PointsRedemptionConfigured,),
+            PointsRedemptionConfiguredEvent {
+                event_id,
+                enabled: config.enabled,
+                point_value: config.point_value,
+                max_discount_bps: config.max_discount_bps,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the point redemption settings for an event, if the organizer set any.
+    pub fn get_points_redemption_config(
+        env: Env,
+        event_id: String,
+    ) -> Option<PointsRedemptionConfig> {
+        storage::get_points_redemption_config(&env, event_id)
+    }
+
+    /// Spends `points` of a guest's redeemable balance on a purchase for `event_id`.
+    /// Callable by the admin or the authorized TicketPayment contract, which applies
+    /// the matching price reduction.
     ///
-    /// # Arguments
-    /// * `caller` - Admin or authorized TicketPayment contract address
-    /// * `guest` - Guest wallet address
-    /// * `tickets_purchased` - Number of tickets purchased in this transaction
-    /// * `amount_spent` - Amount spent in this transaction (in token stroops)
-    /// * `loyalty_multiplier` - Points multiplier from the purchased tier (0 or 1 = 1x, 2 = 2x, etc.)
-    pub fn update_loyalty_score(
+    /// # Errors
+    /// * `PointsRedemptionDisabled` - If the event has not opted in.
+    /// * `InsufficientLoyaltyPoints` - If the guest's balance is below `points`.
+    pub fn redeem_loyalty_points(
         env: Env,
         caller: Address,
         guest: Address,
-        tickets_purchased: u32,
-        amount_spent: i128,
-        loyalty_multiplier: u32,
-    ) -> Result<(), EventRegistryError> {
-        caller.require_auth();
-
-        // Only admin or authorized ticket payment contract can update loyalty scores
-        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
-        let ticket_payment_contract = storage::get_ticket_payment_contract(&env);
-
-        let is_authorized = caller == admin
-            || ticket_payment_contract
-                .as_ref()
-                .map(|c| c == &caller)
-                .unwrap_or(false);
+        event_id: String,
+        points: u64,
+    ) -> Result<u64, EventRegistryError> {
+        require_loyalty_updater(&env, &caller)?;
 
-        if !is_authorized {
-            return Err(EventRegistryError::Unauthorized);
+        let enabled = storage::get_points_redemption_config(&env, event_id.clone())
+            .map(|c| c.enabled)
+            .unwrap_or(false);
+        if !enabled {
+            return Err(EventRegistryError::PointsRedemptionDisabled);
         }
-
-        if tickets_purchased == 0 {
+        if points == 0 {
             return Err(EventRegistryError::InvalidQuantity);
         }
 
-        let mut profile = storage::get_guest_profile(&env, &guest).unwrap_or(GuestProfile {
-            guest_address: guest.clone(),
-            loyalty_score: 0,
-            total_tickets_purchased: 0,
-            total_spent: 0,
-            last_updated: 0,
-        });
-
-        // Award 10 points per ticket, scaled by the tier's loyalty_multiplier.
-        // A multiplier of 0 is treated as 1x to avoid zeroing out points.
-        let effective_multiplier = if loyalty_multiplier == 0 {
-            1u64
-        } else {
-            loyalty_multiplier as u64
-        };
-        let points_earned = (tickets_purchased as u64)
-            .saturating_mul(10)
-            .saturating_mul(effective_multiplier);
-        profile.loyalty_score = profile.loyalty_score.saturating_add(points_earned);
-        profile.total_tickets_purchased = profile
-            .total_tickets_purchased
-            .saturating_add(tickets_purchased);
-        profile.total_spent = profile.total_spent.saturating_add(amount_spent);
-        profile.last_updated = env.ledger().timestamp();
-
-        storage::set_guest_profile(&env, &profile);
+        let balance = storage::get_redeemable_points(&env, &guest);
+        if balance < points {
+            return Err(EventRegistryError::InsufficientLoyaltyPoints);
+        }
+        let remaining_points = balance - points;
+        storage::set_redeemable_points(&env, &guest, remaining_points);
 
         env.events().publish(
-            (AgoraEvent::LoyaltyScoreUpdated,),
-            LoyaltyScoreUpdatedEvent {
+            (AgoraEvent::LoyaltyPointsRedeemed,),
+            LoyaltyPointsRedeemedEvent {
                 guest,
-                new_score: profile.loyalty_score,
-                tickets_purchased,
+                event_id,
+                points,
+                remaining_points,
                 timestamp: env.ledger().timestamp(),
             },
         );
 
-        Ok(())
+        Ok(remaining_points)
     }
 
     /// Returns the guest's loyalty profile, or None if no profile exists.
+    /// `loyalty_score` reflects any decay periods that have elapsed.
     pub fn get_guest_profile(env: Env, guest: Address) -> Option<GuestProfile> {
-        storage::get_guest_profile(&env, &guest)
+        current_guest_profile(&env, &guest).map(|(profile, _)| profile)
     }
 
     /// Returns the platform-fee discount in basis points for a guest based on
-    /// their current loyalty score.
+    /// their current (decayed) loyalty score and the configured tiers.
     ///
-    /// Score tiers:
+    /// Default tiers, until governance sets a `LoyaltyConfig`:
     /// - Score  0  –  99 : 0 bps  (no discount)
     /// - Score 100 – 499 : 250 bps (2.5% off platform fee)
     /// - Score 500 – 999 : 500 bps (5% off platform fee)
     /// - Score 1000+     : 1000 bps (10% off platform fee)
     pub fn get_loyalty_discount_bps(env: Env, guest: Address) -> u32 {
-        let score = storage::get_guest_profile(&env, &guest)
-            .map(|p| p.loyalty_score)
+        let score = current_guest_profile(&env, &guest)
+            .map(|(p, _)| p.loyalty_score)
             .unwrap_or(0);
 
-        if score >= 1000 {
-            1000
-        } else if score >= 500 {
-            500
-        } else if score >= 100 {
-            250
-        } else {
-            0
+        storage::get_loyalty_config(&env)
+            .tiers
+            .iter()
+            .filter(|t| score >= t.min_score)
+            .map(|t| t.discount_bps)
+            .last()
+            .unwrap_or(0)
+    }
+
+    /// Returns the platform loyalty tiers and decay settings.
+    pub fn get_loyalty_config(env: Env) -> LoyaltyConfig {
+        storage::get_loyalty_config(&env)
+    }
+
+    /// Sets or clears the caller's own loyalty ladder. Each rung grants a ticket
+    /// price discount once a guest has attended `min_events_attended` of the
+    /// organizer's events. Passing an empty list removes the program.
+    ///
+    /// # Errors
+    /// * `InvalidLoyaltyConfig` - If there are too many rungs, a rung requires zero
+    ///   events, discounts exceed 10000 bps, or rungs are not in ascending order.
+    pub fn set_organizer_loyalty_program(
+        env: Env,
+        organizer: Address,
+        rungs: Vec<LoyaltyRung>,
+    ) -> Result<(), EventRegistryError> {
+        organizer.require_auth();
+
+        if rungs.len() > MAX_LOYALTY_TIERS {
+            return Err(EventRegistryError::InvalidLoyaltyConfig);
+        }
+        let mut previous: Option<LoyaltyRung> = None;
+        for rung in rungs.iter() {
+            if rung.min_events_attended == 0 || rung.discount_bps > 10_000 {
+                return Err(EventRegistryError::InvalidLoyaltyConfig);
+            }
+            if let Some(prev) = previous {
+                if rung.min_events_attended <= prev.min_events_attended {
+                    return Err(EventRegistryError::InvalidLoyaltyConfig);
+                }
+            }
+            previous = Some(rung);
+        }
+
+        storage::set_organizer_loyalty_program(&env, &organizer, &rungs);
+
+        env.events().publish(
+            (AgoraEvent::OrganizerLoyaltyProgramUpdated,),
+            OrganizerLoyaltyProgramUpdatedEvent {
+                organizer,
+                rungs,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns an organizer's loyalty ladder (empty if none is configured).
+    pub fn get_organizer_loyalty_program(env: Env, organizer: Address) -> Vec<LoyaltyRung> {
+        storage::get_organizer_loyalty_program(&env, &organizer)
+    }
+
+    /// Counts a guest's attendance at an event towards its organizer's loyalty ladder.
+    /// Each event counts once per guest. Callable by the admin or the authorized
+    /// TicketPayment contract (on check-in). Returns the guest's attendance count.
+    pub fn record_attendance(
+        env: Env,
+        caller: Address,
+        guest: Address,
+        event_id: String,
+    ) -> Result<u32, EventRegistryError> {
+        require_loyalty_updater(&env, &caller)?;
+
+        let organizer = storage::get_event(&env, event_id.clone())
+            .ok_or(EventRegistryError::EventNotFound)?
+            .organizer_address;
+
+        match storage::record_organizer_attendance(&env, &organizer, &guest, event_id.clone()) {
+            Some(events_attended) => {
+                env.events().publish(
+                    (AgoraEvent::AttendanceRecorded,),
+                    AttendanceRecordedEvent {
+                        guest,
+                        event_id,
+                        organizer,
+                        events_attended,
+                        timestamp: env.ledger().timestamp(),
+                    },
+                );
+                Ok(events_attended)
+            }
+            None => Ok(storage::get_organizer_attendance(&env, &organizer, &guest)),
         }
     }
 
+    /// Returns how many of the organizer's events the guest has attended.
+    pub fn get_organizer_attendance(env: Env, organizer: Address, guest: Address) -> u32 {
+        storage::get_organizer_attendance(&env, &organizer, &guest)
+    }
+
+    /// Returns the ticket price discount (in basis points) the organizer's ladder
+    /// grants this guest, or 0 if the organizer runs no program.
+    pub fn get_organizer_discount_bps(env: Env, organizer: Address, guest: Address) -> u32 {
+        let attended = storage::get_organizer_attendance(&env, &organizer, &guest);
+        storage::get_organizer_loyalty_program(&env, &organizer)
+            .iter()
+            .filter(|r| attended >= r.min_events_attended)
+            .map(|r| r.discount_bps)
+            .last()
+            .unwrap_or(0)
+    }
+
     // ── Governance / Multi-Sig ─────────────────────────────────────────────────
 
     /// Returns the current multi-sig configuration
@@ -1640,55 +3065,6 @@ impl EventRegistry {
         }
     }
 
-    /// Sets the multi-sig configuration directly. Only callable by an existing admin.
-    ///
-    /// Validates that:
-    /// - `new_admins` is not empty
-    /// - All addresses in `new_admins` are valid
-    /// - `new_threshold` is at least 1
-    /// - `new_threshold` is not greater than the total number of admins
-    ///
-    /// # Arguments
-    /// * `caller` - An existing admin authorizing this change
-    /// * `new_admins` - The new list of admin addresses
-    /// * `new_threshold` - The new approval threshold
-    pub fn set_multisig_config(
-        env: Env,
-        caller: Address,
-        new_admins: Vec<Address>,
-        new_threshold: u32,
-    ) -> Result<(), EventRegistryError> {
-        caller.require_auth();
-
-        let config =
-            storage::get_multisig_config(&env).ok_or(EventRegistryError::NotInitialized)?;
-
-        if !config.admins.contains(&caller) {
-            return Err(EventRegistryError::Unauthorized);
-        }
-
-        if new_admins.is_empty() {
-            return Err(EventRegistryError::CannotRemoveLastAdmin);
-        }
-
-        for addr in new_admins.iter() {
-            validate_address(&env, &addr)?;
-        }
-
-        if new_threshold == 0 || new_threshold > new_admins.len() {
-            return Err(EventRegistryError::InvalidThreshold);
-        }
-
-        let multisig_config = MultiSigConfig {
-            admins: new_admins,
-            threshold: new_threshold,
-        };
-
-        storage::set_multisig_config(&env, &multisig_config);
-
-        Ok(())
-    }
-
     /// Proposes a parameter change. Only callable by an existing admin.
     /// The proposer automatically approves the proposal.
     ///
@@ -1750,6 +3126,28 @@ impl EventRegistry {
                     return Err(EventRegistryError::InvalidStakeAmount);
                 }
             }
+            types::ParameterChange::UpgradeContract(_) => {}
+            types::ParameterChange::SetLoyaltyConfig(loyalty_config) => {
+                validate_loyalty_config(loyalty_config)?;
+            }
+            types::ParameterChange::SetExecutionDelay(delay_secs) => {
+                if *delay_secs > MAX_EXECUTION_DELAY_SECS {
+                    return Err(EventRegistryError::InvalidExecutionDelay);
+                }
+            }
+            types::ParameterChange::TicketPayment(_) => {
+                if storage::get_ticket_payment_contract(&env).is_none() {
+                    return Err(EventRegistryError::NotInitialized);
+                }
+            }
+            types::ParameterChange::SlashStake(organizer, amount) => {
+                if *amount <= 0 {
+                    return Err(EventRegistryError::InvalidStakeAmount);
+                }
+                if storage::get_organizer_stake(&env, organizer).is_none() {
+                    return Err(EventRegistryError::NotStaked);
+                }
+            }
         }
 
         // Create proposal
@@ -1766,7 +3164,7 @@ impl EventRegistry {
         let mut approvals = Vec::new(&env);
         approvals.push_back(proposer.clone());
 
-        let proposal = types::Proposal {
+        let mut proposal = types::Proposal {
             proposal_id,
             proposer: proposer.clone(),
             change,
@@ -1776,7 +3174,24 @@ impl EventRegistry {
             expires_at: env.ledger().timestamp() + expiry,
         };
 
+        open_community_vote(&env, &mut proposal);
+
+        env.events().publish(
+            (AgoraEvent::ProposalCreated,),
+            ProposalCreatedEvent {
+                proposal_id,
+                proposer,
+                change: proposal.change.clone(),
+                expires_at: proposal.expires_at,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        let mut state = storage::get_proposal_state(&env, &proposal);
+        queue_if_threshold_met(&env, &config, &mut proposal, &mut state);
+
         storage::set_proposal(&env, &proposal);
+        storage::set_proposal_state(&env, proposal_id, &state);
         storage::add_active_proposal(&env, proposal_id);
 
         Ok(proposal_id)
@@ -1842,34 +3257,206 @@ impl EventRegistry {
         )
     }
 
-    /// Convenience function to propose updating the platform fee
-    pub fn propose_set_platform_fee(
-        env: Env,
-        proposer: Address,
-        new_fee_percent: u32,
-        expiry_ledgers: u64,
-    ) -> Result<u64, EventRegistryError> {
-        Self::propose_parameter_change(
-            env,
-            proposer,
-            types::ParameterChange::SetPlatformFee(new_fee_percent),
-            expiry_ledgers,
-        )
+    /// Convenience function to propose updating the platform fee
+    pub fn propose_set_platform_fee(
+        env: Env,
+        proposer: Address,
+        new_fee_percent: u32,
+        expiry_ledgers: u64,
+    ) -> Result<u64, EventRegistryError> {
+        Self::propose_parameter_change(
+            env,
+            proposer,
+            types::ParameterChange::SetPlatformFee(new_fee_percent),
+            expiry_ledgers,
+        )
+    }
+
+    /// Convenience function to propose updating the minimum stake amount
+    pub fn propose_set_min_stake_amount(
+        env: Env,
+        proposer: Address,
+        new_min_amount: i128,
+        expiry_ledgers: u64,
+    ) -> Result<u64, EventRegistryError> {
+        Self::propose_parameter_change(
+            env,
+            proposer,
+            types::ParameterChange::SetMinStakeAmount(new_min_amount),
+            expiry_ledgers,
+        )
+    }
+
+    /// Convenience function to propose a timelocked contract upgrade
+    pub fn propose_upgrade(
+        env: Env,
+        proposer: Address,
+        new_wasm_hash: BytesN<32>,
+        expiry_ledgers: u64,
+    ) -> Result<u64, EventRegistryError> {
+        Self::propose_parameter_change(
+            env,
+            proposer,
+            types::ParameterChange::UpgradeContract(new_wasm_hash),
+            expiry_ledgers,
+        )
+    }
+
+    /// Convenience function to propose new platform loyalty tiers and decay settings
+    pub fn propose_set_loyalty_config(
+        env: Env,
+        proposer: Address,
+        loyalty_config: LoyaltyConfig,
+        expiry_ledgers: u64,
+    ) -> Result<u64, EventRegistryError> {
+        Self::propose_parameter_change(
+            env,
+            proposer,
+            types::ParameterChange::SetLoyaltyConfig(loyalty_config),
+            expiry_ledgers,
+        )
+    }
+
+    /// Convenience function to propose slashing an organizer's stake, e.g. after a
+    /// cancelled or fraudulent event. The organizer cannot unstake while it is open.
+    pub fn propose_slash_stake(
+        env: Env,
+        proposer: Address,
+        organizer: Address,
+        amount: i128,
+        expiry_ledgers: u64,
+    ) -> Result<u64, EventRegistryError> {
+        Self::propose_parameter_change(
+            env,
+            proposer,
+            types::ParameterChange::SlashStake(organizer, amount),
+            expiry_ledgers,
+        )
+    }
+
+    /// Convenience function to propose a new execution delay for queued proposals
+    pub fn propose_set_execution_delay(
+        env: Env,
+        proposer: Address,
+        delay_secs: u64,
+        expiry_ledgers: u64,
+    ) -> Result<u64, EventRegistryError> {
+        Self::propose_parameter_change(
+            env,
+            proposer,
+            types::ParameterChange::SetExecutionDelay(delay_secs),
+            expiry_ledgers,
+        )
+    }
+
+    /// Convenience function to propose a TicketPayment parameter change. On execution
+    /// the change is applied to the registered TicketPayment contract.
+    pub fn propose_ticket_payment_change(
+        env: Env,
+        proposer: Address,
+        change: ticket_payment::PaymentParameterChange,
+        expiry_ledgers: u64,
+    ) -> Result<u64, EventRegistryError> {
+        Self::propose_parameter_change(
+            env,
+            proposer,
+            types::ParameterChange::TicketPayment(change),
+            expiry_ledgers,
+        )
+    }
+
+    /// Configures the optional community vote on `SetPlatformFee` and
+    /// `SetMinStakeAmount` proposals. Only callable by the admin; proposals that
+    /// already opened a vote keep the thresholds they were created with.
+    pub fn set_community_vote_config(
+        env: Env,
+        config: CommunityVoteConfig,
+    ) -> Result<(), EventRegistryError> {
+        require_admin(&env)?;
+
+        if config.quorum_bps > 10000
+            || config.approval_bps == 0
+            || config.approval_bps > 10000
+            || (config.enabled && config.voting_period_secs == 0)
+            || config.voting_period_secs > MAX_EXECUTION_DELAY_SECS
+        {
+            return Err(EventRegistryError::InvalidCommunityVoteConfig);
+        }
+
+        storage::set_community_vote_config(&env, &config);
+        Ok(())
+    }
+
+    /// Returns the community vote settings.
+    pub fn get_community_vote_config(env: Env) -> CommunityVoteConfig {
+        storage::get_community_vote_config(&env)
+    }
+
+    /// Casts a staked organizer's vote on a proposal's community vote, weighted by
+    /// the amount they had staked before the proposal was created.
+    pub fn cast_community_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        support: bool,
+    ) -> Result<(), EventRegistryError> {
+        voter.require_auth();
+
+        let proposal =
+            storage::get_proposal(&env, proposal_id).ok_or(EventRegistryError::ProposalNotFound)?;
+        if proposal.executed {
+            return Err(EventRegistryError::ProposalAlreadyExecuted);
+        }
+        require_open(&storage::get_proposal_state(&env, &proposal))?;
+
+        let mut vote = storage::get_community_vote(&env, proposal_id)
+            .ok_or(EventRegistryError::CommunityVoteNotFound)?;
+        if env.ledger().timestamp() > vote.ends_at {
+            return Err(EventRegistryError::CommunityVoteClosed);
+        }
+        if storage::has_community_voted(&env, proposal_id, &voter) {
+            return Err(EventRegistryError::AlreadyVoted);
+        }
+
+        let weight = storage::get_stake_before(&env, &voter, vote.snapshot_at);
+        if weight <= 0 {
+            return Err(EventRegistryError::NoVotingWeight);
+        }
+
+        if support {
+            vote.votes_for += weight;
+        } else {
+            vote.votes_against += weight;
+        }
+        storage::set_community_vote(&env, proposal_id, &vote);
+        storage::set_community_voted(&env, proposal_id, &voter);
+
+        env.events().publish(
+            (AgoraEvent::CommunityVoteCast,),
+            CommunityVoteCastEvent {
+                proposal_id,
+                voter,
+                support,
+                weight,
+                votes_for: vote.votes_for,
+                votes_against: vote.votes_against,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the community vote attached to a proposal, if any.
+    pub fn get_community_vote(env: Env, proposal_id: u64) -> Option<CommunityVote> {
+        storage::get_community_vote(&env, proposal_id)
     }
 
-    /// Convenience function to propose updating the minimum stake amount
-    pub fn propose_set_min_stake_amount(
-        env: Env,
-        proposer: Address,
-        new_min_amount: i128,
-        expiry_ledgers: u64,
-    ) -> Result<u64, EventRegistryError> {
-        Self::propose_parameter_change(
-            env,
-            proposer,
-            types::ParameterChange::SetMinStakeAmount(new_min_amount),
-            expiry_ledgers,
-        )
+    /// Returns the weight an organizer can vote with on a proposal's community vote
+    /// (0 if the proposal has none or the organizer had no stake at its snapshot).
+    pub fn get_voting_weight(env: Env, organizer: Address, proposal_id: u64) -> i128 {
+        storage::get_community_vote(&env, proposal_id)
+            .map(|vote| storage::get_stake_before(&env, &organizer, vote.snapshot_at))
+            .unwrap_or(0)
     }
 
     /// Approves a proposal. Only callable by an admin.
@@ -1902,14 +3489,140 @@ impl EventRegistry {
             return Err(EventRegistryError::ProposalExpired);
         }
 
+        let mut state = storage::get_proposal_state(&env, &proposal);
+        require_open(&state)?;
+
         // Check if already approved by this admin
         if proposal.approvals.contains(&approver) {
             return Err(EventRegistryError::AlreadyApproved);
         }
 
-        // Add approval
-        proposal.approvals.push_back(approver);
+        // Add approval, withdrawing an earlier rejection by the same admin
+        proposal.approvals.push_back(approver.clone());
+        if let Some(index) = state.rejections.first_index_of(&approver) {
+            state.rejections.remove(index);
+        }
+
+        env.events().publish(
+            (AgoraEvent::ProposalApproved,),
+            ProposalApprovedEvent {
+                proposal_id,
+                approver,
+                approvals: proposal.approvals.len(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        queue_if_threshold_met(&env, &config, &mut proposal, &mut state);
+        storage::set_proposal(&env, &proposal);
+        storage::set_proposal_state(&env, proposal_id, &state);
+
+        Ok(())
+    }
+
+    /// Votes against a proposal, withdrawing the admin's approval if they gave one.
+    /// Once so many admins reject it that the threshold can no longer be reached the
+    /// proposal is closed as `Rejected`. Only callable by an admin.
+    pub fn reject_proposal(
+        env: Env,
+        admin: Address,
+        proposal_id: u64,
+    ) -> Result<(), EventRegistryError> {
+        admin.require_auth();
+
+        let config =
+            storage::get_multisig_config(&env).ok_or(EventRegistryError::NotInitialized)?;
+        if !config.admins.contains(&admin) {
+            return Err(EventRegistryError::Unauthorized);
+        }
+
+        let mut proposal =
+            storage::get_proposal(&env, proposal_id).ok_or(EventRegistryError::ProposalNotFound)?;
+        if proposal.executed {
+            return Err(EventRegistryError::ProposalAlreadyExecuted);
+        }
+        if env.ledger().timestamp() > proposal.expires_at {
+            return Err(EventRegistryError::ProposalExpired);
+        }
+
+        let mut state = storage::get_proposal_state(&env, &proposal);
+        require_open(&state)?;
+        if state.rejections.contains(&admin) {
+            return Err(EventRegistryError::AlreadyRejected);
+        }
+
+        if let Some(index) = proposal.approvals.first_index_of(&admin) {
+            proposal.approvals.remove(index);
+        }
+        state.rejections.push_back(admin.clone());
+
+        // A queued proposal that lost its threshold goes back to collecting approvals.
+        if state.status == types::ProposalStatus::Queued
+            && proposal.approvals.len() < config.threshold
+        {
+            state.status = types::ProposalStatus::Pending;
+            state.executable_at = 0;
+        }
+
+        let defeated =
+            state.rejections.len() > config.admins.len().saturating_sub(config.threshold);
+        if defeated {
+            state.status = types::ProposalStatus::Rejected;
+            storage::remove_active_proposal(&env, proposal_id);
+        }
+
         storage::set_proposal(&env, &proposal);
+        storage::set_proposal_state(&env, proposal_id, &state);
+
+        env.events().publish(
+            (AgoraEvent::ProposalRejected,),
+            ProposalRejectedEvent {
+                proposal_id,
+                admin,
+                rejections: state.rejections.len(),
+                defeated,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Cancels a proposal that has not been executed. Callable by its proposer or
+    /// any admin, e.g. while a malicious change waits out the execution delay.
+    pub fn cancel_proposal(
+        env: Env,
+        caller: Address,
+        proposal_id: u64,
+    ) -> Result<(), EventRegistryError> {
+        caller.require_auth();
+
+        let proposal =
+            storage::get_proposal(&env, proposal_id).ok_or(EventRegistryError::ProposalNotFound)?;
+
+        let config =
+            storage::get_multisig_config(&env).ok_or(EventRegistryError::NotInitialized)?;
+        if caller != proposal.proposer && !config.admins.contains(&caller) {
+            return Err(EventRegistryError::Unauthorized);
+        }
+        if proposal.executed {
+            return Err(EventRegistryError::ProposalAlreadyExecuted);
+        }
+
+        let mut state = storage::get_proposal_state(&env, &proposal);
+        require_open(&state)?;
+
+        state.status = types::ProposalStatus::Cancelled;
+        storage::set_proposal_state(&env, proposal_id, &state);
+        storage::remove_active_proposal(&env, proposal_id);
+
+        env.events().publish(
+            (AgoraEvent::ProposalCancelled,),
+            ProposalCancelledEvent {
+                proposal_id,
+                cancelled_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
         Ok(())
     }
@@ -1945,11 +3658,35 @@ impl EventRegistry {
             return Err(EventRegistryError::ProposalExpired);
         }
 
+        let mut state = storage::get_proposal_state(&env, &proposal);
+        require_open(&state)?;
+
         // Check if threshold is met
         if proposal.approvals.len() < config.threshold {
             return Err(EventRegistryError::InsufficientApprovals);
         }
 
+        // Proposals that met a since-lowered threshold are queued now.
+        if state.status == types::ProposalStatus::Pending {
+            queue_if_threshold_met(&env, &config, &mut proposal, &mut state);
+            storage::set_proposal(&env, &proposal);
+            storage::set_proposal_state(&env, proposal_id, &state);
+        }
+        if env.ledger().timestamp() < state.executable_at {
+            return Err(EventRegistryError::ProposalTimelocked);
+        }
+
+        // Fee and staking proposals put to a community vote need it to have passed;
+        // the admins' execution then ratifies the result.
+        if let Some(vote) = storage::get_community_vote(&env, proposal_id) {
+            if env.ledger().timestamp() <= vote.ends_at {
+                return Err(EventRegistryError::CommunityVoteOpen);
+            }
+            if !community_vote_passed(&vote) {
+                return Err(EventRegistryError::CommunityVoteFailed);
+            }
+        }
+
         // Execute the proposal
         match &proposal.change {
             types::ParameterChange::AddAdmin(new_admin) => {
@@ -1994,14 +3731,70 @@ impl EventRegistry {
             }
             types::ParameterChange::SetMinStakeAmount(new_amount) => {
                 storage::set_min_stake_amount(&env, *new_amount);
+                refresh_verified_status(&env, *new_amount);
+            }
+            types::ParameterChange::UpgradeContract(wasm_hash) => {
+                let eta = env.ledger().timestamp() + UPGRADE_TIMELOCK_SECS;
+                storage::set_pending_upgrade(
+                    &env,
+                    &types::PendingUpgrade {
+                        wasm_hash: wasm_hash.clone(),
+                        eta,
+                    },
+                );
+                env.events().publish(
+                    (AgoraEvent::UpgradeScheduled,),
+                    UpgradeScheduledEvent {
+                        proposal_id,
+                        wasm_hash: wasm_hash.clone(),
+                        eta,
+                    },
+                );
+            }
+            types::ParameterChange::SetLoyaltyConfig(loyalty_config) => {
+                storage::set_loyalty_config(&env, loyalty_config);
+                env.events().publish(
+                    (AgoraEvent::LoyaltyConfigUpdated,),
+                    LoyaltyConfigUpdatedEvent {
+                        proposal_id,
+                        decay_period_secs: loyalty_config.decay_period_secs,
+                        decay_bps: loyalty_config.decay_bps,
+                        tiers: loyalty_config.tiers.clone(),
+                    },
+                );
+            }
+            types::ParameterChange::SlashStake(organizer, amount) => {
+                slash_stake(&env, proposal_id, organizer, *amount)?;
+            }
+            types::ParameterChange::SetExecutionDelay(delay_secs) => {
+                storage::set_execution_delay(&env, *delay_secs);
+            }
+            types::ParameterChange::TicketPayment(change) => {
+                let ticket_payment_addr = storage::get_ticket_payment_contract(&env)
+                    .ok_or(EventRegistryError::NotInitialized)?;
+                ticket_payment::Client::new(&env, &ticket_payment_addr)
+                    .try_apply_governance_change(&proposal_id, change)
+                    .map_err(|_| EventRegistryError::TicketPaymentChangeFailed)?
+                    .map_err(|_| EventRegistryError::TicketPaymentChangeFailed)?;
             }
         }
 
         // Mark as executed
         proposal.executed = true;
         storage::set_proposal(&env, &proposal);
+        state.status = types::ProposalStatus::Executed;
+        storage::set_proposal_state(&env, proposal_id, &state);
         storage::remove_active_proposal(&env, proposal_id);
 
+        env.events().publish(
+            (AgoraEvent::ProposalExecuted,),
+            ProposalExecutedEvent {
+                proposal_id,
+                executor,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
         Ok(())
     }
 
@@ -2010,6 +3803,33 @@ impl EventRegistry {
         storage::get_proposal(&env, proposal_id)
     }
 
+    /// Gets a proposal together with its status, reject votes and execution time.
+    pub fn get_proposal_record(env: Env, proposal_id: u64) -> Option<ProposalRecord> {
+        storage::get_proposal(&env, proposal_id).map(|p| proposal_record(&env, p))
+    }
+
+    /// Lists proposals in creation order, including executed, cancelled, rejected
+    /// and expired ones, starting at proposal ID `start`. At most `limit` records
+    /// (capped at `MAX_PROPOSAL_PAGE`) are returned.
+    pub fn get_proposals(env: Env, start: u64, limit: u32) -> Vec<ProposalRecord> {
+        let end = core::cmp::min(
+            storage::get_proposal_counter(&env),
+            start.saturating_add(core::cmp::min(limit, MAX_PROPOSAL_PAGE) as u64),
+        );
+        let mut records = Vec::new(&env);
+        for proposal_id in start..end {
+            if let Some(proposal) = storage::get_proposal(&env, proposal_id) {
+                records.push_back(proposal_record(&env, proposal));
+            }
+        }
+        records
+    }
+
+    /// Returns the delay between a proposal reaching its threshold and its execution.
+    pub fn get_execution_delay(env: Env) -> u64 {
+        storage::get_execution_delay(&env)
+    }
+
     /// Adds a token to the event-specific whitelist. Only callable by the event organizer.
     pub fn add_event_token_whitelist(
         env: Env,
@@ -2104,6 +3924,363 @@ fn require_admin(env: &Env) -> Result<Address, EventRegistryError> {
     Ok(admin)
 }
 
+/// Authorizes loyalty bookkeeping calls: only the admin or the registered
+/// TicketPayment contract may change guest points.
+fn require_loyalty_updater(env: &Env, caller: &Address) -> Result<(), EventRegistryError> {
+    caller.require_auth();
+
+    let admin = storage::get_admin(env).ok_or(EventRegistryError::NotInitialized)?;
+    let is_authorized = caller == &admin
+        || storage::get_ticket_payment_contract(env)
+            .map(|c| &c == caller)
+            .unwrap_or(false);
+
+    if !is_authorized {
+        return Err(EventRegistryError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Loads a guest's profile with elapsed decay periods applied to `loyalty_score`.
+/// Also returns the start of the decay period still in progress, which callers
+/// persist with `storage::set_score_decayed_at` when they write the profile back.
+fn current_guest_profile(env: &Env, guest: &Address) -> Option<(GuestProfile, u64)> {
+    let mut profile = storage::get_guest_profile(env, guest)?;
+    let now = env.ledger().timestamp();
+    let config = storage::get_loyalty_config(env);
+
+    if config.decay_period_secs == 0 || config.decay_bps == 0 {
+        return Some((profile, now));
+    }
+
+    let since = storage::get_score_decayed_at(env, guest).unwrap_or(profile.last_updated);
+    let periods = now.saturating_sub(since) / config.decay_period_secs;
+    let keep_bps = 10_000u64 - config.decay_bps as u64;
+    let mut score = profile.loyalty_score;
+    for _ in 0..periods.min(MAX_DECAY_PERIODS) {
+        if score == 0 {
+            break;
+        }
+        score = score.saturating_mul(keep_bps) / 10_000;
+    }
+    if periods > MAX_DECAY_PERIODS {
+        score = 0;
+    }
+    profile.loyalty_score = score;
+
+    Some((profile, since + periods * config.decay_period_secs))
+}
+
+/// Blacklisted organizers and organizers facing an open slash proposal cannot move
+/// collateral out of their stake.
+fn require_stake_unlocked(env: &Env, organizer: &Address) -> Result<(), EventRegistryError> {
+    if storage::is_blacklisted(env, organizer) {
+        return Err(EventRegistryError::OrganizerBlacklisted);
+    }
+    if has_pending_slash(env, organizer) {
+        return Err(EventRegistryError::SlashPending);
+    }
+    Ok(())
+}
+
+/// Whether any of the organizer's events is still active or holds escrow that the
+/// TicketPayment contract has not settled yet.
+fn has_unsettled_events(env: &Env, organizer: &Address) -> bool {
+    let ticket_payment = storage::get_ticket_payment_contract(env)
+        .map(|addr| ticket_payment::Client::new(env, &addr));
+
+    storage::get_organizer_events(env, organizer)
+        .iter()
+        .any(|event_id| {
+            let Some(event) = storage::get_event(env, event_id.clone()) else {
+                return false;
+            };
+            if event.is_active && event.status == EventStatus::Active {
+                return true;
+            }
+            ticket_payment.as_ref().is_some_and(|client| {
+                matches!(
+                    client.try_get_event_escrow_balance(&event_id),
+                    Ok(Ok(balance)) if balance.organizer_amount > 0 || balance.platform_fee > 0
+                )
+            })
+        })
+}
+
+/// Drops a stake record once nothing is staked, unbonding or owed as rewards.
+fn remove_stake_if_empty(env: &Env, stake: &OrganizerStake) {
+    if stake.amount == 0
+        && stake.reward_balance == 0
+        && storage::get_unbonding_entries(env, &stake.organizer).is_empty()
+    {
+        storage::remove_organizer_stake(env, &stake.organizer);
+        storage::remove_from_stakers_list(env, &stake.organizer);
+        storage::remove_reward_index_paid(env, &stake.organizer);
+    }
+}
+
+/// Rewards earned by `amount` staked tokens while the reward index moved by `index_delta`.
+/// Splits the multiplication so large stakes don't overflow the scaled index.
+fn pending_staker_reward(amount: i128, index_delta: i128) -> i128 {
+    let whole = (amount / REWARD_PRECISION).saturating_mul(index_delta);
+    let fraction = (amount % REWARD_PRECISION).saturating_mul(index_delta) / REWARD_PRECISION;
+    whole.saturating_add(fraction)
+}
+
+/// Returns the stake with rewards accrued since its last settlement added to
+/// `reward_balance`, without persisting anything.
+fn with_accrued_rewards(env: &Env, mut stake: OrganizerStake) -> OrganizerStake {
+    let index_delta =
+        storage::get_reward_per_share(env) - storage::get_reward_index_paid(env, &stake.organizer);
+    stake.reward_balance = stake
+        .reward_balance
+        .saturating_add(pending_staker_reward(stake.amount, index_delta));
+    stake
+}
+
+/// Credits accrued rewards to the stake and moves its settlement marker to the
+/// current index. Must run before the staked amount changes; the caller persists
+/// the stake.
+fn settle_staker_rewards(env: &Env, stake: &mut OrganizerStake) {
+    *stake = with_accrued_rewards(env, stake.clone());
+    storage::set_reward_index_paid(env, &stake.organizer, storage::get_reward_per_share(env));
+}
+
+/// Re-evaluates every staker's Verified status against a new minimum stake.
+#[allow(deprecated)]
+fn refresh_verified_status(env: &Env, min_amount: i128) {
+    for organizer in storage::get_stakers_list(env).iter() {
+        let Some(mut stake) = storage::get_organizer_stake(env, &organizer) else {
+            continue;
+        };
+        let is_verified = stake.amount > 0 && stake.amount >= min_amount;
+        if stake.is_verified == is_verified {
+            continue;
+        }
+        stake.is_verified = is_verified;
+        storage::set_organizer_stake(env, &stake);
+        env.events().publish(
+            (AgoraEvent::StakeVerificationUpdated,),
+            StakeVerificationUpdatedEvent {
+                organizer,
+                amount: stake.amount,
+                min_stake_amount: min_amount,
+                is_verified,
+            },
+        );
+    }
+}
+
+/// Hashes two payment tree nodes in sorted order, matching TicketPayment's tree.
+fn merkle_parent(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a.to_array() <= b.to_array() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let mut preimage = Bytes::from_array(env, &first.to_array());
+    preimage.extend_from_array(&second.to_array());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Fails with `ProposalClosed` once a proposal was cancelled or rejected.
+fn require_open(state: &types::ProposalState) -> Result<(), EventRegistryError> {
+    match state.status {
+        types::ProposalStatus::Cancelled | types::ProposalStatus::Rejected => {
+            Err(EventRegistryError::ProposalClosed)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Starts the execution delay once a pending proposal has enough approvals. While a
+/// delay is configured, the proposal's expiry is pushed back so it stays executable
+/// for a window after the delay.
+#[allow(deprecated)]
+fn queue_if_threshold_met(
+    env: &Env,
+    config: &MultiSigConfig,
+    proposal: &mut types::Proposal,
+    state: &mut types::ProposalState,
+) {
+    if state.status != types::ProposalStatus::Pending || proposal.approvals.len() < config.threshold
+    {
+        return;
+    }
+
+    let delay = storage::get_execution_delay(env);
+    state.status = types::ProposalStatus::Queued;
+    state.executable_at = env.ledger().timestamp() + delay;
+    let min_expiry = state.executable_at + PROPOSAL_EXECUTION_WINDOW_SECS;
+    if delay > 0 && proposal.expires_at < min_expiry {
+        proposal.expires_at = min_expiry;
+    }
+
+    env.events().publish(
+        (AgoraEvent::ProposalQueued,),
+        ProposalQueuedEvent {
+            proposal_id: proposal.proposal_id,
+            executable_at: state.executable_at,
+        },
+    );
+}
+
+/// Attaches a community vote to fee and staking proposals while community votes
+/// are enabled, keeping the proposal alive until the vote has ended.
+fn open_community_vote(env: &Env, proposal: &mut types::Proposal) {
+    let config = storage::get_community_vote_config(env);
+    let community_governed = matches!(
+        proposal.change,
+        types::ParameterChange::SetPlatformFee(_) | types::ParameterChange::SetMinStakeAmount(_)
+    );
+    if !config.enabled || !community_governed {
+        return;
+    }
+
+    let now = env.ledger().timestamp();
+    let vote = CommunityVote {
+        snapshot_at: now,
+        ends_at: now + config.voting_period_secs,
+        total_weight: storage::get_total_staked_before(env, now),
+        votes_for: 0,
+        votes_against: 0,
+        quorum_bps: config.quorum_bps,
+        approval_bps: config.approval_bps,
+    };
+    let min_expiry = vote.ends_at + PROPOSAL_EXECUTION_WINDOW_SECS;
+    if proposal.expires_at < min_expiry {
+        proposal.expires_at = min_expiry;
+    }
+    storage::set_community_vote(env, proposal.proposal_id, &vote);
+}
+
+/// Whether enough stake voted and enough of it voted in favour.
+fn community_vote_passed(vote: &CommunityVote) -> bool {
+    let cast = vote.votes_for + vote.votes_against;
+    let quorum_met = cast * 10000 >= vote.total_weight * vote.quorum_bps as i128;
+    let approved = vote.votes_for > 0 && vote.votes_for * 10000 >= cast * vote.approval_bps as i128;
+    quorum_met && approved
+}
+
+/// Combines a proposal with its lifecycle state, reporting open proposals past
+/// their expiry as `Expired`.
+fn proposal_record(env: &Env, proposal: types::Proposal) -> ProposalRecord {
+    let state = storage::get_proposal_state(env, &proposal);
+    let status = match state.status {
+        types::ProposalStatus::Pending | types::ProposalStatus::Queued
+            if env.ledger().timestamp() > proposal.expires_at =>
+        {
+            types::ProposalStatus::Expired
+        }
+        status => status,
+    };
+    ProposalRecord {
+        proposal,
+        status,
+        rejections: state.rejections,
+        executable_at: state.executable_at,
+    }
+}
+
+/// Whether an unexecuted, unexpired slash proposal targets `organizer`.
+fn has_pending_slash(env: &Env, organizer: &Address) -> bool {
+    let now = env.ledger().timestamp();
+    storage::get_active_proposals(env).iter().any(|id| {
+        storage::get_proposal(env, id).is_some_and(|p| {
+            !p.executed
+                && now <= p.expires_at
+                && matches!(&p.change, types::ParameterChange::SlashStake(target, _) if target == organizer)
+        })
+    })
+}
+
+/// Moves up to `amount` of the organizer's stake, then of their unbonding collateral,
+/// into the guest protection pool and re-evaluates their Verified status.
+#[allow(deprecated)]
+fn slash_stake(
+    env: &Env,
+    proposal_id: u64,
+    organizer: &Address,
+    amount: i128,
+) -> Result<(), EventRegistryError> {
+    let mut stake =
+        storage::get_organizer_stake(env, organizer).ok_or(EventRegistryError::NotStaked)?;
+
+    settle_staker_rewards(env, &mut stake);
+    let from_stake = core::cmp::min(amount, stake.amount);
+    stake.amount -= from_stake;
+    stake.is_verified = stake.amount > 0 && stake.amount >= storage::get_min_stake_amount(env);
+    storage::set_organizer_stake(env, &stake);
+    storage::subtract_from_total_staked(env, from_stake);
+    if stake.amount == 0 {
+        storage::remove_from_stakers_list(env, organizer);
+    }
+
+    // Collateral still unbonding is slashable too, newest requests first.
+    let mut slashed = from_stake;
+    let mut entries = storage::get_unbonding_entries(env, organizer);
+    while slashed < amount {
+        let Some(mut entry) = entries.pop_back() else {
+            break;
+        };
+        let take = core::cmp::min(amount - slashed, entry.amount);
+        slashed += take;
+        entry.amount -= take;
+        if entry.amount > 0 {
+            entries.push_back(entry);
+            break;
+        }
+    }
+    storage::set_unbonding_entries(env, organizer, &entries);
+
+    let pool = storage::get_protection_pool(env, &stake.token);
+    storage::set_protection_pool(env, &stake.token, pool + slashed);
+    storage::add_to_total_slashed(env, organizer, slashed);
+
+    env.events().publish(
+        (AgoraEvent::StakeSlashed,),
+        StakeSlashedEvent {
+            proposal_id,
+            organizer: organizer.clone(),
+            token: stake.token,
+            amount: slashed,
+            remaining_stake: stake.amount,
+            is_verified: stake.is_verified,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+fn validate_loyalty_config(config: &LoyaltyConfig) -> Result<(), EventRegistryError> {
+    if config.decay_bps > 10_000 || config.tiers.len() > MAX_LOYALTY_TIERS {
+        return Err(EventRegistryError::InvalidLoyaltyConfig);
+    }
+    let mut previous: Option<LoyaltyTier> = None;
+    for tier in config.tiers.iter() {
+        if tier.discount_bps > 10_000 {
+            return Err(EventRegistryError::InvalidLoyaltyConfig);
+        }
+        if let Some(prev) = previous {
+            if tier.min_score <= prev.min_score {
+                return Err(EventRegistryError::InvalidLoyaltyConfig);
+            }
+        }
+        previous = Some(tier);
+    }
+    Ok(())
+}
+
+/// Points earned by `tickets`: 10 per ticket, scaled by the tier's loyalty multiplier.
+/// A multiplier of 0 is treated as 1x to avoid zeroing out points.
+fn loyalty_points_for(tickets: u32, loyalty_multiplier: u32) -> u64 {
+    let effective_multiplier = loyalty_multiplier.max(1) as u64;
+    (tickets as u64)
+        .saturating_mul(10)
+        .saturating_mul(effective_multiplier)
+}
+
 fn validate_address(env: &Env, address: &Address) -> Result<(), EventRegistryError> {
     if address == &env.current_contract_address() || is_zero_address(env, address) {
         return Err(EventRegistryError::InvalidAddress);
@@ -2180,6 +4357,37 @@ fn validate_metadata_cid(env: &Env, cid: &String) -> Result<(), EventRegistryErr
     Ok(())
 }
 
+fn validate_revenue_split(env: &Env, payees: &Vec<Payee>) -> Result<(), EventRegistryError> {
+    if payees.is_empty() {
+        return Ok(());
+    }
+    if payees.len() > MAX_PAYEES {
+        return Err(EventRegistryError::InvalidRevenueSplit);
+    }
+
+    let mut total_bps: u32 = 0;
+    for (i, payee) in payees.iter().enumerate() {
+        validate_address(env, &payee.address)?;
+        if payee.share_bps == 0 {
+            return Err(EventRegistryError::InvalidRevenueSplit);
+        }
+        for other in payees.iter().skip(i + 1) {
+            if other.address == payee.address {
+                return Err(EventRegistryError::InvalidRevenueSplit);
+            }
+        }
+        total_bps = total_bps
+            .checked_add(payee.share_bps)
+            .ok_or(EventRegistryError::InvalidRevenueSplit)?;
+    }
+
+    if total_bps != 10000 {
+        return Err(EventRegistryError::InvalidRevenueSplit);
+    }
+
+    Ok(())
+}
+
 fn validate_restocking_fee(args: &EventRegistrationArgs) -> Result<(), EventRegistryError> {
     if args.restocking_fee < 0 {
         return Err(EventRegistryError::InvalidFeeCalculation);
@@ -0,0 +1,123 @@
+//! # Migration Module
+//!
+//! Rewrites stored records after an upgrade changes their layout. Each schema
+//! version that adds fields to a stored type keeps a frozen copy of the previous
+//! layout here, together with the step that converts it to the next version.
+//!
+//! | Version | Change |
+//! |---------|--------|
+//! | 1 | Initial layout |
+//! | 2 | `EventInfo.payees` (revenue split) |
+
+use crate::types::{DataKey, EventInfo, EventStatus, Milestone, TicketTier};
+use soroban_sdk::{contracttype, Address, Env, Map, String, Symbol, Val, Vec};
+
+/// Layout version of stored records written by this build of the contract.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// `EventInfo` as stored by schema version 1, before revenue splits.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventInfoV1 {
+    pub event_id: String,
+    pub name: String,
+    pub organizer_address: Address,
+    pub payment_address: Address,
+    pub platform_fee_percent: u32,
+    pub is_active: bool,
+    pub status: EventStatus,
+    pub created_at: u64,
+    pub metadata_cid: String,
+    pub max_supply: i128,
+    pub current_supply: i128,
+    pub milestone_plan: Option<Vec<Milestone>>,
+    pub tiers: Map<String, TicketTier>,
+    pub refund_deadline: u64,
+    pub restocking_fee: i128,
+    pub resale_cap_bps: Option<u32>,
+    pub is_postponed: bool,
+    pub grace_period_end: u64,
+    pub min_sales_target: i128,
+    pub target_deadline: u64,
+    pub goal_met: bool,
+    pub custom_fee_bps: Option<u32>,
+    pub banner_cid: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub start_time: u64,
+    pub is_private: bool,
+    pub end_time: u64,
+    pub transfer_lock_duration: u64,
+    pub accepted_tokens: Vec<Address>,
+    pub use_global_whitelist: bool,
+    pub feedback_cid: Option<String>,
+}
+
+impl EventInfoV1 {
+    fn into_v2(self, env: &Env) -> EventInfo {
+        EventInfo {
+            event_id: self.event_id,
+            name: self.name,
+            organizer_address: self.organizer_address,
+            payment_address: self.payment_address,
+            platform_fee_percent: self.platform_fee_percent,
+            is_active: self.is_active,
+            status: self.status,
+            created_at: self.created_at,
+            metadata_cid: self.metadata_cid,
+            max_supply: self.max_supply,
+            current_supply: self.current_supply,
+            milestone_plan: self.milestone_plan,
+            tiers: self.tiers,
+            refund_deadline: self.refund_deadline,
+            restocking_fee: self.restocking_fee,
+            resale_cap_bps: self.resale_cap_bps,
+            is_postponed: self.is_postponed,
+            grace_period_end: self.grace_period_end,
+            min_sales_target: self.min_sales_target,
+            target_deadline: self.target_deadline,
+            goal_met: self.goal_met,
+            custom_fee_bps: self.custom_fee_bps,
+            banner_cid: self.banner_cid,
+            tags: self.tags,
+            start_time: self.start_time,
+            is_private: self.is_private,
+            end_time: self.end_time,
+            transfer_lock_duration: self.transfer_lock_duration,
+            accepted_tokens: self.accepted_tokens,
+            use_global_whitelist: self.use_global_whitelist,
+            feedback_cid: self.feedback_cid,
+            payees: Vec::new(env),
+        }
+    }
+}
+
+/// Applies the step that moves stored records from `from_version` to the next
+/// version, returning how many records were rewritten.
+pub fn migrate_step(env: &Env, from_version: u32, event_ids: &Vec<String>) -> u32 {
+    match from_version {
+        1 => migrate_events_v1_to_v2(env, event_ids),
+        _ => 0,
+    }
+}
+
+/// Rewrites version 1 `EventInfo` records with an empty revenue split.
+/// Records already in the current layout, and unknown ids, are skipped.
+fn migrate_events_v1_to_v2(env: &Env, event_ids: &Vec<String>) -> u32 {
+    let mut migrated = 0u32;
+    for event_id in event_ids.iter() {
+        let key = DataKey::Event(event_id);
+        // Decoding a record into the wrong struct traps, so check its field names first.
+        let raw: Option<Map<Symbol, Val>> = env.storage().persistent().get(&key);
+        let Some(raw) = raw else {
+            continue;
+        };
+        if raw.contains_key(Symbol::new(env, "payees")) {
+            continue;
+        }
+        let legacy: EventInfoV1 = env.storage().persistent().get(&key).unwrap();
+        // Written directly: `storage::update_event` would try to decode the old record.
+        env.storage().persistent().set(&key, &legacy.into_v2(env));
+        migrated += 1;
+    }
+    migrated
+}
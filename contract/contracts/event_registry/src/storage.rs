@@ -13,7 +13,7 @@
 //! as event records, organizer indexes, and governance proposals.
 //!
 //! There is **no** use of env.storage().instance() or env.storage().temporary()
-//! in this module.
+//! in this module; the `ttl` module only extends the instance's TTL.
 //!
 //! ## Storage keys
 //!
@@ -57,6 +57,86 @@
 //! | TokenWhitelist(addr) | bool | Payment token whitelist membership flag |
 //! | GlobalEventCount | u32 | Cumulative count of all registered events |
 //! | GlobalTicketsSold | i128 | Cumulative count of all tickets sold |
+//! | PendingUpgrade | PendingUpgrade | Governance-approved upgrade awaiting its timelock |
+//! | SchemaVersion | u32 | Layout version of stored records |
+//! | RedeemablePoints(addr) | u64 | Spendable loyalty point balance per guest |
+//! | PointsRedemption(event_id) | PointsRedemptionConfig | Per-event point redemption opt-in |
+//! | SalesStarted(event_id) | bool | Event has sold a ticket, even if since refunded |
+//!
+//! Loyalty program entries live under the separate [`LoyaltyKey`] enum:
+//!
+//! | Key | Value type | Description |
+//! |-----|-----------|-------------|
+//! | LoyaltyConfig | LoyaltyConfig | Platform loyalty tiers and score decay |
+//! | ScoreDecayedAt(guest) | u64 | Start of the guest's pending decay period |
+//! | OrganizerProgram(organizer) | Vec<LoyaltyRung> | Organizer-run loyalty ladder |
+//! | OrganizerAttendance(organizer, guest) | u32 | Organizer events attended by a guest |
+//! | GuestEventAttended(guest, event_id) | bool | Attendance already counted for an event |
+//!
+//! Proposal lifecycle entries live under [`GovernanceKey`]:
+//!
+//! | Key | Value type | Description |
+//! |-----|-----------|-------------|
+//! | ExecutionDelay | u64 | Delay between reaching the threshold and execution |
+//! | ProposalState(id) | ProposalState | Status, reject votes and execution time |
+//! | CommunityVoteConfig | CommunityVoteConfig | Settings for stake-weighted votes |
+//! | CommunityVote(id) | CommunityVote | Stake-weighted vote attached to a proposal |
+//! | CommunityVoteCast(id, organizer) | bool | Organizer already voted on the proposal |
+//!
+//! TTL policy overrides live under [`TtlKey`]; see the `ttl` module for the
+//! extension strategy:
+//!
+//! | Key | Value type | Description |
+//! |-----|-----------|-------------|
+//! | Policy(family) | TtlPolicy | Admin override of a family's TTL thresholds |
+//!
+//! Invite-only allowlists live under [`AllowlistKey`]:
+//!
+//! | Key | Value type | Description |
+//! |-----|-----------|-------------|
+//! | EventRoot(event_id) | BytesN<32> | Allowlist root for every tier of a private event |
+//! | TierRoot(event_id, tier_id) | TierAllowlist | Allowlist root and hidden flag for one tier |
+//!
+//! Tier attestation requirements live under [`AttestationKey`]:
+//!
+//! | Key | Value type | Description |
+//! |-----|-----------|-------------|
+//! | Tier(event_id, tier_id) | TierAttestation | Attester and claim required to hold a ticket |
+//!
+//! Tier pricing options live under [`PricingKey`]:
+//!
+//! | Key | Value type | Description |
+//! |-----|-----------|-------------|
+//! | PayWhatYouWant(event_id, tier_id) | bool | Tier price is a floor rather than a fixed price |
+//! | Deposit(event_id, tier_id) | TierDeposit | Refundable deposit for RSVPs to a free tier |
+//! | Installments(event_id, tier_id) | InstallmentPlan | Installment deadlines and forfeiture penalty |
+//!
+//! Event add-ons live under [`AddOnKey`]:
+//!
+//! | Key | Value type | Description |
+//! |-----|-----------|-------------|
+//! | Item(event_id, addon_id) | AddOn | Price, stock, sales and tier restriction of an item |
+//! | Ids(event_id) | Vec<String> | Add-on IDs offered by the event |
+//!
+//! Archived event data lives under [`ArchiveKey`]:
+//!
+//! | Key | Value type | Description |
+//! |-----|-----------|-------------|
+//! | PaymentsCommitment(event_id) | PaymentsCommitment | Merkle root over the event's payments |
+//!
+//! Stake slashing entries live under [`StakingKey`]:
+//!
+//! | Key | Value type | Description |
+//! |-----|-----------|-------------|
+//! | ProtectionPool(token) | i128 | Slashed collateral available for refund shortfalls |
+//! | TotalSlashed(organizer) | i128 | Cumulative amount slashed from an organizer |
+//! | UnbondingPeriod | u64 | Cooldown before unstaked collateral can be withdrawn |
+//! | Unbonding(organizer) | Vec<UnbondingEntry> | Collateral waiting out the cooldown |
+//! | RewardPerShare | i128 | Cumulative staker rewards per staked token |
+//! | RewardIndexPaid(organizer) | i128 | Reward index at the staker's last settlement |
+//! | StakerCount | u32 | Length of StakersList, readable without loading it |
+//! | StakeHistory(organizer) | Vec<StakeCheckpoint> | Organizer's staked amount over time |
+//! | TotalStakeHistory | Vec<StakeCheckpoint> | Total staked amount over time |
 //!
 //! ## Sharding strategy
 //!
@@ -65,11 +145,18 @@
 //! growing unboundedly and exceeding Soroban per-entry size limits. The shard
 //! index for a new item is count / SHARD_SIZE, where count is the current
 //! total for that organizer.
+use crate::ticket_payment::PaymentsCommitment;
+use crate::ttl;
 use crate::types::{
-    BlacklistAuditEntry, DataKey, EventInfo, GuestProfile, MultiSigConfig, OrganizerStake, Proposal,
+    AddOn, AddOnKey, AllowlistKey, ArchiveKey, AttestationKey, BlacklistAuditEntry, CommunityVote,
+    CommunityVoteConfig, DataKey, EventInfo, GovernanceKey, GuestProfile, InstallmentPlan,
+    LoyaltyConfig, LoyaltyKey, LoyaltyRung, LoyaltyTier, MultiSigConfig, OrganizerStake,
+    PendingUpgrade, PointsRedemptionConfig, PricingKey, Proposal, ProposalState, ProposalStatus,
+    StakeCheckpoint, StakingKey, TierAllowlist, TierAttestation, TierDeposit, TtlFamily, TtlKey,
+    TtlPolicy, UnbondingEntry,
 };
 use crate::types::{SeriesPass, SeriesRegistry};
-use soroban_sdk::{vec, Address, Env, String, Vec};
+use soroban_sdk::{vec, Address, BytesN, Env, String, Vec};
 // ── Series Storage ────────────────────────────────────────────────────────────
 /// Persists a SeriesRegistry and indexes every event it contains.
 /// Storage keys: DataKey::Series(series_id) and DataKey::SeriesEvent(series_id, event_id).
@@ -90,7 +177,12 @@ pub fn store_series(env: &Env, series: &SeriesRegistry) {
 /// Returns the SeriesRegistry for the given series_id, or None.
 /// Storage key: DataKey::Series(series_id). Storage type: Persistent
 pub fn get_series(env: &Env, series_id: String) -> Option<SeriesRegistry> {
-    env.storage().persistent().get(&DataKey::Series(series_id))
+    let key = DataKey::Series(series_id);
+    let series = env.storage().persistent().get(&key);
+    if series.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Series);
+    }
+    series
 }
 
 /// Returns true if event_id is a member of series_id. O(1) lookup via DataKey::SeriesEvent.
@@ -118,9 +210,12 @@ pub fn store_series_pass(env: &Env, pass: &SeriesPass) {
 /// Returns the SeriesPass with the given pass_id, or None.
 /// Storage key: DataKey::SeriesPass(pass_id). Storage type: Persistent
 pub fn get_series_pass(env: &Env, pass_id: String) -> Option<SeriesPass> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::SeriesPass(pass_id))
+    let key = DataKey::SeriesPass(pass_id);
+    let pass = env.storage().persistent().get(&key);
+    if pass.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Series);
+    }
+    pass
 }
 
 pub fn get_holder_series_pass(
@@ -136,9 +231,7 @@ pub fn get_holder_series_pass(
             series_id.clone(),
         ))
     {
-        env.storage()
-            .persistent()
-            .get(&DataKey::SeriesPass(pass_id))
+        get_series_pass(env, pass_id)
     } else {
         None
     }
@@ -213,6 +306,39 @@ pub fn get_multisig_config(env: &Env) -> Option<MultiSigConfig> {
     env.storage().persistent().get(&DataKey::MultiSigConfig)
 }
 
+/// Stores the governance-approved upgrade awaiting its timelock.
+pub fn set_pending_upgrade(env: &Env, pending: &PendingUpgrade) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingUpgrade, pending);
+}
+
+/// Retrieves the scheduled upgrade, if any.
+pub fn get_pending_upgrade(env: &Env) -> Option<PendingUpgrade> {
+    env.storage().persistent().get(&DataKey::PendingUpgrade)
+}
+
+/// Clears the scheduled upgrade.
+pub fn remove_pending_upgrade(env: &Env) {
+    env.storage().persistent().remove(&DataKey::PendingUpgrade);
+}
+
+/// Returns the layout version of stored records. Deployments that predate
+/// schema versioning report version 1.
+pub fn get_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(1)
+}
+
+/// Sets the layout version of stored records.
+pub fn set_schema_version(env: &Env, version: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SchemaVersion, &version);
+}
+
 /// Checks if an address is an admin.
 pub fn is_admin(env: &Env, address: &Address) -> bool {
     if let Some(config) = get_multisig_config(env) {
@@ -337,6 +463,377 @@ pub fn get_proposal(env: &Env, proposal_id: u64) -> Option<Proposal> {
         .get(&DataKey::Proposal(proposal_id))
 }
 
+/// Gets the lifecycle state of a proposal. Proposals created before state was
+/// tracked default to `Executed` or `Pending` from their `executed` flag.
+pub fn get_proposal_state(env: &Env, proposal: &Proposal) -> ProposalState {
+    env.storage()
+        .persistent()
+        .get(&GovernanceKey::ProposalState(proposal.proposal_id))
+        .unwrap_or_else(|| ProposalState {
+            status: if proposal.executed {
+                ProposalStatus::Executed
+            } else {
+                ProposalStatus::Pending
+            },
+            rejections: Vec::new(env),
+            executable_at: 0,
+        })
+}
+
+/// Stores the lifecycle state of a proposal.
+pub fn set_proposal_state(env: &Env, proposal_id: u64, state: &ProposalState) {
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::ProposalState(proposal_id), state);
+}
+
+/// Gets the delay between a proposal reaching its threshold and its execution.
+pub fn get_execution_delay(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&GovernanceKey::ExecutionDelay)
+        .unwrap_or(0)
+}
+
+/// Sets the delay between a proposal reaching its threshold and its execution.
+pub fn set_execution_delay(env: &Env, delay_secs: u64) {
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::ExecutionDelay, &delay_secs);
+}
+
+/// Gets the admin override of a family's TTL policy.
+pub fn get_ttl_policy(env: &Env, family: &TtlFamily) -> Option<TtlPolicy> {
+    env.storage()
+        .persistent()
+        .get(&TtlKey::Policy(family.clone()))
+}
+
+/// Stores the admin override of a family's TTL policy.
+pub fn set_ttl_policy(env: &Env, family: &TtlFamily, policy: &TtlPolicy) {
+    env.storage()
+        .persistent()
+        .set(&TtlKey::Policy(family.clone()), policy);
+}
+
+/// Extends every entry kept for an event, live or archived: the record or
+/// receipt, per-event settings, allowlists and attestations, the payments commitment and the
+/// organizer's event and receipt indices.
+pub fn extend_event_entries(
+    env: &Env,
+    event_id: String,
+    organizer: &Address,
+    tier_ids: &Vec<String>,
+    accepted_tokens: &Vec<Address>,
+) {
+    let event = TtlFamily::Event;
+    ttl::extend(env, &DataKey::Event(event_id.clone()), &event);
+    ttl::extend(env, &DataKey::EventReceipt(event_id.clone()), &event);
+    ttl::extend(env, &DataKey::PointsRedemption(event_id.clone()), &event);
+    ttl::extend(env, &DataKey::SalesStarted(event_id.clone()), &event);
+    ttl::extend(
+        env,
+        &ArchiveKey::PaymentsCommitment(event_id.clone()),
+        &event,
+    );
+    ttl::extend(env, &AllowlistKey::EventRoot(event_id.clone()), &event);
+    for tier_id in tier_ids.iter() {
+        ttl::extend(
+            env,
+            &AllowlistKey::TierRoot(event_id.clone(), tier_id.clone()),
+            &event,
+        );
+        ttl::extend(
+            env,
+            &AttestationKey::Tier(event_id.clone(), tier_id.clone()),
+            &event,
+        );
+        ttl::extend(
+            env,
+            &PricingKey::PayWhatYouWant(event_id.clone(), tier_id.clone()),
+            &event,
+        );
+        ttl::extend(
+            env,
+            &PricingKey::Deposit(event_id.clone(), tier_id.clone()),
+            &event,
+        );
+        ttl::extend(
+            env,
+            &PricingKey::Installments(event_id.clone(), tier_id),
+            &event,
+        );
+    }
+    for token in accepted_tokens.iter() {
+        ttl::extend(
+            env,
+            &DataKey::EventTokenWhitelist(event_id.clone(), token),
+            &event,
+        );
+    }
+    for addon_id in get_add_on_ids(env, event_id.clone()).iter() {
+        ttl::extend(env, &AddOnKey::Item(event_id.clone(), addon_id), &event);
+    }
+    ttl::extend(env, &AddOnKey::Ids(event_id.clone()), &event);
+
+    let index = TtlFamily::OrganizerIndex;
+    ttl::extend(
+        env,
+        &DataKey::OrganizerEvent(organizer.clone(), event_id.clone()),
+        &index,
+    );
+    ttl::extend(
+        env,
+        &DataKey::OrganizerReceipt(organizer.clone(), event_id),
+        &index,
+    );
+    ttl::extend(
+        env,
+        &DataKey::OrganizerEventCount(organizer.clone()),
+        &index,
+    );
+    ttl::extend(
+        env,
+        &DataKey::OrganizerReceiptCount(organizer.clone()),
+        &index,
+    );
+    for shard_id in 0..get_organizer_event_count(env, organizer).div_ceil(SHARD_SIZE) {
+        ttl::extend(
+            env,
+            &DataKey::OrganizerEventShard(organizer.clone(), shard_id),
+            &index,
+        );
+    }
+    for shard_id in 0..get_organizer_receipt_count(env, organizer).div_ceil(SHARD_SIZE) {
+        ttl::extend(
+            env,
+            &DataKey::OrganizerReceiptShard(organizer.clone(), shard_id),
+            &index,
+        );
+    }
+}
+
+/// Gets the Merkle commitment to an archived event's payments.
+pub fn get_payments_commitment(env: &Env, event_id: String) -> Option<PaymentsCommitment> {
+    env.storage()
+        .persistent()
+        .get(&ArchiveKey::PaymentsCommitment(event_id))
+}
+
+/// Stores the Merkle commitment to an archived event's payments.
+pub fn set_payments_commitment(env: &Env, event_id: String, commitment: &PaymentsCommitment) {
+    env.storage()
+        .persistent()
+        .set(&ArchiveKey::PaymentsCommitment(event_id), commitment);
+}
+
+/// Gets the allowlist root covering every tier of an event.
+pub fn get_event_allowlist(env: &Env, event_id: String) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&AllowlistKey::EventRoot(event_id))
+}
+
+/// Stores or clears the allowlist root covering every tier of an event.
+pub fn set_event_allowlist(env: &Env, event_id: String, root: &Option<BytesN<32>>) {
+    let key = AllowlistKey::EventRoot(event_id);
+    match root {
+        Some(root) => env.storage().persistent().set(&key, root),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Gets the allowlist of a single tier.
+pub fn get_tier_allowlist(env: &Env, event_id: String, tier_id: String) -> Option<TierAllowlist> {
+    env.storage()
+        .persistent()
+        .get(&AllowlistKey::TierRoot(event_id, tier_id))
+}
+
+/// Stores or clears the allowlist of a single tier.
+pub fn set_tier_allowlist(
+    env: &Env,
+    event_id: String,
+    tier_id: String,
+    allowlist: &Option<TierAllowlist>,
+) {
+    let key = AllowlistKey::TierRoot(event_id, tier_id);
+    match allowlist {
+        Some(allowlist) => env.storage().persistent().set(&key, allowlist),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Gets the attestation a tier requires of ticket holders.
+pub fn get_tier_attestation(
+    env: &Env,
+    event_id: String,
+    tier_id: String,
+) -> Option<TierAttestation> {
+    env.storage()
+        .persistent()
+        .get(&AttestationKey::Tier(event_id, tier_id))
+}
+
+/// Stores or clears the attestation a tier requires of ticket holders.
+pub fn set_tier_attestation(
+    env: &Env,
+    event_id: String,
+    tier_id: String,
+    requirement: &Option<TierAttestation>,
+) {
+    let key = AttestationKey::Tier(event_id, tier_id);
+    match requirement {
+        Some(requirement) => env.storage().persistent().set(&key, requirement),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Returns whether a tier accepts any amount at or above its price.
+pub fn is_pay_what_you_want(env: &Env, event_id: String, tier_id: String) -> bool {
+    env.storage()
+        .persistent()
+        .get(&PricingKey::PayWhatYouWant(event_id, tier_id))
+        .unwrap_or(false)
+}
+
+/// Marks a tier's price as a floor, or restores fixed pricing.
+pub fn set_pay_what_you_want(env: &Env, event_id: String, tier_id: String, enabled: bool) {
+    let key = PricingKey::PayWhatYouWant(event_id, tier_id);
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Returns an event's add-on, if offered.
+pub fn get_add_on(env: &Env, event_id: String, addon_id: String) -> Option<AddOn> {
+    env.storage()
+        .persistent()
+        .get(&AddOnKey::Item(event_id, addon_id))
+}
+
+/// Stores an event's add-on, indexing its ID the first time it is offered.
+pub fn set_add_on(env: &Env, event_id: String, addon_id: String, add_on: &AddOn) {
+    let key = AddOnKey::Item(event_id.clone(), addon_id.clone());
+    if !env.storage().persistent().has(&key) {
+        let mut ids = get_add_on_ids(env, event_id.clone());
+        ids.push_back(addon_id);
+        env.storage()
+            .persistent()
+            .set(&AddOnKey::Ids(event_id), &ids);
+    }
+    env.storage().persistent().set(&key, add_on);
+}
+
+/// Returns the IDs of every add-on an event offers.
+pub fn get_add_on_ids(env: &Env, event_id: String) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&AddOnKey::Ids(event_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Returns the deposit a free tier asks of guests, if any.
+pub fn get_tier_deposit(env: &Env, event_id: String, tier_id: String) -> Option<TierDeposit> {
+    env.storage()
+        .persistent()
+        .get(&PricingKey::Deposit(event_id, tier_id))
+}
+
+/// Sets or clears the deposit a free tier asks of guests.
+pub fn set_tier_deposit(
+    env: &Env,
+    event_id: String,
+    tier_id: String,
+    deposit: &Option<TierDeposit>,
+) {
+    let key = PricingKey::Deposit(event_id, tier_id);
+    match deposit {
+        Some(deposit) => env.storage().persistent().set(&key, deposit),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Returns the installment plan a tier offers, if any.
+pub fn get_installment_plan(
+    env: &Env,
+    event_id: String,
+    tier_id: String,
+) -> Option<InstallmentPlan> {
+    env.storage()
+        .persistent()
+        .get(&PricingKey::Installments(event_id, tier_id))
+}
+
+/// Sets or clears the installment plan a tier offers.
+pub fn set_installment_plan(
+    env: &Env,
+    event_id: String,
+    tier_id: String,
+    plan: &Option<InstallmentPlan>,
+) {
+    let key = PricingKey::Installments(event_id, tier_id);
+    match plan {
+        Some(plan) => env.storage().persistent().set(&key, plan),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Gets the community vote settings; votes are disabled until configured.
+pub fn get_community_vote_config(env: &Env) -> CommunityVoteConfig {
+    env.storage()
+        .persistent()
+        .get(&GovernanceKey::CommunityVoteConfig)
+        .unwrap_or(CommunityVoteConfig {
+            enabled: false,
+            voting_period_secs: 0,
+            quorum_bps: 0,
+            approval_bps: 0,
+        })
+}
+
+/// Sets the community vote settings.
+pub fn set_community_vote_config(env: &Env, config: &CommunityVoteConfig) {
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::CommunityVoteConfig, config);
+}
+
+/// Gets the community vote attached to a proposal, if any.
+pub fn get_community_vote(env: &Env, proposal_id: u64) -> Option<CommunityVote> {
+    env.storage()
+        .persistent()
+        .get(&GovernanceKey::CommunityVote(proposal_id))
+}
+
+/// Stores the community vote attached to a proposal.
+pub fn set_community_vote(env: &Env, proposal_id: u64, vote: &CommunityVote) {
+    env.storage()
+        .persistent()
+        .set(&GovernanceKey::CommunityVote(proposal_id), vote);
+}
+
+/// Whether `voter` already voted on the proposal's community vote.
+pub fn has_community_voted(env: &Env, proposal_id: u64, voter: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&GovernanceKey::CommunityVoteCast(
+            proposal_id,
+            voter.clone(),
+        ))
+        .unwrap_or(false)
+}
+
+/// Records that `voter` voted on the proposal's community vote.
+pub fn set_community_voted(env: &Env, proposal_id: u64, voter: &Address) {
+    env.storage().persistent().set(
+        &GovernanceKey::CommunityVoteCast(proposal_id, voter.clone()),
+        &true,
+    );
+}
+
 /// Retrieves all active proposal IDs.
 pub fn get_active_proposals(env: &Env) -> Vec<u64> {
     env.storage()
@@ -453,8 +950,14 @@ pub fn update_event(env: &Env, event_info: EventInfo) {
 }
 
 /// Integrates storage functions to get, remove events and handle their receipts.
+/// Reading an event extends its TTL.
 pub fn get_event(env: &Env, event_id: String) -> Option<EventInfo> {
-    env.storage().persistent().get(&DataKey::Event(event_id))
+    let key = DataKey::Event(event_id);
+    let event_info = env.storage().persistent().get(&key);
+    if event_info.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Event);
+    }
+    event_info
 }
 
 /// Removes an event and cleans up organizer indexes
@@ -679,6 +1182,20 @@ pub fn get_ticket_payment_contract(env: &Env) -> Option<Address> {
         .get(&DataKey::TicketPaymentContract)
 }
 
+/// Whether an event has ever sold a ticket; stays set after refunds.
+pub fn has_sales_started(env: &Env, event_id: String) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SalesStarted(event_id))
+        .unwrap_or(false)
+}
+
+pub fn set_sales_started(env: &Env, event_id: String) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SalesStarted(event_id), &true);
+}
+
 /// Checks if an organizer is blacklisted.
 pub fn is_blacklisted(env: &Env, organizer: &Address) -> bool {
     env.storage()
@@ -775,9 +1292,12 @@ pub fn is_scanner_authorized(env: &Env, event_id: String, scanner: &Address) ->
 
 /// Retrieves a guest's loyalty profile.
 pub fn get_guest_profile(env: &Env, guest: &Address) -> Option<GuestProfile> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::GuestProfile(guest.clone()))
+    let key = DataKey::GuestProfile(guest.clone());
+    let profile = env.storage().persistent().get(&key);
+    if profile.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Guest);
+    }
+    profile
 }
 
 /// Stores (creates or updates) a guest's loyalty profile.
@@ -788,6 +1308,134 @@ pub fn set_guest_profile(env: &Env, profile: &GuestProfile) {
     );
 }
 
+/// Returns a guest's spendable loyalty point balance (0 if none).
+pub fn get_redeemable_points(env: &Env, guest: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RedeemablePoints(guest.clone()))
+        .unwrap_or(0)
+}
+
+/// Stores a guest's spendable loyalty point balance.
+pub fn set_redeemable_points(env: &Env, guest: &Address, points: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::RedeemablePoints(guest.clone()), &points);
+}
+
+/// Retrieves the point redemption settings for an event.
+pub fn get_points_redemption_config(env: &Env, event_id: String) -> Option<PointsRedemptionConfig> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PointsRedemption(event_id))
+}
+
+/// Stores the point redemption settings for an event.
+pub fn set_points_redemption_config(env: &Env, event_id: String, config: &PointsRedemptionConfig) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PointsRedemption(event_id), config);
+}
+
+/// Returns the platform loyalty settings. Defaults to no decay and the original
+/// 100/500/1000 score tiers (2.5%/5%/10% off the platform fee).
+pub fn get_loyalty_config(env: &Env) -> LoyaltyConfig {
+    env.storage()
+        .persistent()
+        .get(&LoyaltyKey::LoyaltyConfig)
+        .unwrap_or_else(|| LoyaltyConfig {
+            decay_period_secs: 0,
+            decay_bps: 0,
+            tiers: vec![
+                env,
+                LoyaltyTier {
+                    min_score: 100,
+                    discount_bps: 250,
+                },
+                LoyaltyTier {
+                    min_score: 500,
+                    discount_bps: 500,
+                },
+                LoyaltyTier {
+                    min_score: 1000,
+                    discount_bps: 1000,
+                },
+            ],
+        })
+}
+
+/// Stores the platform loyalty settings.
+pub fn set_loyalty_config(env: &Env, config: &LoyaltyConfig) {
+    env.storage()
+        .persistent()
+        .set(&LoyaltyKey::LoyaltyConfig, config);
+}
+
+/// Returns when the guest's pending decay period started, if decay was ever anchored.
+pub fn get_score_decayed_at(env: &Env, guest: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&LoyaltyKey::ScoreDecayedAt(guest.clone()))
+}
+
+/// Stores when the guest's pending decay period started.
+pub fn set_score_decayed_at(env: &Env, guest: &Address, timestamp: u64) {
+    env.storage()
+        .persistent()
+        .set(&LoyaltyKey::ScoreDecayedAt(guest.clone()), &timestamp);
+}
+
+/// Returns an organizer's loyalty ladder (empty if the organizer runs none).
+pub fn get_organizer_loyalty_program(env: &Env, organizer: &Address) -> Vec<LoyaltyRung> {
+    env.storage()
+        .persistent()
+        .get(&LoyaltyKey::OrganizerProgram(organizer.clone()))
+        .unwrap_or(vec![env])
+}
+
+/// Stores an organizer's loyalty ladder; an empty ladder removes the entry.
+pub fn set_organizer_loyalty_program(env: &Env, organizer: &Address, rungs: &Vec<LoyaltyRung>) {
+    let key = LoyaltyKey::OrganizerProgram(organizer.clone());
+    if rungs.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, rungs);
+    }
+}
+
+/// Returns how many of the organizer's events the guest has attended.
+pub fn get_organizer_attendance(env: &Env, organizer: &Address, guest: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&LoyaltyKey::OrganizerAttendance(
+            organizer.clone(),
+            guest.clone(),
+        ))
+        .unwrap_or(0)
+}
+
+/// Counts the guest's attendance at `event_id` towards the organizer's ladder.
+/// Returns the updated count, or `None` if this event was already counted.
+pub fn record_organizer_attendance(
+    env: &Env,
+    organizer: &Address,
+    guest: &Address,
+    event_id: String,
+) -> Option<u32> {
+    let seen_key = LoyaltyKey::GuestEventAttended(guest.clone(), event_id);
+    if env.storage().persistent().has(&seen_key) {
+        return None;
+    }
+    env.storage().persistent().set(&seen_key, &true);
+
+    let count = get_organizer_attendance(env, organizer, guest).saturating_add(1);
+    env.storage().persistent().set(
+        &LoyaltyKey::OrganizerAttendance(organizer.clone(), guest.clone()),
+        &count,
+    );
+    Some(count)
+}
+
 /// Retrieves an organizer's stake record.
 pub fn get_organizer_stake(env: &Env, organizer: &Address) -> Option<OrganizerStake> {
     env.storage()
@@ -795,8 +1443,9 @@ pub fn get_organizer_stake(env: &Env, organizer: &Address) -> Option<OrganizerSt
         .get(&DataKey::OrganizerStake(organizer.clone()))
 }
 
-/// Stores (creates or updates) an organizer's stake record.
+/// Stores (creates or updates) an organizer's stake record and checkpoints its amount.
 pub fn set_organizer_stake(env: &Env, stake: &OrganizerStake) {
+    record_stake_checkpoint(env, &stake.organizer, stake.amount);
     env.storage()
         .persistent()
         .set(&DataKey::OrganizerStake(stake.organizer.clone()), stake);
@@ -804,11 +1453,126 @@ pub fn set_organizer_stake(env: &Env, stake: &OrganizerStake) {
 
 /// Removes an organizer's stake record (used on unstake).
 pub fn remove_organizer_stake(env: &Env, organizer: &Address) {
+    record_stake_checkpoint(env, organizer, 0);
     env.storage()
         .persistent()
         .remove(&DataKey::OrganizerStake(organizer.clone()));
 }
 
+/// Appends `amount` to the organizer's stake history. Stakes that predate the
+/// history are seeded from the stored record first.
+fn record_stake_checkpoint(env: &Env, organizer: &Address, amount: i128) {
+    let key = StakingKey::StakeHistory(organizer.clone());
+    let mut history: Vec<StakeCheckpoint> =
+        env.storage().persistent().get(&key).unwrap_or_else(|| {
+            let mut seeded = Vec::new(env);
+            if let Some(previous) = get_organizer_stake(env, organizer) {
+                seeded.push_back(StakeCheckpoint {
+                    timestamp: previous.staked_at,
+                    amount: previous.amount,
+                });
+            }
+            seeded
+        });
+    if push_checkpoint(env, &mut history, amount) {
+        env.storage().persistent().set(&key, &history);
+    }
+}
+
+/// Appends `amount` to the total stake history, seeding it with the running
+/// total for deployments that predate the history.
+fn record_total_stake_checkpoint(env: &Env, previous_total: i128, amount: i128) {
+    let mut history: Vec<StakeCheckpoint> = env
+        .storage()
+        .persistent()
+        .get(&StakingKey::TotalStakeHistory)
+        .unwrap_or_else(|| {
+            let mut seeded = Vec::new(env);
+            seeded.push_back(StakeCheckpoint {
+                timestamp: 0,
+                amount: previous_total,
+            });
+            seeded
+        });
+    if push_checkpoint(env, &mut history, amount) {
+        env.storage()
+            .persistent()
+            .set(&StakingKey::TotalStakeHistory, &history);
+    }
+}
+
+/// Records `amount` as of the current ledger, replacing a checkpoint written in
+/// the same ledger. Returns false when the amount did not change.
+fn push_checkpoint(env: &Env, history: &mut Vec<StakeCheckpoint>, amount: i128) -> bool {
+    let now = env.ledger().timestamp();
+    if let Some(last) = history.last() {
+        if last.amount == amount {
+            return false;
+        }
+        if last.timestamp == now {
+            history.set(
+                history.len() - 1,
+                StakeCheckpoint {
+                    timestamp: now,
+                    amount,
+                },
+            );
+            return true;
+        }
+    }
+    history.push_back(StakeCheckpoint {
+        timestamp: now,
+        amount,
+    });
+    true
+}
+
+/// Amount of the last checkpoint written strictly before `timestamp`.
+fn checkpoint_before(history: &Vec<StakeCheckpoint>, timestamp: u64) -> i128 {
+    // Binary search for the first checkpoint at or after `timestamp`.
+    let (mut low, mut high) = (0u32, history.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if history.get(mid).unwrap().timestamp < timestamp {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        0
+    } else {
+        history.get(low - 1).unwrap().amount
+    }
+}
+
+/// Gets the amount an organizer had staked just before `timestamp`.
+pub fn get_stake_before(env: &Env, organizer: &Address, timestamp: u64) -> i128 {
+    match env
+        .storage()
+        .persistent()
+        .get::<_, Vec<StakeCheckpoint>>(&StakingKey::StakeHistory(organizer.clone()))
+    {
+        Some(history) => checkpoint_before(&history, timestamp),
+        None => get_organizer_stake(env, organizer)
+            .filter(|stake| stake.staked_at < timestamp)
+            .map(|stake| stake.amount)
+            .unwrap_or(0),
+    }
+}
+
+/// Gets the total amount staked just before `timestamp`.
+pub fn get_total_staked_before(env: &Env, timestamp: u64) -> i128 {
+    match env
+        .storage()
+        .persistent()
+        .get::<_, Vec<StakeCheckpoint>>(&StakingKey::TotalStakeHistory)
+    {
+        Some(history) => checkpoint_before(&history, timestamp),
+        None => get_total_staked(env),
+    }
+}
+
 /// Gets the minimum stake amount required for Verified status.
 pub fn get_min_stake_amount(env: &Env) -> i128 {
     env.storage()
@@ -847,6 +1611,7 @@ pub fn get_total_staked(env: &Env) -> i128 {
 /// Adds `amount` to the total staked counter.
 pub fn add_to_total_staked(env: &Env, amount: i128) {
     let current = get_total_staked(env);
+    record_total_stake_checkpoint(env, current, current + amount);
     env.storage()
         .persistent()
         .set(&DataKey::TotalStaked, &(current + amount));
@@ -856,11 +1621,114 @@ pub fn add_to_total_staked(env: &Env, amount: i128) {
 pub fn subtract_from_total_staked(env: &Env, amount: i128) {
     let current = get_total_staked(env);
     let new_val = current.saturating_sub(amount);
+    record_total_stake_checkpoint(env, current, new_val);
     env.storage()
         .persistent()
         .set(&DataKey::TotalStaked, &new_val);
 }
 
+/// Gets the guest protection pool balance for `token`.
+pub fn get_protection_pool(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&StakingKey::ProtectionPool(token.clone()))
+        .unwrap_or(0)
+}
+
+/// Sets the guest protection pool balance for `token`.
+pub fn set_protection_pool(env: &Env, token: &Address, amount: i128) {
+    env.storage()
+        .persistent()
+        .set(&StakingKey::ProtectionPool(token.clone()), &amount);
+}
+
+/// Gets the cumulative amount slashed from an organizer's stake.
+pub fn get_total_slashed(env: &Env, organizer: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&StakingKey::TotalSlashed(organizer.clone()))
+        .unwrap_or(0)
+}
+
+/// Adds `amount` to the organizer's cumulative slashed total.
+pub fn add_to_total_slashed(env: &Env, organizer: &Address, amount: i128) {
+    let current = get_total_slashed(env, organizer);
+    env.storage().persistent().set(
+        &StakingKey::TotalSlashed(organizer.clone()),
+        &(current + amount),
+    );
+}
+
+/// Gets the unbonding cooldown in seconds, defaulting to seven days.
+pub fn get_unbonding_period(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&StakingKey::UnbondingPeriod)
+        .unwrap_or(604_800)
+}
+
+/// Sets the unbonding cooldown in seconds.
+pub fn set_unbonding_period(env: &Env, period_secs: u64) {
+    env.storage()
+        .persistent()
+        .set(&StakingKey::UnbondingPeriod, &period_secs);
+}
+
+/// Gets the organizer's collateral still in the unbonding queue.
+pub fn get_unbonding_entries(env: &Env, organizer: &Address) -> Vec<UnbondingEntry> {
+    env.storage()
+        .persistent()
+        .get(&StakingKey::Unbonding(organizer.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Stores the organizer's unbonding queue; an empty queue removes the entry.
+pub fn set_unbonding_entries(env: &Env, organizer: &Address, entries: &Vec<UnbondingEntry>) {
+    let key = StakingKey::Unbonding(organizer.clone());
+    if entries.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, entries);
+    }
+}
+
+/// Gets the cumulative staker rewards per staked token.
+pub fn get_reward_per_share(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&StakingKey::RewardPerShare)
+        .unwrap_or(0)
+}
+
+/// Sets the cumulative staker rewards per staked token.
+pub fn set_reward_per_share(env: &Env, index: i128) {
+    env.storage()
+        .persistent()
+        .set(&StakingKey::RewardPerShare, &index);
+}
+
+/// Gets the reward index an organizer's rewards were last settled at.
+pub fn get_reward_index_paid(env: &Env, organizer: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&StakingKey::RewardIndexPaid(organizer.clone()))
+        .unwrap_or(0)
+}
+
+/// Records the reward index an organizer's rewards were settled at.
+pub fn set_reward_index_paid(env: &Env, organizer: &Address, index: i128) {
+    env.storage()
+        .persistent()
+        .set(&StakingKey::RewardIndexPaid(organizer.clone()), &index);
+}
+
+/// Removes an organizer's reward settlement marker.
+pub fn remove_reward_index_paid(env: &Env, organizer: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&StakingKey::RewardIndexPaid(organizer.clone()));
+}
+
 /// Gets the list of all currently staked organizer addresses.
 pub fn get_stakers_list(env: &Env) -> Vec<Address> {
     env.storage()
@@ -879,6 +1747,9 @@ pub fn add_to_stakers_list(env: &Env, organizer: &Address) {
     }
     list.push_back(organizer.clone());
     env.storage().persistent().set(&DataKey::StakersList, &list);
+    env.storage()
+        .persistent()
+        .set(&StakingKey::StakerCount, &list.len());
 }
 
 /// Removes an organizer from the stakers list.
@@ -893,6 +1764,18 @@ pub fn remove_from_stakers_list(env: &Env, organizer: &Address) {
     env.storage()
         .persistent()
         .set(&DataKey::StakersList, &new_list);
+    env.storage()
+        .persistent()
+        .set(&StakingKey::StakerCount, &new_list.len());
+}
+
+/// Gets the number of stakers without loading the full list. Falls back to the
+/// list length for stakers lists written before the counter existed.
+pub fn get_staker_count(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&StakingKey::StakerCount)
+        .unwrap_or_else(|| get_stakers_list(env).len())
 }
 
 // ── Token Whitelist Storage ───────────────────────────────────────────────────
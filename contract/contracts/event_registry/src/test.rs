@@ -1,7 +1,10 @@
 use super::*;
 use crate::error::EventRegistryError;
 use crate::types::EventStatus;
-use crate::types::{EventInfo, EventReceipt, EventRegistrationArgs, TicketTier};
+use crate::types::{
+    CommunityVoteConfig, EventInfo, EventReceipt, EventRegistrationArgs, LoyaltyConfig,
+    LoyaltyRung, LoyaltyTier, PointsRedemptionConfig, TicketTier,
+};
 use soroban_sdk::{
     testutils::{Address as _, EnvTestConfig, Events, Ledger},
     Address, Env, Map, String,
@@ -328,6 +331,7 @@ fn test_storage_operations() {
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
         feedback_cid: None,
+        payees: soroban_sdk::Vec::new(&env),
     };
 
     client.store_event(&event_info);
@@ -427,6 +431,7 @@ fn test_get_total_tickets_sold_uses_event_current_supply() {
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
         feedback_cid: None,
+        payees: soroban_sdk::Vec::new(&env),
     });
 
     assert_eq!(client.get_total_tickets_sold(&event_id), 9);
@@ -543,6 +548,7 @@ fn test_organizer_events_list() {
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
         feedback_cid: None,
+        payees: soroban_sdk::Vec::new(&env),
     };
 
     let event_2 = EventInfo {
@@ -580,6 +586,7 @@ fn test_organizer_events_list() {
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
         feedback_cid: None,
+        payees: soroban_sdk::Vec::new(&env),
     };
 
     let contract_id = env.register(EventRegistry, ());
@@ -644,6 +651,7 @@ fn test_get_organizer_receipts_returns_archived_receipts() {
             accepted_tokens: soroban_sdk::Vec::new(&env),
             use_global_whitelist: true,
             feedback_cid: None,
+            payees: soroban_sdk::Vec::new(&env),
         };
 
     let event_id_1 = String::from_str(&env, "archived_1");
@@ -2600,6 +2608,7 @@ fn test_increment_inventory_supply_overflow() {
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
         feedback_cid: None,
+        payees: soroban_sdk::Vec::new(&env),
     });
 
     let result = client.try_increment_inventory(&event_id, &tier_id, &Address::generate(&env), &1);
@@ -2676,6 +2685,7 @@ fn test_increment_inventory_tier_sold_overflow() {
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
         feedback_cid: None,
+        payees: soroban_sdk::Vec::new(&env),
     });
 
     let result = client.try_increment_inventory(&event_id, &tier_id, &Address::generate(&env), &1);
@@ -3614,6 +3624,278 @@ fn test_loyalty_discount_bps_tiers() {
     assert_eq!(client.get_loyalty_discount_bps(&guest), 1000);
 }
 
+// ── Loyalty Reversal & Redemption ────────────────────────────────
+
+#[test]
+fn test_reverse_loyalty_score_full_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let guest = Address::generate(&env);
+    client.update_loyalty_score(&admin, &guest, &3, &3000i128, &2u32); // 60 pts
+    assert_eq!(client.get_redeemable_points(&guest), 60);
+
+    client.reverse_loyalty_score(&admin, &guest, &1, &1000i128, &2u32, &10_000u32);
+
+    let profile = client.get_guest_profile(&guest).unwrap();
+    assert_eq!(profile.loyalty_score, 40);
+    assert_eq!(profile.total_tickets_purchased, 2);
+    assert_eq!(profile.total_spent, 2000);
+    assert_eq!(client.get_redeemable_points(&guest), 40);
+}
+
+#[test]
+fn test_reverse_loyalty_score_partial_refund_prorates_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let guest = Address::generate(&env);
+    client.update_loyalty_score(&admin, &guest, &1, &1000i128, &1u32); // 10 pts
+
+    // 20% refund removes 2 of the ticket's 10 points but keeps the ticket count.
+    client.reverse_loyalty_score(&admin, &guest, &1, &200i128, &1u32, &2_000u32);
+
+    let profile = client.get_guest_profile(&guest).unwrap();
+    assert_eq!(profile.loyalty_score, 8);
+    assert_eq!(profile.total_tickets_purchased, 1);
+    assert_eq!(profile.total_spent, 800);
+}
+
+#[test]
+fn test_reverse_loyalty_score_floors_spent_points_at_zero() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let organizer = Address::generate(&env);
+    client.register_event(&base_args(&env, &organizer, None));
+    let event_id = String::from_str(&env, "evt_milestone");
+    client.set_points_redemption_config(
+        &event_id,
+        &PointsRedemptionConfig {
+            enabled: true,
+            point_value: 100,
+            max_discount_bps: 1000,
+        },
+    );
+
+    let guest = Address::generate(&env);
+    client.update_loyalty_score(&admin, &guest, &1, &1000i128, &1u32);
+    client.redeem_loyalty_points(&admin, &guest, &event_id, &8u64);
+
+    client.reverse_loyalty_score(&admin, &guest, &1, &1000i128, &1u32, &10_000u32);
+    assert_eq!(client.get_redeemable_points(&guest), 0);
+    assert_eq!(client.get_guest_profile(&guest).unwrap().loyalty_score, 0);
+}
+
+#[test]
+fn test_reverse_loyalty_score_unauthorized_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_loyalty_env(&env);
+
+    let guest = Address::generate(&env);
+    let random_caller = Address::generate(&env);
+    let result =
+        client.try_reverse_loyalty_score(&random_caller, &guest, &1, &1000i128, &1u32, &10_000u32);
+    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+}
+
+#[test]
+fn test_redeem_loyalty_points_requires_opt_in_and_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let organizer = Address::generate(&env);
+    client.register_event(&base_args(&env, &organizer, None));
+    let event_id = String::from_str(&env, "evt_milestone");
+
+    let guest = Address::generate(&env);
+    client.update_loyalty_score(&admin, &guest, &5, &5000i128, &1u32); // 50 pts
+
+    assert_eq!(
+        client.try_redeem_loyalty_points(&admin, &guest, &event_id, &10u64),
+        Err(Ok(EventRegistryError::PointsRedemptionDisabled))
+    );
+
+    let invalid = PointsRedemptionConfig {
+        enabled: true,
+        point_value: 0,
+        max_discount_bps: 1000,
+    };
+    assert_eq!(
+        client.try_set_points_redemption_config(&event_id, &invalid),
+        Err(Ok(EventRegistryError::InvalidPointsRedemptionConfig))
+    );
+
+    let config = PointsRedemptionConfig {
+        enabled: true,
+        point_value: 100,
+        max_discount_bps: 2000,
+    };
+    client.set_points_redemption_config(&event_id, &config);
+    assert_eq!(client.get_points_redemption_config(&event_id), Some(config));
+
+    assert_eq!(
+        client.try_redeem_loyalty_points(&admin, &guest, &event_id, &51u64),
+        Err(Ok(EventRegistryError::InsufficientLoyaltyPoints))
+    );
+
+    let remaining = client.redeem_loyalty_points(&admin, &guest, &event_id, &30u64);
+    assert_eq!(remaining, 20);
+    assert_eq!(client.get_redeemable_points(&guest), 20);
+    // Spending points does not lower the loyalty score used for fee discounts.
+    assert_eq!(client.get_guest_profile(&guest).unwrap().loyalty_score, 50);
+}
+
+// ── Loyalty Decay & Organizer Ladders ────────────────────────────
+
+#[test]
+fn test_loyalty_score_decays_per_period_under_governed_tiers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let config = LoyaltyConfig {
+        decay_period_secs: 2_592_000, // 30 days
+        decay_bps: 5_000,
+        tiers: soroban_sdk::vec![
+            &env,
+            LoyaltyTier {
+                min_score: 50,
+                discount_bps: 300,
+            },
+            LoyaltyTier {
+                min_score: 100,
+                discount_bps: 800,
+            },
+        ],
+    };
+    let proposal_id = client.propose_set_loyalty_config(&admin, &config, &0);
+    client.execute_proposal(&admin, &proposal_id);
+    assert_eq!(client.get_loyalty_config(), config);
+
+    let guest = Address::generate(&env);
+    client.update_loyalty_score(&admin, &guest, &10, &1000i128, &1u32); // 100 pts
+    assert_eq!(client.get_loyalty_discount_bps(&guest), 800);
+
+    // A partial period does not decay the score.
+    env.ledger().set_timestamp(2_000_000);
+    assert_eq!(client.get_guest_profile(&guest).unwrap().loyalty_score, 100);
+
+    // One full period halves it.
+    env.ledger().set_timestamp(2_592_000);
+    assert_eq!(client.get_guest_profile(&guest).unwrap().loyalty_score, 50);
+    assert_eq!(client.get_loyalty_discount_bps(&guest), 300);
+
+    // New points land on top of the decayed score; the period in progress is kept.
+    client.update_loyalty_score(&admin, &guest, &1, &100i128, &1u32);
+    assert_eq!(client.get_guest_profile(&guest).unwrap().loyalty_score, 60);
+    env.ledger().set_timestamp(2 * 2_592_000);
+    assert_eq!(client.get_guest_profile(&guest).unwrap().loyalty_score, 30);
+    assert_eq!(client.get_loyalty_discount_bps(&guest), 0);
+}
+
+#[test]
+fn test_loyalty_config_rejects_unsorted_tiers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let config = LoyaltyConfig {
+        decay_period_secs: 0,
+        decay_bps: 0,
+        tiers: soroban_sdk::vec![
+            &env,
+            LoyaltyTier {
+                min_score: 500,
+                discount_bps: 500,
+            },
+            LoyaltyTier {
+                min_score: 100,
+                discount_bps: 250,
+            },
+        ],
+    };
+    assert_eq!(
+        client.try_propose_set_loyalty_config(&admin, &config, &0),
+        Err(Ok(EventRegistryError::InvalidLoyaltyConfig))
+    );
+}
+
+#[test]
+fn test_organizer_loyalty_ladder_counts_each_event_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let organizer = Address::generate(&env);
+    let mut first = base_args(&env, &organizer, None);
+    first.event_id = String::from_str(&env, "ladder_1");
+    client.register_event(&first);
+    let mut second = base_args(&env, &organizer, None);
+    second.event_id = String::from_str(&env, "ladder_2");
+    client.register_event(&second);
+
+    let rungs = soroban_sdk::vec![
+        &env,
+        LoyaltyRung {
+            min_events_attended: 2,
+            discount_bps: 500,
+        },
+        LoyaltyRung {
+            min_events_attended: 5,
+            discount_bps: 1500,
+        },
+    ];
+    client.set_organizer_loyalty_program(&organizer, &rungs);
+    assert_eq!(client.get_organizer_loyalty_program(&organizer), rungs);
+
+    let guest = Address::generate(&env);
+    assert_eq!(client.record_attendance(&admin, &guest, &first.event_id), 1);
+    assert_eq!(client.record_attendance(&admin, &guest, &first.event_id), 1);
+    assert_eq!(client.get_organizer_discount_bps(&organizer, &guest), 0);
+
+    assert_eq!(
+        client.record_attendance(&admin, &guest, &second.event_id),
+        2
+    );
+    assert_eq!(client.get_organizer_attendance(&organizer, &guest), 2);
+    assert_eq!(client.get_organizer_discount_bps(&organizer, &guest), 500);
+
+    // Clearing the ladder removes the discount but keeps attendance history.
+    client.set_organizer_loyalty_program(&organizer, &soroban_sdk::vec![&env]);
+    assert_eq!(client.get_organizer_discount_bps(&organizer, &guest), 0);
+    assert_eq!(client.get_organizer_attendance(&organizer, &guest), 2);
+}
+
+#[test]
+fn test_organizer_loyalty_ladder_rejects_unordered_rungs() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_loyalty_env(&env);
+
+    let organizer = Address::generate(&env);
+    let rungs = soroban_sdk::vec![
+        &env,
+        LoyaltyRung {
+            min_events_attended: 5,
+            discount_bps: 1500,
+        },
+        LoyaltyRung {
+            min_events_attended: 5,
+            discount_bps: 2000,
+        },
+    ];
+    assert_eq!(
+        client.try_set_organizer_loyalty_program(&organizer, &rungs),
+        Err(Ok(EventRegistryError::InvalidLoyaltyConfig))
+    );
+}
+
 // ── Staking Configuration ────────────────────────────────────────
 
 #[test]
@@ -3739,10 +4021,10 @@ fn test_stake_collateral_zero_amount_fails() {
 }
 
 #[test]
-fn test_double_stake_fails() {
+fn test_stake_top_up_restores_verified_after_min_raised() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, _) = setup_loyalty_env(&env);
+    let (client, admin, _) = setup_loyalty_env(&env);
 
     let organizer = Address::generate(&env);
     let stake_amount = 500_0000000i128;
@@ -3752,26 +4034,28 @@ fn test_double_stake_fails() {
         .address();
     let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
     token_admin.mint(&organizer, &(stake_amount * 2));
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    token_client.approve(&organizer, &client.address, &(stake_amount * 2), &99999);
 
-    client.set_staking_config(&token_id, &1000_0000000i128);
+    client.set_staking_config(&token_id, &stake_amount);
+    client.stake_collateral(&organizer, &stake_amount);
+    assert!(client.is_organizer_verified(&organizer));
 
-    soroban_sdk::token::Client::new(&env, &token_id).approve(
-        &organizer,
-        &client.address,
-        &stake_amount,
-        &99999,
-    );
+    // Raising the minimum drops Verified status for existing stakers.
+    let proposal_id = client.propose_set_min_stake_amount(&admin, &(stake_amount * 2), &0);
+    client.execute_proposal(&admin, &proposal_id);
+    assert!(!client.is_organizer_verified(&organizer));
+
+    // Topping up the existing stake restores it.
     client.stake_collateral(&organizer, &stake_amount);
+    let stake = client.get_organizer_stake(&organizer).unwrap();
+    assert_eq!(stake.amount, stake_amount * 2);
+    assert!(stake.is_verified);
+    assert_eq!(token_client.balance(&organizer), 0);
 
-    // Second stake attempt should fail
-    soroban_sdk::token::Client::new(&env, &token_id).approve(
-        &organizer,
-        &client.address,
-        &stake_amount,
-        &99999,
-    );
-    let result = client.try_stake_collateral(&organizer, &stake_amount);
-    assert_eq!(result, Err(Ok(EventRegistryError::AlreadyStaked)));
+    // Lowering it again through the admin config keeps them verified.
+    client.set_staking_config(&token_id, &stake_amount);
+    assert!(client.is_organizer_verified(&organizer));
 }
 
 // ── unstake_collateral ───────────────────────────────────────────
@@ -3800,313 +4084,913 @@ fn test_unstake_collateral_returns_tokens() {
     // Balance should be 0 after staking
     assert_eq!(token_client.balance(&organizer), 0);
 
-    // Unstake
-    client.unstake_collateral(&organizer);
+    // Unstake everything; the funds wait out the unbonding period
+    let release_at = client.unstake_collateral(&organizer, &stake_amount);
+    assert_eq!(release_at, client.get_unbonding_period());
+    assert!(!client.is_organizer_verified(&organizer));
+    assert_eq!(
+        client.try_withdraw_unbonded(&organizer),
+        Err(Ok(EventRegistryError::UnbondingNotReady))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = release_at);
+    assert_eq!(client.withdraw_unbonded(&organizer), stake_amount);
 
     // Balance should be restored
     assert_eq!(token_client.balance(&organizer), stake_amount);
     assert!(client.get_organizer_stake(&organizer).is_none());
-    assert!(!client.is_organizer_verified(&organizer));
+    assert!(client.get_unbonding_entries(&organizer).is_empty());
 }
 
 #[test]
-fn test_unstake_without_stake_fails() {
+fn test_partial_unstake_keeps_remaining_stake() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, _, _) = setup_loyalty_env(&env);
+    let (organizer, token_id) = stake_for_slashing(&env, &client, 1000);
+    client.set_staking_config(&token_id, &500);
+    client.set_unbonding_period(&3600);
 
-    let organizer = Address::generate(&env);
-    let result = client.try_unstake_collateral(&organizer);
-    assert_eq!(result, Err(Ok(EventRegistryError::NotStaked)));
-}
+    client.unstake_collateral(&organizer, &400);
+    env.ledger().with_mut(|li| li.timestamp += 1800);
+    client.unstake_collateral(&organizer, &100);
 
-// ── distribute_staker_rewards & claim_staker_rewards ────────────
+    let stake = client.get_organizer_stake(&organizer).unwrap();
+    assert_eq!(stake.amount, 500);
+    assert!(stake.is_verified);
+    assert_eq!(client.get_unbonding_entries(&organizer).len(), 2);
+    assert_eq!(
+        client.try_unstake_collateral(&organizer, &501),
+        Err(Ok(EventRegistryError::InvalidStakeAmount))
+    );
+
+    // Only the first request has matured.
+    env.ledger().with_mut(|li| li.timestamp += 1800);
+    assert_eq!(client.withdraw_unbonded(&organizer), 400);
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&organizer), 400);
+    assert_eq!(client.get_unbonding_entries(&organizer).len(), 1);
+}
 
 #[test]
-fn test_distribute_and_claim_staker_rewards() {
+fn test_withdraw_unbonded_blocked_by_active_event() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, admin, _) = setup_loyalty_env(&env);
+    let (client, _, _) = setup_loyalty_env(&env);
+    let (organizer, _) = stake_for_slashing(&env, &client, 1000);
+    client.set_unbonding_period(&0);
 
-    let organizer = Address::generate(&env);
-    let stake_amount = 1000_0000000i128;
-    let reward_amount = 100_0000000i128;
+    let args = base_args(&env, &organizer, None);
+    let event_id = args.event_id.clone();
+    client.register_event(&args);
 
-    let token_id = env
-        .register_stellar_asset_contract_v2(Address::generate(&env))
-        .address();
-    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
-    // Mint for organizer (stake) + admin (rewards)
-    token_admin.mint(&organizer, &stake_amount);
-    token_admin.mint(&admin, &reward_amount);
+    client.unstake_collateral(&organizer, &1000);
+    assert_eq!(
+        client.try_withdraw_unbonded(&organizer),
+        Err(Ok(EventRegistryError::OrganizerHasActiveEvents))
+    );
 
-    client.set_staking_config(&token_id, &stake_amount);
+    client.cancel_event(&event_id);
+    assert_eq!(client.withdraw_unbonded(&organizer), 1000);
+}
 
-    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
-    token_client.approve(&organizer, &client.address, &stake_amount, &99999);
-    client.stake_collateral(&organizer, &stake_amount);
+#[soroban_sdk::contract]
+pub struct MockEscrow;
 
-    // Admin approves reward tokens to contract
-    token_client.approve(&admin, &client.address, &reward_amount, &99999);
-    client.distribute_staker_rewards(&admin, &reward_amount);
+#[soroban_sdk::contractimpl]
+impl MockEscrow {
+    pub fn set_unsettled(env: Env, amount: i128) {
+        env.storage().instance().set(&0u32, &amount);
+    }
 
-    // Organizer's reward_balance should be updated
-    let stake = client.get_organizer_stake(&organizer).unwrap();
-    assert_eq!(stake.reward_balance, reward_amount); // 100% since only one staker
+    pub fn get_event_escrow_balance(
+        env: Env,
+        _event_id: String,
+    ) -> crate::ticket_payment::EventBalance {
+        crate::ticket_payment::EventBalance {
+            organizer_amount: env.storage().instance().get(&0u32).unwrap_or(0),
+            total_withdrawn: 0,
+            platform_fee: 0,
+        }
+    }
+}
 
-    // Organizer claims rewards
-    let claimed = client.claim_staker_rewards(&organizer);
-    assert_eq!(claimed, reward_amount);
+#[soroban_sdk::contract]
+pub struct MockArchivingTicketPayment;
 
-    // Check token balance restored
-    assert_eq!(token_client.balance(&organizer), reward_amount);
+#[soroban_sdk::contractimpl]
+impl MockArchivingTicketPayment {
+    pub fn set_commitment(env: Env, commitment: crate::ticket_payment::PaymentsCommitment) {
+        env.storage().instance().set(&0u32, &commitment);
+    }
 
-    // reward_balance should be zero after claiming
-    let stake_after = client.get_organizer_stake(&organizer).unwrap();
-    assert_eq!(stake_after.reward_balance, 0);
-    assert_eq!(stake_after.total_rewards_claimed, reward_amount);
+    pub fn archive_event_payments(
+        env: Env,
+        _event_id: String,
+    ) -> crate::ticket_payment::PaymentsCommitment {
+        env.storage().instance().get(&0u32).unwrap()
+    }
+}
+
+fn archived_leaf(
+    env: &Env,
+    payment_id: &str,
+    status: crate::ticket_payment::PaymentStatus,
+) -> crate::ticket_payment::ArchivedPayment {
+    crate::ticket_payment::ArchivedPayment {
+        payment_id: String::from_str(env, payment_id),
+        buyer_address: Address::generate(env),
+        ticket_tier_id: String::from_str(env, "general"),
+        amount: 1000,
+        refunded_amount: 0,
+        status,
+    }
+}
+
+fn leaf_hash(env: &Env, leaf: &crate::ticket_payment::ArchivedPayment) -> BytesN<32> {
+    env.crypto().sha256(&leaf.clone().to_xdr(env)).into()
 }
 
 #[test]
-fn test_distribute_rewards_proportional_to_multiple_stakers() {
+fn test_archive_event_stores_payments_commitment_and_verifies_proofs() {
+    use crate::ticket_payment::PaymentStatus;
+
     let env = Env::default();
     env.mock_all_auths();
-    let (client, admin, _) = setup_loyalty_env(&env);
+    let (client, _, _) = setup_loyalty_env(&env);
+    let (organizer, _) = stake_for_slashing(&env, &client, 1000);
 
-    let organizer_a = Address::generate(&env);
-    let organizer_b = Address::generate(&env);
-    let stake_a = 1000_0000000i128;
-    let stake_b = 3000_0000000i128;
-    let total_reward = 1000_0000000i128;
+    let leaves = [
+        archived_leaf(&env, "pay_0", PaymentStatus::CheckedIn),
+        archived_leaf(&env, "pay_1", PaymentStatus::Refunded),
+        archived_leaf(&env, "pay_2", PaymentStatus::Confirmed),
+    ];
+    let hashes = [
+        leaf_hash(&env, &leaves[0]),
+        leaf_hash(&env, &leaves[1]),
+        leaf_hash(&env, &leaves[2]),
+    ];
+    let left = merkle_parent(&env, &hashes[0], &hashes[1]);
+    let root = merkle_parent(&env, &left, &hashes[2]);
+
+    let ticket_payment_id = env.register(MockArchivingTicketPayment, ());
+    MockArchivingTicketPaymentClient::new(&env, &ticket_payment_id).set_commitment(
+        &crate::ticket_payment::PaymentsCommitment {
+            root: root.clone(),
+            payment_count: 3,
+        },
+    );
+    client.set_ticket_payment_contract(&ticket_payment_id);
 
-    let token_id = env
-        .register_stellar_asset_contract_v2(Address::generate(&env))
-        .address();
-    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
-    token_admin.mint(&organizer_a, &stake_a);
-    token_admin.mint(&organizer_b, &stake_b);
-    token_admin.mint(&admin, &total_reward);
+    let args = base_args(&env, &organizer, None);
+    client.register_event(&args);
+    let event_id = args.event_id;
+    let proof_0 = soroban_sdk::vec![&env, hashes[1].clone(), hashes[2].clone()];
+    assert!(!client.verify_archived_payment(&event_id, &leaves[0], &proof_0));
 
-    client.set_staking_config(&token_id, &1i128); // min_amount = 1 for simplicity
+    client.cancel_event(&event_id);
+    client.archive_event(&event_id);
 
-    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
-    token_client.approve(&organizer_a, &client.address, &stake_a, &99999);
-    client.stake_collateral(&organizer_a, &stake_a);
+    let commitment = client.get_payments_commitment(&event_id).unwrap();
+    assert_eq!(commitment.root, root);
+    assert_eq!(commitment.payment_count, 3);
 
-    token_client.approve(&organizer_b, &client.address, &stake_b, &99999);
-    client.stake_collateral(&organizer_b, &stake_b);
+    assert!(client.verify_archived_payment(&event_id, &leaves[0], &proof_0));
+    let proof_2 = soroban_sdk::vec![&env, left];
+    assert!(client.verify_archived_payment(&event_id, &leaves[2], &proof_2));
 
-    token_client.approve(&admin, &client.address, &total_reward, &99999);
-    client.distribute_staker_rewards(&admin, &total_reward);
+    let mut tampered = leaves[0].clone();
+    tampered.status = PaymentStatus::Refunded;
+    assert!(!client.verify_archived_payment(&event_id, &tampered, &proof_0));
+    assert!(!client.verify_archived_payment(&event_id, &leaves[2], &proof_0));
+}
 
-    // A has 25% stake (1000/4000), B has 75% (3000/4000)
-    let expected_a = total_reward * stake_a / (stake_a + stake_b); // 250_0000000
-    let expected_b = total_reward * stake_b / (stake_a + stake_b); // 750_0000000
+#[test]
+fn test_withdraw_unbonded_blocked_by_unsettled_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_loyalty_env(&env);
+    let (organizer, _) = stake_for_slashing(&env, &client, 1000);
+    client.set_unbonding_period(&0);
 
-    let stake_a_record = client.get_organizer_stake(&organizer_a).unwrap();
-    let stake_b_record = client.get_organizer_stake(&organizer_b).unwrap();
+    let escrow_id = env.register(MockEscrow, ());
+    let escrow = MockEscrowClient::new(&env, &escrow_id);
+    client.set_ticket_payment_contract(&escrow_id);
 
-    assert_eq!(stake_a_record.reward_balance, expected_a);
-    assert_eq!(stake_b_record.reward_balance, expected_b);
+    let args = base_args(&env, &organizer, None);
+    client.register_event(&args);
+    client.cancel_event(&args.event_id);
+    escrow.set_unsettled(&250);
+
+    client.unstake_collateral(&organizer, &1000);
+    assert_eq!(
+        client.try_withdraw_unbonded(&organizer),
+        Err(Ok(EventRegistryError::OrganizerHasActiveEvents))
+    );
+
+    escrow.set_unsettled(&0);
+    assert_eq!(client.withdraw_unbonded(&organizer), 1000);
 }
 
 #[test]
-fn test_claim_rewards_no_stake_fails() {
+fn test_slash_reaches_unbonding_collateral() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, _) = setup_loyalty_env(&env);
+    let (client, admin, _) = setup_loyalty_env(&env);
+    let (organizer, token_id) = stake_for_slashing(&env, &client, 1000);
 
-    let organizer = Address::generate(&env);
-    let result = client.try_claim_staker_rewards(&organizer);
-    assert_eq!(result, Err(Ok(EventRegistryError::NotStaked)));
+    let release_at = client.unstake_collateral(&organizer, &700);
+    let proposal_id = client.propose_slash_stake(&admin, &organizer, &500, &0);
+    client.execute_proposal(&admin, &proposal_id);
+
+    assert_eq!(client.get_organizer_stake(&organizer).unwrap().amount, 0);
+    let entries = client.get_unbonding_entries(&organizer);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries.get(0).unwrap().amount, 500);
+    assert_eq!(client.get_protection_pool_balance(&token_id), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = release_at);
+    assert_eq!(client.withdraw_unbonded(&organizer), 500);
+    assert!(client.get_organizer_stake(&organizer).is_none());
 }
 
 #[test]
-fn test_claim_rewards_zero_balance_fails() {
+fn test_unstake_without_stake_fails() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, _, _) = setup_loyalty_env(&env);
 
     let organizer = Address::generate(&env);
-    let stake_amount = 500_0000000i128;
+    let result = client.try_unstake_collateral(&organizer, &100);
+    assert_eq!(result, Err(Ok(EventRegistryError::NotStaked)));
+}
+
+// ── Stake slashing & guest protection pool ──────────────────────
 
+fn stake_for_slashing(
+    env: &Env,
+    client: &crate::EventRegistryClient<'static>,
+    stake_amount: i128,
+) -> (Address, Address) {
+    let organizer = Address::generate(env);
     let token_id = env
-        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .register_stellar_asset_contract_v2(Address::generate(env))
         .address();
-    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
-    token_admin.mint(&organizer, &stake_amount);
-
+    soroban_sdk::token::StellarAssetClient::new(env, &token_id).mint(&organizer, &stake_amount);
     client.set_staking_config(&token_id, &stake_amount);
-
-    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
-    token_client.approve(&organizer, &client.address, &stake_amount, &99999);
+    soroban_sdk::token::Client::new(env, &token_id).approve(
+        &organizer,
+        &client.address,
+        &stake_amount,
+        &99999,
+    );
     client.stake_collateral(&organizer, &stake_amount);
-
-    // No rewards distributed yet
-    let result = client.try_claim_staker_rewards(&organizer);
-    assert_eq!(result, Err(Ok(EventRegistryError::NoRewardsAvailable)));
+    (organizer, token_id)
 }
 
 #[test]
-fn test_distribute_rewards_no_stakers_fails() {
+fn test_slash_stake_moves_funds_to_protection_pool() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, admin, _) = setup_loyalty_env(&env);
+    let (organizer, token_id) = stake_for_slashing(&env, &client, 1000);
 
-    let token_id = env
-        .register_stellar_asset_contract_v2(Address::generate(&env))
-        .address();
-    client.set_staking_config(&token_id, &1000i128);
+    let proposal_id = client.propose_slash_stake(&admin, &organizer, &400, &0);
+    client.execute_proposal(&admin, &proposal_id);
 
-    let result = client.try_distribute_staker_rewards(&admin, &100i128);
-    assert_eq!(result, Err(Ok(EventRegistryError::NotStaked)));
+    let stake = client.get_organizer_stake(&organizer).unwrap();
+    assert_eq!(stake.amount, 600);
+    assert!(!stake.is_verified);
+    assert!(!client.is_organizer_verified(&organizer));
+    assert_eq!(client.get_protection_pool_balance(&token_id), 400);
+    assert_eq!(client.get_total_slashed(&organizer), 400);
+
+    // Slashing more than is left only takes what remains.
+    let proposal_id = client.propose_slash_stake(&admin, &organizer, &5000, &0);
+    client.execute_proposal(&admin, &proposal_id);
+    assert_eq!(client.get_organizer_stake(&organizer).unwrap().amount, 0);
+    assert_eq!(client.get_protection_pool_balance(&token_id), 1000);
+    assert_eq!(client.get_total_slashed(&organizer), 1000);
 }
 
 #[test]
-fn test_distribute_rewards_unauthorized_fails() {
+fn test_propose_slash_stake_validates_target() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, _) = setup_loyalty_env(&env);
-
-    let random_caller = Address::generate(&env);
-    let token_id = env
-        .register_stellar_asset_contract_v2(Address::generate(&env))
-        .address();
-    client.set_staking_config(&token_id, &1000i128);
+    let (client, admin, _) = setup_loyalty_env(&env);
+    let (organizer, _) = stake_for_slashing(&env, &client, 1000);
 
-    let result = client.try_distribute_staker_rewards(&random_caller, &100i128);
-    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+    assert_eq!(
+        client.try_propose_slash_stake(&admin, &organizer, &0, &0),
+        Err(Ok(EventRegistryError::InvalidStakeAmount))
+    );
+    assert_eq!(
+        client.try_propose_slash_stake(&admin, &Address::generate(&env), &100, &0),
+        Err(Ok(EventRegistryError::NotStaked))
+    );
 }
 
 #[test]
-fn test_is_organizer_verified_false_when_not_staked() {
+fn test_unstake_blocked_while_slash_pending_or_blacklisted() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, _) = setup_loyalty_env(&env);
+    let (client, admin, _) = setup_loyalty_env(&env);
+    let (organizer, _) = stake_for_slashing(&env, &client, 1000);
 
-    let organizer = Address::generate(&env);
-    assert!(!client.is_organizer_verified(&organizer));
-}
+    let proposal_id = client.propose_slash_stake(&admin, &organizer, &250, &0);
+    assert_eq!(
+        client.try_unstake_collateral(&organizer, &100),
+        Err(Ok(EventRegistryError::SlashPending))
+    );
 
-// ==================== USDC Token Whitelist Tests ====================
+    client.execute_proposal(&admin, &proposal_id);
+    client.blacklist_organizer(&organizer, &String::from_str(&env, "Fraudulent event"));
+    assert_eq!(
+        client.try_unstake_collateral(&organizer, &100),
+        Err(Ok(EventRegistryError::OrganizerBlacklisted))
+    );
+
+    client.remove_from_blacklist(&organizer, &String::from_str(&env, "Resolved"));
+    client.unstake_collateral(&organizer, &750);
+    assert_eq!(client.get_organizer_stake(&organizer).unwrap().amount, 0);
+}
 
 #[test]
-fn test_usdc_token_whitelisted_after_init() {
+fn test_cover_refund_shortfall_pays_up_to_pool_balance() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    let (client, admin, _) = setup_loyalty_env(&env);
+    let (organizer, token_id) = stake_for_slashing(&env, &client, 1000);
+    let ticket_payment = Address::generate(&env);
+    client.set_ticket_payment_contract(&ticket_payment);
 
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
+    let proposal_id = client.propose_slash_stake(&admin, &organizer, &300, &0);
+    client.execute_proposal(&admin, &proposal_id);
 
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    assert_eq!(
+        client.try_cover_refund_shortfall(&Address::generate(&env), &token_id, &100),
+        Err(Ok(EventRegistryError::Unauthorized))
+    );
 
-    // USDC token must be whitelisted automatically after initialization
-    assert!(client.is_token_whitelisted(&usdc_token));
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    assert_eq!(
+        client.cover_refund_shortfall(&ticket_payment, &token_id, &200),
+        200
+    );
+    assert_eq!(
+        client.cover_refund_shortfall(&ticket_payment, &token_id, &200),
+        100
+    );
+    assert_eq!(token_client.balance(&ticket_payment), 300);
+    assert_eq!(client.get_protection_pool_balance(&token_id), 0);
+    assert_eq!(
+        client.cover_refund_shortfall(&ticket_payment, &token_id, &50),
+        0
+    );
 }
 
+// ── distribute_staker_rewards & claim_staker_rewards ────────────
+
 #[test]
-fn test_non_usdc_token_not_whitelisted_after_init() {
+fn test_distribute_and_claim_staker_rewards() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    let (client, admin, _) = setup_loyalty_env(&env);
 
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    let other_token = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let stake_amount = 1000_0000000i128;
+    let reward_amount = 100_0000000i128;
 
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
+    // Mint for organizer (stake) + admin (rewards)
+    token_admin.mint(&organizer, &stake_amount);
+    token_admin.mint(&admin, &reward_amount);
 
-    // A different token should NOT be whitelisted
-    assert!(!client.is_token_whitelisted(&other_token));
-}
+    client.set_staking_config(&token_id, &stake_amount);
 
-#[test]
-fn test_admin_can_add_token_to_whitelist() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    token_client.approve(&organizer, &client.address, &stake_amount, &99999);
+    client.stake_collateral(&organizer, &stake_amount);
 
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    let new_token = Address::generate(&env);
+    // Admin approves reward tokens to contract
+    token_client.approve(&admin, &client.address, &reward_amount, &99999);
+    client.distribute_staker_rewards(&admin, &reward_amount);
 
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    // Organizer's reward_balance should be updated
+    let stake = client.get_organizer_stake(&organizer).unwrap();
+    assert_eq!(stake.reward_balance, reward_amount); // 100% since only one staker
 
-    assert!(!client.is_token_whitelisted(&new_token));
-    client.add_to_token_whitelist(&new_token);
-    assert!(client.is_token_whitelisted(&new_token));
+    // Organizer claims rewards
+    let claimed = client.claim_staker_rewards(&organizer);
+    assert_eq!(claimed, reward_amount);
+
+    // Check token balance restored
+    assert_eq!(token_client.balance(&organizer), reward_amount);
+
+    // reward_balance should be zero after claiming
+    let stake_after = client.get_organizer_stake(&organizer).unwrap();
+    assert_eq!(stake_after.reward_balance, 0);
+    assert_eq!(stake_after.total_rewards_claimed, reward_amount);
 }
 
 #[test]
-fn test_admin_can_remove_token_from_whitelist() {
+fn test_distribute_rewards_proportional_to_multiple_stakers() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    let (client, admin, _) = setup_loyalty_env(&env);
 
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
+    let organizer_a = Address::generate(&env);
+    let organizer_b = Address::generate(&env);
+    let stake_a = 1000_0000000i128;
+    let stake_b = 3000_0000000i128;
+    let total_reward = 1000_0000000i128;
 
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
+    token_admin.mint(&organizer_a, &stake_a);
+    token_admin.mint(&organizer_b, &stake_b);
+    token_admin.mint(&admin, &total_reward);
 
-    // USDC is whitelisted after init
-    assert!(client.is_token_whitelisted(&usdc_token));
+    client.set_staking_config(&token_id, &1i128); // min_amount = 1 for simplicity
 
-    // Admin removes it
-    client.remove_from_token_whitelist(&usdc_token);
-    assert!(!client.is_token_whitelisted(&usdc_token));
-}
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    token_client.approve(&organizer_a, &client.address, &stake_a, &99999);
+    client.stake_collateral(&organizer_a, &stake_a);
 
-#[test]
-#[should_panic] // Authentication failure — non-admin cannot add to whitelist
-fn test_non_admin_cannot_add_token_to_whitelist() {
-    let env = Env::default();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    token_client.approve(&organizer_b, &client.address, &stake_b, &99999);
+    client.stake_collateral(&organizer_b, &stake_b);
 
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    let new_token = Address::generate(&env);
+    token_client.approve(&admin, &client.address, &total_reward, &99999);
+    client.distribute_staker_rewards(&admin, &total_reward);
 
-    // Only mock auth for initialize, not for add_to_token_whitelist
-    env.mock_all_auths();
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    // A has 25% stake (1000/4000), B has 75% (3000/4000)
+    let expected_a = total_reward * stake_a / (stake_a + stake_b); // 250_0000000
+    let expected_b = total_reward * stake_b / (stake_a + stake_b); // 750_0000000
 
-    // Clear mocked auths so the next call requires real auth
-    let env2 = Env::default();
-    let client2 = EventRegistryClient::new(&env2, &contract_id);
-    client2.add_to_token_whitelist(&new_token);
+    let stake_a_record = client.get_organizer_stake(&organizer_a).unwrap();
+    let stake_b_record = client.get_organizer_stake(&organizer_b).unwrap();
+
+    assert_eq!(stake_a_record.reward_balance, expected_a);
+    assert_eq!(stake_b_record.reward_balance, expected_b);
+}
+
+fn fund_reward_pool(
+    env: &Env,
+    client: &crate::EventRegistryClient<'static>,
+    token_id: &Address,
+    admin: &Address,
+    amount: i128,
+) {
+    soroban_sdk::token::StellarAssetClient::new(env, token_id).mint(admin, &amount);
+    soroban_sdk::token::Client::new(env, token_id).approve(admin, &client.address, &amount, &99999);
 }
 
 #[test]
-fn test_register_event_with_banner_cid() {
+fn test_rewards_settle_lazily_on_stake_changes() {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
-    let admin = Address::generate(&env);
-    let organizer = Address::generate(&env);
-    let payment_addr = test_payment_address(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let (client, admin, _) = setup_loyalty_env(&env);
+    let (organizer_a, token_id) = stake_for_slashing(&env, &client, 100);
+    client.set_staking_config(&token_id, &1);
 
-    let event_id = String::from_str(&env, "event_banner");
-    let metadata_cid = String::from_str(
-        &env,
-        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-    );
-    let banner_cid = Some(String::from_str(
-        &env,
+    fund_reward_pool(&env, &client, &token_id, &admin, 100);
+    client.distribute_staker_rewards(&admin, &100);
+
+    // B joins after the first distribution and earns nothing from it.
+    let organizer_b = Address::generate(&env);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&organizer_b, &100);
+    soroban_sdk::token::Client::new(&env, &token_id).approve(
+        &organizer_b,
+        &client.address,
+        &100,
+        &99999,
+    );
+    client.stake_collateral(&organizer_b, &100);
+    assert_eq!(
+        client
+            .get_organizer_stake(&organizer_b)
+            .unwrap()
+            .reward_balance,
+        0
+    );
+
+    fund_reward_pool(&env, &client, &token_id, &admin, 100);
+    client.distribute_staker_rewards(&admin, &100);
+    assert_eq!(
+        client
+            .get_organizer_stake(&organizer_a)
+            .unwrap()
+            .reward_balance,
+        150
+    );
+    assert_eq!(
+        client
+            .get_organizer_stake(&organizer_b)
+            .unwrap()
+            .reward_balance,
+        50
+    );
+
+    // A's partial unstake keeps what was earned and shrinks future shares.
+    client.unstake_collateral(&organizer_a, &50);
+    fund_reward_pool(&env, &client, &token_id, &admin, 150);
+    client.distribute_staker_rewards(&admin, &150);
+
+    assert_eq!(client.claim_staker_rewards(&organizer_a), 200);
+    assert_eq!(client.claim_staker_rewards(&organizer_b), 150);
+    assert_eq!(
+        client.try_claim_staker_rewards(&organizer_a),
+        Err(Ok(EventRegistryError::NoRewardsAvailable))
+    );
+}
+
+#[test]
+fn test_reward_distribution_exact_and_constant_cost_with_hundreds_of_stakers() {
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    client.set_staking_config(&token_id, &1);
+
+    let mut stakers = soroban_sdk::Vec::new(&env);
+    let mut total_staked = 0i128;
+    for i in 0..300i128 {
+        let organizer = Address::generate(&env);
+        let amount = (i + 1) * 1_0000000 + i * 7;
+        token_admin.mint(&organizer, &amount);
+        token_client.approve(&organizer, &client.address, &amount, &99999);
+        client.stake_collateral(&organizer, &amount);
+        stakers.push_back((organizer, amount));
+        total_staked += amount;
+    }
+
+    let total_reward = 12345_6789012i128;
+    fund_reward_pool(&env, &client, &token_id, &admin, total_reward);
+    client.distribute_staker_rewards(&admin, &total_reward);
+    let distribute_cpu = env.cost_estimate().budget().cpu_instruction_cost();
+
+    let index = total_reward * crate::REWARD_PRECISION / total_staked;
+    let mut total_claimed = 0i128;
+    for (n, (organizer, amount)) in stakers.iter().enumerate() {
+        let claimed = client.claim_staker_rewards(&organizer);
+        if n == 0 {
+            // Distributing to every staker costs about as much as one staker's claim.
+            let claim_cpu = env.cost_estimate().budget().cpu_instruction_cost();
+            assert!(distribute_cpu < claim_cpu * 3 / 2);
+        }
+        assert_eq!(claimed, amount * index / crate::REWARD_PRECISION);
+        assert!(total_reward * amount / total_staked - claimed <= 1);
+        assert_eq!(token_client.balance(&organizer), claimed);
+        total_claimed += claimed;
+    }
+    assert!(total_claimed <= total_reward);
+    assert!(total_reward - total_claimed < 300);
+}
+
+// ── Community Votes ──────────────────────────────────────────────
+
+/// Mints `amount` of the staking token to a new organizer and stakes it.
+fn stake_new_organizer(
+    env: &Env,
+    client: &crate::EventRegistryClient<'static>,
+    token_id: &Address,
+    amount: i128,
+) -> Address {
+    let organizer = Address::generate(env);
+    soroban_sdk::token::StellarAssetClient::new(env, token_id).mint(&organizer, &amount);
+    soroban_sdk::token::Client::new(env, token_id).approve(
+        &organizer,
+        &client.address,
+        &amount,
+        &99999,
+    );
+    client.stake_collateral(&organizer, &amount);
+    organizer
+}
+
+#[test]
+fn test_community_vote_weighs_snapshotted_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+    env.ledger().set_timestamp(1_000);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_staking_config(&token_id, &100);
+    let big = stake_new_organizer(&env, &client, &token_id, 600);
+    let mid = stake_new_organizer(&env, &client, &token_id, 300);
+    let small = stake_new_organizer(&env, &client, &token_id, 100);
+
+    let config = CommunityVoteConfig {
+        enabled: true,
+        voting_period_secs: 3_600,
+        quorum_bps: 5_000,
+        approval_bps: 6_000,
+    };
+    client.set_community_vote_config(&config);
+
+    env.ledger().set_timestamp(2_000);
+    let fee_proposal = client.propose_parameter_change(
+        &admin,
+        &crate::types::ParameterChange::SetPlatformFee(300),
+        &0,
+    );
+    let vote = client.get_community_vote(&fee_proposal).unwrap();
+    assert_eq!(vote.total_weight, 1_000);
+    assert_eq!(vote.ends_at, 2_000 + 3_600);
+    assert!(client.get_proposal(&fee_proposal).unwrap().expires_at > vote.ends_at);
+
+    // Stake added or removed after the snapshot does not change voting weight.
+    let late = stake_new_organizer(&env, &client, &token_id, 5_000);
+    client.unstake_collateral(&mid, &200);
+    assert_eq!(client.get_voting_weight(&late, &fee_proposal), 0);
+    assert_eq!(client.get_voting_weight(&mid, &fee_proposal), 300);
+    assert_eq!(
+        client.try_cast_community_vote(&late, &fee_proposal, &true),
+        Err(Ok(EventRegistryError::NoVotingWeight))
+    );
+
+    // 400 of 1000 voting misses the 50% quorum.
+    client.cast_community_vote(&small, &fee_proposal, &true);
+    client.cast_community_vote(&mid, &fee_proposal, &false);
+    assert_eq!(
+        client.try_cast_community_vote(&small, &fee_proposal, &true),
+        Err(Ok(EventRegistryError::AlreadyVoted))
+    );
+    assert_eq!(
+        client.try_execute_proposal(&admin, &fee_proposal),
+        Err(Ok(EventRegistryError::CommunityVoteOpen))
+    );
+    env.ledger().set_timestamp(2_000 + 3_601);
+    assert_eq!(
+        client.try_cast_community_vote(&big, &fee_proposal, &true),
+        Err(Ok(EventRegistryError::CommunityVoteClosed))
+    );
+    assert_eq!(
+        client.try_execute_proposal(&admin, &fee_proposal),
+        Err(Ok(EventRegistryError::CommunityVoteFailed))
+    );
+    assert_eq!(client.get_platform_fee(), 500);
+
+    // 600 for and 300 against reaches quorum and the 60% approval threshold;
+    // the admin then ratifies the result by executing.
+    let stake_proposal = client.propose_parameter_change(
+        &admin,
+        &crate::types::ParameterChange::SetMinStakeAmount(250),
+        &0,
+    );
+    let vote = client.get_community_vote(&stake_proposal).unwrap();
+    assert_eq!(vote.total_weight, 1_000 - 200 + 5_000);
+    client.cast_community_vote(&late, &stake_proposal, &true);
+    client.cast_community_vote(&big, &stake_proposal, &false);
+    env.ledger().set_timestamp(vote.ends_at + 1);
+    client.execute_proposal(&admin, &stake_proposal);
+    assert_eq!(client.get_min_stake_amount(), 250);
+
+    // Other proposals are decided by the admins alone.
+    let delay_proposal = client.propose_set_execution_delay(&admin, &0, &0);
+    assert_eq!(client.get_community_vote(&delay_proposal), None);
+    assert_eq!(
+        client.try_cast_community_vote(&big, &delay_proposal, &true),
+        Err(Ok(EventRegistryError::CommunityVoteNotFound))
+    );
+    client.execute_proposal(&admin, &delay_proposal);
+}
+
+#[test]
+fn test_community_vote_config_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_loyalty_env(&env);
+
+    assert!(!client.get_community_vote_config().enabled);
+    for (voting_period_secs, quorum_bps, approval_bps) in
+        [(0, 5_000, 5_000), (3_600, 10_001, 5_000), (3_600, 5_000, 0)]
+    {
+        let config = CommunityVoteConfig {
+            enabled: true,
+            voting_period_secs,
+            quorum_bps,
+            approval_bps,
+        };
+        assert_eq!(
+            client.try_set_community_vote_config(&config),
+            Err(Ok(EventRegistryError::InvalidCommunityVoteConfig))
+        );
+    }
+}
+
+#[test]
+fn test_claim_rewards_no_stake_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_loyalty_env(&env);
+
+    let organizer = Address::generate(&env);
+    let result = client.try_claim_staker_rewards(&organizer);
+    assert_eq!(result, Err(Ok(EventRegistryError::NotStaked)));
+}
+
+#[test]
+fn test_claim_rewards_zero_balance_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_loyalty_env(&env);
+
+    let organizer = Address::generate(&env);
+    let stake_amount = 500_0000000i128;
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
+    token_admin.mint(&organizer, &stake_amount);
+
+    client.set_staking_config(&token_id, &stake_amount);
+
+    let token_client = soroban_sdk::token::Client::new(&env, &token_id);
+    token_client.approve(&organizer, &client.address, &stake_amount, &99999);
+    client.stake_collateral(&organizer, &stake_amount);
+
+    // No rewards distributed yet
+    let result = client.try_claim_staker_rewards(&organizer);
+    assert_eq!(result, Err(Ok(EventRegistryError::NoRewardsAvailable)));
+}
+
+#[test]
+fn test_distribute_rewards_no_stakers_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_staking_config(&token_id, &1000i128);
+
+    let result = client.try_distribute_staker_rewards(&admin, &100i128);
+    assert_eq!(result, Err(Ok(EventRegistryError::NotStaked)));
+}
+
+#[test]
+fn test_distribute_rewards_unauthorized_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_loyalty_env(&env);
+
+    let random_caller = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_staking_config(&token_id, &1000i128);
+
+    let result = client.try_distribute_staker_rewards(&random_caller, &100i128);
+    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+}
+
+#[test]
+fn test_is_organizer_verified_false_when_not_staked() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_loyalty_env(&env);
+
+    let organizer = Address::generate(&env);
+    assert!(!client.is_organizer_verified(&organizer));
+}
+
+// ==================== USDC Token Whitelist Tests ====================
+
+#[test]
+fn test_usdc_token_whitelisted_after_init() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    // USDC token must be whitelisted automatically after initialization
+    assert!(client.is_token_whitelisted(&usdc_token));
+}
+
+#[test]
+fn test_non_usdc_token_not_whitelisted_after_init() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    let other_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    // A different token should NOT be whitelisted
+    assert!(!client.is_token_whitelisted(&other_token));
+}
+
+#[test]
+fn test_admin_can_add_token_to_whitelist() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    let new_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    assert!(!client.is_token_whitelisted(&new_token));
+    client.add_to_token_whitelist(&new_token);
+    assert!(client.is_token_whitelisted(&new_token));
+}
+
+#[test]
+fn test_admin_can_remove_token_from_whitelist() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    // USDC is whitelisted after init
+    assert!(client.is_token_whitelisted(&usdc_token));
+
+    // Admin removes it
+    client.remove_from_token_whitelist(&usdc_token);
+    assert!(!client.is_token_whitelisted(&usdc_token));
+}
+
+#[test]
+#[should_panic] // Authentication failure — non-admin cannot add to whitelist
+fn test_non_admin_cannot_add_token_to_whitelist() {
+    let env = Env::default();
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    let new_token = Address::generate(&env);
+
+    // Only mock auth for initialize, not for add_to_token_whitelist
+    env.mock_all_auths();
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    // Clear mocked auths so the next call requires real auth
+    let env2 = Env::default();
+    let client2 = EventRegistryClient::new(&env2, &contract_id);
+    client2.add_to_token_whitelist(&new_token);
+}
+
+#[test]
+fn test_register_event_with_banner_cid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let payment_addr = test_payment_address(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    let event_id = String::from_str(&env, "event_banner");
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    let banner_cid = Some(String::from_str(
+        &env,
         "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
     ));
 
@@ -4557,18 +5441,238 @@ fn test_update_platform_wallet_with_multisig() {
     let result = client.try_execute_proposal(&admin1, &proposal_id);
     assert!(result.is_err());
 
-    // Admin2 approves
-    client.approve_proposal(&admin2, &proposal_id);
+    // Admin2 approves
+    client.approve_proposal(&admin2, &proposal_id);
+
+    // Now execute should succeed
+    client.execute_proposal(&admin1, &proposal_id);
+
+    // Verify wallet was updated
+    assert_eq!(client.get_platform_wallet(), new_wallet);
+}
+
+#[test]
+fn test_propose_update_platform_wallet_invalid_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    // Try to set platform wallet to contract address (invalid)
+    let result = client.try_propose_set_platform_wallet(&admin, &contract_id, &0);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidAddress)));
+}
+
+#[test]
+fn test_propose_update_platform_wallet_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    // Try to propose as non-admin - should fail
+    let result = client.try_propose_set_platform_wallet(&non_admin, &new_wallet, &0);
+    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+}
+
+#[test]
+fn test_parameter_change_variants() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin1 = Address::generate(&env);
+    let admin2 = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+
+    client.initialize(&admin1, &platform_wallet, &500, &usdc_token);
+
+    // Test AddAdmin
+    let proposal_id = client.propose_add_admin(&admin1, &admin2, &0);
+    client.execute_proposal(&admin1, &proposal_id);
+    assert!(client.is_admin(&admin2));
+
+    // Test SetThreshold
+    let proposal_id = client.propose_set_threshold(&admin1, &2, &0);
+    client.execute_proposal(&admin1, &proposal_id);
+    let config = client.get_multisig_config();
+    assert_eq!(config.threshold, 2);
+
+    // Test UpdatePlatformWallet
+    let new_wallet = Address::generate(&env);
+    let proposal_id = client.propose_set_platform_wallet(&admin1, &new_wallet, &0);
+    client.approve_proposal(&admin2, &proposal_id);
+    client.execute_proposal(&admin1, &proposal_id);
+    assert_eq!(client.get_platform_wallet(), new_wallet);
+
+    // Test RemoveAdmin
+    let proposal_id = client.propose_remove_admin(&admin1, &admin2, &0);
+    client.approve_proposal(&admin2, &proposal_id);
+    client.execute_proposal(&admin1, &proposal_id);
+    assert!(!client.is_admin(&admin2));
+}
+
+#[test]
+fn test_proposal_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    // Create proposal with short expiry (10 ledgers)
+    let proposal_id = client.propose_set_platform_wallet(&admin, &new_wallet, &10);
+
+    // Advance ledger past expiration
+    env.ledger().with_mut(|li| {
+        li.timestamp += 11;
+    });
+
+    // Try to execute expired proposal - should fail
+    let result = client.try_execute_proposal(&admin, &proposal_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::ProposalExpired)));
+}
+
+#[test]
+fn test_active_proposals_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let admin2 = Address::generate(&env);
+    let admin3 = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    // Create multiple proposals
+    let proposal_id1 = client.propose_add_admin(&admin, &admin2, &0);
+    let proposal_id2 = client.propose_add_admin(&admin, &admin3, &0);
+
+    let active_proposals = client.get_active_proposals();
+    assert_eq!(active_proposals.len(), 2);
+    assert!(active_proposals.contains(proposal_id1));
+    assert!(active_proposals.contains(proposal_id2));
+
+    // Execute one proposal
+    client.execute_proposal(&admin, &proposal_id1);
+
+    // Should have one less active proposal
+    let active_proposals = client.get_active_proposals();
+    assert_eq!(active_proposals.len(), 1);
+    assert!(!active_proposals.contains(proposal_id1));
+    assert!(active_proposals.contains(proposal_id2));
+}
+
+#[test]
+fn test_propose_and_execute_set_platform_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    assert_eq!(client.get_platform_fee(), 500);
+
+    // Propose and execute a fee change via governance
+    let proposal_id = client.propose_set_platform_fee(&admin, &300, &0);
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.proposal_id, proposal_id);
+    assert!(!proposal.executed);
+
+    client.execute_proposal(&admin, &proposal_id);
+
+    assert_eq!(client.get_platform_fee(), 300);
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert!(proposal.executed);
+}
+
+#[test]
+fn test_propose_set_platform_fee_invalid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+
+    // Fee > 10000 bps should be rejected
+    let result = client.try_propose_set_platform_fee(&admin, &10001, &0);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidFeePercent)));
+}
+
+#[test]
+fn test_propose_and_execute_set_min_stake_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    let staking_token = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    // Configure staking so there is an initial min stake amount
+    client.set_staking_config(&staking_token, &1_000_000);
 
-    // Now execute should succeed
-    client.execute_proposal(&admin1, &proposal_id);
+    // Propose and execute a min stake change via governance
+    let proposal_id = client.propose_set_min_stake_amount(&admin, &2_000_000_i128, &0);
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.proposal_id, proposal_id);
+    assert!(!proposal.executed);
 
-    // Verify wallet was updated
-    assert_eq!(client.get_platform_wallet(), new_wallet);
+    client.execute_proposal(&admin, &proposal_id);
+
+    assert_eq!(client.get_min_stake_amount(), 2_000_000_i128);
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert!(proposal.executed);
 }
 
 #[test]
-fn test_propose_update_platform_wallet_invalid_address() {
+fn test_propose_set_min_stake_amount_invalid() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -4581,13 +5685,16 @@ fn test_propose_update_platform_wallet_invalid_address() {
 
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    // Try to set platform wallet to contract address (invalid)
-    let result = client.try_propose_set_platform_wallet(&admin, &contract_id, &0);
-    assert_eq!(result, Err(Ok(EventRegistryError::InvalidAddress)));
+    // Zero or negative amount should be rejected
+    let result = client.try_propose_set_min_stake_amount(&admin, &0_i128, &0);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStakeAmount)));
+
+    let result = client.try_propose_set_min_stake_amount(&admin, &-1_i128, &0);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStakeAmount)));
 }
 
 #[test]
-fn test_propose_update_platform_wallet_unauthorized() {
+fn test_propose_set_platform_fee_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -4597,18 +5704,16 @@ fn test_propose_update_platform_wallet_unauthorized() {
     let admin = Address::generate(&env);
     let non_admin = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    let new_wallet = Address::generate(&env);
     let usdc_token = Address::generate(&env);
 
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    // Try to propose as non-admin - should fail
-    let result = client.try_propose_set_platform_wallet(&non_admin, &new_wallet, &0);
+    let result = client.try_propose_set_platform_fee(&non_admin, &300, &0);
     assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
 }
 
 #[test]
-fn test_parameter_change_variants() {
+fn test_set_platform_fee_with_multisig() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -4622,96 +5727,155 @@ fn test_parameter_change_variants() {
 
     client.initialize(&admin1, &platform_wallet, &500, &usdc_token);
 
-    // Test AddAdmin
-    let proposal_id = client.propose_add_admin(&admin1, &admin2, &0);
-    client.execute_proposal(&admin1, &proposal_id);
-    assert!(client.is_admin(&admin2));
+    // Add admin2 and set threshold to 2
+    let pid = client.propose_add_admin(&admin1, &admin2, &0);
+    client.execute_proposal(&admin1, &pid);
+    let pid = client.propose_set_threshold(&admin1, &2, &0);
+    client.execute_proposal(&admin1, &pid);
 
-    // Test SetThreshold
-    let proposal_id = client.propose_set_threshold(&admin1, &2, &0);
-    client.execute_proposal(&admin1, &proposal_id);
-    let config = client.get_multisig_config();
-    assert_eq!(config.threshold, 2);
+    // Propose fee change — should need 2 approvals
+    let proposal_id = client.propose_set_platform_fee(&admin1, &200, &0);
 
-    // Test UpdatePlatformWallet
-    let new_wallet = Address::generate(&env);
-    let proposal_id = client.propose_set_platform_wallet(&admin1, &new_wallet, &0);
-    client.approve_proposal(&admin2, &proposal_id);
-    client.execute_proposal(&admin1, &proposal_id);
-    assert_eq!(client.get_platform_wallet(), new_wallet);
+    // Single approval should not be enough
+    let result = client.try_execute_proposal(&admin1, &proposal_id);
+    assert!(result.is_err());
 
-    // Test RemoveAdmin
-    let proposal_id = client.propose_remove_admin(&admin1, &admin2, &0);
+    // Second admin approves and execution succeeds
     client.approve_proposal(&admin2, &proposal_id);
     client.execute_proposal(&admin1, &proposal_id);
-    assert!(!client.is_admin(&admin2));
+    assert_eq!(client.get_platform_fee(), 200);
 }
 
 #[test]
-fn test_proposal_expiry() {
+fn test_cancelled_status_guard() {
     let env = Env::default();
     env.mock_all_auths();
-
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    let new_wallet = Address::generate(&env);
     let usdc_token = Address::generate(&env);
-
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    // Create proposal with short expiry (10 ledgers)
-    let proposal_id = client.propose_set_platform_wallet(&admin, &new_wallet, &10);
+    let organizer = Address::generate(&env);
+    let event_id = String::from_str(&env, "cancelled_event");
 
-    // Advance ledger past expiration
-    env.ledger().with_mut(|li| {
-        li.timestamp += 11;
+    // Register event
+    let tiers = Map::new(&env);
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Test Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 0,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
     });
 
-    // Try to execute expired proposal - should fail
-    let result = client.try_execute_proposal(&admin, &proposal_id);
-    assert_eq!(result, Err(Ok(EventRegistryError::ProposalExpired)));
-}
+    // Cancel event
+    client.cancel_event(&event_id);
 
-#[test]
-fn test_active_proposals_list() {
-    let env = Env::default();
-    env.mock_all_auths();
+    // Try to update status - should fail
+    let result = client.try_update_event_status(&event_id, &true);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
 
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    // Try to postpone - should fail
+    let result = client.try_postpone_event(&event_id, &10000000);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
 
-    let admin = Address::generate(&env);
-    let admin2 = Address::generate(&env);
-    let admin3 = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
+    // Try to update metadata - should fail
+    let result = client.try_update_metadata(
+        &event_id,
+        &String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
 
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    // Try to set custom fee - should fail
+    let result = client.try_set_custom_event_fee(&event_id, &Some(100));
+    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
+}
 
-    // Create multiple proposals
-    let proposal_id1 = client.propose_add_admin(&admin, &admin2, &0);
-    let proposal_id2 = client.propose_add_admin(&admin, &admin3, &0);
+// ── Issue #194: Tier Error Message Tests ─────────────────────────────────────
 
-    let active_proposals = client.get_active_proposals();
-    assert_eq!(active_proposals.len(), 2);
-    assert!(active_proposals.contains(proposal_id1));
-    assert!(active_proposals.contains(proposal_id2));
+/// Helper to format a Display value into a fixed-size stack buffer.
+fn fmt_to_str<T: core::fmt::Display>(val: T) -> [u8; 256] {
+    struct Buf {
+        data: [u8; 256],
+        pos: usize,
+    }
+    impl core::fmt::Write for Buf {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let bytes = s.as_bytes();
+            let end = self.pos + bytes.len();
+            if end <= self.data.len() {
+                self.data[self.pos..end].copy_from_slice(bytes);
+                self.pos = end;
+            }
+            Ok(())
+        }
+    }
+    let mut buf = Buf {
+        data: [0u8; 256],
+        pos: 0,
+    };
+    core::fmt::write(&mut buf, format_args!("{}", val)).ok();
+    buf.data
+}
 
-    // Execute one proposal
-    client.execute_proposal(&admin, &proposal_id1);
+fn buf_starts_with(buf: &[u8; 256], expected: &str) -> bool {
+    let e = expected.as_bytes();
+    buf.len() >= e.len() && &buf[..e.len()] == e && (e.len() == 256 || buf[e.len()] == 0)
+}
 
-    // Should have one less active proposal
-    let active_proposals = client.get_active_proposals();
-    assert_eq!(active_proposals.len(), 1);
-    assert!(!active_proposals.contains(proposal_id1));
-    assert!(active_proposals.contains(proposal_id2));
+#[test]
+fn test_tier_not_found_error_message() {
+    let buf = fmt_to_str(EventRegistryError::TierNotFound);
+    assert!(
+        buf_starts_with(
+            &buf,
+            "The specified ticket tier ID does not exist for this event"
+        ),
+        "unexpected message"
+    );
 }
 
 #[test]
-fn test_propose_and_execute_set_platform_fee() {
+fn test_tier_supply_exceeded_error_message() {
+    let buf = fmt_to_str(EventRegistryError::TierSupplyExceeded);
+    assert!(
+        buf_starts_with(
+            &buf,
+            "The requested ticket tier has sold out and cannot accept more registrations"
+        ),
+        "unexpected message"
+    );
+}
+
+// ── Issue #211: Restocking Fee Guard Tests ────────────────────────────────────
+
+#[test]
+fn test_register_event_restocking_fee_exceeds_tier_price_fails() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -4721,25 +5885,60 @@ fn test_propose_and_execute_set_platform_fee() {
     let admin = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
     let usdc_token = Address::generate(&env);
-
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
-    assert_eq!(client.get_platform_fee(), 500);
 
-    // Propose and execute a fee change via governance
-    let proposal_id = client.propose_set_platform_fee(&admin, &300, &0);
-    let proposal = client.get_proposal(&proposal_id).unwrap();
-    assert_eq!(proposal.proposal_id, proposal_id);
-    assert!(!proposal.executed);
+    let organizer = Address::generate(&env);
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        String::from_str(&env, "general"),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 5_000_000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0,
+        },
+    );
 
-    client.execute_proposal(&admin, &proposal_id);
+    // restocking_fee (6_000_000) > tier price (5_000_000) — must fail
+    let result = client.try_register_event(&EventRegistrationArgs {
+        event_id: String::from_str(&env, "evt_restocking_guard"),
+        name: String::from_str(&env, "Test Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 6_000_000,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
 
-    assert_eq!(client.get_platform_fee(), 300);
-    let proposal = client.get_proposal(&proposal_id).unwrap();
-    assert!(proposal.executed);
+    assert_eq!(
+        result,
+        Err(Ok(EventRegistryError::RestockingFeeExceedsTicketPrice))
+    );
 }
 
 #[test]
-fn test_propose_set_platform_fee_invalid() {
+fn test_register_event_restocking_fee_equal_to_tier_price_succeeds() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -4749,16 +5948,57 @@ fn test_propose_set_platform_fee_invalid() {
     let admin = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
     let usdc_token = Address::generate(&env);
-
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    // Fee > 10000 bps should be rejected
-    let result = client.try_propose_set_platform_fee(&admin, &10001, &0);
-    assert_eq!(result, Err(Ok(EventRegistryError::InvalidFeePercent)));
+    let organizer = Address::generate(&env);
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        String::from_str(&env, "general"),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 5_000_000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0,
+        },
+    );
+
+    // restocking_fee == tier price — should succeed
+    let result = client.try_register_event(&EventRegistrationArgs {
+        event_id: String::from_str(&env, "evt_restocking_equal"),
+        name: String::from_str(&env, "Test Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 5_000_000,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
+
+    assert!(result.is_ok());
 }
 
 #[test]
-fn test_propose_and_execute_set_min_stake_amount() {
+fn test_register_event_restocking_fee_zero_always_valid() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -4768,27 +6008,56 @@ fn test_propose_and_execute_set_min_stake_amount() {
     let admin = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
     let usdc_token = Address::generate(&env);
-    let staking_token = Address::generate(&env);
-
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
-    // Configure staking so there is an initial min stake amount
-    client.set_staking_config(&staking_token, &1_000_000);
 
-    // Propose and execute a min stake change via governance
-    let proposal_id = client.propose_set_min_stake_amount(&admin, &2_000_000_i128, &0);
-    let proposal = client.get_proposal(&proposal_id).unwrap();
-    assert_eq!(proposal.proposal_id, proposal_id);
-    assert!(!proposal.executed);
+    let organizer = Address::generate(&env);
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        String::from_str(&env, "general"),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 1_000,
+            tier_limit: 50,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0,
+        },
+    );
 
-    client.execute_proposal(&admin, &proposal_id);
+    let result = client.try_register_event(&EventRegistrationArgs {
+        event_id: String::from_str(&env, "evt_restocking_zero"),
+        name: String::from_str(&env, "Test Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 50,
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 0,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
 
-    assert_eq!(client.get_min_stake_amount(), 2_000_000_i128);
-    let proposal = client.get_proposal(&proposal_id).unwrap();
-    assert!(proposal.executed);
+    assert!(result.is_ok());
 }
 
 #[test]
-fn test_propose_set_min_stake_amount_invalid() {
+fn test_register_event_restocking_fee_overflow_returns_invalid_fee_calculation() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -4798,99 +6067,249 @@ fn test_propose_set_min_stake_amount_invalid() {
     let admin = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
     let usdc_token = Address::generate(&env);
-
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    // Zero or negative amount should be rejected
-    let result = client.try_propose_set_min_stake_amount(&admin, &0_i128, &0);
-    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStakeAmount)));
+    let organizer = Address::generate(&env);
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        String::from_str(&env, "general"),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: i128::MIN,
+            tier_limit: 50,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0,
+        },
+    );
 
-    let result = client.try_propose_set_min_stake_amount(&admin, &-1_i128, &0);
-    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStakeAmount)));
+    let result = client.try_register_event(&EventRegistrationArgs {
+        event_id: String::from_str(&env, "evt_restocking_overflow"),
+        name: String::from_str(&env, "Test Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 50,
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 1,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
+
+    assert_eq!(
+        result,
+        Err(Ok(EventRegistryError::RestockingFeeExceedsTicketPrice))
+    );
 }
 
 #[test]
-fn test_propose_set_platform_fee_unauthorized() {
-    let env = Env::default();
-    env.mock_all_auths();
+fn test_restocking_fee_exceeds_ticket_price_error_message() {
+    let buf = fmt_to_str(EventRegistryError::RestockingFeeExceedsTicketPrice);
+    assert!(
+        buf_starts_with(
+            &buf,
+            "Restocking fee must not exceed the original ticket price"
+        ),
+        "unexpected message"
+    );
+}
 
+#[test]
+fn test_register_event_tier_limit_overflow() {
+    let env = Env::default();
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
-    let non_admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let payment_addr = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
+    env.mock_all_auths();
 
+    let usdc_token = Address::generate(&env);
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    let result = client.try_propose_set_platform_fee(&non_admin, &300, &0);
-    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+    let event_id = String::from_str(&env, "overflow_event");
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+
+    let mut tiers = Map::new(&env);
+    // Two tiers that sum to > i128::MAX
+    tiers.set(
+        String::from_str(&env, "t1"),
+        TicketTier {
+            name: String::from_str(&env, "T1"),
+            price: 100,
+            tier_limit: i128::MAX / 2 + 10,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0,
+        },
+    );
+    tiers.set(
+        String::from_str(&env, "t2"),
+        TicketTier {
+            name: String::from_str(&env, "T2"),
+            price: 100,
+            tier_limit: i128::MAX / 2 + 10,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0,
+        },
+    );
+
+    let result = client.try_register_event(&EventRegistrationArgs {
+        event_id,
+        name: String::from_str(&env, "Overflow Event"),
+        organizer_address: organizer,
+        payment_address: payment_addr,
+        metadata_cid,
+        max_supply: 0, // unlimited global supply, but tier limits still overflow
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 0,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
+    assert_eq!(result, Err(Ok(EventRegistryError::SupplyOverflow)));
 }
 
 #[test]
-fn test_set_platform_fee_with_multisig() {
+fn test_register_event_invalid_tier_limit_negative() {
     let env = Env::default();
-    env.mock_all_auths();
-
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
 
-    let admin1 = Address::generate(&env);
-    let admin2 = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let payment_addr = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-
-    client.initialize(&admin1, &platform_wallet, &500, &usdc_token);
+    env.mock_all_auths();
 
-    // Add admin2 and set threshold to 2
-    let pid = client.propose_add_admin(&admin1, &admin2, &0);
-    client.execute_proposal(&admin1, &pid);
-    let pid = client.propose_set_threshold(&admin1, &2, &0);
-    client.execute_proposal(&admin1, &pid);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    // Propose fee change — should need 2 approvals
-    let proposal_id = client.propose_set_platform_fee(&admin1, &200, &0);
+    let event_id = String::from_str(&env, "negative_tier_event");
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
 
-    // Single approval should not be enough
-    let result = client.try_execute_proposal(&admin1, &proposal_id);
-    assert!(result.is_err());
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        String::from_str(&env, "bad"),
+        TicketTier {
+            name: String::from_str(&env, "Bad"),
+            price: 100,
+            tier_limit: -1,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0,
+        },
+    );
 
-    // Second admin approves and execution succeeds
-    client.approve_proposal(&admin2, &proposal_id);
-    client.execute_proposal(&admin1, &proposal_id);
-    assert_eq!(client.get_platform_fee(), 200);
+    let result = client.try_register_event(&EventRegistrationArgs {
+        event_id,
+        name: String::from_str(&env, "Negative Tier Event"),
+        organizer_address: organizer,
+        payment_address: payment_addr,
+        metadata_cid,
+        max_supply: 0,
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 0,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidQuantity)));
 }
 
 #[test]
-fn test_cancelled_status_guard() {
+fn test_register_event_milestone_overflow() {
     let env = Env::default();
-    env.mock_all_auths();
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let payment_addr = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
+    env.mock_all_auths();
+
     let usdc_token = Address::generate(&env);
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    let organizer = Address::generate(&env);
-    let event_id = String::from_str(&env, "cancelled_event");
+    let event_id = String::from_str(&env, "milestone_overflow");
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
 
-    // Register event
-    let tiers = Map::new(&env);
-    client.register_event(&EventRegistrationArgs {
-        event_id: event_id.clone(),
-        name: String::from_str(&env, "Test Event"),
-        organizer_address: organizer.clone(),
-        payment_address: test_payment_address(&env),
-        metadata_cid: String::from_str(
-            &env,
-            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-        ),
+    let milestones = soroban_sdk::vec![
+        &env,
+        crate::types::Milestone {
+            sales_threshold: 10,
+            release_percent: u32::MAX,
+        },
+        crate::types::Milestone {
+            sales_threshold: 20,
+            release_percent: 1,
+        },
+    ];
+
+    let result = client.try_register_event(&EventRegistrationArgs {
+        event_id,
+        name: String::from_str(&env, "Milestone Overflow Event"),
+        organizer_address: organizer,
+        payment_address: payment_addr,
+        metadata_cid,
         max_supply: 100,
-        milestone_plan: None,
-        tiers,
+        milestone_plan: Some(milestones),
+        tiers: Map::new(&env),
         refund_deadline: 0,
         restocking_fee: 0,
         resale_cap_bps: None,
@@ -4905,253 +6324,290 @@ fn test_cancelled_status_guard() {
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
     });
+    assert_eq!(result, Err(Ok(EventRegistryError::SupplyOverflow)));
+}
 
-    // Cancel event
-    client.cancel_event(&event_id);
-
-    // Try to update status - should fail
-    let result = client.try_update_event_status(&event_id, &true);
-    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
+// ── Tags Tests ────────────────────────────────────────────────────────────────
 
-    // Try to postpone - should fail
-    let result = client.try_postpone_event(&event_id, &10000000);
-    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
+/// Helper: initialise the registry and return (client, admin, organizer).
+fn setup_tags_test(env: &Env) -> (EventRegistryClient<'static>, Address, Address) {
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let platform_wallet = Address::generate(env);
+    let usdc_token = Address::generate(env);
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let organizer = Address::generate(env);
+    (client, admin, organizer)
+}
 
-    // Try to update metadata - should fail
-    let result = client.try_update_metadata(
-        &event_id,
-        &String::from_str(
-            &env,
+fn tags_base_args(env: &Env, event_id: &str, organizer: &Address) -> EventRegistrationArgs {
+    EventRegistrationArgs {
+        event_id: String::from_str(env, event_id),
+        name: String::from_str(env, "Test Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(env),
+        metadata_cid: String::from_str(
+            env,
             "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
         ),
-    );
-    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
+        max_supply: 0,
+        milestone_plan: None,
+        tiers: Map::new(env),
+        refund_deadline: 0,
+        restocking_fee: 0,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(env),
+        use_global_whitelist: true,
+    }
+}
 
-    // Try to set custom fee - should fail
-    let result = client.try_set_custom_event_fee(&event_id, &Some(100));
-    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
+/// Registering with no tags (None) succeeds and EventInfo.tags is None.
+#[test]
+fn test_register_event_without_tags() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin, organizer) = setup_tags_test(&env);
+
+    let args = tags_base_args(&env, "evt_no_tags", &organizer);
+    client.register_event(&args);
+
+    let info = client
+        .get_event(&String::from_str(&env, "evt_no_tags"))
+        .unwrap();
+    assert!(info.tags.is_none());
 }
 
-// ── Issue #194: Tier Error Message Tests ─────────────────────────────────────
+/// Registering with a valid set of tags stores them correctly.
+#[test]
+fn test_register_event_with_tags_stored_correctly() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin, organizer) = setup_tags_test(&env);
 
-/// Helper to format a Display value into a fixed-size stack buffer.
-fn fmt_to_str<T: core::fmt::Display>(val: T) -> [u8; 256] {
-    struct Buf {
-        data: [u8; 256],
-        pos: usize,
-    }
-    impl core::fmt::Write for Buf {
-        fn write_str(&mut self, s: &str) -> core::fmt::Result {
-            let bytes = s.as_bytes();
-            let end = self.pos + bytes.len();
-            if end <= self.data.len() {
-                self.data[self.pos..end].copy_from_slice(bytes);
-                self.pos = end;
-            }
-            Ok(())
-        }
-    }
-    let mut buf = Buf {
-        data: [0u8; 256],
-        pos: 0,
-    };
-    core::fmt::write(&mut buf, format_args!("{}", val)).ok();
-    buf.data
+    let tags = soroban_sdk::vec![
+        &env,
+        String::from_str(&env, "Music"),
+        String::from_str(&env, "Tech"),
+        String::from_str(&env, "Outdoor"),
+    ];
+
+    let mut args = tags_base_args(&env, "evt_tags", &organizer);
+    args.tags = Some(tags.clone());
+    client.register_event(&args);
+
+    let info = client
+        .get_event(&String::from_str(&env, "evt_tags"))
+        .unwrap();
+    let stored = info.tags.unwrap();
+    assert_eq!(stored.len(), 3);
+    assert_eq!(stored.get(0).unwrap(), String::from_str(&env, "Music"));
+    assert_eq!(stored.get(1).unwrap(), String::from_str(&env, "Tech"));
+    assert_eq!(stored.get(2).unwrap(), String::from_str(&env, "Outdoor"));
 }
 
-fn buf_starts_with(buf: &[u8; 256], expected: &str) -> bool {
-    let e = expected.as_bytes();
-    buf.len() >= e.len() && &buf[..e.len()] == e && (e.len() == 256 || buf[e.len()] == 0)
+/// Exactly 10 tags is the maximum allowed — must succeed.
+#[test]
+fn test_register_event_with_exactly_10_tags_succeeds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin, organizer) = setup_tags_test(&env);
+
+    let tags = soroban_sdk::vec![
+        &env,
+        String::from_str(&env, "Tag1"),
+        String::from_str(&env, "Tag2"),
+        String::from_str(&env, "Tag3"),
+        String::from_str(&env, "Tag4"),
+        String::from_str(&env, "Tag5"),
+        String::from_str(&env, "Tag6"),
+        String::from_str(&env, "Tag7"),
+        String::from_str(&env, "Tag8"),
+        String::from_str(&env, "Tag9"),
+        String::from_str(&env, "Tag10"),
+    ];
+
+    let mut args = tags_base_args(&env, "evt_10_tags", &organizer);
+    args.tags = Some(tags);
+    let result = client.try_register_event(&args);
+    assert!(result.is_ok(), "10 tags should be accepted");
 }
 
+/// 11 tags exceeds the maximum — must return InvalidTags.
 #[test]
-fn test_tier_not_found_error_message() {
-    let buf = fmt_to_str(EventRegistryError::TierNotFound);
-    assert!(
-        buf_starts_with(
-            &buf,
-            "The specified ticket tier ID does not exist for this event"
-        ),
-        "unexpected message"
-    );
+fn test_register_event_with_11_tags_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin, organizer) = setup_tags_test(&env);
+
+    let tags = soroban_sdk::vec![
+        &env,
+        String::from_str(&env, "T1"),
+        String::from_str(&env, "T2"),
+        String::from_str(&env, "T3"),
+        String::from_str(&env, "T4"),
+        String::from_str(&env, "T5"),
+        String::from_str(&env, "T6"),
+        String::from_str(&env, "T7"),
+        String::from_str(&env, "T8"),
+        String::from_str(&env, "T9"),
+        String::from_str(&env, "T10"),
+        String::from_str(&env, "T11"),
+    ];
+
+    let mut args = tags_base_args(&env, "evt_11_tags", &organizer);
+    args.tags = Some(tags);
+    let result = client.try_register_event(&args);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidTags)));
 }
 
+/// A tag of exactly 32 characters is the maximum allowed length — must succeed.
 #[test]
-fn test_tier_supply_exceeded_error_message() {
-    let buf = fmt_to_str(EventRegistryError::TierSupplyExceeded);
-    assert!(
-        buf_starts_with(
-            &buf,
-            "The requested ticket tier has sold out and cannot accept more registrations"
-        ),
-        "unexpected message"
-    );
-}
+fn test_register_event_tag_exactly_32_chars_succeeds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin, organizer) = setup_tags_test(&env);
 
-// ── Issue #211: Restocking Fee Guard Tests ────────────────────────────────────
+    // 32 characters exactly
+    let tag_32 = String::from_str(&env, "12345678901234567890123456789012");
+    let tags = soroban_sdk::vec![&env, tag_32];
+
+    let mut args = tags_base_args(&env, "evt_tag_32", &organizer);
+    args.tags = Some(tags);
+    let result = client.try_register_event(&args);
+    assert!(result.is_ok(), "32-char tag should be accepted");
+}
 
+/// A tag of 33 characters exceeds the maximum length — must return InvalidTags.
 #[test]
-fn test_register_event_restocking_fee_exceeds_tier_price_fails() {
+fn test_register_event_tag_33_chars_fails() {
     let env = Env::default();
     env.mock_all_auths();
+    let (client, _admin, organizer) = setup_tags_test(&env);
 
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
-
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    // 33 characters
+    let tag_33 = String::from_str(&env, "123456789012345678901234567890123");
+    let tags = soroban_sdk::vec![&env, tag_33];
 
-    let organizer = Address::generate(&env);
-    let mut tiers = Map::new(&env);
-    tiers.set(
-        String::from_str(&env, "general"),
-        TicketTier {
-            name: String::from_str(&env, "General"),
-            price: 5_000_000,
-            tier_limit: 100,
-            current_sold: 0,
-            is_refundable: true,
-            auction_config: soroban_sdk::vec![&env],
-            loyalty_multiplier: 1,
-            max_per_user: 0,
-        },
-    );
+    let mut args = tags_base_args(&env, "evt_tag_33", &organizer);
+    args.tags = Some(tags);
+    let result = client.try_register_event(&args);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidTags)));
+}
 
-    // restocking_fee (6_000_000) > tier price (5_000_000) — must fail
-    let result = client.try_register_event(&EventRegistrationArgs {
-        event_id: String::from_str(&env, "evt_restocking_guard"),
-        name: String::from_str(&env, "Test Event"),
-        organizer_address: organizer.clone(),
-        payment_address: test_payment_address(&env),
-        metadata_cid: String::from_str(
-            &env,
-            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-        ),
-        max_supply: 100,
-        milestone_plan: None,
-        tiers,
-        refund_deadline: 0,
-        restocking_fee: 6_000_000,
-        resale_cap_bps: None,
-        min_sales_target: None,
-        target_deadline: None,
-        banner_cid: None,
-        tags: None,
-        start_time: 0,
-        is_private: false,
-        end_time: 0,
-        transfer_lock_duration: 0,
-        accepted_tokens: soroban_sdk::Vec::new(&env),
-        use_global_whitelist: true,
-    });
+/// A single empty-string tag is valid (length 0 ≤ 32).
+#[test]
+fn test_register_event_empty_tag_is_valid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin, organizer) = setup_tags_test(&env);
 
-    assert_eq!(
-        result,
-        Err(Ok(EventRegistryError::RestockingFeeExceedsTicketPrice))
-    );
+    let tags = soroban_sdk::vec![&env, String::from_str(&env, "")];
+    let mut args = tags_base_args(&env, "evt_empty_tag", &organizer);
+    args.tags = Some(tags);
+    let result = client.try_register_event(&args);
+    assert!(result.is_ok(), "empty tag string should be accepted");
 }
 
+/// Tags are independent of other fields — an event with tags and a banner_cid
+/// stores both correctly.
 #[test]
-fn test_register_event_restocking_fee_equal_to_tier_price_succeeds() {
+fn test_register_event_tags_and_banner_cid_coexist() {
     let env = Env::default();
     env.mock_all_auths();
+    let (client, _admin, organizer) = setup_tags_test(&env);
 
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    let banner = String::from_str(&env, "bafybeibanner123");
+    let tags = soroban_sdk::vec![
+        &env,
+        String::from_str(&env, "Music"),
+        String::from_str(&env, "Festival"),
+    ];
 
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let mut args = tags_base_args(&env, "evt_both", &organizer);
+    args.banner_cid = Some(banner.clone());
+    args.tags = Some(tags);
+    client.register_event(&args);
 
-    let organizer = Address::generate(&env);
-    let mut tiers = Map::new(&env);
-    tiers.set(
-        String::from_str(&env, "general"),
-        TicketTier {
-            name: String::from_str(&env, "General"),
-            price: 5_000_000,
-            tier_limit: 100,
-            current_sold: 0,
-            is_refundable: true,
-            auction_config: soroban_sdk::vec![&env],
-            loyalty_multiplier: 1,
-            max_per_user: 0,
-        },
-    );
+    let info = client
+        .get_event(&String::from_str(&env, "evt_both"))
+        .unwrap();
+    assert_eq!(info.banner_cid.unwrap(), banner);
+    assert_eq!(info.tags.unwrap().len(), 2);
+}
 
-    // restocking_fee == tier price — should succeed
-    let result = client.try_register_event(&EventRegistrationArgs {
-        event_id: String::from_str(&env, "evt_restocking_equal"),
-        name: String::from_str(&env, "Test Event"),
-        organizer_address: organizer.clone(),
-        payment_address: test_payment_address(&env),
-        metadata_cid: String::from_str(
-            &env,
-            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-        ),
-        max_supply: 100,
-        milestone_plan: None,
-        tiers,
-        refund_deadline: 0,
-        restocking_fee: 5_000_000,
-        resale_cap_bps: None,
-        min_sales_target: None,
-        target_deadline: None,
-        banner_cid: None,
-        tags: None,
-        start_time: 0,
-        is_private: false,
-        end_time: 0,
-        transfer_lock_duration: 0,
-        accepted_tokens: soroban_sdk::Vec::new(&env),
-        use_global_whitelist: true,
-    });
+// ── VERSION constant ──────────────────────────────────────────────────────────
 
-    assert!(result.is_ok());
+/// The VERSION constant must equal 1.
+#[test]
+fn test_version_constant_value() {
+    assert_eq!(crate::VERSION, 1u32);
 }
 
+/// The `version()` contract function must return 1.
 #[test]
-fn test_register_event_restocking_fee_zero_always_valid() {
+fn test_version_fn_returns_1() {
     let env = Env::default();
-    env.mock_all_auths();
-
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
+    assert_eq!(client.version(), 1u32);
+}
 
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
+// ── Private Event Support Tests ───────────────────────────────────────────────
+
+fn setup_private_test(env: &Env) -> (EventRegistryClient<'static>, Address, Address, Address) {
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let organizer = Address::generate(env);
+    let platform_wallet = Address::generate(env);
+    let usdc_token = Address::generate(env);
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let ticket_payment = Address::generate(env);
+    client.set_ticket_payment_contract(&ticket_payment);
+    (client, admin, organizer, ticket_payment)
+}
 
-    let organizer = Address::generate(&env);
-    let mut tiers = Map::new(&env);
+fn register_event_with_privacy(
+    env: &Env,
+    client: &EventRegistryClient,
+    organizer: &Address,
+    event_id: &str,
+    is_private: bool,
+) {
+    let metadata_cid = String::from_str(
+        env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    let mut tiers = Map::new(env);
     tiers.set(
-        String::from_str(&env, "general"),
-        TicketTier {
-            name: String::from_str(&env, "General"),
-            price: 1_000,
-            tier_limit: 50,
+        String::from_str(env, "tier_1"),
+        crate::types::TicketTier {
+            name: String::from_str(env, "General"),
+            price: 1000,
+            tier_limit: 100,
             current_sold: 0,
-            is_refundable: true,
-            auction_config: soroban_sdk::vec![&env],
+            is_refundable: false,
+            auction_config: soroban_sdk::vec![env],
             loyalty_multiplier: 1,
             max_per_user: 0,
         },
     );
-
-    let result = client.try_register_event(&EventRegistrationArgs {
-        event_id: String::from_str(&env, "evt_restocking_zero"),
-        name: String::from_str(&env, "Test Event"),
+    client.register_event(&EventRegistrationArgs {
+        event_id: String::from_str(env, event_id),
+        name: String::from_str(env, "Test Event"),
         organizer_address: organizer.clone(),
-        payment_address: test_payment_address(&env),
-        metadata_cid: String::from_str(
-            &env,
-            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-        ),
-        max_supply: 50,
+        payment_address: test_payment_address(env),
+        metadata_cid,
+        max_supply: 100,
         milestone_plan: None,
         tiers,
         refund_deadline: 0,
@@ -5162,147 +6618,181 @@ fn test_register_event_restocking_fee_zero_always_valid() {
         banner_cid: None,
         tags: None,
         start_time: 0,
-        is_private: false,
+        is_private,
         end_time: 0,
         transfer_lock_duration: 0,
-        accepted_tokens: soroban_sdk::Vec::new(&env),
+        accepted_tokens: soroban_sdk::Vec::new(env),
         use_global_whitelist: true,
     });
+}
 
-    assert!(result.is_ok());
+/// A public event increments the global managed events counter.
+#[test]
+fn test_public_event_increments_managed_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, organizer, _) = setup_private_test(&env);
+
+    assert_eq!(client.get_managed_events_count(), 0);
+    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
+    assert_eq!(client.get_managed_events_count(), 1);
 }
 
+/// A private event does NOT increment the global managed events counter.
 #[test]
-fn test_register_event_restocking_fee_overflow_returns_invalid_fee_calculation() {
+fn test_private_event_excluded_from_managed_count() {
     let env = Env::default();
     env.mock_all_auths();
+    let (client, _, organizer, _) = setup_private_test(&env);
 
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    assert_eq!(client.get_managed_events_count(), 0);
+    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
+    assert_eq!(client.get_managed_events_count(), 0);
+}
 
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+/// A public event increments the global active events counter.
+#[test]
+fn test_public_event_increments_active_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, organizer, _) = setup_private_test(&env);
 
-    let organizer = Address::generate(&env);
-    let mut tiers = Map::new(&env);
-    tiers.set(
-        String::from_str(&env, "general"),
-        TicketTier {
-            name: String::from_str(&env, "General"),
-            price: i128::MIN,
-            tier_limit: 50,
-            current_sold: 0,
-            is_refundable: true,
-            auction_config: soroban_sdk::vec![&env],
-            loyalty_multiplier: 1,
-            max_per_user: 0,
-        },
-    );
+    assert_eq!(client.get_active_events_count(), 0);
+    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
+    assert_eq!(client.get_active_events_count(), 1);
+}
 
-    let result = client.try_register_event(&EventRegistrationArgs {
-        event_id: String::from_str(&env, "evt_restocking_overflow"),
-        name: String::from_str(&env, "Test Event"),
-        organizer_address: organizer.clone(),
-        payment_address: test_payment_address(&env),
-        metadata_cid: String::from_str(
-            &env,
-            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-        ),
-        max_supply: 50,
-        milestone_plan: None,
-        tiers,
-        refund_deadline: 0,
-        restocking_fee: 1,
-        resale_cap_bps: None,
-        min_sales_target: None,
-        target_deadline: None,
-        banner_cid: None,
-        tags: None,
-        start_time: 0,
-        is_private: false,
-        end_time: 0,
-        transfer_lock_duration: 0,
-        accepted_tokens: soroban_sdk::Vec::new(&env),
-        use_global_whitelist: true,
-    });
+/// A private event does NOT increment the global active events counter.
+#[test]
+fn test_private_event_excluded_from_active_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, organizer, _) = setup_private_test(&env);
 
-    assert_eq!(
-        result,
-        Err(Ok(EventRegistryError::RestockingFeeExceedsTicketPrice))
+    assert_eq!(client.get_active_events_count(), 0);
+    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
+    assert_eq!(client.get_active_events_count(), 0);
+}
+
+/// Mixed registration: only public events count toward global counters.
+#[test]
+fn test_mixed_public_private_events_counters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, organizer, _) = setup_private_test(&env);
+
+    register_event_with_privacy(&env, &client, &organizer, "pub_1", false);
+    register_event_with_privacy(&env, &client, &organizer, "priv_1", true);
+    register_event_with_privacy(&env, &client, &organizer, "pub_2", false);
+    register_event_with_privacy(&env, &client, &organizer, "priv_2", true);
+
+    // 2 public events counted, 2 private excluded
+    assert_eq!(client.get_managed_events_count(), 2);
+    assert_eq!(client.get_active_events_count(), 2);
+}
+
+/// Tickets sold for a public event are included in the global counter.
+#[test]
+fn test_public_event_tickets_counted_globally() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, organizer, ticket_payment) = setup_private_test(&env);
+
+    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
+
+    assert_eq!(client.get_global_tickets_sold(), 0);
+    client.increment_inventory(
+        &String::from_str(&env, "pub_evt"),
+        &String::from_str(&env, "tier_1"),
+        &Address::generate(&env),
+        &2u32,
+    );
+    assert_eq!(client.get_global_tickets_sold(), 2);
+
+    // Decrement (refund) also updates the counter
+    client.decrement_inventory(
+        &String::from_str(&env, "pub_evt"),
+        &String::from_str(&env, "tier_1"),
+        &Address::generate(&env),
     );
+    assert_eq!(client.get_global_tickets_sold(), 1);
+    let _ = ticket_payment; // suppress unused warning
 }
 
+/// Tickets sold for a private event are NOT included in the global counter.
 #[test]
-fn test_restocking_fee_exceeds_ticket_price_error_message() {
-    let buf = fmt_to_str(EventRegistryError::RestockingFeeExceedsTicketPrice);
-    assert!(
-        buf_starts_with(
-            &buf,
-            "Restocking fee must not exceed the original ticket price"
-        ),
-        "unexpected message"
+fn test_private_event_tickets_excluded_from_global_counter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, organizer, ticket_payment) = setup_private_test(&env);
+
+    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
+
+    assert_eq!(client.get_global_tickets_sold(), 0);
+    client.increment_inventory(
+        &String::from_str(&env, "priv_evt"),
+        &String::from_str(&env, "tier_1"),
+        &Address::generate(&env),
+        &5u32,
+    );
+    // Global counter must remain 0 for private events
+    assert_eq!(client.get_global_tickets_sold(), 0);
+
+    // Decrement (refund) also must not affect the global counter
+    client.decrement_inventory(
+        &String::from_str(&env, "priv_evt"),
+        &String::from_str(&env, "tier_1"),
+        &Address::generate(&env),
     );
+    assert_eq!(client.get_global_tickets_sold(), 0);
+    let _ = ticket_payment;
 }
 
+/// is_private flag is stored and retrievable via get_event.
 #[test]
-fn test_register_event_tier_limit_overflow() {
+fn test_max_per_user_limit_enforced() {
     let env = Env::default();
+    env.mock_all_auths();
+
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
     let organizer = Address::generate(&env);
-    let payment_addr = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    env.mock_all_auths();
-
     let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let ticket_payment = Address::generate(&env);
 
-    let event_id = String::from_str(&env, "overflow_event");
-    let metadata_cid = String::from_str(
-        &env,
-        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-    );
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    client.set_ticket_payment_contract(&ticket_payment);
 
+    // Create event with per-user limit of 2
     let mut tiers = Map::new(&env);
-    // Two tiers that sum to > i128::MAX
-    tiers.set(
-        String::from_str(&env, "t1"),
-        TicketTier {
-            name: String::from_str(&env, "T1"),
-            price: 100,
-            tier_limit: i128::MAX / 2 + 10,
-            current_sold: 0,
-            is_refundable: true,
-            auction_config: soroban_sdk::vec![&env],
-            loyalty_multiplier: 1,
-            max_per_user: 0,
-        },
-    );
     tiers.set(
-        String::from_str(&env, "t2"),
+        String::from_str(&env, "vip"),
         TicketTier {
-            name: String::from_str(&env, "T2"),
-            price: 100,
-            tier_limit: i128::MAX / 2 + 10,
+            name: String::from_str(&env, "VIP"),
+            price: 1000,
+            tier_limit: 10,
             current_sold: 0,
             is_refundable: true,
             auction_config: soroban_sdk::vec![&env],
             loyalty_multiplier: 1,
-            max_per_user: 0,
+            max_per_user: 2, // Per-user limit of 2
         },
     );
 
-    let result = client.try_register_event(&EventRegistrationArgs {
-        event_id,
-        name: String::from_str(&env, "Overflow Event"),
+    let event_id = String::from_str(&env, "test_event");
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Test Event"),
         organizer_address: organizer,
-        payment_address: payment_addr,
-        metadata_cid,
-        max_supply: 0, // unlimited global supply, but tier limits still overflow
+        payment_address: Address::generate(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 10,
         milestone_plan: None,
         tiers,
         refund_deadline: 0,
@@ -5319,52 +6809,197 @@ fn test_register_event_tier_limit_overflow() {
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
     });
-    assert_eq!(result, Err(Ok(EventRegistryError::SupplyOverflow)));
+
+    let vip_tier = String::from_str(&env, "vip");
+    let user1 = Address::generate(&env);
+
+    // First 2 purchases should succeed
+    client.increment_inventory(&event_id, &vip_tier, &user1, &1);
+    client.increment_inventory(&event_id, &vip_tier, &user1, &1);
+
+    // 3rd purchase should fail
+    let result = client.try_increment_inventory(&event_id, &vip_tier, &user1, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::PerUserLimitExceeded)));
 }
 
 #[test]
-fn test_register_event_invalid_tier_limit_negative() {
+fn test_is_private_flag_stored_on_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, organizer, _) = setup_private_test(&env);
+
+    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
+    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
+
+    let priv_event = client
+        .get_event(&String::from_str(&env, "priv_evt"))
+        .unwrap();
+    let pub_event = client
+        .get_event(&String::from_str(&env, "pub_evt"))
+        .unwrap();
+
+    assert!(priv_event.is_private);
+    assert!(!pub_event.is_private);
+}
+
+/// Deactivating a private event does not affect the active events counter.
+#[test]
+fn test_private_event_status_change_does_not_affect_active_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, organizer, _) = setup_private_test(&env);
+
+    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
+    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
+
+    assert_eq!(client.get_active_events_count(), 1);
+
+    // Deactivate the private event — counter must stay at 1
+    client.update_event_status(&String::from_str(&env, "priv_evt"), &false);
+    assert_eq!(client.get_active_events_count(), 1);
+
+    // Re-activate the private event — counter must stay at 1
+    client.update_event_status(&String::from_str(&env, "priv_evt"), &true);
+    assert_eq!(client.get_active_events_count(), 1);
+}
+
+// ── Tier-Specific Loyalty Multipliers ────────────────────────────────────────
+
+/// A multiplier of 1 (standard) awards 10 points per ticket — baseline behaviour.
+#[test]
+fn test_loyalty_multiplier_1x_awards_base_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let guest = Address::generate(&env);
+    // 3 tickets × 10 pts × 1x = 30 pts
+    client.update_loyalty_score(&admin, &guest, &3, &3000i128, &1u32);
+
+    let profile = client.get_guest_profile(&guest).unwrap();
+    assert_eq!(profile.loyalty_score, 30);
+}
+
+/// A multiplier of 2 (e.g., VIP tier) awards 20 points per ticket.
+#[test]
+fn test_loyalty_multiplier_2x_doubles_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let guest = Address::generate(&env);
+    // 3 tickets × 10 pts × 2x = 60 pts
+    client.update_loyalty_score(&admin, &guest, &3, &6000i128, &2u32);
+
+    let profile = client.get_guest_profile(&guest).unwrap();
+    assert_eq!(profile.loyalty_score, 60);
+}
+
+/// A multiplier of 3 (e.g., Platinum tier) awards 30 points per ticket.
+#[test]
+fn test_loyalty_multiplier_3x_triples_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let guest = Address::generate(&env);
+    // 2 tickets × 10 pts × 3x = 60 pts
+    client.update_loyalty_score(&admin, &guest, &2, &9000i128, &3u32);
+
+    let profile = client.get_guest_profile(&guest).unwrap();
+    assert_eq!(profile.loyalty_score, 60);
+}
+
+/// A multiplier of 0 is treated as 1x (no zeroing of points).
+#[test]
+fn test_loyalty_multiplier_0_treated_as_1x() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let guest = Address::generate(&env);
+    // 4 tickets × 10 pts × 1x (0 treated as 1) = 40 pts
+    client.update_loyalty_score(&admin, &guest, &4, &4000i128, &0u32);
+
+    let profile = client.get_guest_profile(&guest).unwrap();
+    assert_eq!(profile.loyalty_score, 40);
+}
+
+/// Points from multiple purchases with different multipliers accumulate correctly.
+#[test]
+fn test_loyalty_multiplier_accumulates_across_purchases() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_loyalty_env(&env);
+
+    let guest = Address::generate(&env);
+    // Purchase 1: 2 tickets × 10 × 1x = 20 pts
+    client.update_loyalty_score(&admin, &guest, &2, &2000i128, &1u32);
+    // Purchase 2: 1 ticket × 10 × 2x = 20 pts
+    client.update_loyalty_score(&admin, &guest, &1, &5000i128, &2u32);
+    // Total: 40 pts
+
+    let profile = client.get_guest_profile(&guest).unwrap();
+    assert_eq!(profile.loyalty_score, 40);
+}
+
+/// TicketTier with loyalty_multiplier = 2 stores and retrieves the field correctly.
+#[test]
+fn test_ticket_tier_loyalty_multiplier_stored_in_event() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
-
     let admin = Address::generate(&env);
     let organizer = Address::generate(&env);
-    let payment_addr = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    env.mock_all_auths();
-
     let usdc_token = Address::generate(&env);
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    let event_id = String::from_str(&env, "negative_tier_event");
     let metadata_cid = String::from_str(
         &env,
         "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
     );
+    let (client, _admin, _) = setup_loyalty_env(&env);
+
+    let _organizer = Address::generate(&env);
+    let _payment_address = Address::generate(&env);
 
     let mut tiers = Map::new(&env);
     tiers.set(
-        String::from_str(&env, "bad"),
+        String::from_str(&env, "vip"),
         TicketTier {
-            name: String::from_str(&env, "Bad"),
-            price: 100,
-            tier_limit: -1,
+            name: String::from_str(&env, "VIP"),
+            price: 1000_0000000i128,
+            tier_limit: 100,
             current_sold: 0,
-            is_refundable: true,
+            is_refundable: false,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 2,
+            max_per_user: 0,
+        },
+    );
+    tiers.set(
+        String::from_str(&env, "general"),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 1000_0000000i128,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: false,
             auction_config: soroban_sdk::vec![&env],
             loyalty_multiplier: 1,
             max_per_user: 0,
         },
     );
 
-    let result = client.try_register_event(&EventRegistrationArgs {
-        event_id,
-        name: String::from_str(&env, "Negative Tier Event"),
-        organizer_address: organizer,
-        payment_address: payment_addr,
+    client.register_event(&EventRegistrationArgs {
+        event_id: String::from_str(&env, "evt_multiplier"),
+        name: String::from_str(&env, "Multiplier Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(&env),
         metadata_cid,
-        max_supply: 0,
+        max_supply: 250,
         milestone_plan: None,
         tiers,
         refund_deadline: 0,
@@ -5381,51 +7016,44 @@ fn test_register_event_invalid_tier_limit_negative() {
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
     });
-    assert_eq!(result, Err(Ok(EventRegistryError::InvalidQuantity)));
-}
 
-#[test]
-fn test_register_event_milestone_overflow() {
-    let env = Env::default();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    let event = client
+        .get_event(&String::from_str(&env, "evt_multiplier"))
+        .unwrap();
+    let vip_tier = event.tiers.get(String::from_str(&env, "vip")).unwrap();
+    let general_tier = event.tiers.get(String::from_str(&env, "general")).unwrap();
 
-    let admin = Address::generate(&env);
-    let organizer = Address::generate(&env);
-    let payment_addr = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    env.mock_all_auths();
+    assert_eq!(vip_tier.loyalty_multiplier, 2);
+    assert_eq!(general_tier.loyalty_multiplier, 1);
+}
 
-    let usdc_token = Address::generate(&env);
+// ── set_feedback_cid tests ────────────────────────────────────────────────────
+
+fn setup_event_with_end_time(
+    env: &Env,
+    client: &EventRegistryClient,
+    event_id: &str,
+    end_time: u64,
+) -> (Address, Address) {
+    let admin = Address::generate(env);
+    let organizer = Address::generate(env);
+    let platform_wallet = Address::generate(env);
+    let usdc_token = Address::generate(env);
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    let event_id = String::from_str(&env, "milestone_overflow");
     let metadata_cid = String::from_str(
-        &env,
+        env,
         "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
     );
-
-    let milestones = soroban_sdk::vec![
-        &env,
-        crate::types::Milestone {
-            sales_threshold: 10,
-            release_percent: u32::MAX,
-        },
-        crate::types::Milestone {
-            sales_threshold: 20,
-            release_percent: 1,
-        },
-    ];
-
-    let result = client.try_register_event(&EventRegistrationArgs {
-        event_id,
-        name: String::from_str(&env, "Milestone Overflow Event"),
-        organizer_address: organizer,
-        payment_address: payment_addr,
+    client.register_event(&EventRegistrationArgs {
+        event_id: String::from_str(env, event_id),
+        name: String::from_str(env, "Test Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(env),
         metadata_cid,
         max_supply: 100,
-        milestone_plan: Some(milestones),
-        tiers: Map::new(&env),
+        milestone_plan: None,
+        tiers: Map::new(env),
         refund_deadline: 0,
         restocking_fee: 0,
         resale_cap_bps: None,
@@ -5435,39 +7063,32 @@ fn test_register_event_milestone_overflow() {
         tags: None,
         start_time: 0,
         is_private: false,
-        end_time: 0,
+        end_time,
         transfer_lock_duration: 0,
-        accepted_tokens: soroban_sdk::Vec::new(&env),
+        accepted_tokens: soroban_sdk::Vec::new(env),
         use_global_whitelist: true,
     });
-    assert_eq!(result, Err(Ok(EventRegistryError::SupplyOverflow)));
-}
-
-// ── Tags Tests ────────────────────────────────────────────────────────────────
-
-/// Helper: initialise the registry and return (client, admin, organizer).
-fn setup_tags_test(env: &Env) -> (EventRegistryClient<'static>, Address, Address) {
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(env, &contract_id);
-    let admin = Address::generate(env);
-    let platform_wallet = Address::generate(env);
-    let usdc_token = Address::generate(env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
-    let organizer = Address::generate(env);
-    (client, admin, organizer)
+    (admin, organizer)
 }
 
-fn tags_base_args(env: &Env, event_id: &str, organizer: &Address) -> EventRegistrationArgs {
-    EventRegistrationArgs {
+fn setup_event_with_end_time_no_init(
+    env: &Env,
+    client: &EventRegistryClient,
+    organizer: &Address,
+    event_id: &str,
+    end_time: u64,
+) {
+    let metadata_cid = String::from_str(
+        env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    client.register_event(&EventRegistrationArgs {
         event_id: String::from_str(env, event_id),
         name: String::from_str(env, "Test Event"),
         organizer_address: organizer.clone(),
         payment_address: test_payment_address(env),
-        metadata_cid: String::from_str(
-            env,
-            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-        ),
-        max_supply: 0,
+        metadata_cid,
+        max_supply: 100,
         milestone_plan: None,
         tiers: Map::new(env),
         refund_deadline: 0,
@@ -5479,250 +7100,317 @@ fn tags_base_args(env: &Env, event_id: &str, organizer: &Address) -> EventRegist
         tags: None,
         start_time: 0,
         is_private: false,
-        end_time: 0,
+        end_time,
         transfer_lock_duration: 0,
         accepted_tokens: soroban_sdk::Vec::new(env),
         use_global_whitelist: true,
-    }
+    });
 }
 
-/// Registering with no tags (None) succeeds and EventInfo.tags is None.
+/// Organizer can set feedback CID after end_time has passed.
 #[test]
-fn test_register_event_without_tags() {
+fn test_set_feedback_cid_success() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin, organizer) = setup_tags_test(&env);
-
-    let args = tags_base_args(&env, "evt_no_tags", &organizer);
-    client.register_event(&args);
-
-    let info = client
-        .get_event(&String::from_str(&env, "evt_no_tags"))
-        .unwrap();
-    assert!(info.tags.is_none());
-}
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-/// Registering with a valid set of tags stores them correctly.
-#[test]
-fn test_register_event_with_tags_stored_correctly() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let (client, _admin, organizer) = setup_tags_test(&env);
+    // Set ledger time to 1000 so we can have a past end_time
+    env.ledger().set_timestamp(1000);
+    let past_end_time = 500u64;
+    setup_event_with_end_time(&env, &client, "evt_feedback", past_end_time);
 
-    let tags = soroban_sdk::vec![
+    let feedback_cid = String::from_str(
         &env,
-        String::from_str(&env, "Music"),
-        String::from_str(&env, "Tech"),
-        String::from_str(&env, "Outdoor"),
-    ];
-
-    let mut args = tags_base_args(&env, "evt_tags", &organizer);
-    args.tags = Some(tags.clone());
-    client.register_event(&args);
+        "bafkreifeedback222222222222222222222222222222222222222222222",
+    );
+    client.set_feedback_cid(&String::from_str(&env, "evt_feedback"), &feedback_cid);
 
-    let info = client
-        .get_event(&String::from_str(&env, "evt_tags"))
+    let event = client
+        .get_event(&String::from_str(&env, "evt_feedback"))
         .unwrap();
-    let stored = info.tags.unwrap();
-    assert_eq!(stored.len(), 3);
-    assert_eq!(stored.get(0).unwrap(), String::from_str(&env, "Music"));
-    assert_eq!(stored.get(1).unwrap(), String::from_str(&env, "Tech"));
-    assert_eq!(stored.get(2).unwrap(), String::from_str(&env, "Outdoor"));
+    assert_eq!(event.feedback_cid, Some(feedback_cid));
 }
 
-/// Exactly 10 tags is the maximum allowed — must succeed.
+/// set_feedback_cid fails when end_time is 0 (not set).
 #[test]
-fn test_register_event_with_exactly_10_tags_succeeds() {
+fn test_set_feedback_cid_no_end_time_fails() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin, organizer) = setup_tags_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    let tags = soroban_sdk::vec![
-        &env,
-        String::from_str(&env, "Tag1"),
-        String::from_str(&env, "Tag2"),
-        String::from_str(&env, "Tag3"),
-        String::from_str(&env, "Tag4"),
-        String::from_str(&env, "Tag5"),
-        String::from_str(&env, "Tag6"),
-        String::from_str(&env, "Tag7"),
-        String::from_str(&env, "Tag8"),
-        String::from_str(&env, "Tag9"),
-        String::from_str(&env, "Tag10"),
-    ];
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    setup_event_with_end_time_no_init(&env, &client, &organizer, "evt_no_end", 0);
 
-    let mut args = tags_base_args(&env, "evt_10_tags", &organizer);
-    args.tags = Some(tags);
-    let result = client.try_register_event(&args);
-    assert!(result.is_ok(), "10 tags should be accepted");
+    let feedback_cid = String::from_str(
+        &env,
+        "bafkreifeedback222222222222222222222222222222222222222222222",
+    );
+    let result = client.try_set_feedback_cid(&String::from_str(&env, "evt_no_end"), &feedback_cid);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventNotEnded)));
 }
 
-/// 11 tags exceeds the maximum — must return InvalidTags.
+/// set_feedback_cid fails when end_time is in the future.
 #[test]
-fn test_register_event_with_11_tags_fails() {
+fn test_set_feedback_cid_before_end_time_fails() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin, organizer) = setup_tags_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    let tags = soroban_sdk::vec![
-        &env,
-        String::from_str(&env, "T1"),
-        String::from_str(&env, "T2"),
-        String::from_str(&env, "T3"),
-        String::from_str(&env, "T4"),
-        String::from_str(&env, "T5"),
-        String::from_str(&env, "T6"),
-        String::from_str(&env, "T7"),
-        String::from_str(&env, "T8"),
-        String::from_str(&env, "T9"),
-        String::from_str(&env, "T10"),
-        String::from_str(&env, "T11"),
-    ];
+    let future_end_time = env.ledger().timestamp() + 10_000;
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    setup_event_with_end_time_no_init(&env, &client, &organizer, "evt_future", future_end_time);
 
-    let mut args = tags_base_args(&env, "evt_11_tags", &organizer);
-    args.tags = Some(tags);
-    let result = client.try_register_event(&args);
-    assert_eq!(result, Err(Ok(EventRegistryError::InvalidTags)));
+    let feedback_cid = String::from_str(
+        &env,
+        "bafkreifeedback222222222222222222222222222222222222222222222",
+    );
+    let result = client.try_set_feedback_cid(&String::from_str(&env, "evt_future"), &feedback_cid);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventNotEnded)));
 }
 
-/// A tag of exactly 32 characters is the maximum allowed length — must succeed.
+/// set_feedback_cid fails for a non-existent event.
 #[test]
-fn test_register_event_tag_exactly_32_chars_succeeds() {
+fn test_set_feedback_cid_event_not_found() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin, organizer) = setup_tags_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    // 32 characters exactly
-    let tag_32 = String::from_str(&env, "12345678901234567890123456789012");
-    let tags = soroban_sdk::vec![&env, tag_32];
+    let admin = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    let mut args = tags_base_args(&env, "evt_tag_32", &organizer);
-    args.tags = Some(tags);
-    let result = client.try_register_event(&args);
-    assert!(result.is_ok(), "32-char tag should be accepted");
+    let result = client.try_set_feedback_cid(
+        &String::from_str(&env, "nonexistent"),
+        &String::from_str(
+            &env,
+            "bafkreifeedback222222222222222222222222222222222222222222222",
+        ),
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::EventNotFound)));
 }
 
-/// A tag of 33 characters exceeds the maximum length — must return InvalidTags.
+/// set_feedback_cid fails with an invalid CID format.
 #[test]
-fn test_register_event_tag_33_chars_fails() {
+fn test_set_feedback_cid_invalid_cid() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin, organizer) = setup_tags_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    // 33 characters
-    let tag_33 = String::from_str(&env, "123456789012345678901234567890123");
-    let tags = soroban_sdk::vec![&env, tag_33];
+    env.ledger().set_timestamp(1000);
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    setup_event_with_end_time_no_init(&env, &client, &organizer, "evt_bad_cid", 500);
 
-    let mut args = tags_base_args(&env, "evt_tag_33", &organizer);
-    args.tags = Some(tags);
-    let result = client.try_register_event(&args);
-    assert_eq!(result, Err(Ok(EventRegistryError::InvalidTags)));
+    let result = client.try_set_feedback_cid(
+        &String::from_str(&env, "evt_bad_cid"),
+        &String::from_str(&env, "short"),
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidMetadataCid)));
 }
 
-/// A single empty-string tag is valid (length 0 ≤ 32).
+/// set_feedback_cid fails on a cancelled event.
 #[test]
-fn test_register_event_empty_tag_is_valid() {
+fn test_set_feedback_cid_cancelled_event_fails() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _admin, organizer) = setup_tags_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    let tags = soroban_sdk::vec![&env, String::from_str(&env, "")];
-    let mut args = tags_base_args(&env, "evt_empty_tag", &organizer);
-    args.tags = Some(tags);
-    let result = client.try_register_event(&args);
-    assert!(result.is_ok(), "empty tag string should be accepted");
-}
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-/// Tags are independent of other fields — an event with tags and a banner_cid
-/// stores both correctly.
-#[test]
-fn test_register_event_tags_and_banner_cid_coexist() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let (client, _admin, organizer) = setup_tags_test(&env);
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        String::from_str(&env, "vip"),
+        crate::types::TicketTier {
+            name: String::from_str(&env, "VIP"),
+            price: 1000_0000000i128,
+            tier_limit: 50,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 2,
+            max_per_user: 0,
+        },
+    );
+    tiers.set(
+        String::from_str(&env, "general"),
+        crate::types::TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 1000,
+            tier_limit: 200,
+            current_sold: 0,
+            is_refundable: false,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0,
+        },
+    );
 
-    let banner = String::from_str(&env, "bafybeibanner123");
-    let tags = soroban_sdk::vec![
+    let metadata_cid = String::from_str(
         &env,
-        String::from_str(&env, "Music"),
-        String::from_str(&env, "Festival"),
-    ];
-
-    let mut args = tags_base_args(&env, "evt_both", &organizer);
-    args.banner_cid = Some(banner.clone());
-    args.tags = Some(tags);
-    client.register_event(&args);
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    client.register_event(&EventRegistrationArgs {
+        event_id: String::from_str(&env, "evt_multiplier"),
+        name: String::from_str(&env, "Multiplier Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(&env),
+        metadata_cid,
+        max_supply: 250,
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 0,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
 
-    let info = client
-        .get_event(&String::from_str(&env, "evt_both"))
+    let event = client
+        .get_event(&String::from_str(&env, "evt_multiplier"))
         .unwrap();
-    assert_eq!(info.banner_cid.unwrap(), banner);
-    assert_eq!(info.tags.unwrap().len(), 2);
-}
+    let vip_tier = event.tiers.get(String::from_str(&env, "vip")).unwrap();
+    let general_tier = event.tiers.get(String::from_str(&env, "general")).unwrap();
 
-// ── VERSION constant ──────────────────────────────────────────────────────────
+    assert_eq!(vip_tier.loyalty_multiplier, 2);
+    assert_eq!(general_tier.loyalty_multiplier, 1);
+    env.ledger().set_timestamp(1000);
+    setup_event_with_end_time_no_init(&env, &client, &organizer, "evt_cancelled", 500);
 
-/// The VERSION constant must equal 1.
-#[test]
-fn test_version_constant_value() {
-    assert_eq!(crate::VERSION, 1u32);
+    let event_id = String::from_str(&env, "evt_cancelled");
+    client.cancel_event(&event_id);
+
+    let result = client.try_set_feedback_cid(
+        &event_id,
+        &String::from_str(
+            &env,
+            "bafkreifeedback222222222222222222222222222222222222222222222",
+        ),
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
 }
 
-/// The `version()` contract function must return 1.
 #[test]
-fn test_version_fn_returns_1() {
+fn test_transfer_lock_duration_stored() {
     let env = Env::default();
+    env.mock_all_auths();
+
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
-    assert_eq!(client.version(), 1u32);
-}
 
-// ── Private Event Support Tests ───────────────────────────────────────────────
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    let ticket_payment = Address::generate(&env);
 
-fn setup_private_test(env: &Env) -> (EventRegistryClient<'static>, Address, Address, Address) {
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(env, &contract_id);
-    let admin = Address::generate(env);
-    let organizer = Address::generate(env);
-    let platform_wallet = Address::generate(env);
-    let usdc_token = Address::generate(env);
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
-    let ticket_payment = Address::generate(env);
     client.set_ticket_payment_contract(&ticket_payment);
-    (client, admin, organizer, ticket_payment)
-}
 
-fn register_event_with_privacy(
-    env: &Env,
-    client: &EventRegistryClient,
-    organizer: &Address,
-    event_id: &str,
-    is_private: bool,
-) {
-    let metadata_cid = String::from_str(
-        env,
-        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    // Create event with 24-hour transfer lock
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        String::from_str(&env, "general"),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 1000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0, // No per-user limit
+        },
     );
+
+    let event_id = String::from_str(&env, "test_event");
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Test Event"),
+        organizer_address: organizer,
+        payment_address: Address::generate(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 0,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 86400, // 24 hours in seconds
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
+
+    // Verify the transfer lock duration is stored correctly
+    let event_info = client.get_event(&event_id).unwrap();
+    assert_eq!(event_info.transfer_lock_duration, 86400);
+}
+
+fn setup_split_event(env: &Env, client: &EventRegistryClient, organizer: &Address) -> String {
     let mut tiers = Map::new(env);
     tiers.set(
-        String::from_str(env, "tier_1"),
-        crate::types::TicketTier {
+        String::from_str(env, "general"),
+        TicketTier {
             name: String::from_str(env, "General"),
             price: 1000,
             tier_limit: 100,
             current_sold: 0,
-            is_refundable: false,
+            is_refundable: true,
             auction_config: soroban_sdk::vec![env],
             loyalty_multiplier: 1,
             max_per_user: 0,
         },
     );
+
+    let event_id = String::from_str(env, "split_event");
     client.register_event(&EventRegistrationArgs {
-        event_id: String::from_str(env, event_id),
-        name: String::from_str(env, "Test Event"),
+        event_id: event_id.clone(),
+        name: String::from_str(env, "Split Event"),
         organizer_address: organizer.clone(),
         payment_address: test_payment_address(env),
-        metadata_cid,
+        metadata_cid: String::from_str(
+            env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
         max_supply: 100,
         milestone_plan: None,
         tiers,
@@ -5734,183 +7422,266 @@ fn register_event_with_privacy(
         banner_cid: None,
         tags: None,
         start_time: 0,
-        is_private,
+        is_private: false,
         end_time: 0,
         transfer_lock_duration: 0,
         accepted_tokens: soroban_sdk::Vec::new(env),
         use_global_whitelist: true,
     });
+    event_id
 }
 
-/// A public event increments the global managed events counter.
 #[test]
-fn test_public_event_increments_managed_count() {
+fn test_revenue_split_defaults_to_payment_address() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, organizer, _) = setup_private_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    assert_eq!(client.get_managed_events_count(), 0);
-    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
-    assert_eq!(client.get_managed_events_count(), 1);
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Address::generate(&env),
+        &500,
+        &Address::generate(&env),
+    );
+    let event_id = setup_split_event(&env, &client, &organizer);
+
+    let split = client.get_revenue_split(&event_id);
+    assert_eq!(split.len(), 1);
+    let payee = split.get(0).unwrap();
+    assert_eq!(payee.address, test_payment_address(&env));
+    assert_eq!(payee.share_bps, 10000);
 }
 
-/// A private event does NOT increment the global managed events counter.
 #[test]
-fn test_private_event_excluded_from_managed_count() {
+fn test_set_revenue_split_success_and_clear() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, organizer, _) = setup_private_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    assert_eq!(client.get_managed_events_count(), 0);
-    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
-    assert_eq!(client.get_managed_events_count(), 0);
-}
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Address::generate(&env),
+        &500,
+        &Address::generate(&env),
+    );
+    let event_id = setup_split_event(&env, &client, &organizer);
 
-/// A public event increments the global active events counter.
-#[test]
-fn test_public_event_increments_active_count() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let (client, _, organizer, _) = setup_private_test(&env);
+    let venue = Address::generate(&env);
+    let performer = Address::generate(&env);
+    let payees = soroban_sdk::vec![
+        &env,
+        crate::types::Payee {
+            address: venue.clone(),
+            share_bps: 6000,
+        },
+        crate::types::Payee {
+            address: performer.clone(),
+            share_bps: 4000,
+        },
+    ];
+    client.set_revenue_split(&event_id, &payees);
 
-    assert_eq!(client.get_active_events_count(), 0);
-    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
-    assert_eq!(client.get_active_events_count(), 1);
+    assert_eq!(client.get_event(&event_id).unwrap().payees, payees);
+    assert_eq!(client.get_revenue_split(&event_id), payees);
+
+    // Clearing the split falls back to the payment address.
+    client.set_revenue_split(&event_id, &soroban_sdk::Vec::new(&env));
+    let split = client.get_revenue_split(&event_id);
+    assert_eq!(split.len(), 1);
+    assert_eq!(split.get(0).unwrap().address, test_payment_address(&env));
 }
 
-/// A private event does NOT increment the global active events counter.
 #[test]
-fn test_private_event_excluded_from_active_count() {
+fn test_set_revenue_split_rejects_invalid_shares() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, organizer, _) = setup_private_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    assert_eq!(client.get_active_events_count(), 0);
-    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
-    assert_eq!(client.get_active_events_count(), 0);
-}
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Address::generate(&env),
+        &500,
+        &Address::generate(&env),
+    );
+    let event_id = setup_split_event(&env, &client, &organizer);
 
-/// Mixed registration: only public events count toward global counters.
-#[test]
-fn test_mixed_public_private_events_counters() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let (client, _, organizer, _) = setup_private_test(&env);
+    let venue = Address::generate(&env);
+    let performer = Address::generate(&env);
 
-    register_event_with_privacy(&env, &client, &organizer, "pub_1", false);
-    register_event_with_privacy(&env, &client, &organizer, "priv_1", true);
-    register_event_with_privacy(&env, &client, &organizer, "pub_2", false);
-    register_event_with_privacy(&env, &client, &organizer, "priv_2", true);
+    // Shares do not sum to 10000
+    let short = soroban_sdk::vec![
+        &env,
+        crate::types::Payee {
+            address: venue.clone(),
+            share_bps: 5000,
+        },
+        crate::types::Payee {
+            address: performer.clone(),
+            share_bps: 4000,
+        },
+    ];
+    assert_eq!(
+        client.try_set_revenue_split(&event_id, &short),
+        Err(Ok(EventRegistryError::InvalidRevenueSplit))
+    );
 
-    // 2 public events counted, 2 private excluded
-    assert_eq!(client.get_managed_events_count(), 2);
-    assert_eq!(client.get_active_events_count(), 2);
+    // Duplicate payee
+    let duplicate = soroban_sdk::vec![
+        &env,
+        crate::types::Payee {
+            address: venue.clone(),
+            share_bps: 5000,
+        },
+        crate::types::Payee {
+            address: venue.clone(),
+            share_bps: 5000,
+        },
+    ];
+    assert_eq!(
+        client.try_set_revenue_split(&event_id, &duplicate),
+        Err(Ok(EventRegistryError::InvalidRevenueSplit))
+    );
+
+    // Zero share
+    let zero = soroban_sdk::vec![
+        &env,
+        crate::types::Payee {
+            address: venue,
+            share_bps: 10000,
+        },
+        crate::types::Payee {
+            address: performer,
+            share_bps: 0,
+        },
+    ];
+    assert_eq!(
+        client.try_set_revenue_split(&event_id, &zero),
+        Err(Ok(EventRegistryError::InvalidRevenueSplit))
+    );
 }
 
-/// Tickets sold for a public event are included in the global counter.
 #[test]
-fn test_public_event_tickets_counted_globally() {
+fn test_revenue_split_stays_locked_after_all_tickets_refunded() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, organizer, ticket_payment) = setup_private_test(&env);
-
-    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    assert_eq!(client.get_global_tickets_sold(), 0);
-    client.increment_inventory(
-        &String::from_str(&env, "pub_evt"),
-        &String::from_str(&env, "tier_1"),
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let ticket_payment = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Address::generate(&env),
+        &500,
         &Address::generate(&env),
-        &2u32,
     );
-    assert_eq!(client.get_global_tickets_sold(), 2);
+    client.set_ticket_payment_contract(&ticket_payment);
+    let event_id = setup_split_event(&env, &client, &organizer);
 
-    // Decrement (refund) also updates the counter
-    client.decrement_inventory(
-        &String::from_str(&env, "pub_evt"),
-        &String::from_str(&env, "tier_1"),
-        &Address::generate(&env),
+    let tier_id = String::from_str(&env, "general");
+    let buyer = Address::generate(&env);
+    client.increment_inventory(&event_id, &tier_id, &buyer, &2);
+    client.decrement_inventory(&event_id, &tier_id, &buyer);
+    client.decrement_inventory(&event_id, &tier_id, &buyer);
+    assert_eq!(client.get_event(&event_id).unwrap().current_supply, 0);
+
+    // Revenue collected before the refunds was split under the original payees.
+    let payees = soroban_sdk::vec![
+        &env,
+        crate::types::Payee {
+            address: Address::generate(&env),
+            share_bps: 10000,
+        },
+    ];
+    assert_eq!(
+        client.try_set_revenue_split(&event_id, &payees),
+        Err(Ok(EventRegistryError::RevenueSplitLocked))
     );
-    assert_eq!(client.get_global_tickets_sold(), 1);
-    let _ = ticket_payment; // suppress unused warning
 }
 
-/// Tickets sold for a private event are NOT included in the global counter.
 #[test]
-fn test_private_event_tickets_excluded_from_global_counter() {
+fn test_revenue_split_locked_after_first_sale() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, organizer, ticket_payment) = setup_private_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let ticket_payment = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Address::generate(&env),
+        &500,
+        &Address::generate(&env),
+    );
+    client.set_ticket_payment_contract(&ticket_payment);
+    let event_id = setup_split_event(&env, &client, &organizer);
 
-    assert_eq!(client.get_global_tickets_sold(), 0);
     client.increment_inventory(
-        &String::from_str(&env, "priv_evt"),
-        &String::from_str(&env, "tier_1"),
+        &event_id,
+        &String::from_str(&env, "general"),
         &Address::generate(&env),
-        &5u32,
+        &1,
     );
-    // Global counter must remain 0 for private events
-    assert_eq!(client.get_global_tickets_sold(), 0);
 
-    // Decrement (refund) also must not affect the global counter
-    client.decrement_inventory(
-        &String::from_str(&env, "priv_evt"),
-        &String::from_str(&env, "tier_1"),
-        &Address::generate(&env),
+    let payees = soroban_sdk::vec![
+        &env,
+        crate::types::Payee {
+            address: Address::generate(&env),
+            share_bps: 10000,
+        },
+    ];
+    assert_eq!(
+        client.try_set_revenue_split(&event_id, &payees),
+        Err(Ok(EventRegistryError::RevenueSplitLocked))
     );
-    assert_eq!(client.get_global_tickets_sold(), 0);
-    let _ = ticket_payment;
 }
 
-/// is_private flag is stored and retrievable via get_event.
 #[test]
-fn test_max_per_user_limit_enforced() {
-    let env = Env::default();
-    env.mock_all_auths();
+fn test_migrate_rewrites_v1_events() {
+    use crate::migration::{EventInfoV1, SCHEMA_VERSION};
 
+    let env = Env::default();
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
     let organizer = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    let ticket_payment = Address::generate(&env);
 
+    env.mock_all_auths();
+    let usdc_token = Address::generate(&env);
     client.initialize(&admin, &platform_wallet, &500, &usdc_token);
-    client.set_ticket_payment_contract(&ticket_payment);
-
-    // Create event with per-user limit of 2
-    let mut tiers = Map::new(&env);
-    tiers.set(
-        String::from_str(&env, "vip"),
-        TicketTier {
-            name: String::from_str(&env, "VIP"),
-            price: 1000,
-            tier_limit: 10,
-            current_sold: 0,
-            is_refundable: true,
-            auction_config: soroban_sdk::vec![&env],
-            loyalty_multiplier: 1,
-            max_per_user: 2, // Per-user limit of 2
-        },
+    assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
+    assert_eq!(
+        client.try_migrate(&soroban_sdk::vec![&env]),
+        Err(Ok(EventRegistryError::AlreadyMigrated))
     );
 
-    let event_id = String::from_str(&env, "test_event");
+    let event_id = String::from_str(&env, "legacy_event");
     client.register_event(&EventRegistrationArgs {
         event_id: event_id.clone(),
-        name: String::from_str(&env, "Test Event"),
+        name: String::from_str(&env, "Legacy Event"),
         organizer_address: organizer,
-        payment_address: Address::generate(&env),
+        payment_address: test_payment_address(&env),
         metadata_cid: String::from_str(
             &env,
             "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
         ),
-        max_supply: 10,
+        max_supply: 100,
         milestone_plan: None,
-        tiers,
+        tiers: Map::new(&env),
         refund_deadline: 0,
         restocking_fee: 0,
         resale_cap_bps: None,
@@ -5926,196 +7697,349 @@ fn test_max_per_user_limit_enforced() {
         use_global_whitelist: true,
     });
 
-    let vip_tier = String::from_str(&env, "vip");
-    let user1 = Address::generate(&env);
-
-    // First 2 purchases should succeed
-    client.increment_inventory(&event_id, &vip_tier, &user1, &1);
-    client.increment_inventory(&event_id, &vip_tier, &user1, &1);
-
-    // 3rd purchase should fail
-    let result = client.try_increment_inventory(&event_id, &vip_tier, &user1, &1);
-    assert_eq!(result, Err(Ok(EventRegistryError::PerUserLimitExceeded)));
-}
-
-#[test]
-fn test_is_private_flag_stored_on_event() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let (client, _, organizer, _) = setup_private_test(&env);
+    // Rewind storage to the version 1 layout.
+    let current = client.get_event(&event_id).unwrap();
+    env.as_contract(&contract_id, || {
+        let legacy = EventInfoV1 {
+            event_id: current.event_id.clone(),
+            name: current.name.clone(),
+            organizer_address: current.organizer_address.clone(),
+            payment_address: current.payment_address.clone(),
+            platform_fee_percent: current.platform_fee_percent,
+            is_active: current.is_active,
+            status: current.status.clone(),
+            created_at: current.created_at,
+            metadata_cid: current.metadata_cid.clone(),
+            max_supply: current.max_supply,
+            current_supply: current.current_supply,
+            milestone_plan: current.milestone_plan.clone(),
+            tiers: current.tiers.clone(),
+            refund_deadline: current.refund_deadline,
+            restocking_fee: current.restocking_fee,
+            resale_cap_bps: current.resale_cap_bps,
+            is_postponed: current.is_postponed,
+            grace_period_end: current.grace_period_end,
+            min_sales_target: current.min_sales_target,
+            target_deadline: current.target_deadline,
+            goal_met: current.goal_met,
+            custom_fee_bps: current.custom_fee_bps,
+            banner_cid: current.banner_cid.clone(),
+            tags: current.tags.clone(),
+            start_time: current.start_time,
+            is_private: current.is_private,
+            end_time: current.end_time,
+            transfer_lock_duration: current.transfer_lock_duration,
+            accepted_tokens: current.accepted_tokens.clone(),
+            use_global_whitelist: current.use_global_whitelist,
+            feedback_cid: current.feedback_cid.clone(),
+        };
+        env.storage()
+            .persistent()
+            .set(&crate::types::DataKey::Event(event_id.clone()), &legacy);
+        storage::set_schema_version(&env, 1);
+    });
 
-    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
-    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
+    let unknown = String::from_str(&env, "missing_event");
+    let version = client.migrate(&soroban_sdk::vec![&env, event_id.clone(), unknown]);
+    assert_eq!(version, SCHEMA_VERSION);
+    assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
 
-    let priv_event = client
-        .get_event(&String::from_str(&env, "priv_evt"))
-        .unwrap();
-    let pub_event = client
-        .get_event(&String::from_str(&env, "pub_evt"))
-        .unwrap();
+    let migrated = client.get_event(&event_id).unwrap();
+    assert_eq!(migrated.payees.len(), 0);
+    assert_eq!(migrated.name, current.name);
+    assert_eq!(migrated.max_supply, 100);
 
-    assert!(priv_event.is_private);
-    assert!(!pub_event.is_private);
+    assert_eq!(
+        client.try_migrate(&soroban_sdk::vec![&env, event_id]),
+        Err(Ok(EventRegistryError::AlreadyMigrated))
+    );
 }
 
-/// Deactivating a private event does not affect the active events counter.
 #[test]
-fn test_private_event_status_change_does_not_affect_active_count() {
+fn test_bump_event_keeps_event_alive_past_default_ttl() {
+    use crate::ttl::{CONFIG_TTL_EXTEND_TO, RECORD_TTL_EXTEND_TO, RECORD_TTL_THRESHOLD};
+    use crate::types::{DataKey, TtlFamily, TtlPolicy};
+    use soroban_sdk::testutils::storage::Persistent as _;
+
     let env = Env::default();
-    env.mock_all_auths();
-    let (client, _, organizer, _) = setup_private_test(&env);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    register_event_with_privacy(&env, &client, &organizer, "pub_evt", false);
-    register_event_with_privacy(&env, &client, &organizer, "priv_evt", true);
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
 
-    assert_eq!(client.get_active_events_count(), 1);
+    env.mock_all_auths();
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
 
-    // Deactivate the private event — counter must stay at 1
-    client.update_event_status(&String::from_str(&env, "priv_evt"), &false);
-    assert_eq!(client.get_active_events_count(), 1);
+    let event_id = String::from_str(&env, "ttl_event");
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Long Lived Event"),
+        organizer_address: organizer.clone(),
+        payment_address: test_payment_address(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
+        milestone_plan: None,
+        tiers: Map::new(&env),
+        refund_deadline: 0,
+        restocking_fee: 0,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
 
-    // Re-activate the private event — counter must stay at 1
-    client.update_event_status(&String::from_str(&env, "priv_evt"), &true);
-    assert_eq!(client.get_active_events_count(), 1);
-}
+    assert_eq!(
+        client.try_set_ttl_policy(&TtlFamily::Event, &RECORD_TTL_THRESHOLD, &u32::MAX),
+        Err(Ok(EventRegistryError::InvalidTtlPolicy))
+    );
+    client.set_ttl_policy(&TtlFamily::OrganizerIndex, &10_000, &200_000);
+    assert_eq!(
+        client.get_ttl_policy(&TtlFamily::OrganizerIndex),
+        TtlPolicy {
+            threshold: 10_000,
+            extend_to: 200_000,
+        }
+    );
 
-// ── Tier-Specific Loyalty Multipliers ────────────────────────────────────────
+    assert_eq!(
+        client.try_bump_event(&String::from_str(&env, "missing_event")),
+        Err(Ok(EventRegistryError::EventNotFound))
+    );
+    client.bump_event(&event_id);
 
-/// A multiplier of 1 (standard) awards 10 points per ticket — baseline behaviour.
-#[test]
-fn test_loyalty_multiplier_1x_awards_base_points() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let (client, admin, _) = setup_loyalty_env(&env);
+    let ttl_of =
+        |key: DataKey| env.as_contract(&contract_id, || env.storage().persistent().get_ttl(&key));
+    assert_eq!(
+        ttl_of(DataKey::Event(event_id.clone())),
+        RECORD_TTL_EXTEND_TO
+    );
+    assert_eq!(
+        ttl_of(DataKey::OrganizerEvent(organizer.clone(), event_id.clone())),
+        200_000
+    );
+    assert_eq!(
+        ttl_of(DataKey::OrganizerEventShard(organizer.clone(), 0)),
+        200_000
+    );
+    assert_eq!(ttl_of(DataKey::Admin), CONFIG_TTL_EXTEND_TO);
 
-    let guest = Address::generate(&env);
-    // 3 tickets × 10 pts × 1x = 30 pts
-    client.update_loyalty_score(&admin, &guest, &3, &3000i128, &1u32);
+    // Well past the network's default persistent TTL.
+    env.ledger().with_mut(|li| li.sequence_number += 150_000);
 
-    let profile = client.get_guest_profile(&guest).unwrap();
-    assert_eq!(profile.loyalty_score, 30);
+    let event_info = client.get_event(&event_id).unwrap();
+    assert_eq!(event_info.organizer_address, organizer);
+    assert_eq!(client.get_organizer_events(&organizer).len(), 1);
 }
 
-/// A multiplier of 2 (e.g., VIP tier) awards 20 points per ticket.
 #[test]
-fn test_loyalty_multiplier_2x_doubles_points() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let (client, admin, _) = setup_loyalty_env(&env);
+fn test_allowlist_roots_resolve_per_tier_and_privacy() {
+    use crate::types::{AllowlistGate, TierAllowlist};
+    use soroban_sdk::BytesN;
 
-    let guest = Address::generate(&env);
-    // 3 tickets × 10 pts × 2x = 60 pts
-    client.update_loyalty_score(&admin, &guest, &3, &6000i128, &2u32);
+    let env = Env::default();
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    let profile = client.get_guest_profile(&guest).unwrap();
-    assert_eq!(profile.loyalty_score, 60);
-}
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
 
-/// A multiplier of 3 (e.g., Platinum tier) awards 30 points per ticket.
-#[test]
-fn test_loyalty_multiplier_3x_triples_points() {
-    let env = Env::default();
     env.mock_all_auths();
-    let (client, admin, _) = setup_loyalty_env(&env);
+    client.initialize(&admin, &platform_wallet, &500, &Address::generate(&env));
 
-    let guest = Address::generate(&env);
-    // 2 tickets × 10 pts × 3x = 60 pts
-    client.update_loyalty_score(&admin, &guest, &2, &9000i128, &3u32);
+    let general = String::from_str(&env, "general");
+    let presale = String::from_str(&env, "presale");
+    let mut tiers = Map::new(&env);
+    for tier_id in [general.clone(), presale.clone()] {
+        tiers.set(
+            tier_id,
+            TicketTier {
+                name: String::from_str(&env, "Tier"),
+                price: 1_000,
+                tier_limit: 100,
+                current_sold: 0,
+                is_refundable: true,
+                auction_config: soroban_sdk::vec![&env],
+                loyalty_multiplier: 1,
+                max_per_user: 0,
+            },
+        );
+    }
 
-    let profile = client.get_guest_profile(&guest).unwrap();
-    assert_eq!(profile.loyalty_score, 60);
-}
+    let register = |event_id: &String, is_private: bool| {
+        client.register_event(&EventRegistrationArgs {
+            event_id: event_id.clone(),
+            name: String::from_str(&env, "Invite Only"),
+            organizer_address: organizer.clone(),
+            payment_address: test_payment_address(&env),
+            metadata_cid: String::from_str(
+                &env,
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            ),
+            max_supply: 200,
+            milestone_plan: None,
+            tiers: tiers.clone(),
+            refund_deadline: 0,
+            restocking_fee: 0,
+            resale_cap_bps: None,
+            min_sales_target: None,
+            target_deadline: None,
+            banner_cid: None,
+            tags: None,
+            start_time: 0,
+            is_private,
+            end_time: 0,
+            transfer_lock_duration: 0,
+            accepted_tokens: soroban_sdk::Vec::new(&env),
+            use_global_whitelist: true,
+        });
+    };
+    let private_event = String::from_str(&env, "private_event");
+    let public_event = String::from_str(&env, "public_event");
+    register(&private_event, true);
+    register(&public_event, false);
 
-/// A multiplier of 0 is treated as 1x (no zeroing of points).
-#[test]
-fn test_loyalty_multiplier_0_treated_as_1x() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let (client, admin, _) = setup_loyalty_env(&env);
+    let event_root = BytesN::from_array(&env, &[1u8; 32]);
+    let tier_root = BytesN::from_array(&env, &[2u8; 32]);
 
-    let guest = Address::generate(&env);
-    // 4 tickets × 10 pts × 1x (0 treated as 1) = 40 pts
-    client.update_loyalty_score(&admin, &guest, &4, &4000i128, &0u32);
+    // Private event: no root means open sales, an event-wide root gates every tier.
+    assert_eq!(
+        client.get_purchase_allowlist(&private_event, &general),
+        None
+    );
+    client.set_event_allowlist(&private_event, &Some(event_root.clone()));
+    assert_eq!(
+        client.get_purchase_allowlist(&private_event, &general),
+        Some(AllowlistGate {
+            root: event_root.clone(),
+            tier_id: None,
+        })
+    );
 
-    let profile = client.get_guest_profile(&guest).unwrap();
-    assert_eq!(profile.loyalty_score, 40);
-}
+    // A tier root takes precedence over the event-wide root.
+    client.set_tier_allowlist(
+        &private_event,
+        &presale,
+        &Some(TierAllowlist {
+            root: tier_root.clone(),
+            hidden: false,
+        }),
+    );
+    assert_eq!(
+        client.get_purchase_allowlist(&private_event, &presale),
+        Some(AllowlistGate {
+            root: tier_root.clone(),
+            tier_id: Some(presale.clone()),
+        })
+    );
 
-/// Points from multiple purchases with different multipliers accumulate correctly.
-#[test]
-fn test_loyalty_multiplier_accumulates_across_purchases() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let (client, admin, _) = setup_loyalty_env(&env);
+    // Rotation replaces the root; clearing reopens sales.
+    let rotated_root = BytesN::from_array(&env, &[3u8; 32]);
+    client.set_event_allowlist(&private_event, &Some(rotated_root.clone()));
+    assert_eq!(
+        client.get_event_allowlist(&private_event),
+        Some(rotated_root)
+    );
+    client.set_event_allowlist(&private_event, &None);
+    assert_eq!(
+        client.get_purchase_allowlist(&private_event, &general),
+        None
+    );
 
-    let guest = Address::generate(&env);
-    // Purchase 1: 2 tickets × 10 × 1x = 20 pts
-    client.update_loyalty_score(&admin, &guest, &2, &2000i128, &1u32);
-    // Purchase 2: 1 ticket × 10 × 2x = 20 pts
-    client.update_loyalty_score(&admin, &guest, &1, &5000i128, &2u32);
-    // Total: 40 pts
+    // Public event: only hidden tiers are gated.
+    client.set_event_allowlist(&public_event, &Some(event_root));
+    client.set_tier_allowlist(
+        &public_event,
+        &general,
+        &Some(TierAllowlist {
+            root: tier_root.clone(),
+            hidden: false,
+        }),
+    );
+    assert_eq!(client.get_purchase_allowlist(&public_event, &general), None);
+    client.set_tier_allowlist(
+        &public_event,
+        &presale,
+        &Some(TierAllowlist {
+            root: tier_root.clone(),
+            hidden: true,
+        }),
+    );
+    assert_eq!(
+        client.get_purchase_allowlist(&public_event, &presale),
+        Some(AllowlistGate {
+            root: tier_root.clone(),
+            tier_id: Some(presale),
+        })
+    );
 
-    let profile = client.get_guest_profile(&guest).unwrap();
-    assert_eq!(profile.loyalty_score, 40);
+    assert_eq!(
+        client.try_set_tier_allowlist(
+            &public_event,
+            &String::from_str(&env, "missing_tier"),
+            &Some(TierAllowlist {
+                root: tier_root,
+                hidden: true,
+            }),
+        ),
+        Err(Ok(EventRegistryError::TierNotFound))
+    );
 }
 
-/// TicketTier with loyalty_multiplier = 2 stores and retrieves the field correctly.
 #[test]
-fn test_ticket_tier_loyalty_multiplier_stored_in_event() {
+fn test_set_and_clear_tier_attestation() {
+    use crate::types::TierAttestation;
+
     let env = Env::default();
-    env.mock_all_auths();
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
+
     let admin = Address::generate(&env);
     let organizer = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
-
-    let metadata_cid = String::from_str(
-        &env,
-        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-    );
-    let (client, _admin, _) = setup_loyalty_env(&env);
 
-    let _organizer = Address::generate(&env);
-    let _payment_address = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin, &platform_wallet, &500, &Address::generate(&env));
 
+    let event_id = String::from_str(&env, "adults_only");
+    let tier_id = String::from_str(&env, "general");
     let mut tiers = Map::new(&env);
     tiers.set(
-        String::from_str(&env, "vip"),
-        TicketTier {
-            name: String::from_str(&env, "VIP"),
-            price: 1000_0000000i128,
-            tier_limit: 100,
-            current_sold: 0,
-            is_refundable: false,
-            auction_config: soroban_sdk::vec![&env],
-            loyalty_multiplier: 2,
-            max_per_user: 0,
-        },
-    );
-    tiers.set(
-        String::from_str(&env, "general"),
+        tier_id.clone(),
         TicketTier {
             name: String::from_str(&env, "General"),
-            price: 1000_0000000i128,
+            price: 1_000,
             tier_limit: 100,
             current_sold: 0,
-            is_refundable: false,
+            is_refundable: true,
             auction_config: soroban_sdk::vec![&env],
             loyalty_multiplier: 1,
             max_per_user: 0,
         },
     );
-
     client.register_event(&EventRegistrationArgs {
-        event_id: String::from_str(&env, "evt_multiplier"),
-        name: String::from_str(&env, "Multiplier Event"),
-        organizer_address: organizer.clone(),
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Adults Only"),
+        organizer_address: organizer,
         payment_address: test_payment_address(&env),
-        metadata_cid,
-        max_supply: 250,
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
         milestone_plan: None,
         tiers,
         refund_deadline: 0,
@@ -6133,80 +8057,98 @@ fn test_ticket_tier_loyalty_multiplier_stored_in_event() {
         use_global_whitelist: true,
     });
 
-    let event = client
-        .get_event(&String::from_str(&env, "evt_multiplier"))
-        .unwrap();
-    let vip_tier = event.tiers.get(String::from_str(&env, "vip")).unwrap();
-    let general_tier = event.tiers.get(String::from_str(&env, "general")).unwrap();
+    let requirement = TierAttestation {
+        attester: Address::generate(&env),
+        claim: String::from_str(&env, "age_18"),
+    };
+    assert_eq!(client.get_tier_attestation(&event_id, &tier_id), None);
+    client.set_tier_attestation(&event_id, &tier_id, &Some(requirement.clone()));
+    assert_eq!(
+        client.get_tier_attestation(&event_id, &tier_id),
+        Some(requirement.clone())
+    );
 
-    assert_eq!(vip_tier.loyalty_multiplier, 2);
-    assert_eq!(general_tier.loyalty_multiplier, 1);
+    assert_eq!(
+        client.try_set_tier_attestation(
+            &event_id,
+            &String::from_str(&env, "vip"),
+            &Some(requirement.clone()),
+        ),
+        Err(Ok(EventRegistryError::TierNotFound))
+    );
+    assert_eq!(
+        client.try_set_tier_attestation(
+            &event_id,
+            &tier_id,
+            &Some(TierAttestation {
+                attester: contract_id.clone(),
+                claim: requirement.claim,
+            }),
+        ),
+        Err(Ok(EventRegistryError::InvalidAddress))
+    );
+
+    client.set_tier_attestation(&event_id, &tier_id, &None);
+    assert_eq!(client.get_tier_attestation(&event_id, &tier_id), None);
 }
 
-// ── set_feedback_cid tests ────────────────────────────────────────────────────
+#[test]
+fn test_move_inventory_between_tiers() {
+    let env = Env::default();
+    env.mock_all_auths();
 
-fn setup_event_with_end_time(
-    env: &Env,
-    client: &EventRegistryClient,
-    event_id: &str,
-    end_time: u64,
-) -> (Address, Address) {
-    let admin = Address::generate(env);
-    let organizer = Address::generate(env);
-    let platform_wallet = Address::generate(env);
-    let usdc_token = Address::generate(env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
 
-    let metadata_cid = String::from_str(
-        env,
-        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-    );
-    client.register_event(&EventRegistrationArgs {
-        event_id: String::from_str(env, event_id),
-        name: String::from_str(env, "Test Event"),
-        organizer_address: organizer.clone(),
-        payment_address: test_payment_address(env),
-        metadata_cid,
-        max_supply: 100,
-        milestone_plan: None,
-        tiers: Map::new(env),
-        refund_deadline: 0,
-        restocking_fee: 0,
-        resale_cap_bps: None,
-        min_sales_target: None,
-        target_deadline: None,
-        banner_cid: None,
-        tags: None,
-        start_time: 0,
-        is_private: false,
-        end_time,
-        transfer_lock_duration: 0,
-        accepted_tokens: soroban_sdk::Vec::new(env),
-        use_global_whitelist: true,
-    });
-    (admin, organizer)
-}
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let ticket_payment = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &Address::generate(&env));
+    client.set_ticket_payment_contract(&ticket_payment);
 
-fn setup_event_with_end_time_no_init(
-    env: &Env,
-    client: &EventRegistryClient,
-    organizer: &Address,
-    event_id: &str,
-    end_time: u64,
-) {
-    let metadata_cid = String::from_str(
-        env,
-        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    let event_id = String::from_str(&env, "upgrade_event");
+    let general = String::from_str(&env, "general");
+    let vip = String::from_str(&env, "vip");
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        general.clone(),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 1_000,
+            tier_limit: 10,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 1,
+            max_per_user: 0,
+        },
+    );
+    tiers.set(
+        vip.clone(),
+        TicketTier {
+            name: String::from_str(&env, "VIP"),
+            price: 5_000,
+            tier_limit: 1,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 2,
+            max_per_user: 0,
+        },
     );
     client.register_event(&EventRegistrationArgs {
-        event_id: String::from_str(env, event_id),
-        name: String::from_str(env, "Test Event"),
-        organizer_address: organizer.clone(),
-        payment_address: test_payment_address(env),
-        metadata_cid,
-        max_supply: 100,
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Upgrade Event"),
+        organizer_address: organizer,
+        payment_address: test_payment_address(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 11,
         milestone_plan: None,
-        tiers: Map::new(env),
+        tiers,
         refund_deadline: 0,
         restocking_fee: 0,
         resale_cap_bps: None,
@@ -6216,184 +8158,184 @@ fn setup_event_with_end_time_no_init(
         tags: None,
         start_time: 0,
         is_private: false,
-        end_time,
+        end_time: 0,
         transfer_lock_duration: 0,
-        accepted_tokens: soroban_sdk::Vec::new(env),
+        accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
     });
-}
-
-/// Organizer can set feedback CID after end_time has passed.
-#[test]
-fn test_set_feedback_cid_success() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
 
-    // Set ledger time to 1000 so we can have a past end_time
-    env.ledger().set_timestamp(1000);
-    let past_end_time = 500u64;
-    setup_event_with_end_time(&env, &client, "evt_feedback", past_end_time);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.increment_inventory(&event_id, &general, &alice, &1);
+    client.increment_inventory(&event_id, &general, &bob, &1);
 
-    let feedback_cid = String::from_str(
-        &env,
-        "bafkreifeedback222222222222222222222222222222222222222222222",
+    assert_eq!(
+        client.try_move_inventory(&event_id, &general, &general, &alice),
+        Err(Ok(EventRegistryError::InvalidTierChange))
     );
-    client.set_feedback_cid(&String::from_str(&env, "evt_feedback"), &feedback_cid);
-
-    let event = client
-        .get_event(&String::from_str(&env, "evt_feedback"))
-        .unwrap();
-    assert_eq!(event.feedback_cid, Some(feedback_cid));
-}
 
-/// set_feedback_cid fails when end_time is 0 (not set).
-#[test]
-fn test_set_feedback_cid_no_end_time_fails() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
-
-    let admin = Address::generate(&env);
-    let organizer = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
-    setup_event_with_end_time_no_init(&env, &client, &organizer, "evt_no_end", 0);
+    client.move_inventory(&event_id, &general, &vip, &alice);
+    let event_info = client.get_event(&event_id).unwrap();
+    assert_eq!(event_info.current_supply, 2);
+    assert_eq!(
+        event_info.tiers.get(general.clone()).unwrap().current_sold,
+        1
+    );
+    assert_eq!(event_info.tiers.get(vip.clone()).unwrap().current_sold, 1);
 
-    let feedback_cid = String::from_str(
-        &env,
-        "bafkreifeedback222222222222222222222222222222222222222222222",
+    // The VIP tier is now sold out.
+    assert_eq!(
+        client.try_move_inventory(&event_id, &general, &vip, &bob),
+        Err(Ok(EventRegistryError::TierSupplyExceeded))
     );
-    let result = client.try_set_feedback_cid(&String::from_str(&env, "evt_no_end"), &feedback_cid);
-    assert_eq!(result, Err(Ok(EventRegistryError::EventNotEnded)));
+
+    client.move_inventory(&event_id, &vip, &general, &alice);
+    let event_info = client.get_event(&event_id).unwrap();
+    assert_eq!(event_info.tiers.get(general).unwrap().current_sold, 2);
+    assert_eq!(event_info.tiers.get(vip).unwrap().current_sold, 0);
 }
 
-/// set_feedback_cid fails when end_time is in the future.
 #[test]
-fn test_set_feedback_cid_before_end_time_fails() {
+fn test_exchange_inventory_between_events() {
     let env = Env::default();
     env.mock_all_auths();
+
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
 
-    let future_end_time = env.ledger().timestamp() + 10_000;
     let admin = Address::generate(&env);
     let organizer = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
-    setup_event_with_end_time_no_init(&env, &client, &organizer, "evt_future", future_end_time);
-
-    let feedback_cid = String::from_str(
-        &env,
-        "bafkreifeedback222222222222222222222222222222222222222222222",
-    );
-    let result = client.try_set_feedback_cid(&String::from_str(&env, "evt_future"), &feedback_cid);
-    assert_eq!(result, Err(Ok(EventRegistryError::EventNotEnded)));
-}
+    client.initialize(&admin, &platform_wallet, &500, &Address::generate(&env));
+    client.set_ticket_payment_contract(&Address::generate(&env));
 
-/// set_feedback_cid fails for a non-existent event.
-#[test]
-fn test_set_feedback_cid_event_not_found() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
+    let tier_id = String::from_str(&env, "general");
+    let register = |event_id: &str, organizer: &Address| {
+        let mut tiers = Map::new(&env);
+        tiers.set(
+            tier_id.clone(),
+            TicketTier {
+                name: String::from_str(&env, "General"),
+                price: 1_000,
+                tier_limit: 1,
+                current_sold: 0,
+                is_refundable: true,
+                auction_config: soroban_sdk::vec![&env],
+                loyalty_multiplier: 1,
+                max_per_user: 0,
+            },
+        );
+        client.register_event(&EventRegistrationArgs {
+            event_id: String::from_str(&env, event_id),
+            name: String::from_str(&env, event_id),
+            organizer_address: organizer.clone(),
+            payment_address: test_payment_address(&env),
+            metadata_cid: String::from_str(
+                &env,
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            ),
+            max_supply: 1,
+            milestone_plan: None,
+            tiers,
+            refund_deadline: 0,
+            restocking_fee: 0,
+            resale_cap_bps: None,
+            min_sales_target: None,
+            target_deadline: None,
+            banner_cid: None,
+            tags: None,
+            start_time: 0,
+            is_private: false,
+            end_time: 0,
+            transfer_lock_duration: 0,
+            accepted_tokens: soroban_sdk::Vec::new(&env),
+            use_global_whitelist: true,
+        });
+        String::from_str(&env, event_id)
+    };
+    let friday = register("friday", &organizer);
+    let saturday = register("saturday", &organizer);
+    let elsewhere = register("elsewhere", &Address::generate(&env));
 
-    let admin = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let guest = Address::generate(&env);
+    client.increment_inventory(&friday, &tier_id, &guest, &1);
 
-    let result = client.try_set_feedback_cid(
-        &String::from_str(&env, "nonexistent"),
-        &String::from_str(
-            &env,
-            "bafkreifeedback222222222222222222222222222222222222222222222",
-        ),
+    assert_eq!(
+        client.try_exchange_inventory(&friday, &tier_id, &friday, &tier_id, &guest),
+        Err(Ok(EventRegistryError::InvalidTierChange))
+    );
+    assert_eq!(
+        client.try_exchange_inventory(&friday, &tier_id, &elsewhere, &tier_id, &guest),
+        Err(Ok(EventRegistryError::ExchangeNotAllowed))
     );
-    assert_eq!(result, Err(Ok(EventRegistryError::EventNotFound)));
-}
-
-/// set_feedback_cid fails with an invalid CID format.
-#[test]
-fn test_set_feedback_cid_invalid_cid() {
-    let env = Env::default();
-    env.mock_all_auths();
-    let contract_id = env.register(EventRegistry, ());
-    let client = EventRegistryClient::new(&env, &contract_id);
 
-    env.ledger().set_timestamp(1000);
-    let admin = Address::generate(&env);
-    let organizer = Address::generate(&env);
-    let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
-    setup_event_with_end_time_no_init(&env, &client, &organizer, "evt_bad_cid", 500);
+    client.exchange_inventory(&friday, &tier_id, &saturday, &tier_id, &guest);
+    assert_eq!(client.get_event(&friday).unwrap().current_supply, 0);
+    assert_eq!(client.get_event(&saturday).unwrap().current_supply, 1);
 
-    let result = client.try_set_feedback_cid(
-        &String::from_str(&env, "evt_bad_cid"),
-        &String::from_str(&env, "short"),
+    // Saturday is now sold out, so nothing moves back onto it.
+    client.increment_inventory(&friday, &tier_id, &Address::generate(&env), &1);
+    assert_eq!(
+        client.try_exchange_inventory(&friday, &tier_id, &saturday, &tier_id, &guest),
+        Err(Ok(EventRegistryError::MaxSupplyExceeded))
     );
-    assert_eq!(result, Err(Ok(EventRegistryError::InvalidMetadataCid)));
 }
 
-/// set_feedback_cid fails on a cancelled event.
 #[test]
-fn test_set_feedback_cid_cancelled_event_fails() {
+fn test_set_pay_what_you_want() {
     let env = Env::default();
     env.mock_all_auths();
+
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
     let organizer = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    let ticket_payment = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &Address::generate(&env));
+    client.set_ticket_payment_contract(&ticket_payment);
 
+    let event_id = String::from_str(&env, "pwyw_event");
+    let general = String::from_str(&env, "general");
+    let vip = String::from_str(&env, "vip");
     let mut tiers = Map::new(&env);
     tiers.set(
-        String::from_str(&env, "vip"),
-        crate::types::TicketTier {
-            name: String::from_str(&env, "VIP"),
-            price: 1000_0000000i128,
-            tier_limit: 50,
+        general.clone(),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 1_000,
+            tier_limit: 10,
             current_sold: 0,
             is_refundable: true,
             auction_config: soroban_sdk::vec![&env],
-            loyalty_multiplier: 2,
+            loyalty_multiplier: 1,
             max_per_user: 0,
         },
     );
     tiers.set(
-        String::from_str(&env, "general"),
-        crate::types::TicketTier {
-            name: String::from_str(&env, "General"),
-            price: 1000,
-            tier_limit: 200,
+        vip.clone(),
+        TicketTier {
+            name: String::from_str(&env, "VIP"),
+            price: 5_000,
+            tier_limit: 1,
             current_sold: 0,
-            is_refundable: false,
+            is_refundable: true,
             auction_config: soroban_sdk::vec![&env],
-            loyalty_multiplier: 1,
+            loyalty_multiplier: 2,
             max_per_user: 0,
         },
     );
-
-    let metadata_cid = String::from_str(
-        &env,
-        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
-    );
     client.register_event(&EventRegistrationArgs {
-        event_id: String::from_str(&env, "evt_multiplier"),
-        name: String::from_str(&env, "Multiplier Event"),
-        organizer_address: organizer.clone(),
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Pay What You Want"),
+        organizer_address: organizer,
         payment_address: test_payment_address(&env),
-        metadata_cid,
-        max_supply: 250,
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 11,
         milestone_plan: None,
         tiers,
         refund_deadline: 0,
@@ -6411,32 +8353,35 @@ fn test_set_feedback_cid_cancelled_event_fails() {
         use_global_whitelist: true,
     });
 
-    let event = client
-        .get_event(&String::from_str(&env, "evt_multiplier"))
-        .unwrap();
-    let vip_tier = event.tiers.get(String::from_str(&env, "vip")).unwrap();
-    let general_tier = event.tiers.get(String::from_str(&env, "general")).unwrap();
+    assert!(!client.is_pay_what_you_want(&event_id, &general));
+    client.set_pay_what_you_want(&event_id, &general, &true);
+    assert!(client.is_pay_what_you_want(&event_id, &general));
+    assert!(!client.is_pay_what_you_want(&event_id, &vip));
 
-    assert_eq!(vip_tier.loyalty_multiplier, 2);
-    assert_eq!(general_tier.loyalty_multiplier, 1);
-    env.ledger().set_timestamp(1000);
-    setup_event_with_end_time_no_init(&env, &client, &organizer, "evt_cancelled", 500);
+    client.set_pay_what_you_want(&event_id, &general, &false);
+    assert!(!client.is_pay_what_you_want(&event_id, &general));
 
-    let event_id = String::from_str(&env, "evt_cancelled");
-    client.cancel_event(&event_id);
+    assert_eq!(
+        client.try_set_pay_what_you_want(&event_id, &String::from_str(&env, "missing"), &true),
+        Err(Ok(EventRegistryError::TierNotFound))
+    );
 
-    let result = client.try_set_feedback_cid(
-        &event_id,
-        &String::from_str(
-            &env,
-            "bafkreifeedback222222222222222222222222222222222222222222222",
+    // Deposits are only for free tiers.
+    assert_eq!(
+        client.try_set_tier_deposit(
+            &event_id,
+            &general,
+            &Some(crate::types::TierDeposit {
+                amount: 100,
+                forfeit_recipient: None,
+            })
         ),
+        Err(Ok(EventRegistryError::InvalidDeposit))
     );
-    assert_eq!(result, Err(Ok(EventRegistryError::EventCancelled)));
 }
 
 #[test]
-fn test_transfer_lock_duration_stored() {
+fn test_add_ons_stock_and_tier_restriction() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -6446,39 +8391,163 @@ fn test_transfer_lock_duration_stored() {
     let admin = Address::generate(&env);
     let organizer = Address::generate(&env);
     let platform_wallet = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
     let ticket_payment = Address::generate(&env);
-
-    client.initialize(&admin, &platform_wallet, &500, &usdc_token);
+    client.initialize(&admin, &platform_wallet, &500, &Address::generate(&env));
     client.set_ticket_payment_contract(&ticket_payment);
 
-    // Create event with 24-hour transfer lock
+    let event_id = String::from_str(&env, "addon_event");
+    let general = String::from_str(&env, "general");
+    let vip = String::from_str(&env, "vip");
     let mut tiers = Map::new(&env);
     tiers.set(
-        String::from_str(&env, "general"),
+        general.clone(),
         TicketTier {
             name: String::from_str(&env, "General"),
-            price: 1000,
-            tier_limit: 100,
+            price: 1_000,
+            tier_limit: 10,
             current_sold: 0,
             is_refundable: true,
             auction_config: soroban_sdk::vec![&env],
             loyalty_multiplier: 1,
-            max_per_user: 0, // No per-user limit
+            max_per_user: 0,
+        },
+    );
+    tiers.set(
+        vip.clone(),
+        TicketTier {
+            name: String::from_str(&env, "VIP"),
+            price: 5_000,
+            tier_limit: 1,
+            current_sold: 0,
+            is_refundable: true,
+            auction_config: soroban_sdk::vec![&env],
+            loyalty_multiplier: 2,
+            max_per_user: 0,
         },
     );
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Add-on Event"),
+        organizer_address: organizer,
+        payment_address: test_payment_address(&env),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 11,
+        milestone_plan: None,
+        tiers,
+        refund_deadline: 0,
+        restocking_fee: 0,
+        resale_cap_bps: None,
+        min_sales_target: None,
+        target_deadline: None,
+        banner_cid: None,
+        tags: None,
+        start_time: 0,
+        is_private: false,
+        end_time: 0,
+        transfer_lock_duration: 0,
+        accepted_tokens: soroban_sdk::Vec::new(&env),
+        use_global_whitelist: true,
+    });
+
+    let parking = String::from_str(&env, "parking");
+    let name = String::from_str(&env, "Parking");
+    assert_eq!(
+        client.try_set_add_on(&event_id, &parking, &name, &0, &2, &None),
+        Err(Ok(EventRegistryError::InvalidAddOn))
+    );
+    assert_eq!(
+        client.try_set_add_on(
+            &event_id,
+            &parking,
+            &name,
+            &500,
+            &2,
+            &Some(String::from_str(&env, "missing"))
+        ),
+        Err(Ok(EventRegistryError::TierNotFound))
+    );
+    client.set_add_on(&event_id, &parking, &name, &500, &2, &Some(vip.clone()));
+    assert_eq!(
+        client.get_event_add_ons(&event_id),
+        soroban_sdk::vec![&env, parking.clone()]
+    );
 
-    let event_id = String::from_str(&env, "test_event");
+    assert_eq!(client.reserve_add_on(&event_id, &parking, &2).sold, 2);
+    assert_eq!(
+        client.try_reserve_add_on(&event_id, &parking, &1),
+        Err(Ok(EventRegistryError::AddOnSoldOut))
+    );
+    assert_eq!(
+        client.try_reserve_add_on(&event_id, &String::from_str(&env, "merch"), &1),
+        Err(Ok(EventRegistryError::AddOnNotFound))
+    );
+
+    // Stock cannot be cut below items sold; updates keep the sales count.
+    assert_eq!(
+        client.try_set_add_on(&event_id, &parking, &name, &500, &1, &None),
+        Err(Ok(EventRegistryError::InvalidAddOn))
+    );
+    client.set_add_on(&event_id, &parking, &name, &600, &3, &Some(general.clone()));
+    let add_on = client.get_add_on(&event_id, &parking).unwrap();
+    assert_eq!(
+        (add_on.price, add_on.sold, add_on.tier_id),
+        (600, 2, Some(general))
+    );
+    assert_eq!(client.get_event_add_ons(&event_id).len(), 1);
+
+    client.release_add_on(&event_id, &parking, &2);
+    assert_eq!(client.get_add_on(&event_id, &parking).unwrap().sold, 0);
+    assert_eq!(
+        client.try_release_add_on(&event_id, &parking, &1),
+        Err(Ok(EventRegistryError::SupplyUnderflow))
+    );
+}
+
+#[test]
+fn test_set_installment_plan_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    client.initialize(&admin, &platform_wallet, &500, &Address::generate(&env));
+
+    let event_id = String::from_str(&env, "installments_event");
+    let general = String::from_str(&env, "general");
+    let free = String::from_str(&env, "free");
+    let mut tiers = Map::new(&env);
+    for (tier_id, price) in [(&general, 3_000i128), (&free, 0)] {
+        tiers.set(
+            tier_id.clone(),
+            TicketTier {
+                name: tier_id.clone(),
+                price,
+                tier_limit: 10,
+                current_sold: 0,
+                is_refundable: true,
+                auction_config: soroban_sdk::vec![&env],
+                loyalty_multiplier: 1,
+                max_per_user: 0,
+            },
+        );
+    }
     client.register_event(&EventRegistrationArgs {
         event_id: event_id.clone(),
-        name: String::from_str(&env, "Test Event"),
+        name: String::from_str(&env, "Installments"),
         organizer_address: organizer,
-        payment_address: Address::generate(&env),
+        payment_address: test_payment_address(&env),
         metadata_cid: String::from_str(
             &env,
             "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
         ),
-        max_supply: 100,
+        max_supply: 20,
         milestone_plan: None,
         tiers,
         refund_deadline: 0,
@@ -6491,12 +8560,44 @@ fn test_transfer_lock_duration_stored() {
         start_time: 0,
         is_private: false,
         end_time: 0,
-        transfer_lock_duration: 86400, // 24 hours in seconds
+        transfer_lock_duration: 0,
         accepted_tokens: soroban_sdk::Vec::new(&env),
         use_global_whitelist: true,
     });
 
-    // Verify the transfer lock duration is stored correctly
-    let event_info = client.get_event(&event_id).unwrap();
-    assert_eq!(event_info.transfer_lock_duration, 86400);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let plan = |due_dates: soroban_sdk::Vec<u64>, penalty_bps: u32| {
+        Some(crate::types::InstallmentPlan {
+            due_dates,
+            penalty_bps,
+        })
+    };
+    for (tier_id, invalid) in [
+        (&general, plan(soroban_sdk::vec![&env], 500)),
+        (&general, plan(soroban_sdk::vec![&env, 1_000], 500)),
+        (&general, plan(soroban_sdk::vec![&env, 3_000, 2_000], 500)),
+        (&general, plan(soroban_sdk::vec![&env, 2_000], 10_001)),
+        (&free, plan(soroban_sdk::vec![&env, 2_000], 500)),
+    ] {
+        assert_eq!(
+            client.try_set_installment_plan(&event_id, tier_id, &invalid),
+            Err(Ok(EventRegistryError::InvalidInstallmentPlan))
+        );
+    }
+    assert_eq!(
+        client.try_set_installment_plan(
+            &event_id,
+            &String::from_str(&env, "missing"),
+            &plan(soroban_sdk::vec![&env, 2_000], 500)
+        ),
+        Err(Ok(EventRegistryError::TierNotFound))
+    );
+
+    let valid = plan(soroban_sdk::vec![&env, 2_000, 3_000], 1_000);
+    client.set_installment_plan(&event_id, &general, &valid);
+    assert_eq!(client.get_installment_plan(&event_id, &general), valid);
+    assert_eq!(client.get_installment_plan(&event_id, &free), None);
+
+    client.set_installment_plan(&event_id, &general, &None);
+    assert_eq!(client.get_installment_plan(&event_id, &general), None);
 }
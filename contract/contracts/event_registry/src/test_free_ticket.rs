@@ -11,10 +11,11 @@
 //! * Capacity limits still apply even when the price is zero.
 //! * `decrement_inventory` (refund path) works for a zero-price tier.
 //! * Purchasing quantity > 1 in a single call works for a free tier.
+//! * A free tier can ask for a refundable RSVP deposit.
 
 use super::*;
 use crate::error::EventRegistryError;
-use crate::types::{EventRegistrationArgs, TicketTier};
+use crate::types::{EventRegistrationArgs, TicketTier, TierDeposit};
 use soroban_sdk::{testutils::Address as _, Address, Env, Map, String};
 
 // ---------------------------------------------------------------------------
@@ -279,3 +280,37 @@ fn test_free_ticket_zero_quantity_rejected() {
     let result = client.try_increment_inventory(&event_id, &tier_id, &Address::generate(&env), &0);
     assert_eq!(result, Err(Ok(EventRegistryError::InvalidQuantity)));
 }
+
+/// Organizers can put a refundable RSVP deposit on a free tier and clear it.
+#[test]
+fn test_free_tier_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    let organizer = Address::generate(&env);
+    let event_id = register_free_event(&env, &client, &organizer, "free_evt_deposit", 10, 10);
+    let tier_id = String::from_str(&env, FREE_TIER_ID);
+    let charity = Address::generate(&env);
+
+    assert_eq!(client.get_tier_deposit(&event_id, &tier_id), None);
+    let result = client.try_set_tier_deposit(
+        &event_id,
+        &tier_id,
+        &Some(TierDeposit {
+            amount: 0,
+            forfeit_recipient: None,
+        }),
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidDeposit)));
+
+    let deposit = TierDeposit {
+        amount: 5_0000000,
+        forfeit_recipient: Some(charity),
+    };
+    client.set_tier_deposit(&event_id, &tier_id, &Some(deposit.clone()));
+    assert_eq!(client.get_tier_deposit(&event_id, &tier_id), Some(deposit));
+
+    client.set_tier_deposit(&event_id, &tier_id, &None);
+    assert_eq!(client.get_tier_deposit(&event_id, &tier_id), None);
+}
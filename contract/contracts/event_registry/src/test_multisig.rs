@@ -1,5 +1,11 @@
-use crate::{EventRegistry, EventRegistryClient};
-use soroban_sdk::{testutils::Address as _, testutils::Ledger, Address, Env};
+use crate::error::EventRegistryError;
+use crate::events::{AgoraEvent, ProposalExecutedEvent};
+use crate::ticket_payment::{OrganizerCapPolicy, PaymentParameterChange};
+use crate::types::ProposalStatus;
+use crate::{EventRegistry, EventRegistryClient, UPGRADE_TIMELOCK_SECS};
+use soroban_sdk::{
+    testutils::Address as _, testutils::Events, testutils::Ledger, Address, BytesN, Env, TryFromVal,
+};
 
 fn create_test_env() -> (Env, EventRegistryClient<'static>, Address, Address, Address) {
     let env = Env::default();
@@ -351,46 +357,419 @@ fn test_threshold_adjustment_on_admin_removal() {
 }
 
 #[test]
-fn test_set_multisig_config_valid() {
+fn test_lone_admin_cannot_change_admins_or_threshold() {
     let (env, client, admin1, admin2, admin3) = create_test_env();
     let platform_wallet = Address::generate(&env);
     let usdc_token = Address::generate(&env);
     client.initialize(&admin1, &platform_wallet, &500, &usdc_token);
+    env.ledger().set_timestamp(1_000);
 
-    // Set a new multi-sig config with 3 admins and threshold of 2
-    let new_admins = soroban_sdk::vec![&env, admin1.clone(), admin2.clone(), admin3.clone()];
-    client.set_multisig_config(&admin1, &new_admins, &2);
+    let proposal_id = client.propose_add_admin(&admin1, &admin2, &0);
+    client.execute_proposal(&admin1, &proposal_id);
+    let proposal_id = client.propose_set_threshold(&admin1, &2, &0);
+    client.execute_proposal(&admin1, &proposal_id);
 
+    // Admins and threshold only change through proposals, which one admin cannot pass.
+    for proposal_id in [
+        client.propose_remove_admin(&admin1, &admin2, &0),
+        client.propose_add_admin(&admin1, &admin3, &0),
+        client.propose_set_threshold(&admin1, &1, &0),
+    ] {
+        assert_eq!(
+            client.try_execute_proposal(&admin1, &proposal_id),
+            Err(Ok(EventRegistryError::InsufficientApprovals))
+        );
+    }
     let config = client.get_multisig_config();
-    assert_eq!(config.admins.len(), 3);
+    assert_eq!(
+        config.admins,
+        soroban_sdk::vec![&env, admin1.clone(), admin2.clone()]
+    );
     assert_eq!(config.threshold, 2);
-    assert!(client.is_admin(&admin1));
-    assert!(client.is_admin(&admin2));
-    assert!(client.is_admin(&admin3));
+}
+
+#[soroban_sdk::contract]
+pub struct DummyUpgradeable;
+
+#[soroban_sdk::contractimpl]
+impl DummyUpgradeable {
+    pub fn ping(_env: Env) {}
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #36)")]
-fn test_set_multisig_config_invalid_threshold_zero() {
+fn test_upgrade_requires_executed_proposal_and_timelock() {
+    let (env, client, admin1, _, _) = create_test_env();
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin1, &platform_wallet, &500, &usdc_token);
+
+    let dummy_id = env.register(DummyUpgradeable, ());
+    let new_wasm_hash = match dummy_id.executable() {
+        Some(soroban_sdk::Executable::Wasm(hash)) => hash,
+        _ => panic!("Dummy contract is not a Wasm contract"),
+    };
+
+    // Nothing scheduled yet
+    assert_eq!(
+        client.try_upgrade(&new_wasm_hash),
+        Err(Ok(EventRegistryError::UpgradeNotScheduled))
+    );
+
+    env.ledger().set_timestamp(1_000);
+    let proposal_id = client.propose_upgrade(&admin1, &new_wasm_hash, &0);
+    client.execute_proposal(&admin1, &proposal_id);
+
+    let pending = client.get_pending_upgrade().unwrap();
+    assert_eq!(pending.wasm_hash, new_wasm_hash);
+    assert_eq!(pending.eta, 1_000 + UPGRADE_TIMELOCK_SECS);
+
+    assert_eq!(
+        client.try_upgrade(&new_wasm_hash),
+        Err(Ok(EventRegistryError::UpgradeTimelockActive))
+    );
+
+    env.ledger().set_timestamp(pending.eta);
+    let other_hash = BytesN::from_array(&env, &[7u8; 32]);
+    assert_eq!(
+        client.try_upgrade(&other_hash),
+        Err(Ok(EventRegistryError::UpgradeNotScheduled))
+    );
+
+    client.upgrade(&new_wasm_hash);
+    assert_eq!(client.get_pending_upgrade(), None);
+    assert_eq!(
+        client.address.executable(),
+        Some(soroban_sdk::Executable::Wasm(new_wasm_hash))
+    );
+}
+
+#[test]
+fn test_cancel_upgrade_clears_schedule() {
+    let (env, client, admin1, _, _) = create_test_env();
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin1, &platform_wallet, &500, &usdc_token);
+
+    let wasm_hash = BytesN::from_array(&env, &[9u8; 32]);
+    let proposal_id = client.propose_upgrade(&admin1, &wasm_hash, &0);
+    client.execute_proposal(&admin1, &proposal_id);
+    assert!(client.get_pending_upgrade().is_some());
+
+    client.cancel_upgrade();
+    assert_eq!(client.get_pending_upgrade(), None);
+
+    env.ledger().set_timestamp(UPGRADE_TIMELOCK_SECS + 1);
+    assert_eq!(
+        client.try_upgrade(&wasm_hash),
+        Err(Ok(EventRegistryError::UpgradeNotScheduled))
+    );
+    assert_eq!(
+        client.try_cancel_upgrade(),
+        Err(Ok(EventRegistryError::UpgradeNotScheduled))
+    );
+}
+
+/// Initializes with `admin1`, adds `admin2` and `admin3` and raises the threshold to 2.
+fn setup_three_admins(
+    env: &Env,
+    client: &EventRegistryClient<'static>,
+    admin1: &Address,
+    admin2: &Address,
+    admin3: &Address,
+) {
+    let platform_wallet = Address::generate(env);
+    let usdc_token = Address::generate(env);
+    client.initialize(admin1, &platform_wallet, &500, &usdc_token);
+
+    let proposal_id = client.propose_add_admin(admin1, admin2, &0);
+    client.execute_proposal(admin1, &proposal_id);
+    let proposal_id = client.propose_add_admin(admin1, admin3, &0);
+    client.execute_proposal(admin1, &proposal_id);
+    let proposal_id = client.propose_set_threshold(admin1, &2, &0);
+    client.execute_proposal(admin1, &proposal_id);
+}
+
+#[test]
+fn test_execution_delay_blocks_execution_until_elapsed() {
     let (env, client, admin1, admin2, _) = create_test_env();
     let platform_wallet = Address::generate(&env);
     let usdc_token = Address::generate(&env);
     client.initialize(&admin1, &platform_wallet, &500, &usdc_token);
+    env.ledger().set_timestamp(1_000);
+
+    assert_eq!(
+        client.try_propose_set_execution_delay(&admin1, &(31 * 86_400), &0),
+        Err(Ok(EventRegistryError::InvalidExecutionDelay))
+    );
 
-    // threshold = 0 should fail
-    let new_admins = soroban_sdk::vec![&env, admin1.clone(), admin2.clone()];
-    client.set_multisig_config(&admin1, &new_admins, &0);
+    let proposal_id = client.propose_set_execution_delay(&admin1, &3_600, &0);
+    client.execute_proposal(&admin1, &proposal_id);
+    assert_eq!(client.get_execution_delay(), 3_600);
+
+    // The proposer's approval meets the threshold, so the delay starts immediately.
+    let proposal_id = client.propose_add_admin(&admin1, &admin2, &10);
+    let record = client.get_proposal_record(&proposal_id).unwrap();
+    assert_eq!(record.status, ProposalStatus::Queued);
+    assert_eq!(record.executable_at, 1_000 + 3_600);
+    // A short expiry is extended so the proposal can still be executed.
+    assert!(record.proposal.expires_at > record.executable_at);
+
+    assert_eq!(
+        client.try_execute_proposal(&admin1, &proposal_id),
+        Err(Ok(EventRegistryError::ProposalTimelocked))
+    );
+
+    env.ledger().set_timestamp(1_000 + 3_600);
+    client.execute_proposal(&admin1, &proposal_id);
+    assert!(client.is_admin(&admin2));
+    assert_eq!(
+        client.get_proposal_record(&proposal_id).unwrap().status,
+        ProposalStatus::Executed
+    );
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #36)")]
-fn test_set_multisig_config_invalid_threshold_too_high() {
+fn test_cancel_proposal_by_proposer_and_admin() {
+    let (env, client, admin1, admin2, admin3) = create_test_env();
+    setup_three_admins(&env, &client, &admin1, &admin2, &admin3);
+
+    let new_admin = Address::generate(&env);
+    let proposal_id = client.propose_add_admin(&admin1, &new_admin, &0);
+    client.cancel_proposal(&admin1, &proposal_id);
+    assert_eq!(
+        client.get_proposal_record(&proposal_id).unwrap().status,
+        ProposalStatus::Cancelled
+    );
+    assert!(!client.get_active_proposals().contains(proposal_id));
+    assert_eq!(
+        client.try_approve_proposal(&admin2, &proposal_id),
+        Err(Ok(EventRegistryError::ProposalClosed))
+    );
+    assert_eq!(
+        client.try_execute_proposal(&admin1, &proposal_id),
+        Err(Ok(EventRegistryError::ProposalClosed))
+    );
+
+    // Any admin may cancel a fully approved proposal, e.g. during its delay.
+    let proposal_id = client.propose_add_admin(&admin1, &new_admin, &0);
+    client.approve_proposal(&admin2, &proposal_id);
+    client.cancel_proposal(&admin3, &proposal_id);
+    assert_eq!(
+        client.try_execute_proposal(&admin1, &proposal_id),
+        Err(Ok(EventRegistryError::ProposalClosed))
+    );
+    assert!(!client.is_admin(&new_admin));
+
+    // Outsiders cannot cancel.
+    let proposal_id = client.propose_add_admin(&admin1, &new_admin, &0);
+    assert_eq!(
+        client.try_cancel_proposal(&new_admin, &proposal_id),
+        Err(Ok(EventRegistryError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_reject_votes_defeat_proposal() {
+    let (env, client, admin1, admin2, admin3) = create_test_env();
+    setup_three_admins(&env, &client, &admin1, &admin2, &admin3);
+
+    let new_admin = Address::generate(&env);
+    let proposal_id = client.propose_add_admin(&admin1, &new_admin, &0);
+
+    // One rejection out of three admins still leaves the threshold of 2 reachable.
+    client.reject_proposal(&admin2, &proposal_id);
+    assert_eq!(
+        client.try_reject_proposal(&admin2, &proposal_id),
+        Err(Ok(EventRegistryError::AlreadyRejected))
+    );
+    let record = client.get_proposal_record(&proposal_id).unwrap();
+    assert_eq!(record.status, ProposalStatus::Pending);
+    assert_eq!(record.rejections.len(), 1);
+
+    // The proposer changing their mind defeats it.
+    client.reject_proposal(&admin1, &proposal_id);
+    let record = client.get_proposal_record(&proposal_id).unwrap();
+    assert_eq!(record.status, ProposalStatus::Rejected);
+    assert_eq!(record.proposal.approvals.len(), 0);
+    assert!(!client.get_active_proposals().contains(proposal_id));
+    assert_eq!(
+        client.try_approve_proposal(&admin3, &proposal_id),
+        Err(Ok(EventRegistryError::ProposalClosed))
+    );
+}
+
+#[test]
+fn test_rejection_unqueues_approved_proposal() {
+    let (env, client, admin1, admin2, admin3) = create_test_env();
+    setup_three_admins(&env, &client, &admin1, &admin2, &admin3);
+
+    let new_admin = Address::generate(&env);
+    let proposal_id = client.propose_add_admin(&admin1, &new_admin, &0);
+    client.approve_proposal(&admin2, &proposal_id);
+    assert_eq!(
+        client.get_proposal_record(&proposal_id).unwrap().status,
+        ProposalStatus::Queued
+    );
+
+    // admin2 withdraws its approval by rejecting.
+    client.reject_proposal(&admin2, &proposal_id);
+    let record = client.get_proposal_record(&proposal_id).unwrap();
+    assert_eq!(record.status, ProposalStatus::Pending);
+    assert_eq!(record.executable_at, 0);
+    assert_eq!(
+        client.try_execute_proposal(&admin1, &proposal_id),
+        Err(Ok(EventRegistryError::InsufficientApprovals))
+    );
+
+    // admin3 approving re-queues it.
+    client.approve_proposal(&admin3, &proposal_id);
+    client.execute_proposal(&admin1, &proposal_id);
+    assert!(client.is_admin(&new_admin));
+}
+
+#[test]
+fn test_get_proposals_paginates_history() {
+    let (env, client, admin1, admin2, admin3) = create_test_env();
+    setup_three_admins(&env, &client, &admin1, &admin2, &admin3);
+    // Setup executed proposals 0..3.
+
+    let cancelled = client.propose_add_admin(&admin1, &Address::generate(&env), &0);
+    client.cancel_proposal(&admin1, &cancelled);
+    let expiring = client.propose_add_admin(&admin1, &Address::generate(&env), &10);
+    let pending = client.propose_add_admin(&admin1, &Address::generate(&env), &1_000);
+    env.ledger().with_mut(|li| li.timestamp += 100);
+
+    let page = client.get_proposals(&0, &3);
+    assert_eq!(page.len(), 3);
+    for record in page.iter() {
+        assert_eq!(record.status, ProposalStatus::Executed);
+    }
+
+    let page = client.get_proposals(&3, &10);
+    assert_eq!(page.len(), 3);
+    assert_eq!(page.get(0).unwrap().proposal.proposal_id, cancelled);
+    assert_eq!(page.get(0).unwrap().status, ProposalStatus::Cancelled);
+    assert_eq!(page.get(1).unwrap().proposal.proposal_id, expiring);
+    assert_eq!(page.get(1).unwrap().status, ProposalStatus::Expired);
+    assert_eq!(page.get(2).unwrap().proposal.proposal_id, pending);
+    assert_eq!(page.get(2).unwrap().status, ProposalStatus::Pending);
+
+    assert_eq!(client.get_proposals(&6, &10).len(), 0);
+}
+
+#[test]
+fn test_proposal_lifecycle_events() {
     let (env, client, admin1, admin2, _) = create_test_env();
     let platform_wallet = Address::generate(&env);
     let usdc_token = Address::generate(&env);
     client.initialize(&admin1, &platform_wallet, &500, &usdc_token);
 
-    // threshold = 5 > 2 admins should fail
-    let new_admins = soroban_sdk::vec![&env, admin1.clone(), admin2.clone()];
-    client.set_multisig_config(&admin1, &new_admins, &5);
+    let proposal_id = client.propose_add_admin(&admin1, &admin2, &0);
+    let events = env.events().all();
+    assert_eq!(events.len(), 2);
+    let topic_of = |index: u32| {
+        let (_, topics, _) = events.get(index).unwrap();
+        AgoraEvent::try_from_val(&env, &topics.get(0).unwrap()).unwrap()
+    };
+    assert_eq!(topic_of(0), AgoraEvent::ProposalCreated);
+    assert_eq!(topic_of(1), AgoraEvent::ProposalQueued);
+
+    client.execute_proposal(&admin1, &proposal_id);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        AgoraEvent::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+        AgoraEvent::ProposalExecuted
+    );
+    let executed = ProposalExecutedEvent::try_from_val(&env, &data).unwrap();
+    assert_eq!(executed.proposal_id, proposal_id);
+    assert_eq!(executed.executor, admin1);
+}
+
+/// Records the payment parameter changes the registry forwards.
+#[soroban_sdk::contract]
+pub struct MockGovernedTicketPayment;
+
+#[soroban_sdk::contractimpl]
+impl MockGovernedTicketPayment {
+    pub fn apply_governance_change(env: Env, proposal_id: u64, change: PaymentParameterChange) {
+        if let PaymentParameterChange::UpdateSlippage(bps) = change {
+            if bps > 5_000 {
+                panic!("invalid slippage");
+            }
+        }
+        env.storage().instance().set(&proposal_id, &change);
+    }
+
+    pub fn last_change(env: Env, proposal_id: u64) -> Option<PaymentParameterChange> {
+        env.storage().instance().get(&proposal_id)
+    }
+}
+
+#[test]
+fn test_ticket_payment_changes_go_through_registry_proposals() {
+    let (env, client, admin1, admin2, admin3) = create_test_env();
+
+    // No TicketPayment contract registered yet.
+    let platform_wallet = Address::generate(&env);
+    let usdc_token = Address::generate(&env);
+    client.initialize(&admin1, &platform_wallet, &500, &usdc_token);
+    assert_eq!(
+        client.try_propose_ticket_payment_change(
+            &admin1,
+            &PaymentParameterChange::UpdateSlippage(100),
+            &0
+        ),
+        Err(Ok(EventRegistryError::NotInitialized))
+    );
+
+    let ticket_payment_id = env.register(MockGovernedTicketPayment, ());
+    let ticket_payment = MockGovernedTicketPaymentClient::new(&env, &ticket_payment_id);
+    client.set_ticket_payment_contract(&ticket_payment_id);
+
+    for admin in [&admin2, &admin3] {
+        let proposal_id = client.propose_add_admin(&admin1, admin, &0);
+        client.execute_proposal(&admin1, &proposal_id);
+    }
+    let proposal_id = client.propose_set_threshold(&admin1, &2, &0);
+    client.execute_proposal(&admin1, &proposal_id);
+
+    // Payment changes need the same registry admin threshold as registry changes.
+    let new_token = Address::generate(&env);
+    let change = PaymentParameterChange::AddTokenToWhitelist(new_token);
+    let proposal_id = client.propose_ticket_payment_change(&admin1, &change, &0);
+    assert_eq!(
+        client.try_execute_proposal(&admin1, &proposal_id),
+        Err(Ok(EventRegistryError::InsufficientApprovals))
+    );
+    assert_eq!(ticket_payment.last_change(&proposal_id), None);
+
+    client.approve_proposal(&admin2, &proposal_id);
+    client.execute_proposal(&admin1, &proposal_id);
+    assert_eq!(ticket_payment.last_change(&proposal_id), Some(change));
+
+    // Withdrawal limits are governed the same way.
+    let change = PaymentParameterChange::SetOrganizerCapPolicy(
+        Address::generate(&env),
+        OrganizerCapPolicy {
+            default_cap: 100,
+            verified_cap: 1_000,
+        },
+    );
+    let proposal_id = client.propose_ticket_payment_change(&admin1, &change, &0);
+    client.approve_proposal(&admin2, &proposal_id);
+    client.execute_proposal(&admin1, &proposal_id);
+    assert_eq!(ticket_payment.last_change(&proposal_id), Some(change));
+
+    // A change TicketPayment refuses leaves the proposal unexecuted.
+    let proposal_id = client.propose_ticket_payment_change(
+        &admin1,
+        &PaymentParameterChange::UpdateSlippage(9_000),
+        &0,
+    );
+    client.approve_proposal(&admin3, &proposal_id);
+    assert_eq!(
+        client.try_execute_proposal(&admin1, &proposal_id),
+        Err(Ok(EventRegistryError::TicketPaymentChangeFailed))
+    );
+    assert!(!client.get_proposal(&proposal_id).unwrap().executed);
 }
@@ -0,0 +1,113 @@
+//! # TTL Policy
+//!
+//! Persistent entries are archived by the network once their TTL runs out, and
+//! a write only guarantees the network minimum. This module keeps long-lived
+//! records alive:
+//!
+//! - Hot entries (events, series, season passes, guest profiles) are extended
+//!   whenever they are read, so anything in regular use never lapses.
+//! - `bump_event` extends everything an event depends on in one call, for
+//!   events that sit idle between registration and their start date.
+//!
+//! Each [`TtlFamily`] has a default [`TtlPolicy`] that the admin may override.
+//! Policies are stored under [`TtlKey::Policy`] and are themselves part of the
+//! `Config` family.
+
+use crate::storage;
+use crate::types::{DataKey, TtlFamily, TtlKey, TtlPolicy};
+use soroban_sdk::{Env, IntoVal, Val};
+
+/// Ledgers closed per day at a five second close time.
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Default policy for contract-wide settings.
+pub const CONFIG_TTL_THRESHOLD: u32 = 60 * DAY_IN_LEDGERS;
+pub const CONFIG_TTL_EXTEND_TO: u32 = 180 * DAY_IN_LEDGERS;
+
+/// Default policy for event, index and guest records.
+pub const RECORD_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+pub const RECORD_TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+
+/// Default policy for series and season passes, which span many events.
+pub const SERIES_TTL_THRESHOLD: u32 = 60 * DAY_IN_LEDGERS;
+pub const SERIES_TTL_EXTEND_TO: u32 = 240 * DAY_IN_LEDGERS;
+
+const ALL_FAMILIES: [TtlFamily; 5] = [
+    TtlFamily::Config,
+    TtlFamily::Event,
+    TtlFamily::OrganizerIndex,
+    TtlFamily::Series,
+    TtlFamily::Guest,
+];
+
+/// Returns the built-in policy for a family.
+pub fn default_policy(family: &TtlFamily) -> TtlPolicy {
+    let (threshold, extend_to) = match family {
+        TtlFamily::Config => (CONFIG_TTL_THRESHOLD, CONFIG_TTL_EXTEND_TO),
+        TtlFamily::Series => (SERIES_TTL_THRESHOLD, SERIES_TTL_EXTEND_TO),
+        TtlFamily::Event | TtlFamily::OrganizerIndex | TtlFamily::Guest => {
+            (RECORD_TTL_THRESHOLD, RECORD_TTL_EXTEND_TO)
+        }
+    };
+    TtlPolicy {
+        threshold,
+        extend_to,
+    }
+}
+
+/// Returns the effective policy for a family: the admin override if set,
+/// otherwise the default.
+pub fn get_policy(env: &Env, family: &TtlFamily) -> TtlPolicy {
+    storage::get_ttl_policy(env, family).unwrap_or_else(|| default_policy(family))
+}
+
+/// Checks that a policy can be applied on this network.
+pub fn is_valid_policy(env: &Env, policy: &TtlPolicy) -> bool {
+    policy.threshold > 0
+        && policy.threshold < policy.extend_to
+        && policy.extend_to <= env.storage().max_ttl()
+}
+
+/// Extends a persistent entry under its family's policy. Missing keys are
+/// skipped so callers can pass optional entries.
+pub fn extend<K>(env: &Env, key: &K, family: &TtlFamily)
+where
+    K: IntoVal<Env, Val>,
+{
+    if env.storage().persistent().has(key) {
+        let policy = get_policy(env, family);
+        env.storage().persistent().extend_ttl(
+            key,
+            policy.threshold,
+            policy.extend_to.min(env.storage().max_ttl()),
+        );
+    }
+}
+
+/// Extends the contract instance and every contract-wide setting.
+pub fn extend_config(env: &Env) {
+    let policy = get_policy(env, &TtlFamily::Config);
+    env.storage().instance().extend_ttl(
+        policy.threshold,
+        policy.extend_to.min(env.storage().max_ttl()),
+    );
+
+    for key in [
+        DataKey::Admin,
+        DataKey::MultiSigConfig,
+        DataKey::PlatformWallet,
+        DataKey::PlatformFee,
+        DataKey::Initialized,
+        DataKey::TicketPaymentContract,
+        DataKey::GlobalPromoBps,
+        DataKey::PromoExpiry,
+        DataKey::MinStakeAmount,
+        DataKey::StakingToken,
+        DataKey::SchemaVersion,
+    ] {
+        extend(env, &key, &TtlFamily::Config);
+    }
+    for family in ALL_FAMILIES {
+        extend(env, &TtlKey::Policy(family), &TtlFamily::Config);
+    }
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Map, String, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Vec};
 
 /// Represents a series or festival grouping multiple events
 #[contracttype]
@@ -82,6 +82,16 @@ pub struct Milestone {
     pub release_percent: u32,
 }
 
+/// A single recipient in an event's revenue split.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payee {
+    /// The wallet address that receives this share of the organizer revenue
+    pub address: Address,
+    /// Share of the organizer revenue in basis points (10000 = 100%)
+    pub share_bps: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum EventStatus {
@@ -161,6 +171,9 @@ pub struct EventInfo {
     pub use_global_whitelist: bool,
     /// Optional IPFS CID for post-event feedback (only settable after end_time)
     pub feedback_cid: Option<String>,
+    /// Revenue split across multiple payees (empty = everything goes to `payment_address`).
+    /// Shares must sum to 10000 bps and the split is locked once the first ticket is sold.
+    pub payees: Vec<Payee>,
 }
 
 /// Payment information for an event
@@ -272,6 +285,259 @@ pub struct Proposal {
     pub expires_at: u64,
 }
 
+/// Lifecycle stage of a governance proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProposalStatus {
+    /// Collecting approvals
+    Pending,
+    /// Threshold reached; executable once the execution delay has passed
+    Queued,
+    /// The change was applied
+    Executed,
+    /// Withdrawn by the proposer or an admin
+    Cancelled,
+    /// Enough admins rejected it that the threshold can no longer be reached
+    Rejected,
+    /// Not executed before `expires_at`
+    Expired,
+}
+
+/// Governance bookkeeping kept next to a `Proposal`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalState {
+    /// Current lifecycle stage
+    pub status: ProposalStatus,
+    /// Admins that voted against the proposal
+    pub rejections: Vec<Address>,
+    /// Earliest execution time once queued (0 while pending)
+    pub executable_at: u64,
+}
+
+/// A proposal together with its governance state, as returned by history queries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalRecord {
+    /// The proposal itself
+    pub proposal: Proposal,
+    /// Current lifecycle stage
+    pub status: ProposalStatus,
+    /// Admins that voted against the proposal
+    pub rejections: Vec<Address>,
+    /// Earliest execution time once queued (0 while pending)
+    pub executable_at: u64,
+}
+
+/// Storage keys for proposal lifecycle tracking. Kept apart from `DataKey`
+/// for the same reason as `LoyaltyKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GovernanceKey {
+    /// Delay between a proposal reaching its threshold and becoming executable (u64)
+    ExecutionDelay,
+    /// Lifecycle state of a proposal, keyed by proposal ID (ProposalState)
+    ProposalState(u64),
+    /// Settings for stake-weighted community votes (CommunityVoteConfig)
+    CommunityVoteConfig,
+    /// Community vote attached to a proposal, keyed by proposal ID (CommunityVote)
+    CommunityVote(u64),
+    /// Whether an organizer voted on a proposal, keyed by (proposal ID, organizer) (bool)
+    CommunityVoteCast(u64, Address),
+}
+
+/// Storage keys for archived event data. Kept apart from `DataKey` for the
+/// same reason as `LoyaltyKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArchiveKey {
+    /// Merkle commitment to an archived event's payments, keyed by event_id
+    /// (ticket_payment::PaymentsCommitment)
+    PaymentsCommitment(String),
+}
+
+/// Merkle allowlist gating one tier of an event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierAllowlist {
+    /// Root over SHA-256 hashes of `(buyer, max_quantity)` allowlist entries
+    pub root: BytesN<32>,
+    /// Whether the tier requires a proof even when the event is public
+    pub hidden: bool,
+}
+
+/// Allowlist a purchase must prove membership of, as resolved for one tier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowlistGate {
+    /// Root the buyer's entry must be proven against
+    pub root: BytesN<32>,
+    /// Tier the buyer's allowance is scoped to, or None for an event-wide allowance
+    pub tier_id: Option<String>,
+}
+
+/// Storage keys for invite-only allowlists. Kept apart from `DataKey` for the
+/// same reason as `LoyaltyKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AllowlistKey {
+    /// Allowlist root covering every tier of a private event (BytesN<32>)
+    EventRoot(String),
+    /// Allowlist for a single tier, keyed by (event_id, tier_id) (TierAllowlist)
+    TierRoot(String, String),
+}
+
+/// Claim a buyer or transfer recipient must hold to own a ticket in a tier,
+/// checked against an external attester contract (e.g. age or KYC checks)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierAttestation {
+    /// Contract implementing `has_valid_claim(subject, claim) -> bool`
+    pub attester: Address,
+    /// Claim identifier understood by the attester (e.g. "age_18", "kyc")
+    pub claim: String,
+}
+
+/// Storage keys for attestation requirements. Kept apart from `DataKey` for
+/// the same reason as `LoyaltyKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AttestationKey {
+    /// Attestation required for a tier, keyed by (event_id, tier_id) (TierAttestation)
+    Tier(String, String),
+}
+
+/// Refundable deposit a free tier asks of guests to discourage no-shows. Check-in
+/// returns it; deposits left unclaimed after the event go to `forfeit_recipient`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierDeposit {
+    /// Deposit per ticket in the payment token
+    pub amount: i128,
+    /// Recipient of forfeited deposits, such as a charity (`None` = the organizer)
+    pub forfeit_recipient: Option<Address>,
+}
+
+/// Layaway plan a tier offers: a first installment reserves the ticket and the
+/// rest fall due by `due_dates`, after which the ticket can be forfeited.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentPlan {
+    /// Deadlines of the installments after the first, in increasing order
+    pub due_dates: Vec<u64>,
+    /// Share of the amount paid the organizer keeps on forfeiture, in basis points
+    pub penalty_bps: u32,
+}
+
+/// Storage keys for tier pricing options. Kept apart from `DataKey` for the
+/// same reason as `LoyaltyKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PricingKey {
+    /// Whether a tier's price is a floor buyers may pay above, keyed by (event_id, tier_id) (bool)
+    PayWhatYouWant(String, String),
+    /// Refundable deposit required to RSVP for a free tier, keyed by (event_id, tier_id) (TierDeposit)
+    Deposit(String, String),
+    /// Installment plan offered by a tier, keyed by (event_id, tier_id) (InstallmentPlan)
+    Installments(String, String),
+}
+
+/// An extra item (parking, merchandise, a meal voucher) sold alongside an event's tickets.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddOn {
+    /// Display name of the item
+    pub name: String,
+    /// Price of one item in the payment token
+    pub price: i128,
+    /// Items available in total (0 = unlimited)
+    pub stock: u32,
+    /// Items sold and not refunded
+    pub sold: u32,
+    /// Tier whose tickets the item can be bought with (`None` = any tier)
+    pub tier_id: Option<String>,
+}
+
+/// Storage keys for event add-ons. Kept apart from `DataKey` for the same
+/// reason as `LoyaltyKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AddOnKey {
+    /// An add-on keyed by (event_id, addon_id) (AddOn)
+    Item(String, String),
+    /// Add-on IDs offered by an event (Vec<String>)
+    Ids(String),
+}
+
+/// Families of persistent entries that share a TTL policy
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TtlFamily {
+    /// Contract-wide settings: admin, fees, linked contracts and TTL policies
+    Config,
+    /// Event records, receipts and per-event settings
+    Event,
+    /// Organizer event and receipt indices
+    OrganizerIndex,
+    /// Series registries and season passes
+    Series,
+    /// Guest loyalty profiles
+    Guest,
+}
+
+/// TTL thresholds, in ledgers, for a family of persistent entries. An entry
+/// whose remaining TTL has dropped below `threshold` is extended to `extend_to`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlPolicy {
+    pub threshold: u32,
+    pub extend_to: u32,
+}
+
+/// Storage keys for TTL management. Kept apart from `DataKey` for the same
+/// reason as `LoyaltyKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TtlKey {
+    /// Admin-configured TTL policy for a family of entries (TtlPolicy)
+    Policy(TtlFamily),
+}
+
+/// Settings for the optional community vote on fee and staking proposals
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommunityVoteConfig {
+    /// Whether `SetPlatformFee` and `SetMinStakeAmount` proposals open a community vote
+    pub enabled: bool,
+    /// How long staked organizers can vote after the proposal is created
+    pub voting_period_secs: u64,
+    /// Share of the snapshotted stake that must vote (in basis points)
+    pub quorum_bps: u32,
+    /// Share of the cast weight that must vote in favour (in basis points)
+    pub approval_bps: u32,
+}
+
+/// Stake-weighted vote of staked organizers on a proposal. Weights are read
+/// from stake checkpoints taken before `snapshot_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommunityVote {
+    /// Proposal creation time; stake held before it counts as voting weight
+    pub snapshot_at: u64,
+    /// Last timestamp at which votes are accepted
+    pub ends_at: u64,
+    /// Total stake at the snapshot, the base for the quorum
+    pub total_weight: i128,
+    /// Weight cast in favour
+    pub votes_for: i128,
+    /// Weight cast against
+    pub votes_against: i128,
+    /// Quorum required when the vote opened (in basis points)
+    pub quorum_bps: u32,
+    /// Approval required when the vote opened (in basis points)
+    pub approval_bps: u32,
+}
+
 /// Loyalty profile for a guest (event attendee / ticket buyer)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -288,6 +554,114 @@ pub struct GuestProfile {
     pub last_updated: u64,
 }
 
+/// Organizer opt-in for paying part of a ticket price with redeemable loyalty points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointsRedemptionConfig {
+    /// Whether guests may redeem points on this event
+    pub enabled: bool,
+    /// Price reduction granted per redeemed point (in token stroops)
+    pub point_value: i128,
+    /// Maximum share of the ticket total that points may cover (in basis points)
+    pub max_discount_bps: u32,
+}
+
+/// A platform loyalty tier: guests whose (decayed) score reaches `min_score`
+/// receive `discount_bps` off the platform fee
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyTier {
+    /// Minimum loyalty score required for this tier
+    pub min_score: u64,
+    /// Platform-fee discount granted at this tier (in basis points)
+    pub discount_bps: u32,
+}
+
+/// Governance-controlled platform loyalty settings
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyConfig {
+    /// Length of one decay period in seconds (0 disables decay)
+    pub decay_period_secs: u64,
+    /// Share of the score lost at the end of each full decay period (in basis points)
+    pub decay_bps: u32,
+    /// Discount tiers, sorted by ascending `min_score`
+    pub tiers: Vec<LoyaltyTier>,
+}
+
+/// One rung of an organizer's own loyalty ladder, e.g. "attended 5 of our events → 15% off"
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyRung {
+    /// Number of the organizer's events the guest must have attended
+    pub min_events_attended: u32,
+    /// Ticket price discount granted by the organizer (in basis points)
+    pub discount_bps: u32,
+}
+
+/// Storage keys for the loyalty programs. Kept apart from `DataKey` so the
+/// main key enum stays within the contract spec's variant limit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LoyaltyKey {
+    /// Platform loyalty tiers and decay settings (LoyaltyConfig)
+    LoyaltyConfig,
+    /// Start of the guest's current, not yet applied decay period (u64)
+    ScoreDecayedAt(Address),
+    /// Loyalty ladder run by an organizer (Vec<LoyaltyRung>)
+    OrganizerProgram(Address),
+    /// Number of the organizer's events a guest attended, keyed by (organizer, guest) (u32)
+    OrganizerAttendance(Address, Address),
+    /// Whether attendance of a guest at an event was already counted, keyed by (guest, event_id)
+    GuestEventAttended(Address, String),
+}
+
+/// Storage keys for stake slashing and the guest protection pool. Kept apart
+/// from `DataKey` for the same reason as `LoyaltyKey`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StakingKey {
+    /// Slashed collateral available to cover refund shortfalls, keyed by token (i128)
+    ProtectionPool(Address),
+    /// Cumulative amount slashed from an organizer's stake (i128)
+    TotalSlashed(Address),
+    /// Cooldown between an unstake request and the withdrawal of its funds (u64)
+    UnbondingPeriod,
+    /// Collateral waiting out the unbonding period, keyed by organizer (Vec<UnbondingEntry>)
+    Unbonding(Address),
+    /// Cumulative rewards per staked token, scaled by `REWARD_PRECISION` (i128)
+    RewardPerShare,
+    /// `RewardPerShare` at the staker's last settlement (i128)
+    RewardIndexPaid(Address),
+    /// Number of addresses in `StakersList` (u32)
+    StakerCount,
+    /// History of an organizer's staked amount (Vec<StakeCheckpoint>)
+    StakeHistory(Address),
+    /// History of the total staked amount (Vec<StakeCheckpoint>)
+    TotalStakeHistory,
+}
+
+/// Staked amount from `timestamp` until the next checkpoint.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeCheckpoint {
+    /// Ledger timestamp at which the amount took effect
+    pub timestamp: u64,
+    /// Staked amount
+    pub amount: i128,
+}
+
+/// Collateral removed from an organizer's stake that can be withdrawn once
+/// `release_at` has passed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnbondingEntry {
+    /// Amount of staking token being unbonded
+    pub amount: i128,
+    /// Earliest timestamp at which the amount can be withdrawn
+    pub release_at: u64,
+}
+
 /// Represents an organizer's staked collateral for Verified status
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -324,6 +698,26 @@ pub enum ParameterChange {
     SetPlatformFee(u32),
     /// Update the minimum stake amount required for Verified organizer status
     SetMinStakeAmount(i128),
+    /// Schedule a contract upgrade to the given WASM hash, subject to the upgrade timelock
+    UpgradeContract(BytesN<32>),
+    /// Replace the platform loyalty tiers and decay settings
+    SetLoyaltyConfig(LoyaltyConfig),
+    /// Slash up to the given amount of an organizer's stake into the guest protection pool
+    SlashStake(Address, i128),
+    /// Update the delay between a proposal reaching its threshold and its execution
+    SetExecutionDelay(u64),
+    /// Apply a parameter change to the registered TicketPayment contract
+    TicketPayment(crate::ticket_payment::PaymentParameterChange),
+}
+
+/// A governance-approved upgrade waiting for its timelock to elapse.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    /// Hash of the WASM the contract will be upgraded to
+    pub wasm_hash: BytesN<32>,
+    /// Earliest timestamp at which `upgrade` may apply the new WASM
+    pub eta: u64,
 }
 
 /// Storage keys for the Event Registry contract.
@@ -408,4 +802,14 @@ pub enum DataKey {
     GlobalTicketsSold,
     /// Mapping of (event_id, tier_id, user_address) to ticket count for per-user limits (Persistent)
     UserTicketCount(String, String, Address),
+    /// Governance-approved upgrade awaiting its timelock (PendingUpgrade)
+    PendingUpgrade,
+    /// Layout version of the stored records, advanced by `migrate` (u32)
+    SchemaVersion,
+    /// Spendable loyalty point balance keyed by guest address (u64, Persistent)
+    RedeemablePoints(Address),
+    /// Loyalty point redemption settings keyed by event_id (Persistent)
+    PointsRedemption(String),
+    /// Set once an event sells its first ticket, locking its revenue split (bool, Persistent)
+    SalesStarted(String),
 }
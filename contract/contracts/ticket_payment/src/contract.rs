@@ -1,41 +1,72 @@
+use crate::migration::{migrate_step, SCHEMA_VERSION};
 use crate::storage::{
-    add_discount_hash, add_payment_to_buyer_index, add_to_active_escrow_by_token,
-    add_to_active_escrow_total, add_to_daily_withdrawn_amount,
+    add_add_on_redemptions, add_discount_hash, add_payment_to_buyer_index,
+    add_to_active_escrow_by_token, add_to_active_escrow_total, add_to_donation_total,
+    add_to_event_fees_by_token, add_to_payee_earnings, add_to_released_fees,
     add_to_total_fees_collected_by_token, add_to_total_volume_processed, add_token_to_whitelist,
-    get_admin, get_bulk_refund_index, get_daily_withdrawn_amount, get_event_balance,
-    get_event_payments, get_event_registry, get_highest_bid, get_oracle_address,
-    get_partial_refund_index, get_partial_refund_percentage, get_payment, get_platform_wallet,
-    get_proposal, get_slippage_bps, get_total_fees_collected_by_token, get_total_governors,
-    get_transfer_fee, get_usdc_token, get_withdrawal_cap, has_price_switched,
-    increment_proposal_count, is_auction_closed, is_discount_hash_used, is_discount_hash_valid,
-    is_event_disputed, is_governor, is_initialized, is_paused, is_token_whitelisted,
-    mark_discount_hash_used, remove_payment_from_buyer_index, remove_token_from_whitelist,
-    set_admin, set_auction_closed, set_bulk_refund_index, set_event_dispute_status,
-    set_event_registry, set_governor, set_highest_bid, set_initialized, set_is_paused,
-    set_oracle_address, set_partial_refund_index, set_partial_refund_percentage,
-    set_platform_wallet, set_price_switched, set_proposal, set_slippage_bps, set_total_governors,
-    set_transfer_fee, set_usdc_token, set_withdrawal_cap, store_payment,
+    extend_event_payment_entries, get_add_on_purchase, get_add_on_redemptions, get_admin,
+    get_allowlist_purchases, get_bulk_refund_index, get_credit_balance, get_credit_offer,
+    get_deposit_sweep_index, get_donation_config, get_donation_totals, get_downgrade_refunds,
+    get_event_balance, get_event_fees_by_token, get_event_payments, get_event_registry,
+    get_exchanges_enabled, get_group_order, get_held_deposit, get_highest_bid,
+    get_installment_schedule, get_oracle_address, get_organizer_cap_policy,
+    get_organizer_withdrawal_cap, get_partial_refund_index, get_partial_refund_percentage,
+    get_paused_events, get_paused_scopes, get_payee_earnings, get_payment, get_payment_add_ons,
+    get_pending_upgrade, get_platform_wallet, get_queued_withdrawal, get_released_fees,
+    get_schema_version, get_slippage_bps, get_total_fees_collected_by_token, get_transfer_fee,
+    get_treasury_wallets, get_usdc_token, get_window_withdrawn_amount, get_withdrawal_cap,
+    get_withdrawal_limit_config, has_price_switched, increment_queued_withdrawal_count,
+    is_auction_closed, is_discount_hash_used, is_discount_hash_valid, is_event_disputed,
+    is_initialized, is_paused, is_token_whitelisted, mark_discount_hash_used,
+    move_payment_to_event, record_window_withdrawal, remove_event_payments, remove_held_deposit,
+    remove_installment_schedule, remove_organizer_withdrawal_cap, remove_payment_from_buyer_index,
+    remove_pending_upgrade, remove_token_from_whitelist, set_add_on_purchase, set_admin,
+    set_allowlist_purchases, set_auction_closed, set_bulk_refund_index, set_credit_balance,
+    set_credit_offer, set_deposit_sweep_index, set_donation_config, set_downgrade_refunds,
+    set_event_dispute_status, set_event_registry, set_exchanges_enabled, set_group_order,
+    set_held_deposit, set_highest_bid, set_initialized, set_installment_schedule, set_is_paused,
+    set_oracle_address, set_organizer_cap_policy, set_organizer_withdrawal_cap,
+    set_partial_refund_index, set_partial_refund_percentage, set_paused_events, set_paused_scopes,
+    set_pending_upgrade, set_platform_wallet, set_price_switched, set_queued_withdrawal,
+    set_schema_version, set_slippage_bps, set_transfer_fee, set_treasury_wallets, set_ttl_policy,
+    set_usdc_token, set_withdrawal_cap, set_withdrawal_limit_config, store_payment,
     subtract_from_active_escrow_by_token, subtract_from_active_escrow_total,
+    subtract_from_event_fees_by_token, subtract_from_released_fees,
     subtract_from_total_fees_collected_by_token, update_event_balance,
 };
 use crate::types::{
-    DataKey, HighestBid, ParameterChange, ParameterProposal, Payment, PaymentStatus,
-    ProposalStatus, MAX_BPS, TRANSFER_FEE_BPS,
+    AddOnPurchase, AllowlistEntry, ArchivedPayment, CreditBalance, CreditOffer, DataKey,
+    DonationConfig, EventBalance, GroupOrder, GroupOrderStatus, HeldDeposit, HighestBid,
+    InstallmentSchedule, ParameterChange, PauseScope, PauseState, Payment, PaymentStatus,
+    PaymentsCommitment, PendingUpgrade, PurchaseOptions, QueuedWithdrawal, QueuedWithdrawalStatus,
+    TreasuryWallet, TtlFamily, TtlPolicy, WithdrawalKind, WithdrawalLimitConfig, MAX_BPS,
+    TRANSFER_FEE_BPS,
 };
 use crate::{
     error::TicketPaymentError,
     events::{
-        AgoraEvent, AuctionClosedEvent, BidPlacedEvent, BulkRefundProcessedEvent,
-        ContractPausedEvent, ContractUpgraded, ContractVerificationFailedEvent,
-        DiscountCodeAppliedEvent, DisputeStatusChangedEvent, FeeSettledEvent,
-        GlobalPromoAppliedEvent, GovernanceActionExecutedEvent, InitializationEvent,
-        PartialRefundProcessedEvent, PaymentProcessedEvent, PaymentStatusChangedEvent,
-        PriceSwitchedEvent, ProposalCreatedEvent, ProposalVotedEvent, RevenueClaimedEvent,
-        TicketTransferredEvent,
+        AddOnPurchasedEvent, AddOnRedeemedEvent, AddOnRefundedEvent, AgoraEvent, AgoraEventExt,
+        AuctionClosedEvent, BidPlacedEvent, BulkRefundProcessedEvent, ContractMigratedEvent,
+        ContractPausedEvent, ContractUpgraded, ContractVerificationFailedEvent, CreditIssuedEvent,
+        CreditRedeemedEvent, CreditReleasedEvent, DepositHeldEvent, DepositReturnedEvent,
+        DepositsSweptEvent, DiscountCodeAppliedEvent, DisputeStatusChangedEvent,
+        DonationReceivedEvent, FeeSettledEvent, GlobalPromoAppliedEvent,
+        GovernanceActionExecutedEvent, GroupOrderCreatedEvent, GroupOrderFilledEvent,
+        GroupOrderJoinedEvent, GroupOrderRefundedEvent, InitializationEvent,
+        InstallmentForfeitedEvent, InstallmentPaidEvent, LoyaltyPointsAppliedEvent,
+        OrganizerLoyaltyDiscountAppliedEvent, PartialRefundProcessedEvent, PauseScopeChangedEvent,
+        PauseTarget, PayeePaidEvent, PaymentArchivedEvent, PaymentProcessedEvent,
+        PaymentStatusChangedEvent, PriceSwitchedEvent, ProtectionPoolDrawnEvent,
+        QueuedWithdrawalCancelledEvent, QueuedWithdrawalExecutedEvent, RevenueClaimedEvent,
+        TicketExchangedEvent, TicketTransferredEvent, TicketUpgradedEvent, TreasuryPayoutEvent,
+        TreasuryWalletsUpdatedEvent, UpgradeCancelledEvent, UpgradeScheduledEvent,
+        WithdrawalQueuedEvent,
     },
+    ttl,
 };
 use soroban_sdk::{
-    contract, contractimpl, token, Address, Bytes, BytesN, Env, String, Symbol, Vec,
+    contract, contractimpl, token, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Symbol,
+    Vec,
 };
 
 const MAX_ORACLE_PRICE_AGE_SECS: u64 = 3600;
@@ -44,6 +75,15 @@ const MAX_ORACLE_PRICE_AGE_SECS: u64 = 3600;
 /// Balances at or below this threshold are swept in full to avoid dust.
 const DUST_THRESHOLD: i128 = 10_000;
 
+/// Maximum number of treasury wallets that platform fees can be split across.
+const MAX_TREASURY_WALLETS: u32 = 10;
+
+/// Maximum number of tickets a single group order can hold.
+const MAX_GROUP_ORDER_TICKETS: u32 = 20;
+
+/// Delay between a governance-approved upgrade and the earliest time it can be applied.
+pub const UPGRADE_TIMELOCK_SECS: u64 = 172_800;
+
 // Price Oracle interface
 pub mod price_oracle {
     use soroban_sdk::{contractclient, Address, Env};
@@ -61,6 +101,16 @@ pub mod price_oracle {
     }
 }
 
+// Attester interface for tiers gated on age, KYC or similar claims
+pub mod attester {
+    use soroban_sdk::{contractclient, Address, Env, String};
+
+    #[contractclient(name = "AttesterClient")]
+    pub trait AttesterInterface {
+        fn has_valid_claim(env: Env, subject: Address, claim: String) -> bool;
+    }
+}
+
 // Event Registry interface
 pub mod event_registry {
     use soroban_sdk::{contractclient, Address, Env, String};
@@ -99,6 +149,66 @@ pub mod event_registry {
         pub last_updated: u64,
     }
 
+    /// Loyalty point redemption settings mirrored from the event_registry contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct PointsRedemptionConfig {
+        pub enabled: bool,
+        pub point_value: i128,
+        pub max_discount_bps: u32,
+    }
+
+    /// Revenue split recipient mirrored from the event_registry contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct Payee {
+        pub address: Address,
+        pub share_bps: u32,
+    }
+
+    /// Allowlist a purchase must prove membership of, mirrored from the event_registry contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct AllowlistGate {
+        pub root: soroban_sdk::BytesN<32>,
+        pub tier_id: Option<String>,
+    }
+
+    /// Tier attestation requirement mirrored from the event_registry contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct TierAttestation {
+        pub attester: Address,
+        pub claim: String,
+    }
+
+    /// Event add-on mirrored from the event_registry contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct AddOn {
+        pub name: String,
+        pub price: i128,
+        pub stock: u32,
+        pub sold: u32,
+        pub tier_id: Option<String>,
+    }
+
+    /// Free-tier RSVP deposit mirrored from the event_registry contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct TierDeposit {
+        pub amount: i128,
+        pub forfeit_recipient: Option<Address>,
+    }
+
+    /// Tier installment plan mirrored from the event_registry contract
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct InstallmentPlan {
+        pub due_dates: soroban_sdk::Vec<u64>,
+        pub penalty_bps: u32,
+    }
+
     #[contractclient(name = "Client")]
     pub trait EventRegistryInterface {
         fn get_event_payment_info(env: Env, event_id: String) -> PaymentInfo;
@@ -106,6 +216,21 @@ pub mod event_registry {
         fn get_organizer_address(env: Env, event_id: String) -> Option<Address>;
         fn increment_inventory(env: Env, event_id: String, tier_id: String, quantity: u32);
         fn decrement_inventory(env: Env, event_id: String, tier_id: String);
+        fn move_inventory(
+            env: Env,
+            event_id: String,
+            from_tier_id: String,
+            to_tier_id: String,
+            user: Address,
+        );
+        fn exchange_inventory(
+            env: Env,
+            from_event_id: String,
+            from_tier_id: String,
+            to_event_id: String,
+            to_tier_id: String,
+            user: Address,
+        );
         fn get_global_promo_bps(env: Env) -> u32;
         fn get_promo_expiry(env: Env) -> u64;
         fn is_scanner_authorized(env: Env, event_id: String, scanner: Address) -> bool;
@@ -117,8 +242,53 @@ pub mod event_registry {
             amount_spent: i128,
             loyalty_multiplier: u32,
         );
+        fn reverse_loyalty_score(
+            env: Env,
+            caller: Address,
+            guest: Address,
+            tickets_refunded: u32,
+            amount_refunded: i128,
+            loyalty_multiplier: u32,
+            refund_bps: u32,
+        );
+        fn get_points_redemption_config(
+            env: Env,
+            event_id: String,
+        ) -> Option<PointsRedemptionConfig>;
+        fn redeem_loyalty_points(
+            env: Env,
+            caller: Address,
+            guest: Address,
+            event_id: String,
+            points: u64,
+        ) -> u64;
+        fn cover_refund_shortfall(env: Env, caller: Address, token: Address, amount: i128) -> i128;
         fn get_loyalty_discount_bps(env: Env, guest: Address) -> u32;
+        fn get_organizer_discount_bps(env: Env, organizer: Address, guest: Address) -> u32;
+        fn record_attendance(env: Env, caller: Address, guest: Address, event_id: String) -> u32;
         fn get_guest_profile(env: Env, guest: Address) -> Option<GuestProfile>;
+        fn get_revenue_split(env: Env, event_id: String) -> soroban_sdk::Vec<Payee>;
+        fn is_organizer_verified(env: Env, organizer: Address) -> bool;
+        fn get_purchase_allowlist(
+            env: Env,
+            event_id: String,
+            tier_id: String,
+        ) -> Option<AllowlistGate>;
+        fn get_tier_attestation(
+            env: Env,
+            event_id: String,
+            tier_id: String,
+        ) -> Option<TierAttestation>;
+        fn is_pay_what_you_want(env: Env, event_id: String, tier_id: String) -> bool;
+        fn get_add_on(env: Env, event_id: String, addon_id: String) -> Option<AddOn>;
+        fn reserve_add_on(env: Env, event_id: String, addon_id: String, quantity: u32) -> AddOn;
+        fn release_add_on(env: Env, event_id: String, addon_id: String, quantity: u32);
+        fn get_tier_deposit(env: Env, event_id: String, tier_id: String) -> Option<TierDeposit>;
+        fn get_installment_plan(
+            env: Env,
+            event_id: String,
+            tier_id: String,
+        ) -> Option<InstallmentPlan>;
     }
 
     pub use crate::types::AuctionConfig;
@@ -181,1612 +351,4472 @@ fn require_admin(env: &Env) -> Result<Address, TicketPaymentError> {
     Ok(admin)
 }
 
-#[contract]
-pub struct TicketPaymentContract;
-
-#[contractimpl]
-#[allow(deprecated)]
-impl TicketPaymentContract {
-    /// Initializes the contract with necessary configurations.
-    pub fn initialize(
-        env: Env,
-        admin: Address,
-        usdc_token: Address,
-        platform_wallet: Address,
-        event_registry: Address,
-    ) -> Result<(), TicketPaymentError> {
-        if is_initialized(&env) {
-            return Err(TicketPaymentError::AlreadyInitialized);
+/// Fails with `ContractPaused` if the contract is paused globally, `scope` is
+/// paused, or `event_id` has been paused.
+fn require_not_paused(
+    env: &Env,
+    scope: PauseScope,
+    event_id: Option<&String>,
+) -> Result<(), TicketPaymentError> {
+    if is_paused(env) || get_paused_scopes(env).iter().any(|s| s == scope) {
+        return Err(TicketPaymentError::ContractPaused);
+    }
+    if let Some(event_id) = event_id {
+        if get_paused_events(env).iter().any(|id| &id == event_id) {
+            return Err(TicketPaymentError::ContractPaused);
         }
+    }
+    Ok(())
+}
 
-        validate_address(&env, &admin)?;
-        validate_address(&env, &usdc_token)?;
-        validate_address(&env, &platform_wallet)?;
-        validate_address(&env, &event_registry)?;
-
-        set_admin(&env, &admin);
-        set_governor(&env, &admin, true);
-        set_total_governors(&env, 1);
-        set_usdc_token(&env, usdc_token.clone());
-        set_platform_wallet(&env, platform_wallet.clone());
-        set_event_registry(&env, event_registry.clone());
-        set_initialized(&env, true);
-
-        // Whitelist USDC by default
-        add_token_to_whitelist(&env, &usdc_token);
-
-        #[allow(deprecated)]
-        env.events().publish(
-            (AgoraEvent::ContractInitialized,),
-            InitializationEvent {
-                usdc_token,
-                platform_wallet,
-                event_registry,
-            },
-        );
+/// Takes back the loyalty points and spend a refunded ticket earned. `refund_bps` is
+/// the refunded share of the ticket (MAX_BPS for a full refund). Best-effort, like the
+/// award in `process_payment`, so registries without loyalty support don't block refunds.
+fn reverse_loyalty(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    payment: &Payment,
+    loyalty_multiplier: u32,
+    amount_refunded: i128,
+    refund_bps: u32,
+) {
+    let _ = registry_client.try_reverse_loyalty_score(
+        &env.current_contract_address(),
+        &payment.buyer_address,
+        &1,
+        &amount_refunded,
+        &loyalty_multiplier,
+        &refund_bps,
+    );
+}
 
-        Ok(())
+/// Checks that `buyer` may hold a ticket in `tier` and returns the tier's current price.
+/// Oracle-priced tiers have no fixed price to settle against. Tier-specific allowlists
+/// always reject, since the holder never proved membership for this ticket; an
+/// event-wide allowlist rejects unless `event_allowlist_satisfied`.
+fn tier_change_price(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    event_id: &String,
+    tier_id: &String,
+    tier: &event_registry::TicketTier,
+    buyer: &Address,
+    event_allowlist_satisfied: bool,
+) -> Result<i128, TicketPaymentError> {
+    if tier.usd_price > 0 {
+        return Err(TicketPaymentError::InvalidPrice);
     }
 
-    /// Pauses or resumes the contract. Only callable by the multi-sig admin.
-    /// Upgrade and emergency-withdrawal remain available while the contract is paused.
-    pub fn set_pause(env: Env, paused: bool) -> Result<(), TicketPaymentError> {
-        require_admin(&env)?;
-        set_is_paused(&env, paused);
-        #[allow(deprecated)]
-        env.events().publish(
-            (AgoraEvent::ContractPaused,),
-            ContractPausedEvent {
-                paused,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
-        Ok(())
+    let gated = registry_client
+        .try_get_purchase_allowlist(event_id, tier_id)
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+        .is_some_and(|gate| gate.tier_id.is_some() || !event_allowlist_satisfied);
+    if gated {
+        return Err(TicketPaymentError::NotOnAllowlist);
     }
 
-    /// Returns the current paused state of the contract.
-    pub fn get_is_paused(env: Env) -> bool {
-        is_paused(&env)
-    }
+    require_attestation(env, registry_client, event_id, tier_id, buyer)?;
 
-    /// Returns true if the contract is currently paused.
-    pub fn is_paused(env: Env) -> bool {
-        is_paused(&env)
+    let now = env.ledger().timestamp();
+    if tier.early_bird_deadline > 0 && now <= tier.early_bird_deadline {
+        Ok(tier.early_bird_price)
+    } else {
+        Ok(tier.price)
     }
+}
 
-    /// Sets or clears a dispute for an event.
-    pub fn set_event_dispute(
-        env: Env,
-        event_id: String,
-        disputed: bool,
-    ) -> Result<(), TicketPaymentError> {
-        require_admin(&env)?;
+/// Settles the difference between `new_price` and what `payment` paid against the
+/// balance of `payment.event_id`. A dearer ticket is charged with the `fee_bps` split;
+/// a cheaper one is refunded only if `refund_cheaper`, otherwise the payment keeps its
+/// original amount. Returns `(amount_charged, amount_refunded)`.
+fn settle_price_difference(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    payment: &mut Payment,
+    new_price: i128,
+    fee_bps: u32,
+    refund_cheaper: bool,
+) -> Result<(i128, i128), TicketPaymentError> {
+    let difference = new_price
+        .checked_sub(payment.amount)
+        .ok_or(TicketPaymentError::ArithmeticError)?;
+    let buyer = payment.buyer_address.clone();
+    let token_address = payment.token.clone();
+    let token_client = token::Client::new(env, &token_address);
+    let contract_address = env.current_contract_address();
+
+    if difference > 0 {
+        if token_client.allowance(&buyer, &contract_address) < difference {
+            return Err(TicketPaymentError::InsufficientAllowance);
+        }
+        token_client.transfer_from(&contract_address, &buyer, &contract_address, &difference);
 
-        set_event_dispute_status(&env, event_id.clone(), disputed);
+        let platform_fee = difference
+            .checked_mul(fee_bps as i128)
+            .and_then(|v| v.checked_div(MAX_BPS as i128))
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        let organizer_amount = difference - platform_fee;
 
-        env.events().publish(
-            (AgoraEvent::DisputeStatusChanged,),
-            DisputeStatusChangedEvent {
-                event_id,
-                is_disputed: disputed,
-                timestamp: env.ledger().timestamp(),
-            },
+        update_event_balance(
+            env,
+            payment.event_id.clone(),
+            organizer_amount,
+            platform_fee,
+        );
+        add_to_total_volume_processed(env, difference);
+        add_to_total_fees_collected_by_token(env, token_address.clone(), platform_fee);
+        add_to_event_fees_by_token(
+            env,
+            payment.event_id.clone(),
+            token_address.clone(),
+            platform_fee,
         );
+        add_to_active_escrow_total(env, difference);
+        add_to_active_escrow_by_token(env, token_address, difference);
 
-        Ok(())
+        payment.amount = new_price;
+        payment.platform_fee += platform_fee;
+        payment.organizer_amount += organizer_amount;
+        return Ok((difference, 0));
     }
 
-    /// Returns if an event is currently disputed.
-    pub fn is_event_disputed(env: Env, event_id: String) -> bool {
-        is_event_disputed(&env, event_id)
+    if difference == 0 || !refund_cheaper {
+        return Ok((0, 0));
     }
 
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        require_admin(&env).expect("Admin not set");
+    let refund = -difference;
+    let platform_share = core::cmp::min(
+        refund
+            .checked_mul(fee_bps as i128)
+            .and_then(|v| v.checked_div(MAX_BPS as i128))
+            .ok_or(TicketPaymentError::ArithmeticError)?,
+        payment.platform_fee,
+    );
+    let organizer_share = refund - platform_share;
 
-        let old_wasm_hash = match env.current_contract_address().executable() {
-            Some(soroban_sdk::Executable::Wasm(hash)) => hash,
-            _ => panic!("Current contract is not a Wasm contract"),
-        };
+    let mut escrow = get_event_balance(env, payment.event_id.clone());
+    let covered = cover_refund_from_pool(
+        env,
+        registry_client,
+        &payment.event_id,
+        &mut escrow,
+        &token_address,
+        refund,
+    );
+    if covered > 0 {
+        update_event_balance(env, payment.event_id.clone(), covered, 0);
+    }
 
-        env.deployer()
-            .update_current_contract_wasm(new_wasm_hash.clone());
+    token_client.transfer(&contract_address, &buyer, &refund);
 
-        #[allow(deprecated)]
-        env.events().publish(
-            (AgoraEvent::ContractUpgraded,),
-            ContractUpgraded {
-                old_wasm_hash,
-                new_wasm_hash,
-            },
-        );
+    // Fees still held for this event are returned to the buyer, as in a refund.
+    return_unsettled_fee(
+        env,
+        &payment.event_id,
+        &token_address,
+        platform_share,
+        get_event_balance(env, payment.event_id.clone())
+            .platform_fee
+            .max(0),
+    );
+    update_event_balance(
+        env,
+        payment.event_id.clone(),
+        -organizer_share,
+        -platform_share,
+    );
+    subtract_from_active_escrow_total(env, refund);
+    subtract_from_active_escrow_by_token(env, token_address, refund);
 
-        // Post-upgrade state verification
-        let mut missing_keys = soroban_sdk::Vec::new(&env);
-        if !env.storage().persistent().has(&DataKey::Admin) {
-            missing_keys.push_back(String::from_str(&env, "Admin"));
-        }
-        if !env.storage().persistent().has(&DataKey::UsdcToken) {
-            missing_keys.push_back(String::from_str(&env, "UsdcToken"));
-        }
-        if !env.storage().persistent().has(&DataKey::PlatformWallet) {
-            missing_keys.push_back(String::from_str(&env, "PlatformWallet"));
-        }
-        if !env.storage().persistent().has(&DataKey::EventRegistry) {
-            missing_keys.push_back(String::from_str(&env, "EventRegistry"));
-        }
+    payment.amount = new_price;
+    payment.platform_fee -= platform_share;
+    payment.organizer_amount -= organizer_share;
+    Ok((0, refund))
+}
 
-        if !missing_keys.is_empty() {
-            for key in missing_keys.iter() {
-                env.events().publish(
-                    (Symbol::new(&env, "ContractVerificationFailed"),),
-                    ContractVerificationFailedEvent {
-                        missing_key: key,
-                        timestamp: env.ledger().timestamp(),
-                    },
-                );
-            }
-        }
+/// Adds `refund_amount` plus the organizer-funded `bonus` to the buyer's store credit
+/// with `organizer`. A later voucher extends the expiry of earlier unexpired credit,
+/// and one without expiry makes the whole balance permanent.
+#[allow(deprecated)]
+fn issue_credit(
+    env: &Env,
+    payment: &Payment,
+    organizer: &Address,
+    refund_amount: i128,
+    bonus: i128,
+    expires_at: u64,
+) -> Result<(), TicketPaymentError> {
+    let guest = &payment.buyer_address;
+    release_expired_credit(env, guest, organizer);
+
+    let credit_amount = refund_amount
+        .checked_add(bonus)
+        .ok_or(TicketPaymentError::ArithmeticError)?;
+    let mut balance = get_credit_balance(env, guest, organizer);
+    balance.expires_at = if balance.amount > 0 && (balance.expires_at == 0 || expires_at == 0) {
+        0
+    } else {
+        balance.expires_at.max(expires_at)
+    };
+    balance.amount = balance
+        .amount
+        .checked_add(credit_amount)
+        .ok_or(TicketPaymentError::ArithmeticError)?;
+    set_credit_balance(env, guest, organizer, &balance);
+
+    env.events().publish(
+        (AgoraEvent::CreditIssued,),
+        CreditIssuedEvent {
+            payment_id: payment.payment_id.clone(),
+            guest: guest.clone(),
+            organizer: organizer.clone(),
+            refund_amount,
+            credit_amount,
+            expires_at: balance.expires_at,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Sends a guest's expired credit with `organizer` to the organizer, who funded it
+/// from their escrow. Returns the amount released.
+#[allow(deprecated)]
+fn release_expired_credit(env: &Env, guest: &Address, organizer: &Address) -> i128 {
+    let balance = get_credit_balance(env, guest, organizer);
+    let now = env.ledger().timestamp();
+    if balance.amount <= 0 || balance.expires_at == 0 || now <= balance.expires_at {
+        return 0;
     }
 
-    /// Proposes a parameter change for the platform. Only callable by a governor.
-    pub fn propose_parameter_change(
-        env: Env,
-        proposer: Address,
-        change: ParameterChange,
-    ) -> Result<u64, TicketPaymentError> {
-        proposer.require_auth();
-        if !is_governor(&env, &proposer) {
-            return Err(TicketPaymentError::NotGovernor);
-        }
-
-        let proposal_id = increment_proposal_count(&env);
-        let proposal = ParameterProposal {
-            id: proposal_id,
-            proposer: proposer.clone(),
-            change: change.clone(),
-            status: ProposalStatus::Pending,
-            created_at: env.ledger().timestamp(),
-            expires_at: env.ledger().timestamp() + 604800, // 7 days
-            vote_count: 1,                                 // proposer automatically votes
-            voters: soroban_sdk::vec![&env, proposer.clone()],
-        };
+    let token_address = get_usdc_token(env);
+    token::Client::new(env, &token_address).transfer(
+        &env.current_contract_address(),
+        organizer,
+        &balance.amount,
+    );
+    subtract_from_active_escrow_total(env, balance.amount);
+    subtract_from_active_escrow_by_token(env, token_address, balance.amount);
+    set_credit_balance(
+        env,
+        guest,
+        organizer,
+        &CreditBalance {
+            amount: 0,
+            expires_at: 0,
+        },
+    );
 
-        set_proposal(&env, &proposal);
+    env.events().publish(
+        (AgoraEvent::CreditReleased,),
+        CreditReleasedEvent {
+            guest: guest.clone(),
+            organizer: organizer.clone(),
+            amount: balance.amount,
+            timestamp: now,
+        },
+    );
+    balance.amount
+}
 
-        env.events().publish(
-            (AgoraEvent::ProposalCreated,),
-            ProposalCreatedEvent {
-                proposal_id,
-                proposer,
-                change,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+/// Moves a ticket's loyalty points from its old tier's multiplier to its new one.
+/// The ticket is reversed and re-awarded once so the guest's ticket count is unchanged,
+/// while `spent_delta` adjusts their total spend. Failures are ignored like other
+/// best-effort loyalty updates.
+fn rebase_loyalty(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    guest: &Address,
+    old_multiplier: u32,
+    new_multiplier: u32,
+    spent_delta: i128,
+) {
+    let caller = env.current_contract_address();
+    let _ = registry_client.try_reverse_loyalty_score(
+        &caller,
+        guest,
+        &1,
+        &(-spent_delta).max(0),
+        &old_multiplier,
+        &MAX_BPS,
+    );
+    let _ = registry_client.try_update_loyalty_score(
+        &caller,
+        guest,
+        &1,
+        &spent_delta.max(0),
+        &new_multiplier,
+    );
+}
 
-        Ok(proposal_id)
+/// Sells add-on items against a ticket in the refund token, splitting their
+/// price under the event's fee like the ticket itself. Returns the amount paid.
+#[allow(deprecated)]
+fn buy_add_on(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    payment: &Payment,
+    fee_bps: u32,
+    addon_id: &String,
+    quantity: u32,
+) -> Result<i128, TicketPaymentError> {
+    if quantity == 0 {
+        return Err(TicketPaymentError::InvalidQuantity);
     }
 
-    /// Votes on an active proposal. Only callable by a governor.
-    pub fn vote_on_proposal(
-        env: Env,
-        voter: Address,
-        proposal_id: u64,
-    ) -> Result<(), TicketPaymentError> {
-        voter.require_auth();
-        if !is_governor(&env, &voter) {
-            return Err(TicketPaymentError::NotGovernor);
+    let add_on = registry_client
+        .try_get_add_on(&payment.event_id, addon_id)
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+        .ok_or(TicketPaymentError::AddOnNotFound)?;
+    if let Some(tier_id) = &add_on.tier_id {
+        if tier_id != &payment.ticket_tier_id {
+            return Err(TicketPaymentError::AddOnNotAvailable);
         }
+    }
+    let add_on = match registry_client.try_reserve_add_on(&payment.event_id, addon_id, &quantity) {
+        Ok(Ok(add_on)) => add_on,
+        _ => return Err(TicketPaymentError::AddOnSoldOut),
+    };
+
+    let amount = add_on
+        .price
+        .checked_mul(quantity as i128)
+        .ok_or(TicketPaymentError::ArithmeticError)?;
+    let platform_fee = amount
+        .checked_mul(fee_bps as i128)
+        .and_then(|v| v.checked_div(MAX_BPS as i128))
+        .ok_or(TicketPaymentError::ArithmeticError)?;
+
+    let token_address = get_usdc_token(env);
+    let token_client = token::Client::new(env, &token_address);
+    let contract_address = env.current_contract_address();
+    if token_client.allowance(&payment.buyer_address, &contract_address) < amount {
+        return Err(TicketPaymentError::InsufficientAllowance);
+    }
+    token_client.transfer_from(
+        &contract_address,
+        &payment.buyer_address,
+        &contract_address,
+        &amount,
+    );
 
-        let mut proposal =
-            get_proposal(&env, proposal_id).ok_or(TicketPaymentError::InvalidProposal)?;
-
-        if proposal.status != ProposalStatus::Pending {
-            return Err(TicketPaymentError::ProposalNotActive);
-        }
+    update_event_balance(
+        env,
+        payment.event_id.clone(),
+        amount - platform_fee,
+        platform_fee,
+    );
+    add_to_total_volume_processed(env, amount);
+    add_to_total_fees_collected_by_token(env, token_address.clone(), platform_fee);
+    add_to_event_fees_by_token(
+        env,
+        payment.event_id.clone(),
+        token_address.clone(),
+        platform_fee,
+    );
+    add_to_active_escrow_total(env, amount);
+    add_to_active_escrow_by_token(env, token_address, amount);
+
+    let mut purchase = get_add_on_purchase(env, payment.payment_id.clone(), addon_id.clone())
+        .unwrap_or(AddOnPurchase {
+            quantity: 0,
+            redeemed: 0,
+            refunded: 0,
+            amount: 0,
+            platform_fee: 0,
+        });
+    purchase.quantity += quantity;
+    purchase.amount += amount;
+    purchase.platform_fee += platform_fee;
+    set_add_on_purchase(env, payment.payment_id.clone(), addon_id.clone(), &purchase);
+
+    env.events().publish(
+        (AgoraEvent::AddOnPurchased,),
+        AddOnPurchasedEvent {
+            payment_id: payment.payment_id.clone(),
+            event_id: payment.event_id.clone(),
+            addon_id: addon_id.clone(),
+            quantity,
+            amount,
+            platform_fee,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
 
-        if env.ledger().timestamp() > proposal.expires_at {
-            return Err(TicketPaymentError::ProposalExpired);
-        }
+    Ok(amount)
+}
 
-        if proposal.voters.contains(&voter) {
-            return Err(TicketPaymentError::AlreadyVoted);
+/// Refunds a ticket's add-on items that were not handed out yet and returns
+/// them to stock. Returns the amount refunded and the organizer and platform
+/// shares it came from; the caller settles those against the event balance.
+#[allow(deprecated)]
+fn refund_add_ons(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    payment: &Payment,
+) -> (i128, i128, i128) {
+    let (mut refunded, mut organizer_share, mut platform_share) = (0i128, 0i128, 0i128);
+    for addon_id in get_payment_add_ons(env, payment.payment_id.clone()).iter() {
+        let Some(mut purchase) =
+            get_add_on_purchase(env, payment.payment_id.clone(), addon_id.clone())
+        else {
+            continue;
+        };
+        let open = purchase.quantity - purchase.redeemed - purchase.refunded;
+        if open == 0 {
+            continue;
         }
+        let amount = purchase.amount * open as i128 / purchase.quantity as i128;
+        let fee = purchase.platform_fee * open as i128 / purchase.quantity as i128;
+        purchase.refunded += open;
+        set_add_on_purchase(env, payment.payment_id.clone(), addon_id.clone(), &purchase);
+        let _ = registry_client.try_release_add_on(&payment.event_id, &addon_id, &open);
 
-        proposal.voters.push_back(voter.clone());
-        proposal.vote_count += 1;
-        set_proposal(&env, &proposal);
+        refunded += amount;
+        organizer_share += amount - fee;
+        platform_share += fee;
 
         env.events().publish(
-            (AgoraEvent::ProposalVoted,),
-            ProposalVotedEvent {
-                proposal_id,
-                voter,
+            (AgoraEvent::AddOnRefunded,),
+            AddOnRefundedEvent {
+                payment_id: payment.payment_id.clone(),
+                event_id: payment.event_id.clone(),
+                addon_id,
+                quantity: open,
+                amount,
                 timestamp: env.ledger().timestamp(),
             },
         );
+    }
 
-        Ok(())
+    if refunded > 0 {
+        token::Client::new(env, &get_usdc_token(env)).transfer(
+            &env.current_contract_address(),
+            &payment.buyer_address,
+            &refunded,
+        );
     }
+    (refunded, organizer_share, platform_share)
+}
 
-    /// Executes a proposal if it has met the voting threshold and time lock.
-    pub fn execute_proposal(
-        env: Env,
-        executor: Address,
-        proposal_id: u64,
-    ) -> Result<(), TicketPaymentError> {
-        executor.require_auth();
-        let mut proposal =
-            get_proposal(&env, proposal_id).ok_or(TicketPaymentError::InvalidProposal)?;
+/// Collects `participant`'s share of an open group order for the ticket
+/// `payment_id`, and issues every ticket once the last share is paid.
+#[allow(deprecated)]
+fn pay_group_share(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    order: &mut GroupOrder,
+    payment_id: String,
+    participant: Address,
+    options: Option<&PurchaseOptions>,
+) -> Result<(), TicketPaymentError> {
+    if order.participants.contains_key(participant.clone()) {
+        return Err(TicketPaymentError::AlreadyInGroupOrder);
+    }
+    if get_payment(env, payment_id.clone()).is_some()
+        || order.participants.values().contains(&payment_id)
+    {
+        return Err(TicketPaymentError::PaymentAlreadyExists);
+    }
 
-        if proposal.status != ProposalStatus::Pending {
-            return Err(TicketPaymentError::ProposalNotActive);
-        }
+    let (allowlist_gate, allowlist_purchased) = check_allowlist(
+        env,
+        registry_client,
+        &order.event_id,
+        &order.tier_id,
+        &participant,
+        1,
+        options,
+    )?;
+    require_attestation(
+        env,
+        registry_client,
+        &order.event_id,
+        &order.tier_id,
+        &participant,
+    )?;
+
+    let token_address = get_usdc_token(env);
+    let token_client = token::Client::new(env, &token_address);
+    let contract_address = env.current_contract_address();
+    if token_client.allowance(&participant, &contract_address) < order.price {
+        return Err(TicketPaymentError::InsufficientAllowance);
+    }
+    token_client.transfer_from(
+        &contract_address,
+        &participant,
+        &contract_address,
+        &order.price,
+    );
+    add_to_active_escrow_total(env, order.price);
+    add_to_active_escrow_by_token(env, token_address, order.price);
+
+    if let Some(gate) = allowlist_gate {
+        set_allowlist_purchases(
+            env,
+            order.event_id.clone(),
+            gate.tier_id,
+            &participant,
+            allowlist_purchased,
+        );
+    }
 
-        let current_time = env.ledger().timestamp();
+    order
+        .participants
+        .set(participant.clone(), payment_id.clone());
+    env.events().publish(
+        (AgoraEventExt::GroupOrderJoined,),
+        GroupOrderJoinedEvent {
+            order_id: order.order_id.clone(),
+            participant,
+            payment_id,
+            amount: order.price,
+            joined: order.participants.len(),
+            tickets: order.tickets,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
 
-        if current_time > proposal.expires_at {
-            return Err(TicketPaymentError::ProposalExpired);
-        }
+    if order.participants.len() == order.tickets {
+        fill_group_order(env, registry_client, order)?;
+    }
+    Ok(())
+}
 
-        // 48 hours = 48 * 60 * 60 = 172800 seconds
-        if current_time < proposal.created_at + 172800 {
-            return Err(TicketPaymentError::VotingPeriodNotMet);
+/// Issues every participant of a full group order their ticket as a `Pending`
+/// payment and books the shares held in escrow to the event.
+#[allow(deprecated)]
+fn fill_group_order(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    order: &mut GroupOrder,
+) -> Result<(), TicketPaymentError> {
+    let platform_fee = order
+        .price
+        .checked_mul(order.fee_bps as i128)
+        .and_then(|v| v.checked_div(MAX_BPS as i128))
+        .ok_or(TicketPaymentError::ArithmeticError)?;
+    let organizer_amount = order.price - platform_fee;
+    let multiplier = registry_client
+        .try_get_event(&order.event_id)
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+        .and_then(|e| e.tiers.get(order.tier_id.clone()))
+        .map(|t| t.loyalty_multiplier)
+        .unwrap_or(1);
+
+    // A payment ID taken since its participant joined would be overwritten.
+    for payment_id in order.participants.values().iter() {
+        if get_payment(env, payment_id).is_some() {
+            return Err(TicketPaymentError::PaymentAlreadyExists);
         }
+    }
+
+    let now = env.ledger().timestamp();
+    for (participant, payment_id) in order.participants.iter() {
+        store_payment(
+            env,
+            Payment {
+                payment_id,
+                event_id: order.event_id.clone(),
+                buyer_address: participant.clone(),
+                ticket_tier_id: order.tier_id.clone(),
+                token: get_usdc_token(env),
+                amount: order.price,
+                platform_fee,
+                organizer_amount,
+                status: PaymentStatus::Pending,
+                transaction_hash: String::from_str(env, ""),
+                created_at: now,
+                confirmed_at: None,
+                refunded_amount: 0,
+            },
+        );
+        // Loyalty points are best-effort, as for single purchases.
+        let _ = registry_client.try_update_loyalty_score(
+            &env.current_contract_address(),
+            &participant,
+            &1,
+            &order.price,
+            &multiplier,
+        );
+    }
+
+    let tickets = order.tickets as i128;
+    let total = order.price * tickets;
+    update_event_balance(
+        env,
+        order.event_id.clone(),
+        organizer_amount * tickets,
+        platform_fee * tickets,
+    );
+    add_to_total_volume_processed(env, total);
+    let token_address = get_usdc_token(env);
+    add_to_total_fees_collected_by_token(env, token_address.clone(), platform_fee * tickets);
+    add_to_event_fees_by_token(
+        env,
+        order.event_id.clone(),
+        token_address,
+        platform_fee * tickets,
+    );
+
+    order.status = GroupOrderStatus::Filled;
+    env.events().publish(
+        (AgoraEventExt::GroupOrderFilled,),
+        GroupOrderFilledEvent {
+            order_id: order.order_id.clone(),
+            event_id: order.event_id.clone(),
+            tickets: order.tickets,
+            total,
+            timestamp: now,
+        },
+    );
+    Ok(())
+}
+
+/// Collects the next installment of `payment` in the refund token and splits it
+/// under the fee fixed at purchase. The last installment takes the rounding remainder.
+#[allow(deprecated)]
+fn collect_installment(
+    env: &Env,
+    payment: &mut Payment,
+    schedule: &mut InstallmentSchedule,
+) -> Result<i128, TicketPaymentError> {
+    let amount = if schedule.installments_paid + 1 == schedule.installments {
+        schedule.total - schedule.paid
+    } else {
+        schedule.total / schedule.installments as i128
+    };
+    let platform_fee = amount
+        .checked_mul(schedule.fee_bps as i128)
+        .and_then(|v| v.checked_div(MAX_BPS as i128))
+        .ok_or(TicketPaymentError::ArithmeticError)?;
+
+    let token_address = get_usdc_token(env);
+    let token_client = token::Client::new(env, &token_address);
+    let contract_address = env.current_contract_address();
+    if token_client.allowance(&payment.buyer_address, &contract_address) < amount {
+        return Err(TicketPaymentError::InsufficientAllowance);
+    }
+    token_client.transfer_from(
+        &contract_address,
+        &payment.buyer_address,
+        &contract_address,
+        &amount,
+    );
+
+    update_event_balance(
+        env,
+        payment.event_id.clone(),
+        amount - platform_fee,
+        platform_fee,
+    );
+    add_to_total_volume_processed(env, amount);
+    add_to_total_fees_collected_by_token(env, token_address.clone(), platform_fee);
+    add_to_event_fees_by_token(
+        env,
+        payment.event_id.clone(),
+        token_address.clone(),
+        platform_fee,
+    );
+    add_to_active_escrow_total(env, amount);
+    add_to_active_escrow_by_token(env, token_address, amount);
+
+    payment.amount += amount;
+    payment.platform_fee += platform_fee;
+    payment.organizer_amount += amount - platform_fee;
+    schedule.paid += amount;
+    schedule.installments_paid += 1;
+
+    env.events().publish(
+        (AgoraEventExt::InstallmentPaid,),
+        InstallmentPaidEvent {
+            payment_id: payment.payment_id.clone(),
+            event_id: payment.event_id.clone(),
+            buyer_address: payment.buyer_address.clone(),
+            amount,
+            installments_paid: schedule.installments_paid,
+            installments: schedule.installments,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(amount)
+}
+
+/// Returns a ticket's RSVP deposit, if one is still held, to its current holder.
+#[allow(deprecated)]
+fn return_deposit(env: &Env, payment: &Payment) -> i128 {
+    let Some(deposit) = get_held_deposit(env, payment.payment_id.clone()) else {
+        return 0;
+    };
+    let token_address = get_usdc_token(env);
+    token::Client::new(env, &token_address).transfer(
+        &env.current_contract_address(),
+        &payment.buyer_address,
+        &deposit.amount,
+    );
+    remove_held_deposit(env, payment.payment_id.clone());
+    subtract_from_active_escrow_total(env, deposit.amount);
+    subtract_from_active_escrow_by_token(env, token_address, deposit.amount);
+
+    env.events().publish(
+        (AgoraEvent::DepositReturned,),
+        DepositReturnedEvent {
+            payment_id: payment.payment_id.clone(),
+            guest: payment.buyer_address.clone(),
+            amount: deposit.amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    deposit.amount
+}
+
+/// Draws on the registry's guest protection pool when `balance` cannot cover a refund
+/// of `refund_amount`. The drawn funds are credited to the event's organizer share and
+/// to active escrow so the refund nets out against them. Returns the amount covered.
+#[allow(deprecated)]
+fn cover_refund_from_pool(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    event_id: &String,
+    balance: &mut EventBalance,
+    token: &Address,
+    refund_amount: i128,
+) -> i128 {
+    let available = (balance.organizer_amount + balance.platform_fee).max(0);
+    let shortfall = refund_amount - available;
+    if shortfall <= 0 {
+        return 0;
+    }
+
+    let covered = match registry_client.try_cover_refund_shortfall(
+        &env.current_contract_address(),
+        token,
+        &shortfall,
+    ) {
+        Ok(Ok(amount)) if amount > 0 => amount,
+        _ => return 0,
+    };
+
+    balance.organizer_amount += covered;
+    add_to_active_escrow_total(env, covered);
+    add_to_active_escrow_by_token(env, token.clone(), covered);
+
+    env.events().publish(
+        (AgoraEvent::ProtectionPoolDrawn,),
+        ProtectionPoolDrawnEvent {
+            event_id: event_id.clone(),
+            token: token.clone(),
+            shortfall,
+            amount: covered,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
 
-        let total_governors = get_total_governors(&env);
-        // Simple majority: > 50%
-        let threshold = (total_governors / 2) + 1;
-        if proposal.vote_count < threshold {
-            return Err(TicketPaymentError::InsufficientVotes);
+    covered
+}
+
+/// Hash of an archived payment leaf: SHA-256 of its XDR encoding.
+fn archived_payment_hash(env: &Env, leaf: &ArchivedPayment) -> BytesN<32> {
+    env.crypto().sha256(&leaf.clone().to_xdr(env)).into()
+}
+
+/// Hashes two tree nodes in sorted order, so proofs need no left/right flags.
+fn merkle_parent(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a.to_array() <= b.to_array() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let mut preimage = Bytes::from_array(env, &first.to_array());
+    preimage.extend_from_array(&second.to_array());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Invite-only tiers: the buyer proves an allowlist entry that still covers
+/// `quantity` more tickets. Returns the gate, if any, and the buyer's purchases
+/// against it including these. Registries without allowlists leave sales open.
+fn check_allowlist(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    event_id: &String,
+    tier_id: &String,
+    buyer: &Address,
+    quantity: u32,
+    options: Option<&PurchaseOptions>,
+) -> Result<(Option<event_registry::AllowlistGate>, u32), TicketPaymentError> {
+    let allowlist_gate = registry_client
+        .try_get_purchase_allowlist(event_id, tier_id)
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten();
+    let allowlist_purchased = if let Some(gate) = &allowlist_gate {
+        let opts = options.ok_or(TicketPaymentError::NotOnAllowlist)?;
+        if !verify_allowlist_proof(
+            env,
+            &gate.root,
+            buyer,
+            opts.allowlist_max_quantity,
+            &opts.allowlist_proof,
+        ) {
+            return Err(TicketPaymentError::NotOnAllowlist);
         }
+        let purchased = get_allowlist_purchases(env, event_id.clone(), gate.tier_id.clone(), buyer)
+            .checked_add(quantity)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        if purchased > opts.allowlist_max_quantity {
+            return Err(TicketPaymentError::AllowlistAllowanceExceeded);
+        }
+        purchased
+    } else {
+        0
+    };
+    Ok((allowlist_gate, allowlist_purchased))
+}
 
-        // Execute the change
-        match &proposal.change {
-            ParameterChange::AddGovernor(new_governor) => {
-                if !is_governor(&env, new_governor) {
-                    set_governor(&env, new_governor, true);
-                    set_total_governors(&env, total_governors + 1);
-                }
-            }
-            ParameterChange::RemoveGovernor(old_governor) => {
-                if total_governors <= 1 {
-                    return Err(TicketPaymentError::CannotRemoveLastGovernor);
-                }
-                if is_governor(&env, old_governor) {
-                    set_governor(&env, old_governor, false);
-                    set_total_governors(&env, total_governors - 1);
-                }
-            }
-            ParameterChange::AddTokenToWhitelist(token) => {
-                add_token_to_whitelist(&env, token);
-            }
-            ParameterChange::RemoveTokenFromWhitelist(token) => {
-                remove_token_from_whitelist(&env, token);
-            }
-            ParameterChange::UpdateWithdrawalCap(token, cap) => {
-                set_withdrawal_cap(&env, token.clone(), *cap);
-            }
-            ParameterChange::UpdateSlippage(bps) => {
-                set_slippage_bps(&env, *bps)?;
-            }
-            ParameterChange::UpdateTransferFee(event_id, fee) => {
-                set_transfer_fee(&env, event_id.clone(), *fee);
+/// Checks that `buyer`'s allowlist entry, with the claimed `max_quantity`, is a
+/// leaf of the tree under `root`.
+fn verify_allowlist_proof(
+    env: &Env,
+    root: &BytesN<32>,
+    buyer: &Address,
+    max_quantity: u32,
+    proof: &Vec<BytesN<32>>,
+) -> bool {
+    let entry = AllowlistEntry {
+        buyer: buyer.clone(),
+        max_quantity,
+    };
+    let mut node: BytesN<32> = env.crypto().sha256(&entry.to_xdr(env)).into();
+    for sibling in proof.iter() {
+        node = merkle_parent(env, &node, &sibling);
+    }
+    &node == root
+}
+
+/// Fails with `AttestationMissing` unless `subject` holds the claim a tier's
+/// attester requires. Tiers without a requirement, and registries that predate
+/// attestations, are unrestricted; an attester that errors counts as a refusal.
+fn require_attestation(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    event_id: &String,
+    tier_id: &String,
+    subject: &Address,
+) -> Result<(), TicketPaymentError> {
+    let Some(requirement) = registry_client
+        .try_get_tier_attestation(event_id, tier_id)
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+    else {
+        return Ok(());
+    };
+    match attester::AttesterClient::new(env, &requirement.attester)
+        .try_has_valid_claim(subject, &requirement.claim)
+    {
+        Ok(Ok(true)) => Ok(()),
+        _ => Err(TicketPaymentError::AttestationMissing),
+    }
+}
+
+/// Builds a Merkle root over `leaves` by hashing adjacent pairs level by level.
+/// An unpaired last node moves up unchanged; no leaves yield an all-zero root.
+fn merkle_root(env: &Env, leaves: &Vec<BytesN<32>>) -> BytesN<32> {
+    if leaves.is_empty() {
+        return BytesN::from_array(env, &[0u8; 32]);
+    }
+    let mut level = leaves.clone();
+    while level.len() > 1 {
+        let mut next = Vec::new(env);
+        let mut i = 0;
+        while i < level.len() {
+            let left = level.get(i).unwrap();
+            match level.get(i + 1) {
+                Some(right) => next.push_back(merkle_parent(env, &left, &right)),
+                None => next.push_back(left),
             }
+            i += 2;
+        }
+        level = next;
+    }
+    level.get(0).unwrap()
+}
+
+#[contract]
+pub struct TicketPaymentContract;
+
+#[contractimpl]
+#[allow(deprecated)]
+impl TicketPaymentContract {
+    /// Initializes the contract with necessary configurations.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        usdc_token: Address,
+        platform_wallet: Address,
+        event_registry: Address,
+    ) -> Result<(), TicketPaymentError> {
+        if is_initialized(&env) {
+            return Err(TicketPaymentError::AlreadyInitialized);
         }
 
-        proposal.status = ProposalStatus::Executed;
-        set_proposal(&env, &proposal);
+        validate_address(&env, &admin)?;
+        validate_address(&env, &usdc_token)?;
+        validate_address(&env, &platform_wallet)?;
+        validate_address(&env, &event_registry)?;
+
+        set_admin(&env, &admin);
+        set_usdc_token(&env, usdc_token.clone());
+        set_platform_wallet(&env, platform_wallet.clone());
+        set_event_registry(&env, event_registry.clone());
+        set_schema_version(&env, SCHEMA_VERSION);
+        set_initialized(&env, true);
+
+        // Whitelist USDC by default
+        add_token_to_whitelist(&env, &usdc_token);
 
+        #[allow(deprecated)]
         env.events().publish(
-            (AgoraEvent::GovernanceActionExecuted,),
-            GovernanceActionExecutedEvent {
-                proposal_id,
-                change: proposal.change.clone(),
-                timestamp: current_time,
+            (AgoraEvent::ContractInitialized,),
+            InitializationEvent {
+                usdc_token,
+                platform_wallet,
+                event_registry,
             },
         );
 
         Ok(())
     }
 
-    pub fn is_token_allowed(env: Env, token: Address) -> bool {
-        is_token_whitelisted(&env, &token)
-    }
-
-    /// Sets the oracle contract address. Only callable by admin.
-    pub fn set_oracle(env: Env, oracle_address: Address) -> Result<(), TicketPaymentError> {
+    /// Pauses or resumes the contract. Only callable by the multi-sig admin.
+    /// Upgrade and emergency-withdrawal remain available while the contract is paused.
+    /// Admin-only operations are likewise exempt from pause scopes.
+    pub fn set_pause(env: Env, paused: bool) -> Result<(), TicketPaymentError> {
         require_admin(&env)?;
-        set_oracle_address(&env, &oracle_address);
+        set_is_paused(&env, paused);
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::ContractPaused,),
+            ContractPausedEvent {
+                paused,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
         Ok(())
     }
 
-    /// Returns the current oracle price for an asset.
-    pub fn get_asset_price(
+    /// Pauses or resumes one category of entrypoints without touching the others.
+    pub fn set_pause_scope(
         env: Env,
-        asset: Address,
-    ) -> Result<price_oracle::PriceData, TicketPaymentError> {
-        fetch_fresh_asset_price(&env, &asset)
-    }
+        scope: PauseScope,
+        paused: bool,
+    ) -> Result<(), TicketPaymentError> {
+        require_admin(&env)?;
 
-    /// Returns the current slippage tolerance in basis points.
-    pub fn get_slippage(env: Env) -> u32 {
-        get_slippage_bps(&env)
+        let mut scopes = get_paused_scopes(&env);
+        let index = scopes.iter().position(|s| s == scope);
+        match (index, paused) {
+            (None, true) => scopes.push_back(scope.clone()),
+            (Some(i), false) => {
+                scopes.remove(i as u32);
+            }
+            _ => {}
+        }
+        set_paused_scopes(&env, &scopes);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::PauseScopeChanged,),
+            PauseScopeChangedEvent {
+                target: PauseTarget::Scope(scope),
+                paused,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
     }
 
-    /// Processes a payment for an event ticket.
-    #[allow(clippy::too_many_arguments)]
-    pub fn process_payment(
+    /// Pauses or resumes every scoped entrypoint for a single event.
+    pub fn set_event_pause(
         env: Env,
-        payment_id: String,
         event_id: String,
-        ticket_tier_id: String,
-        buyer_address: Address,
-        token_address: Address,
-        amount: i128, // price for ONE ticket
-        quantity: u32,
-        code_preimage: Option<Bytes>,
-        referrer: Option<Address>,
-    ) -> Result<String, TicketPaymentError> {
-        if !is_initialized(&env) {
-            panic!("Contract not initialized");
-        }
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
-        }
-        buyer_address.require_auth();
+        paused: bool,
+    ) -> Result<(), TicketPaymentError> {
+        require_admin(&env)?;
 
-        if let Some(ref ref_addr) = referrer {
-            if ref_addr == &buyer_address {
-                return Err(TicketPaymentError::SelfReferralNotAllowed);
+        let mut event_ids = get_paused_events(&env);
+        let index = event_ids.iter().position(|id| id == event_id);
+        match (index, paused) {
+            (None, true) => event_ids.push_back(event_id.clone()),
+            (Some(i), false) => {
+                event_ids.remove(i as u32);
             }
+            _ => {}
         }
+        set_paused_events(&env, &event_ids);
 
-        if amount <= 0 {
-            panic!("Amount must be positive");
-        }
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::PauseScopeChanged,),
+            PauseScopeChangedEvent {
+                target: PauseTarget::Event(event_id),
+                paused,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
 
-        if quantity == 0 {
-            panic!("Quantity must be positive");
+    /// Returns the global flag together with all paused scopes and events.
+    pub fn get_pause_state(env: Env) -> PauseState {
+        PauseState {
+            global: is_paused(&env),
+            scopes: get_paused_scopes(&env),
+            event_ids: get_paused_events(&env),
         }
+    }
 
-        if !is_token_whitelisted(&env, &token_address) {
-            return Err(TicketPaymentError::TokenNotWhitelisted);
-        }
+    /// Returns true if `scope` is blocked, either globally, by scope or for `event_id`.
+    pub fn is_scope_paused(env: Env, scope: PauseScope, event_id: Option<String>) -> bool {
+        require_not_paused(&env, scope, event_id.as_ref()).is_err()
+    }
 
-        let total_amount = amount
-            .checked_mul(quantity as i128)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
+    /// Returns the current paused state of the contract.
+    pub fn get_is_paused(env: Env) -> bool {
+        is_paused(&env)
+    }
 
-        // Apply platform-wide global promo if active (self-expiring via timestamp check)
-        let event_registry_addr_promo = get_event_registry(&env);
-        let registry_client_promo = event_registry::Client::new(&env, &event_registry_addr_promo);
-        let global_promo_bps = registry_client_promo.get_global_promo_bps();
-        let promo_expiry = registry_client_promo.get_promo_expiry();
-        let current_ts = env.ledger().timestamp();
+    /// Returns true if the contract is currently paused.
+    pub fn is_paused(env: Env) -> bool {
+        is_paused(&env)
+    }
 
-        let (after_promo, promo_applied_bps) = if global_promo_bps > 0 && current_ts < promo_expiry
-        {
-            let discounted = total_amount
-                .checked_mul((MAX_BPS - global_promo_bps) as i128)
-                .and_then(|v| v.checked_div(MAX_BPS as i128))
-                .ok_or(TicketPaymentError::ArithmeticError)?;
-            (discounted, global_promo_bps)
-        } else {
-            (total_amount, 0u32)
-        };
-
-        // Optionally apply a discount code (10% off) on top of the promo price
-        let (effective_total, discount_code_hash) = if let Some(preimage) = code_preimage {
-            let hash: soroban_sdk::BytesN<32> = env.crypto().sha256(&preimage).into();
-            if !is_discount_hash_valid(&env, &hash) {
-                return Err(TicketPaymentError::InvalidDiscountCode);
-            }
-            if is_discount_hash_used(&env, &hash) {
-                return Err(TicketPaymentError::DiscountCodeUsed);
-            }
-            // 10% discount
-            let discounted = after_promo
-                .checked_mul(90)
-                .and_then(|v| v.checked_div(100))
-                .ok_or(TicketPaymentError::ArithmeticError)?;
-            (discounted, Some(hash))
-        } else {
-            (after_promo, None)
-        };
-        // 1. Query Event Registry for event info and check inventory
-        let event_registry_addr = get_event_registry(&env);
-        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+    /// Sets or clears a dispute for an event.
+    pub fn set_event_dispute(
+        env: Env,
+        event_id: String,
+        disputed: bool,
+    ) -> Result<(), TicketPaymentError> {
+        require_admin(&env)?;
 
-        let event_info = match registry_client.try_get_event(&event_id) {
-            Ok(Ok(Some(info))) => info,
-            Ok(Ok(None)) => return Err(TicketPaymentError::EventNotFound),
-            _ => return Err(TicketPaymentError::EventNotFound),
-        };
+        set_event_dispute_status(&env, event_id.clone(), disputed);
 
-        if !event_info.is_active
-            || matches!(event_info.status, event_registry::EventStatus::Cancelled)
-        {
-            return Err(TicketPaymentError::EventInactive);
-        }
+        env.events().publish(
+            (AgoraEvent::DisputeStatusChanged,),
+            DisputeStatusChangedEvent {
+                event_id,
+                is_disputed: disputed,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-        let tier = event_info
-            .tiers
-            .get(ticket_tier_id.clone())
-            .ok_or(TicketPaymentError::TierNotFound)?;
+        Ok(())
+    }
 
-        let current_time = env.ledger().timestamp();
+    /// Returns if an event is currently disputed.
+    pub fn is_event_disputed(env: Env, event_id: String) -> bool {
+        is_event_disputed(&env, event_id)
+    }
 
-        if tier.usd_price > 0 {
-            // ── Oracle-based USD pricing ──────────────────────────────────
-            let price_data = fetch_fresh_asset_price(&env, &token_address)?;
+    /// Upgrades the contract to a new WASM hash. Only callable by the admin.
+    ///
+    /// The hash must have been scheduled by an executed `UpgradeContract` proposal and
+    /// `UPGRADE_TIMELOCK_SECS` must have passed since. Run `migrate` afterwards if the
+    /// new WASM bumps the schema version.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TicketPaymentError> {
+        require_admin(&env)?;
 
-            // expected = usd_price * oracle_price / 1_0000000
-            let expected = tier
-                .usd_price
-                .checked_mul(price_data.price)
-                .and_then(|v| v.checked_div(1_0000000))
-                .ok_or(TicketPaymentError::ArithmeticError)?;
+        let pending = get_pending_upgrade(&env).ok_or(TicketPaymentError::UpgradeNotScheduled)?;
+        if pending.wasm_hash != new_wasm_hash {
+            return Err(TicketPaymentError::UpgradeNotScheduled);
+        }
+        if env.ledger().timestamp() < pending.eta {
+            return Err(TicketPaymentError::UpgradeTimelockActive);
+        }
+        remove_pending_upgrade(&env);
 
-            let bps = get_slippage_bps(&env) as i128;
-            let min_amount = expected
-                .checked_mul(MAX_BPS as i128 - bps)
-                .and_then(|v| v.checked_div(MAX_BPS as i128))
-                .ok_or(TicketPaymentError::ArithmeticError)?;
-            let max_amount = expected
-                .checked_mul(MAX_BPS as i128 + bps)
-                .and_then(|v| v.checked_div(MAX_BPS as i128))
-                .ok_or(TicketPaymentError::ArithmeticError)?;
+        let old_wasm_hash = match env.current_contract_address().executable() {
+            Some(soroban_sdk::Executable::Wasm(hash)) => hash,
+            _ => panic!("Current contract is not a Wasm contract"),
+        };
 
-            if amount < min_amount || amount > max_amount {
-                return Err(TicketPaymentError::PriceOutsideSlippage);
-            }
-        } else {
-            // ── Exact token-price matching (existing behaviour) ───────────
-            let mut active_price = tier.price;
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
 
-            if tier.early_bird_deadline > 0 && current_time <= tier.early_bird_deadline {
-                active_price = tier.early_bird_price;
-            }
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::ContractUpgraded,),
+            ContractUpgraded {
+                old_wasm_hash,
+                new_wasm_hash,
+            },
+        );
 
-            if amount != active_price {
-                return Err(TicketPaymentError::InvalidPrice);
-            }
+        // Post-upgrade state verification
+        let mut missing_keys = soroban_sdk::Vec::new(&env);
+        if !env.storage().persistent().has(&DataKey::Admin) {
+            missing_keys.push_back(String::from_str(&env, "Admin"));
         }
-
-        // Check if we just transitioned from early bird to standard
-        if tier.early_bird_deadline > 0
-            && current_time > tier.early_bird_deadline
-            && !has_price_switched(&env, event_id.clone(), ticket_tier_id.clone())
-        {
-            set_price_switched(&env, event_id.clone(), ticket_tier_id.clone());
-            #[allow(deprecated)]
-            env.events().publish(
-                (AgoraEvent::PriceSwitched,),
-                PriceSwitchedEvent {
-                    event_id: event_id.clone(),
-                    tier_id: ticket_tier_id.clone(),
-                    new_price: tier.price,
-                    timestamp: current_time,
-                },
-            );
+        if !env.storage().persistent().has(&DataKey::UsdcToken) {
+            missing_keys.push_back(String::from_str(&env, "UsdcToken"));
+        }
+        if !env.storage().persistent().has(&DataKey::PlatformWallet) {
+            missing_keys.push_back(String::from_str(&env, "PlatformWallet"));
+        }
+        if !env.storage().persistent().has(&DataKey::EventRegistry) {
+            missing_keys.push_back(String::from_str(&env, "EventRegistry"));
         }
 
-        // 2. Calculate platform fee
-        let fee_bps = event_info
-            .custom_fee_bps
-            .unwrap_or(event_info.platform_fee_percent);
+        if !missing_keys.is_empty() {
+            for key in missing_keys.iter() {
+                env.events().publish(
+                    (Symbol::new(&env, "ContractVerificationFailed"),),
+                    ContractVerificationFailedEvent {
+                        missing_key: key,
+                        timestamp: env.ledger().timestamp(),
+                    },
+                );
+            }
+        }
 
-        let mut total_platform_fee = effective_total
-            .checked_mul(fee_bps as i128)
-            .and_then(|v| v.checked_div(MAX_BPS as i128))
-            .ok_or(TicketPaymentError::ArithmeticError)?;
+        Ok(())
+    }
 
-        // Apply loyalty discount: reduce the platform fee for guests with high scores.
-        // Uses try_ variant so that contracts without loyalty support are unaffected.
-        let loyalty_discount_bps: u32 = registry_client_promo
-            .try_get_loyalty_discount_bps(&buyer_address)
-            .ok()
-            .and_then(|r| r.ok())
-            .unwrap_or(0);
+    /// Cancels the scheduled upgrade. Only callable by the admin.
+    pub fn cancel_upgrade(env: Env) -> Result<(), TicketPaymentError> {
+        require_admin(&env)?;
 
-        let loyalty_discount_amount = if loyalty_discount_bps > 0 {
-            core::cmp::min(
-                total_platform_fee
-                    .checked_mul(loyalty_discount_bps as i128)
-                    .and_then(|v| v.checked_div(MAX_BPS as i128))
-                    .ok_or(TicketPaymentError::ArithmeticError)?,
-                total_platform_fee,
-            )
-        } else {
-            0
-        };
-        total_platform_fee = total_platform_fee
-            .checked_sub(loyalty_discount_amount)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
+        let pending = get_pending_upgrade(&env).ok_or(TicketPaymentError::UpgradeNotScheduled)?;
+        remove_pending_upgrade(&env);
 
-        // Adjust effective_total to reflect the loyalty discount (guest pays less)
-        let effective_total = effective_total
-            .checked_sub(loyalty_discount_amount)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::UpgradeCancelled,),
+            UpgradeCancelledEvent {
+                wasm_hash: pending.wasm_hash,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-        let total_organizer_amount = effective_total
-            .checked_sub(total_platform_fee)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
+        Ok(())
+    }
 
-        let referral_reward = if referrer.is_some() {
-            let reward = total_platform_fee
-                .checked_mul(20)
-                .and_then(|v| v.checked_div(100))
-                .ok_or(TicketPaymentError::ArithmeticError)?; // 20%
-                                                              // Cap: referral reward must never exceed the remaining platform fee.
-            let reward = core::cmp::min(reward, total_platform_fee);
-            total_platform_fee = total_platform_fee
-                .checked_sub(reward)
-                .ok_or(TicketPaymentError::ArithmeticError)?;
-            reward
-        } else {
-            0
-        };
+    /// Returns the upgrade awaiting its timelock, if any.
+    pub fn get_pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        get_pending_upgrade(&env)
+    }
 
-        // 3. Transfer tokens to contract (escrow)
-        let token_client = token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
+    /// Migrates stored records to the schema version of the running WASM.
+    ///
+    /// Runs every pending step once, rewriting the given `payment_ids` from their old
+    /// layout, and returns the new schema version. Fails with `AlreadyMigrated` when
+    /// storage is already current.
+    pub fn migrate(env: Env, payment_ids: Vec<String>) -> Result<u32, TicketPaymentError> {
+        require_admin(&env)?;
 
-        // Verify allowance
-        let allowance = token_client.allowance(&buyer_address, &contract_address);
-        if allowance < effective_total {
-            return Err(TicketPaymentError::InsufficientAllowance);
+        let from_version = get_schema_version(&env);
+        if from_version >= SCHEMA_VERSION {
+            return Err(TicketPaymentError::AlreadyMigrated);
         }
 
-        // Get balance before transfer
-        let balance_before = token_client.balance(&contract_address);
+        let mut records_migrated = 0u32;
+        for version in from_version..SCHEMA_VERSION {
+            records_migrated += migrate_step(&env, version, &payment_ids);
+        }
+        set_schema_version(&env, SCHEMA_VERSION);
 
-        // Transfer full amount to contract
-        token_client.transfer_from(
-            &contract_address,
-            &buyer_address,
-            &contract_address,
-            &effective_total,
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::ContractMigrated,),
+            ContractMigratedEvent {
+                from_version,
+                to_version: SCHEMA_VERSION,
+                records_migrated,
+                timestamp: env.ledger().timestamp(),
+            },
         );
 
-        // Verify balance after transfer
-        let balance_after = token_client.balance(&contract_address);
-        if balance_after
-            .checked_sub(balance_before)
-            .ok_or(TicketPaymentError::ArithmeticError)?
-            != effective_total
-        {
-            return Err(TicketPaymentError::TransferVerificationFailed);
-        }
+        Ok(SCHEMA_VERSION)
+    }
 
-        // Transfer referral reward if applicable
-        if let Some(ref ref_addr) = referrer {
-            if referral_reward > 0 {
-                token_client.transfer(&contract_address, ref_addr, &referral_reward);
-            }
+    /// Returns the layout version of the stored records.
+    pub fn get_schema_version(env: Env) -> u32 {
+        get_schema_version(&env)
+    }
+
+    /// Applies a parameter change approved through the event registry's multisig
+    /// proposal queue. Only the configured event registry may call this, so
+    /// ticket_payment and the registry share one admin set and one proposal queue.
+    pub fn apply_governance_change(
+        env: Env,
+        proposal_id: u64,
+        change: ParameterChange,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            return Err(TicketPaymentError::NotInitialized);
         }
+        get_event_registry(&env).require_auth();
 
-        // 4. Update escrow balances
-        update_event_balance(
+        let current_time = env.ledger().timestamp();
+
+        match &change {
+            ParameterChange::AddTokenToWhitelist(token) => {
+                add_token_to_whitelist(&env, token);
+            }
+            ParameterChange::RemoveTokenFromWhitelist(token) => {
+                remove_token_from_whitelist(&env, token);
+            }
+            ParameterChange::UpdateWithdrawalCap(token, cap) => {
+                if *cap < 0 {
+                    return Err(TicketPaymentError::ArithmeticError);
+                }
+                set_withdrawal_cap(&env, token.clone(), *cap);
+            }
+            ParameterChange::UpdateSlippage(bps) => {
+                set_slippage_bps(&env, *bps)?;
+            }
+            ParameterChange::UpdateTransferFee(event_id, fee) => {
+                set_transfer_fee(&env, event_id.clone(), *fee);
+            }
+            ParameterChange::SetTreasuryWallets(wallets) => {
+                validate_treasury_wallets(&env, wallets)?;
+                set_treasury_wallets(&env, wallets);
+                env.events().publish(
+                    (AgoraEvent::TreasuryWalletsUpdated,),
+                    TreasuryWalletsUpdatedEvent {
+                        wallets: wallets.clone(),
+                        timestamp: current_time,
+                    },
+                );
+            }
+            ParameterChange::SetWithdrawalLimitConfig(config) => {
+                if config.window_secs == 0 {
+                    return Err(TicketPaymentError::InvalidWithdrawalLimit);
+                }
+                set_withdrawal_limit_config(&env, config);
+            }
+            ParameterChange::SetOrganizerCapPolicy(token, policy) => {
+                if policy.default_cap < 0 || policy.verified_cap < 0 {
+                    return Err(TicketPaymentError::InvalidWithdrawalLimit);
+                }
+                set_organizer_cap_policy(&env, token.clone(), policy);
+            }
+            ParameterChange::SetOrganizerWithdrawalCap(organizer, token, cap) => match cap {
+                Some(amount) if *amount < 0 => {
+                    return Err(TicketPaymentError::InvalidWithdrawalLimit);
+                }
+                Some(amount) => {
+                    set_organizer_withdrawal_cap(&env, organizer.clone(), token.clone(), *amount)
+                }
+                None => remove_organizer_withdrawal_cap(&env, organizer.clone(), token.clone()),
+            },
+            ParameterChange::UpgradeContract(wasm_hash) => {
+                let eta = current_time + UPGRADE_TIMELOCK_SECS;
+                set_pending_upgrade(
+                    &env,
+                    &PendingUpgrade {
+                        wasm_hash: wasm_hash.clone(),
+                        eta,
+                    },
+                );
+                env.events().publish(
+                    (AgoraEvent::UpgradeScheduled,),
+                    UpgradeScheduledEvent {
+                        proposal_id,
+                        wasm_hash: wasm_hash.clone(),
+                        eta,
+                    },
+                );
+            }
+        }
+
+        env.events().publish(
+            (AgoraEvent::GovernanceActionExecuted,),
+            GovernanceActionExecutedEvent {
+                proposal_id,
+                change,
+                timestamp: current_time,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Compacts an archived event's payment records into a Merkle root. Every
+    /// record is emitted as a `PaymentArchived` leaf in tree order, so attendees can
+    /// later build proofs against the root, and is then deleted. Only callable by the
+    /// event registry while archiving the event, once its escrow has been settled.
+    pub fn archive_event_payments(
+        env: Env,
+        event_id: String,
+    ) -> Result<PaymentsCommitment, TicketPaymentError> {
+        if !is_initialized(&env) {
+            return Err(TicketPaymentError::NotInitialized);
+        }
+        get_event_registry(&env).require_auth();
+
+        let balance = get_event_balance(&env, event_id.clone());
+        if balance.organizer_amount > 0 || balance.platform_fee > 0 {
+            return Err(TicketPaymentError::EventNotSettled);
+        }
+
+        let payment_ids = get_event_payments(&env, event_id.clone());
+        let mut leaves = Vec::new(&env);
+        for payment_id in payment_ids.iter() {
+            let Some(payment) = get_payment(&env, payment_id) else {
+                continue;
+            };
+            let leaf = ArchivedPayment {
+                payment_id: payment.payment_id,
+                buyer_address: payment.buyer_address,
+                ticket_tier_id: payment.ticket_tier_id,
+                amount: payment.amount,
+                refunded_amount: payment.refunded_amount,
+                status: payment.status,
+            };
+            leaves.push_back(archived_payment_hash(&env, &leaf));
+            env.events().publish(
+                (AgoraEvent::PaymentArchived,),
+                PaymentArchivedEvent {
+                    event_id: event_id.clone(),
+                    index: leaves.len() - 1,
+                    leaf,
+                },
+            );
+        }
+        remove_event_payments(&env, event_id, &payment_ids);
+
+        Ok(PaymentsCommitment {
+            root: merkle_root(&env, &leaves),
+            payment_count: leaves.len(),
+        })
+    }
+
+    /// Extends the TTL of an event's balance, settings, payment records and their
+    /// indices, along with contract-wide settings. Anyone may call this; the event
+    /// registry forwards its own `bump_event` here.
+    pub fn bump_event(env: Env, event_id: String) {
+        ttl::extend_config(&env);
+        extend_event_payment_entries(&env, event_id);
+    }
+
+    /// Overrides the TTL thresholds, in ledgers, for a family of persistent entries.
+    /// Only callable by admin.
+    pub fn set_ttl_policy(
+        env: Env,
+        family: TtlFamily,
+        threshold: u32,
+        extend_to: u32,
+    ) -> Result<(), TicketPaymentError> {
+        require_admin(&env)?;
+
+        let policy = TtlPolicy {
+            threshold,
+            extend_to,
+        };
+        if !ttl::is_valid_policy(&env, &policy) {
+            return Err(TicketPaymentError::InvalidTtlPolicy);
+        }
+        set_ttl_policy(&env, &family, &policy);
+        Ok(())
+    }
+
+    /// Returns the TTL policy in effect for a family of persistent entries.
+    pub fn get_ttl_policy(env: Env, family: TtlFamily) -> TtlPolicy {
+        ttl::get_policy(&env, &family)
+    }
+
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        is_token_whitelisted(&env, &token)
+    }
+
+    /// Sets the oracle contract address. Only callable by admin.
+    pub fn set_oracle(env: Env, oracle_address: Address) -> Result<(), TicketPaymentError> {
+        require_admin(&env)?;
+        set_oracle_address(&env, &oracle_address);
+        Ok(())
+    }
+
+    /// Returns the current oracle price for an asset.
+    pub fn get_asset_price(
+        env: Env,
+        asset: Address,
+    ) -> Result<price_oracle::PriceData, TicketPaymentError> {
+        fetch_fresh_asset_price(&env, &asset)
+    }
+
+    /// Returns the current slippage tolerance in basis points.
+    pub fn get_slippage(env: Env) -> u32 {
+        get_slippage_bps(&env)
+    }
+
+    /// Processes a payment for an event ticket.
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_payment(
+        env: Env,
+        payment_id: String,
+        event_id: String,
+        ticket_tier_id: String,
+        buyer_address: Address,
+        token_address: Address,
+        amount: i128, // price for ONE ticket
+        quantity: u32,
+        code_preimage: Option<Bytes>,
+        referrer: Option<Address>,
+        options: Option<PurchaseOptions>,
+    ) -> Result<String, TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        require_not_paused(&env, PauseScope::Payments, Some(&event_id))?;
+        buyer_address.require_auth();
+
+        if let Some(ref ref_addr) = referrer {
+            if ref_addr == &buyer_address {
+                return Err(TicketPaymentError::SelfReferralNotAllowed);
+            }
+        }
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        if quantity == 0 {
+            panic!("Quantity must be positive");
+        }
+
+        if !is_token_whitelisted(&env, &token_address) {
+            return Err(TicketPaymentError::TokenNotWhitelisted);
+        }
+
+        let total_amount = amount
+            .checked_mul(quantity as i128)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        // Apply platform-wide global promo if active (self-expiring via timestamp check)
+        let event_registry_addr_promo = get_event_registry(&env);
+        let registry_client_promo = event_registry::Client::new(&env, &event_registry_addr_promo);
+        let global_promo_bps = registry_client_promo.get_global_promo_bps();
+        let promo_expiry = registry_client_promo.get_promo_expiry();
+        let current_ts = env.ledger().timestamp();
+
+        let (after_promo, promo_applied_bps) = if global_promo_bps > 0 && current_ts < promo_expiry
+        {
+            let discounted = total_amount
+                .checked_mul((MAX_BPS - global_promo_bps) as i128)
+                .and_then(|v| v.checked_div(MAX_BPS as i128))
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            (discounted, global_promo_bps)
+        } else {
+            (total_amount, 0u32)
+        };
+
+        // Optionally apply a discount code (10% off) on top of the promo price
+        let (effective_total, discount_code_hash) = if let Some(preimage) = code_preimage {
+            let hash: soroban_sdk::BytesN<32> = env.crypto().sha256(&preimage).into();
+            if !is_discount_hash_valid(&env, &hash) {
+                return Err(TicketPaymentError::InvalidDiscountCode);
+            }
+            if is_discount_hash_used(&env, &hash) {
+                return Err(TicketPaymentError::DiscountCodeUsed);
+            }
+            // 10% discount
+            let discounted = after_promo
+                .checked_mul(90)
+                .and_then(|v| v.checked_div(100))
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            (discounted, Some(hash))
+        } else {
+            (after_promo, None)
+        };
+        // 1. Query Event Registry for event info and check inventory
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            Ok(Ok(None)) => return Err(TicketPaymentError::EventNotFound),
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+
+        if !event_info.is_active
+            || matches!(event_info.status, event_registry::EventStatus::Cancelled)
+        {
+            return Err(TicketPaymentError::EventInactive);
+        }
+
+        let tier = event_info
+            .tiers
+            .get(ticket_tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
+
+        let (allowlist_gate, allowlist_purchased) = check_allowlist(
+            &env,
+            &registry_client,
+            &event_id,
+            &ticket_tier_id,
+            &buyer_address,
+            quantity,
+            options.as_ref(),
+        )?;
+
+        require_attestation(
+            &env,
+            &registry_client,
+            &event_id,
+            &ticket_tier_id,
+            &buyer_address,
+        )?;
+
+        let current_time = env.ledger().timestamp();
+
+        if tier.usd_price > 0 {
+            // ── Oracle-based USD pricing ──────────────────────────────────
+            let price_data = fetch_fresh_asset_price(&env, &token_address)?;
+
+            // expected = usd_price * oracle_price / 1_0000000
+            let expected = tier
+                .usd_price
+                .checked_mul(price_data.price)
+                .and_then(|v| v.checked_div(1_0000000))
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+
+            let bps = get_slippage_bps(&env) as i128;
+            let min_amount = expected
+                .checked_mul(MAX_BPS as i128 - bps)
+                .and_then(|v| v.checked_div(MAX_BPS as i128))
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            let max_amount = expected
+                .checked_mul(MAX_BPS as i128 + bps)
+                .and_then(|v| v.checked_div(MAX_BPS as i128))
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+
+            if amount < min_amount || amount > max_amount {
+                return Err(TicketPaymentError::PriceOutsideSlippage);
+            }
+        } else {
+            // ── Exact token-price matching (existing behaviour) ───────────
+            let mut active_price = tier.price;
+
+            if tier.early_bird_deadline > 0 && current_time <= tier.early_bird_deadline {
+                active_price = tier.early_bird_price;
+            }
+
+            // Pay-what-you-want tiers treat the active price as a floor.
+            let pay_what_you_want = registry_client
+                .try_is_pay_what_you_want(&event_id, &ticket_tier_id)
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(false);
+            if amount < active_price || (!pay_what_you_want && amount != active_price) {
+                return Err(TicketPaymentError::InvalidPrice);
+            }
+        }
+
+        // Check if we just transitioned from early bird to standard
+        if tier.early_bird_deadline > 0
+            && current_time > tier.early_bird_deadline
+            && !has_price_switched(&env, event_id.clone(), ticket_tier_id.clone())
+        {
+            set_price_switched(&env, event_id.clone(), ticket_tier_id.clone());
+            #[allow(deprecated)]
+            env.events().publish(
+                (AgoraEvent::PriceSwitched,),
+                PriceSwitchedEvent {
+                    event_id: event_id.clone(),
+                    tier_id: ticket_tier_id.clone(),
+                    new_price: tier.price,
+                    timestamp: current_time,
+                },
+            );
+        }
+
+        // Apply the organizer's own loyalty ladder discount (funded by the organizer)
+        let organizer_discount_bps = registry_client
+            .try_get_organizer_discount_bps(&event_info.organizer_address, &buyer_address)
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(0)
+            .min(MAX_BPS);
+        let organizer_discount = effective_total
+            .checked_mul(organizer_discount_bps as i128)
+            .and_then(|v| v.checked_div(MAX_BPS as i128))
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        let effective_total = effective_total
+            .checked_sub(organizer_discount)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        // Spend redeemable loyalty points against the price if the organizer opted in
+        let redeem_points = options.as_ref().map(|o| o.redeem_points).unwrap_or(0);
+        let (effective_total, points_redeemed, points_discount) = if redeem_points > 0 {
+            let config = registry_client
+                .try_get_points_redemption_config(&event_id)
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+                .filter(|c| c.enabled && c.point_value > 0)
+                .ok_or(TicketPaymentError::PointsRedemptionDisabled)?;
+
+            let max_discount = effective_total
+                .checked_mul(config.max_discount_bps as i128)
+                .and_then(|v| v.checked_div(MAX_BPS as i128))
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            let usable_points =
+                core::cmp::min(redeem_points as i128, max_discount / config.point_value) as u64;
+            if usable_points > 0 {
+                match registry_client.try_redeem_loyalty_points(
+                    &env.current_contract_address(),
+                    &buyer_address,
+                    &event_id,
+                    &usable_points,
+                ) {
+                    Ok(Ok(_)) => {}
+                    _ => return Err(TicketPaymentError::InsufficientLoyaltyPoints),
+                }
+            }
+            let discount = (usable_points as i128)
+                .checked_mul(config.point_value)
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            let discounted = effective_total
+                .checked_sub(discount)
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            (discounted, usable_points, discount)
+        } else {
+            (effective_total, 0u64, 0i128)
+        };
+
+        // 2. Calculate platform fee
+        let fee_bps = event_info
+            .custom_fee_bps
+            .unwrap_or(event_info.platform_fee_percent);
+
+        let mut total_platform_fee = effective_total
+            .checked_mul(fee_bps as i128)
+            .and_then(|v| v.checked_div(MAX_BPS as i128))
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        // Apply loyalty discount: reduce the platform fee for guests with high scores.
+        // Uses try_ variant so that contracts without loyalty support are unaffected.
+        let loyalty_discount_bps: u32 = registry_client_promo
+            .try_get_loyalty_discount_bps(&buyer_address)
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(0);
+
+        let loyalty_discount_amount = if loyalty_discount_bps > 0 {
+            core::cmp::min(
+                total_platform_fee
+                    .checked_mul(loyalty_discount_bps as i128)
+                    .and_then(|v| v.checked_div(MAX_BPS as i128))
+                    .ok_or(TicketPaymentError::ArithmeticError)?,
+                total_platform_fee,
+            )
+        } else {
+            0
+        };
+        total_platform_fee = total_platform_fee
+            .checked_sub(loyalty_discount_amount)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        // Adjust effective_total to reflect the loyalty discount (guest pays less)
+        let effective_total = effective_total
+            .checked_sub(loyalty_discount_amount)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        let total_organizer_amount = effective_total
+            .checked_sub(total_platform_fee)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        let referral_reward = if referrer.is_some() {
+            let reward = total_platform_fee
+                .checked_mul(20)
+                .and_then(|v| v.checked_div(100))
+                .ok_or(TicketPaymentError::ArithmeticError)?; // 20%
+                                                              // Cap: referral reward must never exceed the remaining platform fee.
+            let reward = core::cmp::min(reward, total_platform_fee);
+            total_platform_fee = total_platform_fee
+                .checked_sub(reward)
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            reward
+        } else {
+            0
+        };
+
+        // Store credit with the organizer pays for part or all of the total. Its
+        // backing tokens are already held in escrow, so only the rest is transferred.
+        let credit_requested = options.as_ref().map(|o| o.credit).unwrap_or(0);
+        let (credit_applied, credit_remaining) = if credit_requested > 0 {
+            // Credit is held in the refund token and only pays for purchases in it.
+            if token_address != get_usdc_token(&env) {
+                return Err(TicketPaymentError::CreditTokenMismatch);
+            }
+            let organizer = &event_info.organizer_address;
+            release_expired_credit(&env, &buyer_address, organizer);
+            let applied = core::cmp::min(credit_requested, effective_total);
+            let mut credit = get_credit_balance(&env, &buyer_address, organizer);
+            if credit.amount < applied {
+                return Err(TicketPaymentError::InsufficientCredit);
+            }
+            credit.amount -= applied;
+            set_credit_balance(&env, &buyer_address, organizer, &credit);
+            (applied, credit.amount)
+        } else {
+            (0, 0)
+        };
+        let token_total = effective_total
+            .checked_sub(credit_applied)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        let donation = options.as_ref().map(|o| o.donation).unwrap_or(0);
+        if donation < 0 {
+            return Err(TicketPaymentError::InvalidDonation);
+        }
+        let donation_config = if donation > 0 {
+            Some(
+                get_donation_config(&env, event_id.clone())
+                    .ok_or(TicketPaymentError::DonationsNotEnabled)?,
+            )
+        } else {
+            None
+        };
+
+        // 3. Transfer tokens to contract (escrow)
+        let token_client = token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+
+        // Verify allowance
+        let allowance = token_client.allowance(&buyer_address, &contract_address);
+        if allowance
+            < token_total
+                .checked_add(donation)
+                .ok_or(TicketPaymentError::ArithmeticError)?
+        {
+            return Err(TicketPaymentError::InsufficientAllowance);
+        }
+
+        // Get balance before transfer
+        let balance_before = token_client.balance(&contract_address);
+
+        // Transfer the amount not covered by credit to contract
+        if token_total > 0 {
+            token_client.transfer_from(
+                &contract_address,
+                &buyer_address,
+                &contract_address,
+                &token_total,
+            );
+        }
+
+        // Verify balance after transfer
+        let balance_after = token_client.balance(&contract_address);
+        if balance_after
+            .checked_sub(balance_before)
+            .ok_or(TicketPaymentError::ArithmeticError)?
+            != token_total
+        {
+            return Err(TicketPaymentError::TransferVerificationFailed);
+        }
+
+        // Donations go straight to their recipient, less the platform fee if it applies.
+        let donation_fee = if let Some(config) = &donation_config {
+            let fee = if config.apply_platform_fee {
+                donation
+                    .checked_mul(fee_bps as i128)
+                    .and_then(|v| v.checked_div(MAX_BPS as i128))
+                    .ok_or(TicketPaymentError::ArithmeticError)?
+            } else {
+                0
+            };
+            token_client.transfer_from(
+                &contract_address,
+                &buyer_address,
+                &config.recipient,
+                &(donation - fee),
+            );
+            if fee > 0 {
+                token_client.transfer_from(
+                    &contract_address,
+                    &buyer_address,
+                    &contract_address,
+                    &fee,
+                );
+                update_event_balance(&env, event_id.clone(), 0, fee);
+                add_to_total_fees_collected_by_token(&env, token_address.clone(), fee);
+                add_to_event_fees_by_token(&env, event_id.clone(), token_address.clone(), fee);
+                add_to_active_escrow_total(&env, fee);
+                add_to_active_escrow_by_token(&env, token_address.clone(), fee);
+            }
+            add_to_donation_total(&env, event_id.clone(), token_address.clone(), donation);
+            fee
+        } else {
+            0
+        };
+
+        // Transfer referral reward if applicable
+        if let Some(ref ref_addr) = referrer {
+            if referral_reward > 0 {
+                token_client.transfer(&contract_address, ref_addr, &referral_reward);
+            }
+        }
+
+        // 4. Update escrow balances
+        update_event_balance(
+            &env,
+            event_id.clone(),
+            total_organizer_amount,
+            total_platform_fee,
+        );
+        add_to_total_volume_processed(&env, total_amount);
+        add_to_total_fees_collected_by_token(&env, token_address.clone(), total_platform_fee);
+        add_to_event_fees_by_token(
+            &env,
+            event_id.clone(),
+            token_address.clone(),
+            total_platform_fee,
+        );
+        // Escrow tracks what the contract actually holds: the discounted total minus
+        // any referral reward already paid out, and minus credit already counted.
+        let escrowed_amount = effective_total
+            .checked_sub(referral_reward)
+            .and_then(|v| v.checked_sub(credit_applied))
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        add_to_active_escrow_total(&env, escrowed_amount);
+        add_to_active_escrow_by_token(&env, token_address.clone(), escrowed_amount);
+
+        // 5. Mark the discount code as used (after funds are safely transferred)
+        if let Some(hash) = discount_code_hash.clone() {
+            mark_discount_hash_used(&env, hash);
+        }
+
+        // 6. Increment inventory after successful payment
+        registry_client.increment_inventory(&event_id, &ticket_tier_id, &quantity);
+        if let Some(gate) = allowlist_gate {
+            set_allowlist_purchases(
+                &env,
+                event_id.clone(),
+                gate.tier_id,
+                &buyer_address,
+                allowlist_purchased,
+            );
+        }
+
+        // 7. Create payment records for each individual ticket
+        let quantity_i128 = quantity as i128;
+        let platform_fee_per_ticket = total_platform_fee
+            .checked_div(quantity_i128)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        let organizer_amount_per_ticket = total_organizer_amount
+            .checked_div(quantity_i128)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        let created_at = env.ledger().timestamp();
+        let empty_tx_hash = String::from_str(&env, "");
+        let mut first_ticket = None;
+
+        for i in 0..quantity {
+            // Re-initialize the sub_payment_id with a unique ID for each ticket in a batch.
+            // Since concatenation is complex in Soroban no_std, we use a match for common indices.
+            let sub_payment_id = if quantity == 1 {
+                payment_id.clone()
+            } else {
+                match i {
+                    0 => String::from_str(&env, "p-0"),
+                    1 => String::from_str(&env, "p-1"),
+                    2 => String::from_str(&env, "p-2"),
+                    3 => String::from_str(&env, "p-3"),
+                    4 => String::from_str(&env, "p-4"),
+                    _ => String::from_str(&env, "p-many"),
+                }
+            };
+
+            let payment = Payment {
+                payment_id: sub_payment_id.clone(),
+                event_id: event_id.clone(),
+                buyer_address: buyer_address.clone(),
+                ticket_tier_id: ticket_tier_id.clone(),
+                token: token_address.clone(),
+                amount,
+                platform_fee: platform_fee_per_ticket,
+                organizer_amount: organizer_amount_per_ticket,
+                status: PaymentStatus::Pending,
+                transaction_hash: empty_tx_hash.clone(),
+                created_at,
+                confirmed_at: None,
+                refunded_amount: 0,
+            };
+
+            if first_ticket.is_none() {
+                first_ticket = Some(payment.clone());
+            }
+            store_payment(&env, payment);
+        }
+
+        // 7a. Sell add-ons requested with the tickets against the first of them
+        if let (Some(opts), Some(ticket)) = (&options, &first_ticket) {
+            for order in opts.add_ons.iter() {
+                buy_add_on(
+                    &env,
+                    &registry_client,
+                    ticket,
+                    fee_bps,
+                    &order.addon_id,
+                    order.quantity,
+                )?;
+            }
+        }
+
+        // 8. Emit payment event
+        env.events().publish(
+            (AgoraEvent::PaymentProcessed,),
+            PaymentProcessedEvent {
+                payment_id: payment_id.clone(),
+                event_id: event_id.clone(),
+                buyer_address: buyer_address.clone(),
+                amount: effective_total,
+                platform_fee: total_platform_fee,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        // 8a. Award loyalty points to buyer (best-effort; ignore failures)
+        match registry_client_promo.try_update_loyalty_score(
+            &env.current_contract_address(),
+            &buyer_address,
+            &quantity,
+            &effective_total,
+            &tier.loyalty_multiplier,
+        ) {
+            Ok(_) | Err(_) => {}
+        }
+
+        if credit_applied > 0 {
+            env.events().publish(
+                (AgoraEvent::CreditRedeemed,),
+                CreditRedeemedEvent {
+                    payment_id: payment_id.clone(),
+                    guest: buyer_address.clone(),
+                    organizer: event_info.organizer_address.clone(),
+                    amount: credit_applied,
+                    remaining: credit_remaining,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        if let Some(config) = donation_config {
+            env.events().publish(
+                (AgoraEvent::DonationReceived,),
+                DonationReceivedEvent {
+                    payment_id: payment_id.clone(),
+                    event_id: event_id.clone(),
+                    donor: buyer_address.clone(),
+                    recipient: config.recipient,
+                    token: token_address.clone(),
+                    amount: donation,
+                    platform_fee: donation_fee,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        // 9. Emit discount applied event if a code was used
+        if let Some(hash) = discount_code_hash {
+            let discount_amount = total_amount.checked_sub(effective_total).unwrap_or(0);
+            env.events().publish(
+                (AgoraEvent::DiscountCodeApplied,),
+                DiscountCodeAppliedEvent {
+                    payment_id: payment_id.clone(),
+                    event_id: event_id.clone(),
+                    code_hash: hash,
+                    discount_amount,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        // 9a. Emit loyalty events if the organizer ladder or points reduced the price
+        if organizer_discount > 0 {
+            env.events().publish(
+                (AgoraEvent::OrganizerLoyaltyDiscountApplied,),
+                OrganizerLoyaltyDiscountAppliedEvent {
+                    payment_id: payment_id.clone(),
+                    event_id: event_id.clone(),
+                    buyer_address: buyer_address.clone(),
+                    discount_bps: organizer_discount_bps,
+                    discount_amount: organizer_discount,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        if points_redeemed > 0 {
+            env.events().publish(
+                (AgoraEvent::LoyaltyPointsApplied,),
+                LoyaltyPointsAppliedEvent {
+                    payment_id: payment_id.clone(),
+                    event_id: event_id.clone(),
+                    buyer_address: buyer_address.clone(),
+                    points: points_redeemed,
+                    discount_amount: points_discount,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        // 10. Emit global promo applied event if promo was active
+        if promo_applied_bps > 0 {
+            let promo_discount_amount = total_amount.checked_sub(after_promo).unwrap_or(0);
+            env.events().publish(
+                (AgoraEvent::GlobalPromoApplied,),
+                GlobalPromoAppliedEvent {
+                    payment_id: payment_id.clone(),
+                    event_id: event_id.clone(),
+                    promo_bps: promo_applied_bps,
+                    discount_amount: promo_discount_amount,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        Ok(payment_id)
+    }
+
+    /// Confirms a payment after backend verification.
+    pub fn confirm_payment(env: Env, payment_id: String, transaction_hash: String) {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        require_admin(&env).expect("Admin not set");
+        if let Some(mut payment) = get_payment(&env, payment_id.clone()) {
+            if payment.status == PaymentStatus::PartiallyPaid {
+                panic!("Payment not fully paid");
+            }
+            let old_status = payment.status.clone();
+            payment.status = PaymentStatus::Confirmed;
+            payment.confirmed_at = Some(env.ledger().timestamp());
+            payment.transaction_hash = transaction_hash.clone();
+
+            // Update payment in storage
+            let key = DataKey::Payment(payment_id.clone());
+            env.storage().persistent().set(&key, &payment);
+
+            // Update status index
+            if old_status != PaymentStatus::Confirmed {
+                crate::storage::update_payment_status_index(
+                    &env,
+                    payment.event_id.clone(),
+                    old_status.clone(),
+                    PaymentStatus::Confirmed,
+                    payment_id.clone(),
+                );
+            }
+        }
+
+        // Emit confirmation event
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::PaymentStatusChanged,),
+            PaymentStatusChangedEvent {
+                payment_id: payment_id.clone(),
+                old_status: PaymentStatus::Pending,
+                new_status: PaymentStatus::Confirmed,
+                transaction_hash: transaction_hash.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Refunds a ticket to its holder. With `as_credit`, the refund is issued as store
+    /// credit with the event's organizer under their `CreditOffer` instead of tokens.
+    pub fn request_guest_refund(
+        env: Env,
+        payment_id: String,
+        as_credit: bool,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        let event_id = get_payment(&env, payment_id.clone()).map(|p| p.event_id);
+        require_not_paused(&env, PauseScope::Refunds, event_id.as_ref())?;
+
+        Self::internal_refund(env, payment_id, as_credit)
+    }
+
+    /// Triggers a refund as an administrator, regardless of dispute status.
+    pub fn admin_refund(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
+        require_admin(&env)?;
+
+        Self::internal_refund(env, payment_id, false)
+    }
+
+    /// Public wrapper for automatic refunds, specifically for cancelled events.
+    pub fn claim_automatic_refund(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        let event_id = get_payment(&env, payment_id.clone()).map(|p| p.event_id);
+        require_not_paused(&env, PauseScope::Refunds, event_id.as_ref())?;
+
+        let payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+
+        let event_info = match registry_client.try_get_event(&payment.event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+
+        // Ensure the event is cancelled for automatic refund OR goal failed after deadline
+        let current_ts = env.ledger().timestamp();
+        let goal_failed = !event_info.goal_met
+            && event_info.min_sales_target > 0
+            && current_ts > event_info.target_deadline;
+
+        if !matches!(event_info.status, event_registry::EventStatus::Cancelled) && !goal_failed {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+
+        Self::internal_refund(env, payment_id, false)
+    }
+
+    fn internal_refund(
+        env: Env,
+        payment_id: String,
+        as_credit: bool,
+    ) -> Result<(), TicketPaymentError> {
+        let mut payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+
+        payment.buyer_address.require_auth();
+
+        if payment.status == PaymentStatus::Refunded || payment.status == PaymentStatus::Failed {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+        // Installment tickets are refunded through `forfeit_installment_plan` or the
+        // bulk refund of a cancelled event.
+        if payment.status == PaymentStatus::PartiallyPaid {
+            return Err(TicketPaymentError::PaymentNotFullyPaid);
+        }
+
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+
+        let event_info = match registry_client.try_get_event(&payment.event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+
+        let tier = event_info
+            .tiers
+            .get(payment.ticket_tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
+
+        let is_cancelled = matches!(event_info.status, event_registry::EventStatus::Cancelled);
+        let current_ts = env.ledger().timestamp();
+        let goal_failed = !event_info.goal_met
+            && event_info.min_sales_target > 0
+            && current_ts > event_info.target_deadline;
+
+        // Check if refundable or if EVENT IS CANCELLED or GOAL FAILED
+        if !tier.is_refundable && !is_cancelled && !goal_failed && event_info.is_active {
+            return Err(TicketPaymentError::TicketNotRefundable);
+        }
+
+        // Validate against refund deadline if event is active and not cancelled
+        if !is_cancelled
+            && event_info.is_active
+            && event_info.refund_deadline > 0
+            && env.ledger().timestamp() > event_info.refund_deadline
+        {
+            return Err(TicketPaymentError::RefundDeadlinePassed);
+        }
+
+        // Deduct restocking fee if specified (capped at payment amount)
+        // Bypass restocking fee if the event is cancelled or goal failed.
+        let effective_restocking_fee = if is_cancelled || goal_failed {
+            0
+        } else if event_info.restocking_fee > payment.amount {
+            payment.amount
+        } else if event_info.restocking_fee > 0 {
+            event_info.restocking_fee
+        } else {
+            0
+        };
+
+        let refund_amount = payment
+            .amount
+            .checked_sub(effective_restocking_fee)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        // Guest receives payment.amount - effective_restocking_fee
+        // Organizer keeps effective_restocking_fee (adjust from original organizer_amount)
+        // Platform fee is refunded (removed from escrow)
+        let org_adjustment = payment
+            .organizer_amount
+            .checked_sub(effective_restocking_fee)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        let platform_adjustment = payment.platform_fee;
+
+        // A credit refund keeps the tokens in escrow as the voucher's backing, and the
+        // organizer funds the offer's bonus from their remaining share up front.
+        let credit = if as_credit {
+            if payment.token != get_usdc_token(&env) {
+                return Err(TicketPaymentError::CreditTokenMismatch);
+            }
+            let offer = get_credit_offer(&env, &event_info.organizer_address)
+                .ok_or(TicketPaymentError::CreditNotOffered)?;
+            let bonus = refund_amount
+                .checked_mul(offer.bonus_bps as i128)
+                .and_then(|v| v.checked_div(MAX_BPS as i128))
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            let organizer_left = get_event_balance(&env, payment.event_id.clone())
+                .organizer_amount
+                .checked_sub(org_adjustment)
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            if organizer_left < bonus {
+                return Err(TicketPaymentError::NoFundsAvailable);
+            }
+            let expires_at = if offer.validity_secs > 0 {
+                current_ts.saturating_add(offer.validity_secs)
+            } else {
+                0
+            };
+            Some((bonus, expires_at))
+        } else {
+            None
+        };
+
+        // Return ticket to inventory (increments available inventory)
+        registry_client.decrement_inventory(&payment.event_id, &payment.ticket_tier_id);
+
+        let old_status = payment.status.clone();
+        payment.status = PaymentStatus::Refunded;
+        payment.confirmed_at = Some(env.ledger().timestamp());
+
+        // Update payment in storage
+        let key = DataKey::Payment(payment_id.clone());
+        env.storage().persistent().set(&key, &payment);
+
+        // Update status index
+        if old_status != PaymentStatus::Refunded {
+            crate::storage::update_payment_status_index(
+                &env,
+                payment.event_id.clone(),
+                old_status.clone(),
+                PaymentStatus::Refunded,
+                payment_id.clone(),
+            );
+        }
+
+        reverse_loyalty(
+            &env,
+            &registry_client,
+            &payment,
+            tier.loyalty_multiplier,
+            payment.organizer_amount + payment.platform_fee,
+            MAX_BPS,
+        );
+        return_deposit(&env, &payment);
+
+        if let Some((bonus, expires_at)) = credit {
+            issue_credit(
+                &env,
+                &payment,
+                &event_info.organizer_address,
+                refund_amount,
+                bonus,
+                expires_at,
+            )?;
+        } else {
+            // Top up from the protection pool if the event's escrow can't cover the refund
+            let mut escrow = get_event_balance(&env, payment.event_id.clone());
+            let covered = cover_refund_from_pool(
+                &env,
+                &registry_client,
+                &payment.event_id,
+                &mut escrow,
+                &payment.token,
+                refund_amount,
+            );
+            if covered > 0 {
+                update_event_balance(&env, payment.event_id.clone(), covered, 0);
+            }
+
+            // Process token transfer, in the token the ticket was paid in
+            if refund_amount > 0 {
+                token::Client::new(&env, &payment.token).transfer(
+                    &env.current_contract_address(),
+                    &payment.buyer_address,
+                    &refund_amount,
+                );
+            }
+        }
+        let org_adjustment = org_adjustment
+            .checked_add(credit.map(|(bonus, _)| bonus).unwrap_or(0))
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        // Add-ons not handed out yet are refunded with the ticket.
+        let (addon_refund, addon_organizer, addon_fee) =
+            refund_add_ons(&env, &registry_client, &payment);
+        let org_adjustment = org_adjustment
+            .checked_add(addon_organizer)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        let platform_adjustment = platform_adjustment
+            .checked_add(addon_fee)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        // Fees still held for this event are returned to the buyer, so they are no
+        // longer collectable by the platform. The ticket's fee was collected in the
+        // token it was paid in, the add-ons' fee in the refund token.
+        let held_fees = get_event_balance(&env, payment.event_id.clone())
+            .platform_fee
+            .max(0);
+        let returned = return_unsettled_fee(
+            &env,
+            &payment.event_id,
+            &payment.token,
+            payment.platform_fee,
+            held_fees,
+        );
+        return_unsettled_fee(
+            &env,
+            &payment.event_id,
+            &crate::storage::get_usdc_token(&env),
+            addon_fee,
+            held_fees - returned,
+        );
+
+        crate::storage::update_event_balance(
+            &env,
+            payment.event_id.clone(),
+            -org_adjustment,
+            -platform_adjustment,
+        );
+
+        if credit.is_none() {
+            subtract_from_active_escrow_total(&env, refund_amount);
+            subtract_from_active_escrow_by_token(&env, payment.token.clone(), refund_amount);
+        }
+        if addon_refund > 0 {
+            subtract_from_active_escrow_total(&env, addon_refund);
+            subtract_from_active_escrow_by_token(
+                &env,
+                crate::storage::get_usdc_token(&env),
+                addon_refund,
+            );
+        }
+
+        // Clear escrow record if both amounts are now zero (fully refunded event)
+        let updated_balance = get_event_balance(&env, payment.event_id.clone());
+        if updated_balance.organizer_amount == 0 && updated_balance.platform_fee == 0 {
+            // Keep the record but ensure it's clean
+            update_event_balance(&env, payment.event_id.clone(), 0, 0);
+        }
+
+        // Emit confirmation event
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::PaymentStatusChanged,),
+            PaymentStatusChangedEvent {
+                payment_id: payment_id.clone(),
+                old_status,
+                new_status: PaymentStatus::Refunded,
+                transaction_hash: String::from_str(&env, "refund"),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+    pub fn get_payment_status(env: Env, payment_id: String) -> Option<Payment> {
+        get_payment(&env, payment_id)
+    }
+
+    // pub fn check_in(
+    //     env: Env,
+    //     payment_id: String,
+    //     scanner: Address,
+    //     // Optionally, a series_id and pass_holder for series pass check-in
+    //     series_id: Option<String>,
+    //     pass_holder: Option<Address>,
+    // ) -> Result<(), TicketPaymentError> {
+    //     if !is_initialized(&env) {
+    //         panic!("Contract not initialized");
+    //     }
+    //     if is_paused(&env) {
+    //         return Err(TicketPaymentError::ContractPaused);
+    //     }
+
+    //     // If series_id and pass_holder are provided, check for a valid series pass
+    //     if let (Some(series_id), Some(holder)) = (series_id.clone(), pass_holder.clone()) {
+    //         // Call EventRegistry to verify pass
+    //         let event_registry_addr = get_event_registry(&env);
+    //         let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+    //         // Check if event is part of the series
+    //         let series_opt: Option<_> = registry_client.get_series(&series_id);
+    //         if let Some(series) = series_opt {
+    //             let found = series.event_ids.iter().any(|eid| eid == payment_id);
+    //             if !found {
+    //                 return Err(TicketPaymentError::EventNotFound);
+    //             }
+    //             // Get the pass for the holder
+    //             let pass_opt = registry_client.get_holder_series_pass(&holder, &series_id);
+    //             if let Some(mut pass) = pass_opt {
+    //                 // Check expiry
+    //                 if pass.expires_at > 0 && env.ledger().timestamp() > pass.expires_at {
+    //                     return Err(TicketPaymentError::InvalidPaymentStatus); // Use a better error if available
+    //                 }
+    //                 // Check usage limit
+    //                 if pass.usage_count >= pass.usage_limit {
+    //                     return Err(TicketPaymentError::TicketAlreadyUsed);
+    //                 }
+    //                 // Increment usage
+    //                 let _ = registry_client.increment_series_pass_usage(&pass.pass_id);
+    //                 // Emit event for series pass check-in (optional)
+    //                 // (You may want to add a new event type for this)
+    //                 return Ok(());
+    //             } else {
+    //                 return Err(TicketPaymentError::PaymentNotFound);
+    //             }
+    //         } else {
+    //             return Err(TicketPaymentError::EventNotFound);
+    //         }
+    //     }
+
+    //     // Fallback: normal ticket check-in
+    //     let mut payment =
+    //         get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+
+    //     // Must authenticate the scanner wallet calling this entry point
+    //     scanner.require_auth();
+
+    //     if payment.status == PaymentStatus::CheckedIn {
+    //         return Err(TicketPaymentError::TicketAlreadyUsed);
+    //     }
+
+    //     // Verify scanner authorization
+    //     let event_registry_addr = get_event_registry(&env);
+    //     let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+    //     let is_auth = registry_client.is_scanner_authorized(&payment.event_id, &scanner);
+    //     if !is_auth {
+    //         return Err(TicketPaymentError::UnauthorizedScanner);
+    //     }
+
+    //     // Update status and store arrival timestamp
+    //     payment.status = PaymentStatus::CheckedIn;
+    //     payment.confirmed_at = Some(env.ledger().timestamp());
+
+    //     store_payment(&env, payment.clone());
+
+    //     #[allow(deprecated)]
+    //     env.events().publish(
+    //         (AgoraEvent::TicketCheckedIn,),
+    //         crate::events::TicketCheckedInEvent {
+    //             payment_id,
+    //             event_id: payment.event_id,
+    //             scanner,
+    //             timestamp: env.ledger().timestamp(),
+    //         },
+    //     );
+
+    //     Ok(())
+    // }
+
+    /// Verifies scanner authorization and marks a ticket as CheckedIn.
+    pub fn check_in(
+        env: Env,
+        payment_id: String,
+        scanner: Address,
+        _series_id: Option<String>,
+        _pass_holder: Option<Address>,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        let event_id = get_payment(&env, payment_id.clone()).map(|p| p.event_id);
+        require_not_paused(&env, PauseScope::CheckIn, event_id.as_ref())?;
+
+        // ── Temporarily disabled ── series pass logic ────────────────────────
+        /*
+        if let (Some(series_id), Some(holder)) = (series_id, pass_holder) {
+            let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+            let series_opt = registry_client.get_series(&series_id);           // ← does not exist
+            // ...
+        }
+        */
+
+        // Normal single-ticket check-in
+        let mut payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+
+        scanner.require_auth();
+
+        if payment.status == PaymentStatus::CheckedIn {
+            return Err(TicketPaymentError::TicketAlreadyUsed);
+        }
+        if payment.status == PaymentStatus::PartiallyPaid {
+            return Err(TicketPaymentError::PaymentNotFullyPaid);
+        }
+
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        if !registry_client.is_scanner_authorized(&payment.event_id, &scanner) {
+            return Err(TicketPaymentError::UnauthorizedScanner);
+        }
+
+        // Check if the event has ended (prevent check-ins after end_time)
+        let event_info = registry_client
+            .try_get_event(&payment.event_id)
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+            .ok_or(TicketPaymentError::EventNotFound)?;
+
+        let current_time = env.ledger().timestamp();
+        if event_info.end_time > 0 && current_time > event_info.end_time {
+            return Err(TicketPaymentError::EventEnded);
+        }
+
+        payment.status = PaymentStatus::CheckedIn;
+        payment.confirmed_at = Some(env.ledger().timestamp());
+        let (guest, checked_in_event) = (payment.buyer_address.clone(), payment.event_id.clone());
+        return_deposit(&env, &payment);
+        store_payment(&env, payment);
+
+        // Count attendance towards the organizer's loyalty ladder (best-effort)
+        let _ = registry_client.try_record_attendance(
+            &env.current_contract_address(),
+            &guest,
+            &checked_in_event,
+        );
+
+        // env.events().publish(
+        //     (AgoraEvent::TicketCheckedIn,),
+        //     crate::events::TicketCheckedInEvent {
+        //             payment_id,
+        //             // event_id: payment.event_id.clone(),
+        //             scanner,
+        //             timestamp: env.ledger().timestamp(),
+        //         },
+        // );
+
+        Ok(())
+    }
+    /// Returns the escrowed balance for an event.
+    pub fn get_event_escrow_balance(env: Env, event_id: String) -> crate::types::EventBalance {
+        get_event_balance(&env, event_id)
+    }
+
+    /// Withdraw organizer funds from escrow.
+    pub fn withdraw_organizer_funds(
+        env: Env,
+        event_id: String,
+        token_address: Address,
+    ) -> Result<i128, TicketPaymentError> {
+        require_not_paused(&env, PauseScope::Withdrawals, Some(&event_id))?;
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+        let event_info = registry_client
+            .try_get_event(&event_id)
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+            .ok_or(TicketPaymentError::EventNotFound)?;
+
+        event_info.organizer_address.require_auth();
+
+        let balance = get_event_balance(&env, event_id.clone());
+        // Block all claim_revenue attempts for an event while a dispute is active.
+        if is_event_disputed(&env, event_id.clone()) {
+            return Err(TicketPaymentError::EventDisputed);
+        }
+
+        // Block any further organizer payouts once an event is in the Cancelled state.
+        if matches!(event_info.status, event_registry::EventStatus::Cancelled) {
+            return Err(TicketPaymentError::EventCancelled);
+        }
+
+        // Check if goal was met if a target was set
+        if event_info.min_sales_target > 0 && !event_info.goal_met {
+            return Err(TicketPaymentError::GoalNotMet);
+        }
+
+        let total_revenue = balance
+            .organizer_amount
+            .checked_add(balance.total_withdrawn)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        if total_revenue == 0 {
+            return Ok(0);
+        }
+
+        let mut release_percent = MAX_BPS;
+        if let Some(milestones) = event_info.milestone_plan {
+            let mut highest_met = 0u32;
+            for milestone in milestones.iter() {
+                if event_info.current_supply >= milestone.sales_threshold
+                    && milestone.release_percent > highest_met
+                {
+                    highest_met = milestone.release_percent;
+                }
+            }
+            if !milestones.is_empty() {
+                release_percent = highest_met;
+            }
+        }
+
+        let max_allowed = total_revenue
+            .checked_mul(release_percent as i128)
+            .and_then(|v| v.checked_div(MAX_BPS as i128))
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+        let mut available_to_withdraw = max_allowed
+            .checked_sub(balance.total_withdrawn)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        if available_to_withdraw <= 0 {
+            return Ok(0);
+        }
+
+        if available_to_withdraw > balance.organizer_amount {
+            available_to_withdraw = balance.organizer_amount;
+        }
+
+        let queued = check_organizer_withdrawal(
+            &env,
+            &registry_client,
+            &event_info.organizer_address,
+            &event_id,
+            &token_address,
+            available_to_withdraw,
+        )?;
+
+        if queued.is_none() {
+            distribute_to_payees(
+                &env,
+                &registry_client,
+                &event_id,
+                &event_info.payment_address,
+                &token_address,
+                available_to_withdraw,
+            )?;
+        }
+
+        crate::storage::set_event_balance(
+            &env,
+            event_id,
+            crate::types::EventBalance {
+                organizer_amount: balance
+                    .organizer_amount
+                    .checked_sub(available_to_withdraw)
+                    .ok_or(TicketPaymentError::ArithmeticError)?,
+                total_withdrawn: balance
+                    .total_withdrawn
+                    .checked_add(available_to_withdraw)
+                    .ok_or(TicketPaymentError::ArithmeticError)?,
+                platform_fee: balance.platform_fee,
+            },
+        );
+
+        // Queued withdrawals stay in escrow until they are executed.
+        if queued.is_some() {
+            return Ok(0);
+        }
+        subtract_from_active_escrow_total(&env, available_to_withdraw);
+        subtract_from_active_escrow_by_token(&env, token_address, available_to_withdraw);
+
+        Ok(available_to_withdraw)
+    }
+
+    /// Settles an event's platform fees by paying them out of escrow to the treasury.
+    ///
+    /// The fees are transferred in `token_address` to the configured treasury wallets
+    /// (or the platform wallet when none are configured), count against the token's
+    /// withdrawal cap, and are removed from `TotalFeesCollected` and the escrow totals.
+    /// Only the fees the event collected in `token_address` are settled; a token the
+    /// event collected no fees in fails with `InsufficientFees`.
+    /// If the payout would exceed the rolling withdrawal cap it is queued instead and
+    /// 0 is returned; see `execute_queued_withdrawal`.
+    pub fn settle_platform_fees(
+        env: Env,
+        event_id: String,
+        token_address: Address,
+    ) -> Result<i128, TicketPaymentError> {
+        require_admin(&env)?;
+
+        let balance = get_event_balance(&env, event_id.clone());
+        if balance.platform_fee <= 0 {
+            return Ok(0);
+        }
+
+        // Only fees the event actually collected in this token can be settled in it.
+        let collected = get_event_fees_by_token(&env, event_id.clone())
+            .get(token_address.clone())
+            .unwrap_or(0);
+        if collected <= 0 {
+            return Err(TicketPaymentError::InsufficientFees);
+        }
+        let fee_amount = core::cmp::min(balance.platform_fee, collected);
+
+        let queued =
+            release_platform_fees(&env, &token_address, fee_amount, Some(event_id.clone()))?;
+        subtract_from_event_fees_by_token(&env, event_id.clone(), token_address, fee_amount);
+
+        crate::storage::set_event_balance(
             &env,
             event_id.clone(),
-            total_organizer_amount,
-            total_platform_fee,
+            crate::types::EventBalance {
+                organizer_amount: balance.organizer_amount,
+                total_withdrawn: balance.total_withdrawn,
+                platform_fee: balance.platform_fee - fee_amount,
+            },
         );
-        add_to_total_volume_processed(&env, total_amount);
-        add_to_total_fees_collected_by_token(&env, token_address.clone(), total_platform_fee);
-        add_to_active_escrow_total(&env, total_amount);
-        add_to_active_escrow_by_token(&env, token_address.clone(), total_amount);
 
-        // 5. Mark the discount code as used (after funds are safely transferred)
-        if let Some(hash) = discount_code_hash.clone() {
-            mark_discount_hash_used(&env, hash);
+        // Emit settlement event
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::FeeSettled,),
+            FeeSettledEvent {
+                event_id,
+                platform_wallet: get_platform_wallet(&env),
+                fee_amount,
+                fee_bps: 0, // Not applicable here
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        if queued.is_some() {
+            return Ok(0);
+        }
+        Ok(fee_amount)
+    }
+
+    /// Withdraw platform fees that were released from event escrow (e.g. by
+    /// `claim_revenue`) but not yet paid out.
+    /// Incorporates a rolling-window withdrawal cap and requires admin (multi-sig)
+    /// authorization. A withdrawal over the cap is queued and its id is returned.
+    pub fn withdraw_platform_fees(
+        env: Env,
+        amount: i128,
+        token_address: Address,
+    ) -> Result<Option<u64>, TicketPaymentError> {
+        require_admin(&env)?;
+
+        if amount <= 0 {
+            return Err(TicketPaymentError::ArithmeticError);
+        }
+
+        // Only fees that no longer belong to an event escrow may be withdrawn in bulk;
+        // unsettled event fees must go through `settle_platform_fees`.
+        if amount > get_released_fees(&env, token_address.clone()) {
+            return Err(TicketPaymentError::InsufficientFees);
+        }
+
+        subtract_from_released_fees(&env, token_address.clone(), amount);
+        release_platform_fees(&env, &token_address, amount, None)
+    }
+
+    /// Returns platform fees released from event escrow and awaiting payout.
+    pub fn get_released_fees(env: Env, token_address: Address) -> i128 {
+        get_released_fees(&env, token_address)
+    }
+
+    /// Returns the effective treasury wallets (the platform wallet at 100% by default).
+    pub fn get_treasury_wallets(env: Env) -> Vec<TreasuryWallet> {
+        effective_treasury_wallets(&env)
+    }
+
+    pub fn get_withdrawal_limit_config(env: Env) -> WithdrawalLimitConfig {
+        get_withdrawal_limit_config(&env)
+    }
+
+    /// Returns the cap that applies to `organizer` for `token` (0 = unlimited).
+    pub fn get_organizer_withdrawal_cap(env: Env, organizer: Address, token: Address) -> i128 {
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        effective_organizer_cap(&env, &registry_client, &organizer, &token)
+    }
+
+    /// Returns how much `organizer` withdrew in `token` within the current rolling window.
+    pub fn get_organizer_withdrawn_amount(env: Env, organizer: Address, token: Address) -> i128 {
+        let config = get_withdrawal_limit_config(&env);
+        get_window_withdrawn_amount(&env, organizer, token, config.window_secs)
+    }
+
+    pub fn get_queued_withdrawal(env: Env, id: u64) -> Option<QueuedWithdrawal> {
+        get_queued_withdrawal(&env, id)
+    }
+
+    /// Pays out a queued withdrawal once its delay has elapsed. Anyone may call this;
+    /// funds only go to the destinations fixed when the withdrawal was queued.
+    /// Organizer revenue queued for an event that has since been cancelled is not
+    /// paid out: the withdrawal is cancelled, its amount returns to the event's
+    /// escrow for refunds, and 0 is returned.
+    pub fn execute_queued_withdrawal(env: Env, id: u64) -> Result<i128, TicketPaymentError> {
+        let mut queued =
+            get_queued_withdrawal(&env, id).ok_or(TicketPaymentError::QueuedWithdrawalNotFound)?;
+        require_not_paused(&env, PauseScope::Withdrawals, queued.event_id.as_ref())?;
+        if queued.status != QueuedWithdrawalStatus::Pending {
+            return Err(TicketPaymentError::QueuedWithdrawalNotPending);
+        }
+        if env.ledger().timestamp() < queued.available_at {
+            return Err(TicketPaymentError::WithdrawalNotReady);
+        }
+
+        let config = get_withdrawal_limit_config(&env);
+        match queued.kind {
+            WithdrawalKind::PlatformFees => {
+                pay_out_platform_fees(&env, &queued.token, queued.amount)?;
+                record_window_withdrawal(
+                    &env,
+                    env.current_contract_address(),
+                    queued.token.clone(),
+                    config.window_secs,
+                    queued.amount,
+                );
+            }
+            WithdrawalKind::OrganizerRevenue => {
+                let event_id = queued
+                    .event_id
+                    .clone()
+                    .ok_or(TicketPaymentError::EventNotFound)?;
+                let organizer = queued
+                    .organizer
+                    .clone()
+                    .ok_or(TicketPaymentError::Unauthorized)?;
+                if is_event_disputed(&env, event_id.clone()) {
+                    return Err(TicketPaymentError::EventDisputed);
+                }
+
+                let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+                let event_info = registry_client
+                    .try_get_event(&event_id)
+                    .ok()
+                    .and_then(|r| r.ok())
+                    .flatten();
+                // Revenue queued before a cancellation stays in escrow for refunds.
+                if event_info.as_ref().is_some_and(|info| {
+                    matches!(info.status, event_registry::EventStatus::Cancelled)
+                }) {
+                    cancel_withdrawal(&env, &mut queued)?;
+                    return Ok(0);
+                }
+                let default_payee = event_info
+                    .map(|info| info.payment_address)
+                    .unwrap_or_else(|| organizer.clone());
+                distribute_to_payees(
+                    &env,
+                    &registry_client,
+                    &event_id,
+                    &default_payee,
+                    &queued.token,
+                    queued.amount,
+                )?;
+                record_window_withdrawal(
+                    &env,
+                    organizer,
+                    queued.token.clone(),
+                    config.window_secs,
+                    queued.amount,
+                );
+                subtract_from_active_escrow_total(&env, queued.amount);
+                subtract_from_active_escrow_by_token(&env, queued.token.clone(), queued.amount);
+            }
+        }
+
+        queued.status = QueuedWithdrawalStatus::Executed;
+        set_queued_withdrawal(&env, &queued);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::QueuedWithdrawalExecuted,),
+            QueuedWithdrawalExecutedEvent {
+                id,
+                amount: queued.amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(queued.amount)
+    }
+
+    /// Cancels a pending queued withdrawal and returns the funds to where they came from.
+    pub fn cancel_queued_withdrawal(env: Env, id: u64) -> Result<(), TicketPaymentError> {
+        require_admin(&env)?;
+
+        let mut queued =
+            get_queued_withdrawal(&env, id).ok_or(TicketPaymentError::QueuedWithdrawalNotFound)?;
+        if queued.status != QueuedWithdrawalStatus::Pending {
+            return Err(TicketPaymentError::QueuedWithdrawalNotPending);
+        }
+
+        cancel_withdrawal(&env, &mut queued)
+    }
+
+    /// Claim revenue after event completion.
+    pub fn claim_revenue(
+        env: Env,
+        event_id: String,
+        token_address: Address,
+    ) -> Result<i128, TicketPaymentError> {
+        require_not_paused(&env, PauseScope::Withdrawals, Some(&event_id))?;
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+
+        let event_info = registry_client
+            .try_get_event(&event_id)
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+            .ok_or(TicketPaymentError::EventNotFound)?;
+
+        event_info.organizer_address.require_auth();
+
+        if event_info.is_active
+            || !matches!(event_info.status, event_registry::EventStatus::Inactive)
+        {
+            return Err(TicketPaymentError::EventNotCompleted);
+        }
+
+        // Check if goal was met if a target was set
+        if event_info.min_sales_target > 0 && !event_info.goal_met {
+            return Err(TicketPaymentError::GoalNotMet);
+        }
+
+        let balance = get_event_balance(&env, event_id.clone());
+        if balance.organizer_amount == 0 && balance.platform_fee == 0 {
+            return Err(TicketPaymentError::NoFundsAvailable);
         }
 
-        // 6. Increment inventory after successful payment
-        registry_client.increment_inventory(&event_id, &ticket_tier_id, &quantity);
+        let platform_wallet = get_platform_wallet(&env);
+        let token_client = token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+        let timestamp = env.ledger().timestamp();
 
-        // 7. Create payment records for each individual ticket
-        let quantity_i128 = quantity as i128;
-        let platform_fee_per_ticket = total_platform_fee
-            .checked_div(quantity_i128)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
-        let organizer_amount_per_ticket = total_organizer_amount
-            .checked_div(quantity_i128)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
-        let created_at = env.ledger().timestamp();
-        let empty_tx_hash = String::from_str(&env, "");
+        let platform_fee_amount = balance.platform_fee;
+        let organizer_amount = balance.organizer_amount;
 
-        for i in 0..quantity {
-            // Re-initialize the sub_payment_id with a unique ID for each ticket in a batch.
-            // Since concatenation is complex in Soroban no_std, we use a match for common indices.
-            let sub_payment_id = if quantity == 1 {
-                payment_id.clone()
-            } else {
-                match i {
-                    0 => String::from_str(&env, "p-0"),
-                    1 => String::from_str(&env, "p-1"),
-                    2 => String::from_str(&env, "p-2"),
-                    3 => String::from_str(&env, "p-3"),
-                    4 => String::from_str(&env, "p-4"),
-                    _ => String::from_str(&env, "p-many"),
+        // If the organizer's remaining balance is at or below the dust threshold,
+        // sweep the full contract balance for this event to avoid leaving tiny amounts.
+        let contract_token_balance = token_client.balance(&contract_address);
+        let organizer_amount = if organizer_amount > 0
+            && organizer_amount <= DUST_THRESHOLD
+            && contract_token_balance >= organizer_amount
+        {
+            contract_token_balance
+                .checked_sub(platform_fee_amount)
+                .unwrap_or(organizer_amount)
+                .max(organizer_amount)
+        } else {
+            organizer_amount
+        };
+
+        // Settlement logic: platform fees stay in the contract but are cleared from EventBalance.
+        // They remain in TotalFeesCollected and become withdrawable via `withdraw_platform_fees`.
+        if platform_fee_amount > 0 {
+            // Fees are released in the tokens the event collected them in, and what is
+            // released must add up to the fee balance being settled.
+            let mut releases: Vec<(Address, i128)> = Vec::new(&env);
+            let mut unreleased = platform_fee_amount;
+            for (fee_token, fee) in get_event_fees_by_token(&env, event_id.clone()).iter() {
+                let fee = fee.min(unreleased);
+                if fee > 0 {
+                    releases.push_back((fee_token, fee));
+                    unreleased -= fee;
                 }
-            };
+            }
+            if unreleased != 0 {
+                return Err(TicketPaymentError::ArithmeticError);
+            }
+            for (fee_token, fee) in releases.iter() {
+                add_to_released_fees(&env, fee_token.clone(), fee);
+                subtract_from_event_fees_by_token(&env, event_id.clone(), fee_token, fee);
+            }
+            #[allow(deprecated)]
+            env.events().publish(
+                (AgoraEvent::FeeSettled,),
+                FeeSettledEvent {
+                    event_id: event_id.clone(),
+                    platform_wallet: platform_wallet.clone(),
+                    fee_amount: platform_fee_amount,
+                    fee_bps: event_info
+                        .custom_fee_bps
+                        .unwrap_or(event_info.platform_fee_percent),
+                    timestamp,
+                },
+            );
+        }
 
-            let payment = Payment {
-                payment_id: sub_payment_id.clone(),
-                event_id: event_id.clone(),
-                buyer_address: buyer_address.clone(),
-                ticket_tier_id: ticket_tier_id.clone(),
-                amount,
-                platform_fee: platform_fee_per_ticket,
-                organizer_amount: organizer_amount_per_ticket,
-                status: PaymentStatus::Pending,
-                transaction_hash: empty_tx_hash.clone(),
-                created_at,
-                confirmed_at: None,
-                refunded_amount: 0,
-            };
+        let queued = check_organizer_withdrawal(
+            &env,
+            &registry_client,
+            &event_info.organizer_address,
+            &event_id,
+            &token_address,
+            organizer_amount,
+        )?;
 
-            store_payment(&env, payment);
+        // Update balances
+        crate::storage::set_event_balance(
+            &env,
+            event_id.clone(),
+            crate::types::EventBalance {
+                organizer_amount: 0,
+                total_withdrawn: balance.total_withdrawn + organizer_amount,
+                platform_fee: 0,
+            },
+        );
+
+        // Over-cap revenue stays in escrow until the queued withdrawal is executed.
+        if queued.is_some() {
+            return Ok(0);
         }
 
-        // 8. Emit payment event
+        // Transfer net revenue to the event's payees
+        distribute_to_payees(
+            &env,
+            &registry_client,
+            &event_id,
+            &event_info.payment_address,
+            &token_address,
+            organizer_amount,
+        )?;
+
+        let total_transferred = organizer_amount;
+        if total_transferred > 0 {
+            subtract_from_active_escrow_total(&env, total_transferred);
+            subtract_from_active_escrow_by_token(&env, token_address, total_transferred);
+        }
+
+        #[allow(deprecated)]
         env.events().publish(
-            (AgoraEvent::PaymentProcessed,),
-            PaymentProcessedEvent {
-                payment_id: payment_id.clone(),
-                event_id: event_id.clone(),
-                buyer_address: buyer_address.clone(),
-                amount: effective_total,
-                platform_fee: total_platform_fee,
-                timestamp: env.ledger().timestamp(),
+            (AgoraEvent::RevenueClaimed,),
+            RevenueClaimedEvent {
+                event_id,
+                organizer_address: event_info.organizer_address,
+                amount: organizer_amount,
+                timestamp,
             },
         );
 
-        // 8a. Award loyalty points to buyer (best-effort; ignore failures)
-        match registry_client_promo.try_update_loyalty_score(
-            &env.current_contract_address(),
-            &buyer_address,
-            &quantity,
-            &effective_total,
-            &tier.loyalty_multiplier,
-        ) {
-            Ok(_) | Err(_) => {}
+        Ok(organizer_amount)
+    }
+
+    /// Returns all payments for a specific buyer.
+    pub fn get_buyer_payments(env: Env, buyer_address: Address) -> soroban_sdk::Vec<String> {
+        crate::storage::get_buyer_payments(&env, buyer_address)
+    }
+
+    /// Returns all payment IDs for an event with a specific status.
+    /// This allows querying payments by status (e.g., Pending, Confirmed, Refunded, Failed, CheckedIn).
+    ///
+    /// # Arguments
+    /// * `event_id` - The event ID to query
+    /// * `status` - The payment status to filter by
+    ///
+    /// # Returns
+    /// A vector of payment IDs matching the specified status for the event
+    pub fn get_payments_by_status(
+        env: Env,
+        event_id: String,
+        status: PaymentStatus,
+    ) -> soroban_sdk::Vec<String> {
+        crate::storage::get_payments_by_status(&env, event_id, status)
+    }
+
+    /// Sets the transfer fee for an event. Only the organizer can call this.
+    pub fn set_transfer_fee(
+        env: Env,
+        event_id: String,
+        bps: u32, // Changed from i128 amount to u32 basis points
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
         }
 
-        // 9. Emit discount applied event if a code was used
-        if let Some(hash) = discount_code_hash {
-            let discount_amount = total_amount.checked_sub(effective_total).unwrap_or(0);
-            env.events().publish(
-                (AgoraEvent::DiscountCodeApplied,),
-                DiscountCodeAppliedEvent {
-                    payment_id: payment_id.clone(),
-                    event_id: event_id.clone(),
-                    code_hash: hash,
-                    discount_amount,
-                    timestamp: env.ledger().timestamp(),
-                },
-            );
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+
+        event_info.organizer_address.require_auth();
+
+        if bps > MAX_BPS {
+            return Err(TicketPaymentError::InvalidFeePercent);
+        }
+
+        // Store the basis points, not the calculated amount
+        set_transfer_fee(&env, event_id, bps);
+        Ok(())
+    }
+
+    /// Transfers a ticket from the current holder to a new owner.
+    /// If `sale_price` is provided, it is validated against the event's resale cap.
+    pub fn transfer_ticket(
+        env: Env,
+        payment_id: String,
+        to: Address,
+        sale_price: Option<i128>,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        let event_id = get_payment(&env, payment_id.clone()).map(|p| p.event_id);
+        require_not_paused(&env, PauseScope::Transfers, event_id.as_ref())?;
+
+        let mut payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+
+        if payment.status == PaymentStatus::PartiallyPaid {
+            return Err(TicketPaymentError::PaymentNotFullyPaid);
+        }
+        if payment.status != PaymentStatus::Confirmed {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+
+        let from = payment.buyer_address.clone();
+        from.require_auth();
+
+        // Prevent self-transfer
+        if from == to {
+            return Err(TicketPaymentError::InvalidAddress);
         }
+        // Prevent transfer to the zero address or the contract itself
+        validate_recipient(&env, &to)?;
+
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        require_attestation(
+            &env,
+            &registry_client,
+            &payment.event_id,
+            &payment.ticket_tier_id,
+            &to,
+        )?;
+
+        // Validate resale price against the organizer's cap
+        if let Some(price) = sale_price {
+            let event_registry_addr = get_event_registry(&env);
+            let registry_client = event_registry::Client::new(&env, &event_registry_addr); // Re-initialize client
+
+            let event_info = match registry_client.try_get_event(&payment.event_id) {
+                Ok(Ok(Some(info))) => info,
+                _ => return Err(TicketPaymentError::EventNotFound), // Should not happen if payment exists
+            };
+
+            // Check if resale cap is defined for the event
+            if event_info.resale_cap_bps.is_some() {
+                if let Some(cap_bps) = event_info.resale_cap_bps {
+                    // Look up the original tier face-value price
+                    let tier = event_info
+                        .tiers
+                        .get(payment.ticket_tier_id.clone())
+                        .ok_or(TicketPaymentError::TierNotFound)?;
+                    // Use the original price from the payment record, not the tier's current price,
+                    // as tier prices can change (e.g., early bird expiry).
+                    // The payment.amount is the actual price paid for this specific ticket.
+                    let original_price = tier.price;
+
+                    // max_price = original_price * (MAX_BPS + cap_bps) / MAX_BPS
+                    let max_price = original_price
+                        .checked_mul(
+                            (MAX_BPS as i128)
+                                .checked_add(cap_bps as i128)
+                                .unwrap_or(i128::MAX),
+                        )
+                        .ok_or(TicketPaymentError::ArithmeticError)?
+                        / (MAX_BPS as i128);
 
-        // 10. Emit global promo applied event if promo was active
-        if promo_applied_bps > 0 {
-            let promo_discount_amount = total_amount.checked_sub(after_promo).unwrap_or(0);
-            env.events().publish(
-                (AgoraEvent::GlobalPromoApplied,),
-                GlobalPromoAppliedEvent {
-                    payment_id: payment_id.clone(),
-                    event_id: event_id.clone(),
-                    promo_bps: promo_applied_bps,
-                    discount_amount: promo_discount_amount,
-                    timestamp: env.ledger().timestamp(),
-                },
-            );
+                    if price > max_price {
+                        return Err(TicketPaymentError::ResalePriceExceedsCap);
+                    }
+                }
+            }
         }
 
-        Ok(payment_id)
-    }
+        let transfer_fee_bps =
+            get_transfer_fee(&env, payment.event_id.clone()).unwrap_or(TRANSFER_FEE_BPS);
 
-    /// Confirms a payment after backend verification.
-    pub fn confirm_payment(env: Env, payment_id: String, transaction_hash: String) {
-        if !is_initialized(&env) {
-            panic!("Contract not initialized");
-        }
-        require_admin(&env).expect("Admin not set");
-        if let Some(mut payment) = get_payment(&env, payment_id.clone()) {
-            let old_status = payment.status.clone();
-            payment.status = PaymentStatus::Confirmed;
-            payment.confirmed_at = Some(env.ledger().timestamp());
-            payment.transaction_hash = transaction_hash.clone();
+        let mut actual_transfer_fee: i128 = 0;
 
-            // Update payment in storage
-            let key = DataKey::Payment(payment_id.clone());
-            env.storage().persistent().set(&key, &payment);
+        if transfer_fee_bps > 0 {
+            // Calculate the actual transfer fee based on the original ticket amount
+            actual_transfer_fee = payment
+                .amount
+                .checked_mul(transfer_fee_bps as i128)
+                .and_then(|v| v.checked_div(MAX_BPS as i128))
+                .ok_or(TicketPaymentError::ArithmeticError)?;
 
-            // Update status index
-            if old_status != PaymentStatus::Confirmed {
-                crate::storage::update_payment_status_index(
-                    &env,
-                    payment.event_id.clone(),
-                    old_status.clone(),
-                    PaymentStatus::Confirmed,
-                    payment_id.clone(),
+            if actual_transfer_fee > 0 {
+                let token_address = crate::storage::get_usdc_token(&env);
+                let token_client = token::Client::new(&env, &token_address);
+                let contract_address = env.current_contract_address();
+
+                // Transfer fee from old owner to contract
+                token_client.transfer_from(
+                    &contract_address,
+                    &from,
+                    &contract_address,
+                    &actual_transfer_fee,
                 );
+
+                // Update escrow balances (fee goes to organizer)
+                update_event_balance(&env, payment.event_id.clone(), actual_transfer_fee, 0);
             }
         }
+        // Update payment record
+        payment.buyer_address = to.clone();
+        let key = crate::types::DataKey::Payment(payment_id.clone());
+        env.storage().persistent().set(&key, &payment);
 
-        // Emit confirmation event
+        // Update indices
+        remove_payment_from_buyer_index(&env, from.clone(), payment_id.clone());
+        add_payment_to_buyer_index(&env, to.clone(), payment_id.clone());
+
+        // Emit transfer event
         #[allow(deprecated)]
         env.events().publish(
-            (AgoraEvent::PaymentStatusChanged,),
-            PaymentStatusChangedEvent {
-                payment_id: payment_id.clone(),
-                old_status: PaymentStatus::Pending,
-                new_status: PaymentStatus::Confirmed,
-                transaction_hash: transaction_hash.clone(),
+            (AgoraEvent::TicketTransferred,),
+            TicketTransferredEvent {
+                payment_id,
+                from,
+                to: to.clone(),
+                transfer_fee: actual_transfer_fee,
                 timestamp: env.ledger().timestamp(),
             },
         );
-    }
-
-    pub fn request_guest_refund(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
-        if !is_initialized(&env) {
-            panic!("Contract not initialized");
-        }
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
-        }
-
-        Self::internal_refund(env, payment_id)
-    }
-
-    /// Triggers a refund as an administrator, regardless of dispute status.
-    pub fn admin_refund(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
-        require_admin(&env)?;
 
-        Self::internal_refund(env, payment_id)
+        Ok(())
     }
 
-    /// Public wrapper for automatic refunds, specifically for cancelled events.
-    pub fn claim_automatic_refund(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
+    /// Lets an organizer refund the price difference when a guest downgrades a ticket.
+    /// Disabled by default, in which case downgrades keep the original payment.
+    pub fn set_downgrade_refunds(
+        env: Env,
+        event_id: String,
+        enabled: bool,
+    ) -> Result<(), TicketPaymentError> {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
-        }
-
-        let payment =
-            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
 
-        let event_registry_addr = get_event_registry(&env);
-        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
-
-        let event_info = match registry_client.try_get_event(&payment.event_id) {
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let event_info = match registry_client.try_get_event(&event_id) {
             Ok(Ok(Some(info))) => info,
             _ => return Err(TicketPaymentError::EventNotFound),
         };
+        event_info.organizer_address.require_auth();
 
-        // Ensure the event is cancelled for automatic refund OR goal failed after deadline
-        let current_ts = env.ledger().timestamp();
-        let goal_failed = !event_info.goal_met
-            && event_info.min_sales_target > 0
-            && current_ts > event_info.target_deadline;
-
-        if !matches!(event_info.status, event_registry::EventStatus::Cancelled) && !goal_failed {
-            return Err(TicketPaymentError::InvalidPaymentStatus);
-        }
+        set_downgrade_refunds(&env, event_id, enabled);
+        Ok(())
+    }
 
-        Self::internal_refund(env, payment_id)
+    /// Returns whether downgrades refund the price difference for an event.
+    pub fn get_downgrade_refunds(env: Env, event_id: String) -> bool {
+        get_downgrade_refunds(&env, event_id)
     }
 
-    fn internal_refund(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
+    /// Moves a ticket to another tier of the same event, keeping its payment ID.
+    ///
+    /// Upgrades charge the difference between the new tier's current price and the
+    /// amount paid, split with the event's platform fee. Downgrades refund the
+    /// difference only if the organizer enabled `set_downgrade_refunds`. Inventory
+    /// moves between the tiers in one registry call and loyalty points follow the
+    /// new tier's multiplier.
+    pub fn upgrade_ticket(
+        env: Env,
+        payment_id: String,
+        new_tier_id: String,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
         let mut payment =
             get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        require_not_paused(&env, PauseScope::Payments, Some(&payment.event_id))?;
 
-        payment.buyer_address.require_auth();
-
-        if payment.status == PaymentStatus::Refunded || payment.status == PaymentStatus::Failed {
+        if payment.status != PaymentStatus::Pending && payment.status != PaymentStatus::Confirmed {
             return Err(TicketPaymentError::InvalidPaymentStatus);
         }
+        if payment.ticket_tier_id == new_tier_id {
+            return Err(TicketPaymentError::InvalidTierChange);
+        }
 
-        let event_registry_addr = get_event_registry(&env);
-        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+        let buyer = payment.buyer_address.clone();
+        buyer.require_auth();
 
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
         let event_info = match registry_client.try_get_event(&payment.event_id) {
             Ok(Ok(Some(info))) => info,
             _ => return Err(TicketPaymentError::EventNotFound),
         };
+        if !event_info.is_active
+            || matches!(event_info.status, event_registry::EventStatus::Cancelled)
+        {
+            return Err(TicketPaymentError::EventInactive);
+        }
 
-        let tier = event_info
+        let old_tier = event_info
             .tiers
             .get(payment.ticket_tier_id.clone())
             .ok_or(TicketPaymentError::TierNotFound)?;
+        let new_tier = event_info
+            .tiers
+            .get(new_tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
 
-        let is_cancelled = matches!(event_info.status, event_registry::EventStatus::Cancelled);
-        let current_ts = env.ledger().timestamp();
-        let goal_failed = !event_info.goal_met
-            && event_info.min_sales_target > 0
-            && current_ts > event_info.target_deadline;
+        // Tier-specific allowlists need a proof the holder never gave for this ticket;
+        // an event-wide allowlist was already satisfied by the original purchase.
+        let new_price = tier_change_price(
+            &env,
+            &registry_client,
+            &payment.event_id,
+            &new_tier_id,
+            &new_tier,
+            &buyer,
+            true,
+        )?;
 
-        // Check if refundable or if EVENT IS CANCELLED or GOAL FAILED
-        if !tier.is_refundable && !is_cancelled && !goal_failed && event_info.is_active {
-            return Err(TicketPaymentError::TicketNotRefundable);
+        let fee_bps = event_info
+            .custom_fee_bps
+            .unwrap_or(event_info.platform_fee_percent);
+        let refund_cheaper = get_downgrade_refunds(&env, payment.event_id.clone());
+        let (amount_charged, amount_refunded) = settle_price_difference(
+            &env,
+            &registry_client,
+            &mut payment,
+            new_price,
+            fee_bps,
+            refund_cheaper,
+        )?;
+
+        // Both tier counters move together; a sold-out target tier rejects the change.
+        if registry_client
+            .try_move_inventory(
+                &payment.event_id,
+                &payment.ticket_tier_id,
+                &new_tier_id,
+                &buyer,
+            )
+            .is_err()
+        {
+            return Err(TicketPaymentError::MaxSupplyExceeded);
         }
 
-        // Validate against refund deadline if event is active and not cancelled
-        if !is_cancelled
-            && event_info.is_active
-            && event_info.refund_deadline > 0
-            && env.ledger().timestamp() > event_info.refund_deadline
-        {
-            return Err(TicketPaymentError::RefundDeadlinePassed);
+        let from_tier_id = payment.ticket_tier_id.clone();
+        payment.ticket_tier_id = new_tier_id.clone();
+        let key = DataKey::Payment(payment_id.clone());
+        env.storage().persistent().set(&key, &payment);
+
+        rebase_loyalty(
+            &env,
+            &registry_client,
+            &buyer,
+            old_tier.loyalty_multiplier,
+            new_tier.loyalty_multiplier,
+            amount_charged - amount_refunded,
+        );
+
+        env.events().publish(
+            (AgoraEvent::TicketUpgraded,),
+            TicketUpgradedEvent {
+                payment_id,
+                event_id: payment.event_id,
+                from_tier_id,
+                to_tier_id: new_tier_id,
+                amount_charged,
+                amount_refunded,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Lets holders of an event's tickets exchange them for another event of the same
+    /// organizer, such as a rescheduled show or another date of a series.
+    pub fn set_exchanges_enabled(
+        env: Env,
+        event_id: String,
+        enabled: bool,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
         }
 
-        // Deduct restocking fee if specified (capped at payment amount)
-        // Bypass restocking fee if the event is cancelled or goal failed.
-        let effective_restocking_fee = if is_cancelled || goal_failed {
-            0
-        } else if event_info.restocking_fee > payment.amount {
-            payment.amount
-        } else if event_info.restocking_fee > 0 {
-            event_info.restocking_fee
-        } else {
-            0
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
         };
+        event_info.organizer_address.require_auth();
 
-        let refund_amount = payment
-            .amount
-            .checked_sub(effective_restocking_fee)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
+        set_exchanges_enabled(&env, event_id, enabled);
+        Ok(())
+    }
 
-        // Return ticket to inventory (increments available inventory)
-        registry_client.decrement_inventory(&payment.event_id, &payment.ticket_tier_id);
+    /// Returns whether tickets of an event can be exchanged for another event.
+    pub fn get_exchanges_enabled(env: Env, event_id: String) -> bool {
+        get_exchanges_enabled(&env, event_id)
+    }
 
-        let old_status = payment.status.clone();
-        payment.status = PaymentStatus::Refunded;
-        payment.confirmed_at = Some(env.ledger().timestamp());
+    /// Moves a confirmed ticket to a tier of another event of the same organizer,
+    /// keeping its payment ID.
+    ///
+    /// The organizer must have enabled exchanges on the ticket's current event. The
+    /// payment's escrowed shares move to the new event's balance, then the difference to
+    /// the new tier's current price is settled there as in `upgrade_ticket`, with
+    /// cheaper tickets refunded only if the new event has `set_downgrade_refunds` on.
+    pub fn exchange_ticket(
+        env: Env,
+        payment_id: String,
+        new_event_id: String,
+        new_tier_id: String,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        let mut payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        require_not_paused(&env, PauseScope::Payments, Some(&payment.event_id))?;
+        require_not_paused(&env, PauseScope::Payments, Some(&new_event_id))?;
 
-        // Update payment in storage
-        let key = DataKey::Payment(payment_id.clone());
-        env.storage().persistent().set(&key, &payment);
+        if payment.status != PaymentStatus::Confirmed {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+        if payment.event_id == new_event_id {
+            return Err(TicketPaymentError::InvalidTierChange);
+        }
+        if !get_exchanges_enabled(&env, payment.event_id.clone()) {
+            return Err(TicketPaymentError::ExchangeNotAllowed);
+        }
+        // Add-ons belong to the old event and cannot follow the ticket.
+        if !get_payment_add_ons(&env, payment_id.clone()).is_empty() {
+            return Err(TicketPaymentError::ExchangeNotAllowed);
+        }
 
-        // Update status index
-        if old_status != PaymentStatus::Refunded {
-            crate::storage::update_payment_status_index(
-                &env,
-                payment.event_id.clone(),
-                old_status.clone(),
-                PaymentStatus::Refunded,
-                payment_id.clone(),
-            );
+        let buyer = payment.buyer_address.clone();
+        buyer.require_auth();
+
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let old_event = match registry_client.try_get_event(&payment.event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        let new_event = match registry_client.try_get_event(&new_event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        if !new_event.is_active
+            || matches!(new_event.status, event_registry::EventStatus::Cancelled)
+        {
+            return Err(TicketPaymentError::EventInactive);
         }
-
-        // Process token transfer
-        if refund_amount > 0 {
-            let token_address = crate::storage::get_usdc_token(&env);
-            token::Client::new(&env, &token_address).transfer(
-                &env.current_contract_address(),
-                &payment.buyer_address,
-                &refund_amount,
-            );
+        if old_event.organizer_address != new_event.organizer_address {
+            return Err(TicketPaymentError::ExchangeNotAllowed);
         }
 
-        // Guest receives payment.amount - effective_restocking_fee
-        // Organizer keeps effective_restocking_fee (adjust from original organizer_amount)
-        // Platform fee is refunded (removed from escrow)
-        let org_adjustment = payment
-            .organizer_amount
-            .checked_sub(effective_restocking_fee)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
-        let platform_adjustment = payment.platform_fee;
+        let old_tier = old_event
+            .tiers
+            .get(payment.ticket_tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
+        let new_tier = new_event
+            .tiers
+            .get(new_tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
 
-        crate::storage::update_event_balance(
+        // The original purchase proved nothing about the new event's allowlists.
+        let new_price = tier_change_price(
+            &env,
+            &registry_client,
+            &new_event_id,
+            &new_tier_id,
+            &new_tier,
+            &buyer,
+            false,
+        )?;
+
+        if registry_client
+            .try_exchange_inventory(
+                &payment.event_id,
+                &payment.ticket_tier_id,
+                &new_event_id,
+                &new_tier_id,
+                &buyer,
+            )
+            .is_err()
+        {
+            return Err(TicketPaymentError::MaxSupplyExceeded);
+        }
+
+        // The escrowed shares follow the ticket to its new event.
+        update_event_balance(
             &env,
             payment.event_id.clone(),
-            -org_adjustment,
-            -platform_adjustment,
+            -payment.organizer_amount,
+            -payment.platform_fee,
         );
-
-        subtract_from_active_escrow_total(&env, refund_amount);
-        subtract_from_active_escrow_by_token(
+        update_event_balance(
             &env,
-            crate::storage::get_usdc_token(&env),
-            refund_amount,
+            new_event_id.clone(),
+            payment.organizer_amount,
+            payment.platform_fee,
         );
+        let moved_fee = return_unsettled_fee(
+            &env,
+            &payment.event_id,
+            &payment.token,
+            payment.platform_fee,
+            payment.platform_fee,
+        );
+        add_to_total_fees_collected_by_token(&env, payment.token.clone(), moved_fee);
+        add_to_event_fees_by_token(&env, new_event_id.clone(), payment.token.clone(), moved_fee);
+        move_payment_to_event(&env, &payment, new_event_id.clone());
 
-        // Clear escrow record if both amounts are now zero (fully refunded event)
-        let updated_balance = get_event_balance(&env, payment.event_id.clone());
-        if updated_balance.organizer_amount == 0 && updated_balance.platform_fee == 0 {
-            // Keep the record but ensure it's clean
-            update_event_balance(&env, payment.event_id.clone(), 0, 0);
-        }
+        let from_event_id = payment.event_id.clone();
+        let from_tier_id = payment.ticket_tier_id.clone();
+        payment.event_id = new_event_id.clone();
+        payment.ticket_tier_id = new_tier_id.clone();
+
+        let fee_bps = new_event
+            .custom_fee_bps
+            .unwrap_or(new_event.platform_fee_percent);
+        let refund_cheaper = get_downgrade_refunds(&env, new_event_id.clone());
+        let (amount_charged, amount_refunded) = settle_price_difference(
+            &env,
+            &registry_client,
+            &mut payment,
+            new_price,
+            fee_bps,
+            refund_cheaper,
+        )?;
+
+        let key = DataKey::Payment(payment_id.clone());
+        env.storage().persistent().set(&key, &payment);
+
+        rebase_loyalty(
+            &env,
+            &registry_client,
+            &buyer,
+            old_tier.loyalty_multiplier,
+            new_tier.loyalty_multiplier,
+            amount_charged - amount_refunded,
+        );
 
-        // Emit confirmation event
-        #[allow(deprecated)]
         env.events().publish(
-            (AgoraEvent::PaymentStatusChanged,),
-            PaymentStatusChangedEvent {
-                payment_id: payment_id.clone(),
-                old_status,
-                new_status: PaymentStatus::Refunded,
-                transaction_hash: String::from_str(&env, "refund"),
+            (AgoraEvent::TicketExchanged,),
+            TicketExchangedEvent {
+                payment_id,
+                from_event_id,
+                from_tier_id,
+                to_event_id: new_event_id,
+                to_tier_id: new_tier_id,
+                amount_charged,
+                amount_refunded,
                 timestamp: env.ledger().timestamp(),
             },
         );
 
         Ok(())
     }
-    pub fn get_payment_status(env: Env, payment_id: String) -> Option<Payment> {
-        get_payment(&env, payment_id)
+
+    /// Sets or clears the store credit an organizer offers instead of cash refunds.
+    pub fn set_credit_offer(
+        env: Env,
+        organizer: Address,
+        offer: Option<CreditOffer>,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        organizer.require_auth();
+
+        if offer.as_ref().is_some_and(|o| o.bonus_bps > MAX_BPS) {
+            return Err(TicketPaymentError::InvalidCreditOffer);
+        }
+
+        set_credit_offer(&env, &organizer, offer);
+        Ok(())
     }
 
-    // pub fn check_in(
-    //     env: Env,
-    //     payment_id: String,
-    //     scanner: Address,
-    //     // Optionally, a series_id and pass_holder for series pass check-in
-    //     series_id: Option<String>,
-    //     pass_holder: Option<Address>,
-    // ) -> Result<(), TicketPaymentError> {
-    //     if !is_initialized(&env) {
-    //         panic!("Contract not initialized");
-    //     }
-    //     if is_paused(&env) {
-    //         return Err(TicketPaymentError::ContractPaused);
-    //     }
+    /// Returns the store credit an organizer offers instead of cash refunds.
+    pub fn get_credit_offer(env: Env, organizer: Address) -> Option<CreditOffer> {
+        get_credit_offer(&env, &organizer)
+    }
 
-    //     // If series_id and pass_holder are provided, check for a valid series pass
-    //     if let (Some(series_id), Some(holder)) = (series_id.clone(), pass_holder.clone()) {
-    //         // Call EventRegistry to verify pass
-    //         let event_registry_addr = get_event_registry(&env);
-    //         let registry_client = event_registry::Client::new(&env, &event_registry_addr);
-    //         // Check if event is part of the series
-    //         let series_opt: Option<_> = registry_client.get_series(&series_id);
-    //         if let Some(series) = series_opt {
-    //             let found = series.event_ids.iter().any(|eid| eid == payment_id);
-    //             if !found {
-    //                 return Err(TicketPaymentError::EventNotFound);
-    //             }
-    //             // Get the pass for the holder
-    //             let pass_opt = registry_client.get_holder_series_pass(&holder, &series_id);
-    //             if let Some(mut pass) = pass_opt {
-    //                 // Check expiry
-    //                 if pass.expires_at > 0 && env.ledger().timestamp() > pass.expires_at {
-    //                     return Err(TicketPaymentError::InvalidPaymentStatus); // Use a better error if available
-    //                 }
-    //                 // Check usage limit
-    //                 if pass.usage_count >= pass.usage_limit {
-    //                     return Err(TicketPaymentError::TicketAlreadyUsed);
-    //                 }
-    //                 // Increment usage
-    //                 let _ = registry_client.increment_series_pass_usage(&pass.pass_id);
-    //                 // Emit event for series pass check-in (optional)
-    //                 // (You may want to add a new event type for this)
-    //                 return Ok(());
-    //             } else {
-    //                 return Err(TicketPaymentError::PaymentNotFound);
-    //             }
-    //         } else {
-    //             return Err(TicketPaymentError::EventNotFound);
-    //         }
-    //     }
+    /// Returns a guest's store credit with an organizer; expired credit reads as zero.
+    pub fn get_credit_balance(env: Env, guest: Address, organizer: Address) -> CreditBalance {
+        let balance = get_credit_balance(&env, &guest, &organizer);
+        if balance.expires_at > 0 && env.ledger().timestamp() > balance.expires_at {
+            return CreditBalance {
+                amount: 0,
+                expires_at: balance.expires_at,
+            };
+        }
+        balance
+    }
 
-    //     // Fallback: normal ticket check-in
-    //     let mut payment =
-    //         get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+    /// Sends a guest's expired store credit to the organizer. Callable by anyone.
+    /// Returns the amount released, or 0 if the credit has not expired.
+    pub fn release_expired_credit(
+        env: Env,
+        guest: Address,
+        organizer: Address,
+    ) -> Result<i128, TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        require_not_paused(&env, PauseScope::Payments, None)?;
+        Ok(release_expired_credit(&env, &guest, &organizer))
+    }
 
-    //     // Must authenticate the scanner wallet calling this entry point
-    //     scanner.require_auth();
+    /// Sets or clears where an event's donations go and whether the platform fee
+    /// applies to them. Only the organizer can call this.
+    pub fn set_donation_config(
+        env: Env,
+        event_id: String,
+        config: Option<DonationConfig>,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
 
-    //     if payment.status == PaymentStatus::CheckedIn {
-    //         return Err(TicketPaymentError::TicketAlreadyUsed);
-    //     }
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        event_info.organizer_address.require_auth();
 
-    //     // Verify scanner authorization
-    //     let event_registry_addr = get_event_registry(&env);
-    //     let registry_client = event_registry::Client::new(&env, &event_registry_addr);
-    //     let is_auth = registry_client.is_scanner_authorized(&payment.event_id, &scanner);
-    //     if !is_auth {
-    //         return Err(TicketPaymentError::UnauthorizedScanner);
-    //     }
+        if let Some(config) = &config {
+            validate_recipient(&env, &config.recipient)?;
+        }
 
-    //     // Update status and store arrival timestamp
-    //     payment.status = PaymentStatus::CheckedIn;
-    //     payment.confirmed_at = Some(env.ledger().timestamp());
+        set_donation_config(&env, event_id, config);
+        Ok(())
+    }
 
-    //     store_payment(&env, payment.clone());
+    /// Returns where an event's donations go, if donations are enabled.
+    pub fn get_donation_config(env: Env, event_id: String) -> Option<DonationConfig> {
+        get_donation_config(&env, event_id)
+    }
 
-    //     #[allow(deprecated)]
-    //     env.events().publish(
-    //         (AgoraEvent::TicketCheckedIn,),
-    //         crate::events::TicketCheckedInEvent {
-    //             payment_id,
-    //             event_id: payment.event_id,
-    //             scanner,
-    //             timestamp: env.ledger().timestamp(),
-    //         },
-    //     );
+    /// Returns an event's gross donations per token, for receipts.
+    pub fn get_donation_totals(env: Env, event_id: String) -> Map<Address, i128> {
+        get_donation_totals(&env, event_id)
+    }
 
-    //     Ok(())
-    // }
+    /// Buys add-on items (parking, merchandise, meal vouchers) against an
+    /// existing ticket, paid in the refund token. Returns the amount paid.
+    pub fn purchase_add_on(
+        env: Env,
+        payment_id: String,
+        addon_id: String,
+        quantity: u32,
+    ) -> Result<i128, TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        let payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        require_not_paused(&env, PauseScope::Payments, Some(&payment.event_id))?;
 
-    /// Verifies scanner authorization and marks a ticket as CheckedIn.
-    pub fn check_in(
+        if !matches!(
+            payment.status,
+            PaymentStatus::Pending | PaymentStatus::Confirmed | PaymentStatus::CheckedIn
+        ) {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+        payment.buyer_address.require_auth();
+
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let event_info = match registry_client.try_get_event(&payment.event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        if !event_info.is_active
+            || matches!(event_info.status, event_registry::EventStatus::Cancelled)
+        {
+            return Err(TicketPaymentError::EventInactive);
+        }
+        let fee_bps = event_info
+            .custom_fee_bps
+            .unwrap_or(event_info.platform_fee_percent);
+
+        buy_add_on(
+            &env,
+            &registry_client,
+            &payment,
+            fee_bps,
+            &addon_id,
+            quantity,
+        )
+    }
+
+    /// Hands out add-on items at the venue. Scanners count redemptions apart
+    /// from ticket check-in, so items can be collected before or after it.
+    pub fn redeem_add_on(
         env: Env,
         payment_id: String,
+        addon_id: String,
         scanner: Address,
-        _series_id: Option<String>,
-        _pass_holder: Option<Address>,
+        quantity: u32,
     ) -> Result<(), TicketPaymentError> {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
+        let payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        require_not_paused(&env, PauseScope::CheckIn, Some(&payment.event_id))?;
+
+        scanner.require_auth();
+
+        if payment.status == PaymentStatus::Refunded || payment.status == PaymentStatus::Failed {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
         }
 
-        // ── Temporarily disabled ── series pass logic ────────────────────────
-        /*
-        if let (Some(series_id), Some(holder)) = (series_id, pass_holder) {
-            let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
-            let series_opt = registry_client.get_series(&series_id);           // ← does not exist
-            // ...
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        if !registry_client.is_scanner_authorized(&payment.event_id, &scanner) {
+            return Err(TicketPaymentError::UnauthorizedScanner);
         }
-        */
 
-        // Normal single-ticket check-in
-        let mut payment =
-            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        let mut purchase = get_add_on_purchase(&env, payment_id.clone(), addon_id.clone())
+            .ok_or(TicketPaymentError::AddOnNotFound)?;
+        let redeemed = purchase.redeemed.saturating_add(quantity);
+        if quantity == 0 || redeemed + purchase.refunded > purchase.quantity {
+            return Err(TicketPaymentError::AddOnFullyRedeemed);
+        }
+        purchase.redeemed = redeemed;
+        set_add_on_purchase(&env, payment_id.clone(), addon_id.clone(), &purchase);
+        add_add_on_redemptions(&env, payment.event_id.clone(), addon_id.clone(), quantity);
 
-        scanner.require_auth();
+        env.events().publish(
+            (AgoraEvent::AddOnRedeemed,),
+            AddOnRedeemedEvent {
+                payment_id,
+                event_id: payment.event_id,
+                addon_id,
+                quantity,
+                scanner,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the add-on items bought against a ticket.
+    pub fn get_add_on_purchase(
+        env: Env,
+        payment_id: String,
+        addon_id: String,
+    ) -> Option<AddOnPurchase> {
+        get_add_on_purchase(&env, payment_id, addon_id)
+    }
+
+    /// Returns the IDs of every add-on bought against a ticket.
+    pub fn get_payment_add_ons(env: Env, payment_id: String) -> Vec<String> {
+        get_payment_add_ons(&env, payment_id)
+    }
+
+    /// Returns the items redeemed per add-on at an event.
+    pub fn get_add_on_redemptions(env: Env, event_id: String) -> Map<String, u32> {
+        get_add_on_redemptions(&env, event_id)
+    }
 
-        if payment.status == PaymentStatus::CheckedIn {
-            return Err(TicketPaymentError::TicketAlreadyUsed);
+    /// RSVPs for a free tier that asks for a refundable deposit. The guest
+    /// escrows the deposit in the refund token; `check_in` or a refund returns
+    /// it, and `sweep_unclaimed_deposits` forfeits it once the event has ended.
+    pub fn rsvp_with_deposit(
+        env: Env,
+        payment_id: String,
+        event_id: String,
+        tier_id: String,
+        guest: Address,
+        options: Option<PurchaseOptions>,
+    ) -> Result<String, TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
         }
+        require_not_paused(&env, PauseScope::Payments, Some(&event_id))?;
+        guest.require_auth();
 
         let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
-        if !registry_client.is_scanner_authorized(&payment.event_id, &scanner) {
-            return Err(TicketPaymentError::UnauthorizedScanner);
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        if !event_info.is_active
+            || matches!(event_info.status, event_registry::EventStatus::Cancelled)
+        {
+            return Err(TicketPaymentError::EventInactive);
         }
-
-        // Check if the event has ended (prevent check-ins after end_time)
-        let event_info = registry_client
-            .try_get_event(&payment.event_id)
+        if !event_info.tiers.contains_key(tier_id.clone()) {
+            return Err(TicketPaymentError::TierNotFound);
+        }
+        let deposit = registry_client
+            .try_get_tier_deposit(&event_id, &tier_id)
             .ok()
             .and_then(|r| r.ok())
             .flatten()
-            .ok_or(TicketPaymentError::EventNotFound)?;
+            .ok_or(TicketPaymentError::DepositNotRequired)?;
 
-        let current_time = env.ledger().timestamp();
-        if event_info.end_time > 0 && current_time > event_info.end_time {
-            return Err(TicketPaymentError::EventEnded);
+        let (allowlist_gate, allowlist_purchased) = check_allowlist(
+            &env,
+            &registry_client,
+            &event_id,
+            &tier_id,
+            &guest,
+            1,
+            options.as_ref(),
+        )?;
+        require_attestation(&env, &registry_client, &event_id, &tier_id, &guest)?;
+
+        let token_address = get_usdc_token(&env);
+        let token_client = token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+        if token_client.allowance(&guest, &contract_address) < deposit.amount {
+            return Err(TicketPaymentError::InsufficientAllowance);
         }
+        token_client.transfer_from(
+            &contract_address,
+            &guest,
+            &contract_address,
+            &deposit.amount,
+        );
+        add_to_active_escrow_total(&env, deposit.amount);
+        add_to_active_escrow_by_token(&env, token_address, deposit.amount);
 
-        payment.status = PaymentStatus::CheckedIn;
-        payment.confirmed_at = Some(env.ledger().timestamp());
-        store_payment(&env, payment);
+        registry_client.increment_inventory(&event_id, &tier_id, &1);
+        if let Some(gate) = allowlist_gate {
+            set_allowlist_purchases(
+                &env,
+                event_id.clone(),
+                gate.tier_id,
+                &guest,
+                allowlist_purchased,
+            );
+        }
 
-        // env.events().publish(
-        //     (AgoraEvent::TicketCheckedIn,),
-        //     crate::events::TicketCheckedInEvent {
-        //             payment_id,
-        //             // event_id: payment.event_id.clone(),
-        //             scanner,
-        //             timestamp: env.ledger().timestamp(),
-        //         },
-        // );
+        store_payment(
+            &env,
+            Payment {
+                payment_id: payment_id.clone(),
+                event_id: event_id.clone(),
+                buyer_address: guest.clone(),
+                ticket_tier_id: tier_id,
+                token: get_usdc_token(&env),
+                amount: 0,
+                platform_fee: 0,
+                organizer_amount: 0,
+                status: PaymentStatus::Pending,
+                transaction_hash: String::from_str(&env, ""),
+                created_at: env.ledger().timestamp(),
+                confirmed_at: None,
+                refunded_amount: 0,
+            },
+        );
+        set_held_deposit(
+            &env,
+            payment_id.clone(),
+            &HeldDeposit {
+                amount: deposit.amount,
+                forfeit_to: deposit
+                    .forfeit_recipient
+                    .unwrap_or(event_info.organizer_address),
+            },
+        );
 
-        Ok(())
+        env.events().publish(
+            (AgoraEvent::DepositHeld,),
+            DepositHeldEvent {
+                payment_id: payment_id.clone(),
+                event_id,
+                guest,
+                amount: deposit.amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(payment_id)
     }
-    /// Returns the escrowed balance for an event.
-    pub fn get_event_escrow_balance(env: Env, event_id: String) -> crate::types::EventBalance {
-        get_event_balance(&env, event_id)
+
+    /// Returns the RSVP deposit still held for a ticket, if any.
+    pub fn get_held_deposit(env: Env, payment_id: String) -> Option<HeldDeposit> {
+        get_held_deposit(&env, payment_id)
     }
 
-    /// Withdraw organizer funds from escrow.
-    pub fn withdraw_organizer_funds(
+    /// Sends deposits nobody checked in for to their forfeit recipient, the
+    /// organizer or a charity, once the event has ended. Permissionless and
+    /// processed in batches of the event's payments; returns how many were swept.
+    pub fn sweep_unclaimed_deposits(
         env: Env,
         event_id: String,
-        token_address: Address,
-    ) -> Result<i128, TicketPaymentError> {
-        let event_registry_addr = get_event_registry(&env);
-        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
-        let event_info = registry_client
-            .try_get_event(&event_id)
-            .ok()
-            .and_then(|r| r.ok())
-            .flatten()
-            .ok_or(TicketPaymentError::EventNotFound)?;
-
-        event_info.organizer_address.require_auth();
-
-        let balance = get_event_balance(&env, event_id.clone());
-        // Block all claim_revenue attempts for an event while a dispute is active.
-        if is_event_disputed(&env, event_id.clone()) {
-            return Err(TicketPaymentError::EventDisputed);
+        batch_size: u32,
+    ) -> Result<u32, TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
         }
+        require_not_paused(&env, PauseScope::Withdrawals, Some(&event_id))?;
 
-        // Block any further organizer payouts once an event is in the Cancelled state.
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        // Guests of a cancelled event reclaim their deposits through refunds.
         if matches!(event_info.status, event_registry::EventStatus::Cancelled) {
-            return Err(TicketPaymentError::EventCancelled);
+            return Err(TicketPaymentError::EventInactive);
         }
-
-        // Check if goal was met if a target was set
-        if event_info.min_sales_target > 0 && !event_info.goal_met {
-            return Err(TicketPaymentError::GoalNotMet);
+        if event_info.end_time == 0 || env.ledger().timestamp() <= event_info.end_time {
+            return Err(TicketPaymentError::EventNotCompleted);
         }
 
-        let total_revenue = balance
-            .organizer_amount
-            .checked_add(balance.total_withdrawn)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
-        if total_revenue == 0 {
+        let start_index = get_deposit_sweep_index(&env, event_id.clone());
+        let payment_ids = get_event_payments(&env, event_id.clone());
+        let end_index = core::cmp::min(start_index.saturating_add(batch_size), payment_ids.len());
+        if start_index >= end_index {
             return Ok(0);
         }
 
-        let mut release_percent = MAX_BPS;
-        if let Some(milestones) = event_info.milestone_plan {
-            let mut highest_met = 0u32;
-            for milestone in milestones.iter() {
-                if event_info.current_supply >= milestone.sales_threshold
-                    && milestone.release_percent > highest_met
-                {
-                    highest_met = milestone.release_percent;
-                }
-            }
-            if !milestones.is_empty() {
-                release_percent = highest_met;
+        let token_address = get_usdc_token(&env);
+        let token_client = token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+        let mut count = 0u32;
+        let mut total = 0i128;
+        for i in start_index..end_index {
+            let payment_id = payment_ids.get(i).unwrap();
+            if let Some(deposit) = get_held_deposit(&env, payment_id.clone()) {
+                token_client.transfer(&contract_address, &deposit.forfeit_to, &deposit.amount);
+                remove_held_deposit(&env, payment_id);
+                total += deposit.amount;
+                count += 1;
             }
         }
+        set_deposit_sweep_index(&env, event_id.clone(), end_index);
 
-        let max_allowed = total_revenue
-            .checked_mul(release_percent as i128)
-            .and_then(|v| v.checked_div(MAX_BPS as i128))
-            .ok_or(TicketPaymentError::ArithmeticError)?;
-        let mut available_to_withdraw = max_allowed
-            .checked_sub(balance.total_withdrawn)
-            .ok_or(TicketPaymentError::ArithmeticError)?;
-
-        if available_to_withdraw <= 0 {
-            return Ok(0);
-        }
-
-        if available_to_withdraw > balance.organizer_amount {
-            available_to_withdraw = balance.organizer_amount;
+        if total > 0 {
+            subtract_from_active_escrow_total(&env, total);
+            subtract_from_active_escrow_by_token(&env, token_address, total);
         }
 
-        token::Client::new(&env, &token_address).transfer(
-            &env.current_contract_address(),
-            &event_info.organizer_address,
-            &available_to_withdraw,
-        );
-
-        crate::storage::set_event_balance(
-            &env,
-            event_id,
-            crate::types::EventBalance {
-                organizer_amount: balance
-                    .organizer_amount
-                    .checked_sub(available_to_withdraw)
-                    .ok_or(TicketPaymentError::ArithmeticError)?,
-                total_withdrawn: balance
-                    .total_withdrawn
-                    .checked_add(available_to_withdraw)
-                    .ok_or(TicketPaymentError::ArithmeticError)?,
-                platform_fee: balance.platform_fee,
+        env.events().publish(
+            (AgoraEvent::DepositsSwept,),
+            DepositsSweptEvent {
+                event_id,
+                count,
+                total,
+                timestamp: env.ledger().timestamp(),
             },
         );
-        subtract_from_active_escrow_total(&env, available_to_withdraw);
-        subtract_from_active_escrow_by_token(&env, token_address, available_to_withdraw);
 
-        Ok(available_to_withdraw)
+        Ok(count)
     }
 
-    /// Settles platform fees from an event escrow into the global treasury pool.
-    pub fn settle_platform_fees(
+    /// Buys a ticket on its tier's installment plan. The first installment
+    /// reserves the ticket in `PartiallyPaid` status; the rest are paid with
+    /// `pay_installment` by the plan's deadlines, and the ticket becomes
+    /// `Pending` like any other purchase once fully paid.
+    pub fn buy_with_installments(
         env: Env,
+        payment_id: String,
         event_id: String,
-        _token_address: Address,
-    ) -> Result<i128, TicketPaymentError> {
-        require_admin(&env)?;
+        tier_id: String,
+        buyer: Address,
+        options: Option<PurchaseOptions>,
+    ) -> Result<String, TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        require_not_paused(&env, PauseScope::Payments, Some(&event_id))?;
+        buyer.require_auth();
+        if get_payment(&env, payment_id.clone()).is_some() {
+            return Err(TicketPaymentError::PaymentAlreadyExists);
+        }
 
-        let balance = get_event_balance(&env, event_id.clone());
-        if balance.platform_fee == 0 {
-            return Ok(0);
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        if !event_info.is_active
+            || matches!(event_info.status, event_registry::EventStatus::Cancelled)
+        {
+            return Err(TicketPaymentError::EventInactive);
         }
+        let tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
+        let plan = registry_client
+            .try_get_installment_plan(&event_id, &tier_id)
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+            .ok_or(TicketPaymentError::InstallmentsNotOffered)?;
 
-        // We clarify that these are now "Settled" but they remain in the contract
-        // until a bulk withdrawal is made via `withdraw_platform_fees`.
-        crate::storage::set_event_balance(
+        let (allowlist_gate, allowlist_purchased) = check_allowlist(
             &env,
-            event_id.clone(),
-            crate::types::EventBalance {
-                organizer_amount: balance.organizer_amount,
-                total_withdrawn: balance.total_withdrawn,
-                platform_fee: 0,
-            },
-        );
+            &registry_client,
+            &event_id,
+            &tier_id,
+            &buyer,
+            1,
+            options.as_ref(),
+        )?;
+        require_attestation(&env, &registry_client, &event_id, &tier_id, &buyer)?;
 
-        // Emit settlement event
-        #[allow(deprecated)]
-        env.events().publish(
-            (AgoraEvent::FeeSettled,),
-            FeeSettledEvent {
-                event_id,
-                platform_wallet: get_platform_wallet(&env),
-                fee_amount: balance.platform_fee,
-                fee_bps: 0, // Not applicable here
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        if tier.usd_price > 0 {
+            return Err(TicketPaymentError::InvalidPrice);
+        }
+        let now = env.ledger().timestamp();
+        let total = if tier.early_bird_deadline > 0 && now <= tier.early_bird_deadline {
+            tier.early_bird_price
+        } else {
+            tier.price
+        };
+
+        registry_client.increment_inventory(&event_id, &tier_id, &1);
+        if let Some(gate) = allowlist_gate {
+            set_allowlist_purchases(
+                &env,
+                event_id.clone(),
+                gate.tier_id,
+                &buyer,
+                allowlist_purchased,
+            );
+        }
+
+        let mut payment = Payment {
+            payment_id: payment_id.clone(),
+            event_id,
+            buyer_address: buyer,
+            ticket_tier_id: tier_id,
+            token: get_usdc_token(&env),
+            amount: 0,
+            platform_fee: 0,
+            organizer_amount: 0,
+            status: PaymentStatus::PartiallyPaid,
+            transaction_hash: String::from_str(&env, ""),
+            created_at: now,
+            confirmed_at: None,
+            refunded_amount: 0,
+        };
+        let mut schedule = InstallmentSchedule {
+            total,
+            paid: 0,
+            installments: plan.due_dates.len() + 1,
+            installments_paid: 0,
+            due_dates: plan.due_dates,
+            penalty_bps: plan.penalty_bps,
+            fee_bps: event_info
+                .custom_fee_bps
+                .unwrap_or(event_info.platform_fee_percent),
+        };
+        collect_installment(&env, &mut payment, &mut schedule)?;
+        store_payment(&env, payment);
+        set_installment_schedule(&env, payment_id.clone(), &schedule);
 
-        Ok(balance.platform_fee)
+        Ok(payment_id)
     }
 
-    /// Withdraw accumulated platform fees from the contract treasury.
-    /// Incorporates a daily withdrawal cap and requires admin (multi-sig) authorization.
-    pub fn withdraw_platform_fees(
-        env: Env,
-        amount: i128,
-        token_address: Address,
-    ) -> Result<(), TicketPaymentError> {
-        require_admin(&env)?;
+    /// Pays the next installment of a ticket before its deadline. Returns the
+    /// amount paid.
+    pub fn pay_installment(env: Env, payment_id: String) -> Result<i128, TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        let mut payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        require_not_paused(&env, PauseScope::Payments, Some(&payment.event_id))?;
 
-        if amount <= 0 {
-            return Err(TicketPaymentError::ArithmeticError);
+        if payment.status != PaymentStatus::PartiallyPaid {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+        payment.buyer_address.require_auth();
+
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let event_info = match registry_client.try_get_event(&payment.event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        if !event_info.is_active
+            || matches!(event_info.status, event_registry::EventStatus::Cancelled)
+        {
+            return Err(TicketPaymentError::EventInactive);
         }
 
-        // 1. Verify that the amount requested is less than or equal to the recorded total_fees_collected.
-        let total_accumulated = get_total_fees_collected_by_token(&env, token_address.clone());
-        if amount > total_accumulated {
-            return Err(TicketPaymentError::InsufficientFees);
+        let mut schedule = get_installment_schedule(&env, payment_id.clone())
+            .ok_or(TicketPaymentError::InstallmentsNotOffered)?;
+        let due_date = schedule
+            .due_dates
+            .get(schedule.installments_paid - 1)
+            .unwrap_or(0);
+        if env.ledger().timestamp() > due_date {
+            return Err(TicketPaymentError::InstallmentOverdue);
         }
 
-        // 2. Incorporate a 'Withdrawal Cap' per day.
-        let cap = get_withdrawal_cap(&env, token_address.clone());
-        if cap > 0 {
-            let current_day = env.ledger().timestamp() / 86400;
-            let already_withdrawn =
-                get_daily_withdrawn_amount(&env, token_address.clone(), current_day);
-            if already_withdrawn
-                .checked_add(amount)
-                .ok_or(TicketPaymentError::ArithmeticError)?
-                > cap
-            {
-                return Err(TicketPaymentError::WithdrawalCapExceeded);
-            }
-            add_to_daily_withdrawn_amount(&env, token_address.clone(), current_day, amount);
-        }
+        let amount = collect_installment(&env, &mut payment, &mut schedule)?;
+        set_installment_schedule(&env, payment_id.clone(), &schedule);
 
-        // 3. Process the transfer
-        let platform_wallet = get_platform_wallet(&env);
-        token::Client::new(&env, &token_address).transfer(
-            &env.current_contract_address(),
-            &platform_wallet,
-            &amount,
-        );
+        if schedule.installments_paid == schedule.installments {
+            payment.status = PaymentStatus::Pending;
+            crate::storage::update_payment_status_index(
+                &env,
+                payment.event_id.clone(),
+                PaymentStatus::PartiallyPaid,
+                PaymentStatus::Pending,
+                payment_id.clone(),
+            );
 
-        // 4. Update global accounting
-        subtract_from_total_fees_collected_by_token(&env, token_address.clone(), amount);
-        subtract_from_active_escrow_total(&env, amount);
-        subtract_from_active_escrow_by_token(&env, token_address, amount);
+            // Loyalty points are earned once the ticket is fully paid (best-effort).
+            let multiplier = event_info
+                .tiers
+                .get(payment.ticket_tier_id.clone())
+                .map(|t| t.loyalty_multiplier)
+                .unwrap_or(1);
+            let _ = registry_client.try_update_loyalty_score(
+                &env.current_contract_address(),
+                &payment.buyer_address,
+                &1,
+                &schedule.total,
+                &multiplier,
+            );
+        }
+        store_payment(&env, payment);
 
-        Ok(())
+        Ok(amount)
     }
 
-    /// Sets a daily withdrawal cap for a specific token.
-    pub fn set_withdrawal_cap(
-        env: Env,
-        token: Address,
-        amount: i128,
-    ) -> Result<(), TicketPaymentError> {
-        require_admin(&env)?;
-
-        if amount < 0 {
-            return Err(TicketPaymentError::ArithmeticError);
-        }
-
-        set_withdrawal_cap(&env, token, amount);
-        Ok(())
+    /// Returns the installment schedule of a ticket bought on a plan.
+    pub fn get_installment_schedule(env: Env, payment_id: String) -> Option<InstallmentSchedule> {
+        get_installment_schedule(&env, payment_id)
     }
 
-    /// Claim revenue after event completion.
-    pub fn claim_revenue(
+    /// Forfeits a ticket whose next installment is overdue, returning it to
+    /// inventory. The organizer keeps the plan's penalty share of the amount
+    /// paid and the rest is refunded to the buyer. Once the event is cancelled
+    /// the ticket can be forfeited at any time and the full amount paid is
+    /// refunded. Permissionless; returns the amount refunded.
+    pub fn forfeit_installment_plan(
         env: Env,
-        event_id: String,
-        token_address: Address,
+        payment_id: String,
     ) -> Result<i128, TicketPaymentError> {
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
-        }
-        let event_registry_addr = get_event_registry(&env);
-        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
-
-        let event_info = registry_client
-            .try_get_event(&event_id)
-            .ok()
-            .and_then(|r| r.ok())
-            .flatten()
-            .ok_or(TicketPaymentError::EventNotFound)?;
-
-        event_info.organizer_address.require_auth();
-
-        if event_info.is_active
-            || !matches!(event_info.status, event_registry::EventStatus::Inactive)
-        {
-            return Err(TicketPaymentError::EventNotCompleted);
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
         }
+        let mut payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        require_not_paused(&env, PauseScope::Refunds, Some(&payment.event_id))?;
 
-        // Check if goal was met if a target was set
-        if event_info.min_sales_target > 0 && !event_info.goal_met {
-            return Err(TicketPaymentError::GoalNotMet);
+        if payment.status != PaymentStatus::PartiallyPaid {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
         }
+        let schedule = get_installment_schedule(&env, payment_id.clone())
+            .ok_or(TicketPaymentError::InstallmentsNotOffered)?;
 
-        let balance = get_event_balance(&env, event_id.clone());
-        if balance.organizer_amount == 0 && balance.platform_fee == 0 {
-            return Err(TicketPaymentError::NoFundsAvailable);
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let is_cancelled = match registry_client.try_get_event(&payment.event_id) {
+            Ok(Ok(Some(info))) => matches!(info.status, event_registry::EventStatus::Cancelled),
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        let due_date = schedule
+            .due_dates
+            .get(schedule.installments_paid - 1)
+            .unwrap_or(0);
+        if !is_cancelled && env.ledger().timestamp() <= due_date {
+            return Err(TicketPaymentError::InstallmentNotOverdue);
         }
 
-        let platform_wallet = get_platform_wallet(&env);
-        let token_client = token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
-        let timestamp = env.ledger().timestamp();
-
-        let platform_fee_amount = balance.platform_fee;
-        let organizer_amount = balance.organizer_amount;
-
-        // If the organizer's remaining balance is at or below the dust threshold,
-        // sweep the full contract balance for this event to avoid leaving tiny amounts.
-        let contract_token_balance = token_client.balance(&contract_address);
-        let organizer_amount = if organizer_amount > 0
-            && organizer_amount <= DUST_THRESHOLD
-            && contract_token_balance >= organizer_amount
-        {
-            contract_token_balance
-                .checked_sub(platform_fee_amount)
-                .unwrap_or(organizer_amount)
-                .max(organizer_amount)
+        // A cancelled event waives the penalty.
+        let penalty = if is_cancelled {
+            0
         } else {
-            organizer_amount
+            schedule
+                .paid
+                .checked_mul(schedule.penalty_bps as i128)
+                .and_then(|v| v.checked_div(MAX_BPS as i128))
+                .ok_or(TicketPaymentError::ArithmeticError)?
         };
+        let refund = schedule.paid - penalty;
 
-        // Settlement logic: platform fees stay in the contract but are cleared from EventBalance.
-        // They are already tracked in TotalFeesCollected.
-        if platform_fee_amount > 0 {
-            #[allow(deprecated)]
-            env.events().publish(
-                (AgoraEvent::FeeSettled,),
-                FeeSettledEvent {
-                    event_id: event_id.clone(),
-                    platform_wallet: platform_wallet.clone(),
-                    fee_amount: platform_fee_amount,
-                    fee_bps: event_info
-                        .custom_fee_bps
-                        .unwrap_or(event_info.platform_fee_percent),
-                    timestamp,
-                },
+        registry_client.decrement_inventory(&payment.event_id, &payment.ticket_tier_id);
+
+        let token_address = get_usdc_token(&env);
+        if refund > 0 {
+            token::Client::new(&env, &token_address).transfer(
+                &env.current_contract_address(),
+                &payment.buyer_address,
+                &refund,
             );
         }
 
-        // Transfer net revenue to organizer
-        if organizer_amount > 0 {
-            token_client.transfer(
-                &contract_address,
-                &event_info.payment_address,
-                &organizer_amount,
+        // The organizer keeps the penalty; the platform fee is returned with the rest.
+        let unsettled_fee = core::cmp::min(
+            payment.platform_fee,
+            get_event_balance(&env, payment.event_id.clone())
+                .platform_fee
+                .max(0),
+        );
+        if unsettled_fee > 0 {
+            subtract_from_total_fees_collected_by_token(&env, token_address.clone(), unsettled_fee);
+            subtract_from_event_fees_by_token(
+                &env,
+                payment.event_id.clone(),
+                token_address.clone(),
+                unsettled_fee,
             );
         }
-
-        // Update balances
-        crate::storage::set_event_balance(
+        update_event_balance(
             &env,
-            event_id.clone(),
-            crate::types::EventBalance {
-                organizer_amount: 0,
-                total_withdrawn: balance.total_withdrawn + organizer_amount,
-                platform_fee: 0,
-            },
+            payment.event_id.clone(),
+            -(payment.organizer_amount - penalty),
+            -payment.platform_fee,
         );
+        subtract_from_active_escrow_total(&env, refund);
+        subtract_from_active_escrow_by_token(&env, token_address, refund);
 
-        let total_transferred = organizer_amount;
-        if total_transferred > 0 {
-            subtract_from_active_escrow_total(&env, total_transferred);
-            subtract_from_active_escrow_by_token(&env, token_address, total_transferred);
-        }
+        payment.status = PaymentStatus::Refunded;
+        payment.refunded_amount = refund;
+        payment.confirmed_at = Some(env.ledger().timestamp());
+        crate::storage::update_payment_status_index(
+            &env,
+            payment.event_id.clone(),
+            PaymentStatus::PartiallyPaid,
+            PaymentStatus::Refunded,
+            payment_id.clone(),
+        );
+        let (event_id, buyer_address) = (payment.event_id.clone(), payment.buyer_address.clone());
+        store_payment(&env, payment);
+        remove_installment_schedule(&env, payment_id.clone());
 
-        #[allow(deprecated)]
         env.events().publish(
-            (AgoraEvent::RevenueClaimed,),
-            RevenueClaimedEvent {
+            (AgoraEventExt::InstallmentForfeited,),
+            InstallmentForfeitedEvent {
+                payment_id,
                 event_id,
-                organizer_address: event_info.organizer_address,
-                amount: organizer_amount,
-                timestamp,
+                buyer_address,
+                refunded: refund,
+                penalty,
+                timestamp: env.ledger().timestamp(),
             },
         );
 
-        Ok(organizer_amount)
-    }
-
-    /// Returns all payments for a specific buyer.
-    pub fn get_buyer_payments(env: Env, buyer_address: Address) -> soroban_sdk::Vec<String> {
-        crate::storage::get_buyer_payments(&env, buyer_address)
-    }
-
-    /// Returns all payment IDs for an event with a specific status.
-    /// This allows querying payments by status (e.g., Pending, Confirmed, Refunded, Failed, CheckedIn).
-    ///
-    /// # Arguments
-    /// * `event_id` - The event ID to query
-    /// * `status` - The payment status to filter by
-    ///
-    /// # Returns
-    /// A vector of payment IDs matching the specified status for the event
-    pub fn get_payments_by_status(
-        env: Env,
-        event_id: String,
-        status: PaymentStatus,
-    ) -> soroban_sdk::Vec<String> {
-        crate::storage::get_payments_by_status(&env, event_id, status)
+        Ok(refund)
     }
 
-    /// Sets the transfer fee for an event. Only the organizer can call this.
-    pub fn set_transfer_fee(
+    /// Opens a group order holding `tickets` tickets of a tier until `deadline`.
+    /// The initiator pays their share for the ticket `payment_id` up front; every
+    /// other participant pays theirs with `join_group_order`, and each gets their
+    /// own ticket once the group fills.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_group_order(
         env: Env,
+        order_id: String,
         event_id: String,
-        bps: u32, // Changed from i128 amount to u32 basis points
+        tier_id: String,
+        initiator: Address,
+        payment_id: String,
+        tickets: u32,
+        deadline: u64,
+        options: Option<PurchaseOptions>,
     ) -> Result<(), TicketPaymentError> {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
+        require_not_paused(&env, PauseScope::Payments, Some(&event_id))?;
+        initiator.require_auth();
 
-        let event_registry_addr = get_event_registry(&env);
-        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
-
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
         let event_info = match registry_client.try_get_event(&event_id) {
             Ok(Ok(Some(info))) => info,
             _ => return Err(TicketPaymentError::EventNotFound),
         };
-
-        event_info.organizer_address.require_auth();
-
-        if bps > MAX_BPS {
-            return Err(TicketPaymentError::InvalidFeePercent);
+        if !event_info.is_active
+            || matches!(event_info.status, event_registry::EventStatus::Cancelled)
+        {
+            return Err(TicketPaymentError::EventInactive);
         }
+        let tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
 
-        // Store the basis points, not the calculated amount
-        set_transfer_fee(&env, event_id, bps);
-        Ok(())
-    }
-
-    /// Transfers a ticket from the current holder to a new owner.
-    /// If `sale_price` is provided, it is validated against the event's resale cap.
-    pub fn transfer_ticket(
-        env: Env,
-        payment_id: String,
-        to: Address,
-        sale_price: Option<i128>,
-    ) -> Result<(), TicketPaymentError> {
-        if !is_initialized(&env) {
-            panic!("Contract not initialized");
-        }
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
+        let now = env.ledger().timestamp();
+        if get_group_order(&env, order_id.clone()).is_some()
+            || !(2..=MAX_GROUP_ORDER_TICKETS).contains(&tickets)
+            || deadline <= now
+            || (event_info.start_time > 0 && deadline > event_info.start_time)
+        {
+            return Err(TicketPaymentError::InvalidGroupOrder);
         }
-
-        let mut payment =
-            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
-
-        if payment.status != PaymentStatus::Confirmed {
-            return Err(TicketPaymentError::InvalidPaymentStatus);
+        if tier.usd_price > 0 {
+            return Err(TicketPaymentError::InvalidPrice);
         }
-
-        let from = payment.buyer_address.clone();
-        from.require_auth();
-
-        // Prevent self-transfer
-        if from == to {
-            return Err(TicketPaymentError::InvalidAddress);
+        let price = if tier.early_bird_deadline > 0 && now <= tier.early_bird_deadline {
+            tier.early_bird_price
+        } else {
+            tier.price
+        };
+        if price <= 0 {
+            return Err(TicketPaymentError::InvalidPrice);
         }
-        // Prevent transfer to the zero address or the contract itself
-        validate_recipient(&env, &to)?;
 
-        // Validate resale price against the organizer's cap
-        if let Some(price) = sale_price {
-            let event_registry_addr = get_event_registry(&env);
-            let registry_client = event_registry::Client::new(&env, &event_registry_addr); // Re-initialize client
+        // Hold the group's tickets until it fills or the deadline passes.
+        registry_client.increment_inventory(&event_id, &tier_id, &tickets);
 
-            let event_info = match registry_client.try_get_event(&payment.event_id) {
-                Ok(Ok(Some(info))) => info,
-                _ => return Err(TicketPaymentError::EventNotFound), // Should not happen if payment exists
-            };
+        let mut order = GroupOrder {
+            order_id: order_id.clone(),
+            initiator: initiator.clone(),
+            event_id: event_id.clone(),
+            tier_id: tier_id.clone(),
+            tickets,
+            price,
+            fee_bps: event_info
+                .custom_fee_bps
+                .unwrap_or(event_info.platform_fee_percent),
+            deadline,
+            participants: Map::new(&env),
+            status: GroupOrderStatus::Open,
+            created_at: now,
+        };
 
-            // Check if resale cap is defined for the event
-            if event_info.resale_cap_bps.is_some() {
-                if let Some(cap_bps) = event_info.resale_cap_bps {
-                    // Look up the original tier face-value price
-                    let tier = event_info
-                        .tiers
-                        .get(payment.ticket_tier_id.clone())
-                        .ok_or(TicketPaymentError::TierNotFound)?;
-                    // Use the original price from the payment record, not the tier's current price,
-                    // as tier prices can change (e.g., early bird expiry).
-                    // The payment.amount is the actual price paid for this specific ticket.
-                    let original_price = tier.price;
+        env.events().publish(
+            (AgoraEventExt::GroupOrderCreated,),
+            GroupOrderCreatedEvent {
+                order_id,
+                event_id,
+                tier_id,
+                initiator: initiator.clone(),
+                tickets,
+                price,
+                deadline,
+                timestamp: now,
+            },
+        );
 
-                    // max_price = original_price * (MAX_BPS + cap_bps) / MAX_BPS
-                    let max_price = original_price
-                        .checked_mul(
-                            (MAX_BPS as i128)
-                                .checked_add(cap_bps as i128)
-                                .unwrap_or(i128::MAX),
-                        )
-                        .ok_or(TicketPaymentError::ArithmeticError)?
-                        / (MAX_BPS as i128);
+        pay_group_share(
+            &env,
+            &registry_client,
+            &mut order,
+            payment_id,
+            initiator,
+            options.as_ref(),
+        )?;
+        set_group_order(&env, &order);
 
-                    if price > max_price {
-                        return Err(TicketPaymentError::ResalePriceExceedsCap);
-                    }
-                }
-            }
+        Ok(())
+    }
+
+    /// Pays a participant's share of an open group order. Their ticket is
+    /// issued as `payment_id` once the last share is paid.
+    pub fn join_group_order(
+        env: Env,
+        order_id: String,
+        payment_id: String,
+        participant: Address,
+        options: Option<PurchaseOptions>,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
         }
+        let mut order = get_group_order(&env, order_id.clone())
+            .ok_or(TicketPaymentError::GroupOrderNotFound)?;
+        require_not_paused(&env, PauseScope::Payments, Some(&order.event_id))?;
+        participant.require_auth();
 
-        let transfer_fee_bps =
-            get_transfer_fee(&env, payment.event_id.clone()).unwrap_or(TRANSFER_FEE_BPS);
+        if order.status != GroupOrderStatus::Open || env.ledger().timestamp() > order.deadline {
+            return Err(TicketPaymentError::GroupOrderClosed);
+        }
 
-        let mut actual_transfer_fee: i128 = 0;
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let event_info = match registry_client.try_get_event(&order.event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        if !event_info.is_active
+            || matches!(event_info.status, event_registry::EventStatus::Cancelled)
+        {
+            return Err(TicketPaymentError::EventInactive);
+        }
 
-        if transfer_fee_bps > 0 {
-            // Calculate the actual transfer fee based on the original ticket amount
-            actual_transfer_fee = payment
-                .amount
-                .checked_mul(transfer_fee_bps as i128)
-                .and_then(|v| v.checked_div(MAX_BPS as i128))
-                .ok_or(TicketPaymentError::ArithmeticError)?;
+        pay_group_share(
+            &env,
+            &registry_client,
+            &mut order,
+            payment_id,
+            participant,
+            options.as_ref(),
+        )?;
+        set_group_order(&env, &order);
 
-            if actual_transfer_fee > 0 {
-                let token_address = crate::storage::get_usdc_token(&env);
-                let token_client = token::Client::new(&env, &token_address);
-                let contract_address = env.current_contract_address();
+        Ok(())
+    }
 
-                // Transfer fee from old owner to contract
-                token_client.transfer_from(
-                    &contract_address,
-                    &from,
-                    &contract_address,
-                    &actual_transfer_fee,
-                );
+    /// Refunds every share of a group order that did not fill by its deadline,
+    /// or at any time once the event is cancelled, and releases its held
+    /// tickets. Permissionless; returns the amount refunded.
+    pub fn refund_group_order(env: Env, order_id: String) -> Result<i128, TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        let mut order = get_group_order(&env, order_id.clone())
+            .ok_or(TicketPaymentError::GroupOrderNotFound)?;
+        require_not_paused(&env, PauseScope::Refunds, Some(&order.event_id))?;
 
-                // Update escrow balances (fee goes to organizer)
-                update_event_balance(&env, payment.event_id.clone(), actual_transfer_fee, 0);
-            }
+        if order.status != GroupOrderStatus::Open {
+            return Err(TicketPaymentError::GroupOrderClosed);
+        }
+        let registry_client = event_registry::Client::new(&env, &get_event_registry(&env));
+        let event_cancelled = registry_client
+            .try_get_event(&order.event_id)
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+            .is_some_and(|e| matches!(e.status, event_registry::EventStatus::Cancelled));
+        if !event_cancelled && env.ledger().timestamp() <= order.deadline {
+            return Err(TicketPaymentError::GroupOrderNotExpired);
         }
-        // Update payment record
-        payment.buyer_address = to.clone();
-        let key = crate::types::DataKey::Payment(payment_id.clone());
-        env.storage().persistent().set(&key, &payment);
 
-        // Update indices
-        remove_payment_from_buyer_index(&env, from.clone(), payment_id.clone());
-        add_payment_to_buyer_index(&env, to.clone(), payment_id.clone());
+        let token_address = get_usdc_token(&env);
+        let token_client = token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+        for participant in order.participants.keys().iter() {
+            token_client.transfer(&contract_address, &participant, &order.price);
+        }
+        let total_refunded = order.price * order.participants.len() as i128;
+        subtract_from_active_escrow_total(&env, total_refunded);
+        subtract_from_active_escrow_by_token(&env, token_address, total_refunded);
+
+        for _ in 0..order.tickets {
+            registry_client.decrement_inventory(&order.event_id, &order.tier_id);
+        }
+
+        order.status = GroupOrderStatus::Refunded;
+        set_group_order(&env, &order);
 
-        // Emit transfer event
-        #[allow(deprecated)]
         env.events().publish(
-            (AgoraEvent::TicketTransferred,),
-            TicketTransferredEvent {
-                payment_id,
-                from,
-                to: to.clone(),
-                transfer_fee: actual_transfer_fee,
+            (AgoraEventExt::GroupOrderRefunded,),
+            GroupOrderRefundedEvent {
+                order_id,
+                event_id: order.event_id,
+                participants: order.participants.len(),
+                total_refunded,
                 timestamp: env.ledger().timestamp(),
             },
         );
 
-        Ok(())
+        Ok(total_refunded)
+    }
+
+    /// Returns a group order and the participants who have paid so far.
+    pub fn get_group_order(env: Env, order_id: String) -> Option<GroupOrder> {
+        get_group_order(&env, order_id)
     }
 
     /// Triggers a bulk refund for a cancelled event. Processes in batches.
@@ -1798,9 +4828,7 @@ impl TicketPaymentContract {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
-        }
+        require_not_paused(&env, PauseScope::Refunds, Some(&event_id))?;
 
         let event_registry_addr = get_event_registry(&env);
         let registry_client = event_registry::Client::new(&env, &event_registry_addr);
@@ -1832,19 +4860,35 @@ impl TicketPaymentContract {
         let mut balance = get_event_balance(&env, event_id.clone());
 
         let token_address = crate::storage::get_usdc_token(&env);
-        let token_client = token::Client::new(&env, &token_address);
         let contract_address = env.current_contract_address();
+        let mut add_ons_refunded = 0;
 
         for i in start_index..end_index {
             let payment_id = payment_ids.get(i).unwrap();
             if let Some(mut payment) = get_payment(&env, payment_id.clone()) {
-                if payment.status == PaymentStatus::Confirmed {
-                    // Refund full amount to buyer
-                    token_client.transfer(
+                // Installment tickets get back what they paid so far.
+                let partially_paid = payment.status == PaymentStatus::PartiallyPaid;
+                if payment.status == PaymentStatus::Confirmed || partially_paid {
+                    cover_refund_from_pool(
+                        &env,
+                        &registry_client,
+                        &event_id,
+                        &mut balance,
+                        &payment.token,
+                        payment.amount,
+                    );
+
+                    // Refund full amount to buyer, in the token they paid in
+                    token::Client::new(&env, &payment.token).transfer(
                         &contract_address,
                         &payment.buyer_address,
                         &payment.amount,
                     );
+                    subtract_from_active_escrow_by_token(
+                        &env,
+                        payment.token.clone(),
+                        payment.amount,
+                    );
 
                     // Update payment status
                     payment.status = PaymentStatus::Refunded;
@@ -1852,9 +4896,59 @@ impl TicketPaymentContract {
                     store_payment(&env, payment.clone());
 
                     // Update event balance in-memory; persist once per batch.
+                    let held_fees = balance.platform_fee.max(0);
                     balance.organizer_amount -= payment.organizer_amount;
                     balance.platform_fee -= payment.platform_fee;
 
+                    let (addon_refund, addon_organizer, addon_fee) =
+                        refund_add_ons(&env, &registry_client, &payment);
+                    balance.organizer_amount -= addon_organizer;
+                    balance.platform_fee -= addon_fee;
+
+                    // Fees still held are returned in the tokens they were collected in.
+                    let returned = return_unsettled_fee(
+                        &env,
+                        &event_id,
+                        &payment.token,
+                        payment.platform_fee,
+                        held_fees,
+                    );
+                    return_unsettled_fee(
+                        &env,
+                        &event_id,
+                        &token_address,
+                        addon_fee,
+                        held_fees - returned,
+                    );
+                    total_refunded += addon_refund;
+                    add_ons_refunded += addon_refund;
+                    return_deposit(&env, &payment);
+
+                    if partially_paid {
+                        crate::storage::update_payment_status_index(
+                            &env,
+                            event_id.clone(),
+                            PaymentStatus::PartiallyPaid,
+                            PaymentStatus::Refunded,
+                            payment_id.clone(),
+                        );
+                        remove_installment_schedule(&env, payment_id.clone());
+                    } else {
+                        let multiplier = event_info
+                            .tiers
+                            .get(payment.ticket_tier_id.clone())
+                            .map(|t| t.loyalty_multiplier)
+                            .unwrap_or(1);
+                        reverse_loyalty(
+                            &env,
+                            &registry_client,
+                            &payment,
+                            multiplier,
+                            payment.organizer_amount + payment.platform_fee,
+                            MAX_BPS,
+                        );
+                    }
+
                     total_refunded += payment.amount;
                     processed_count += 1;
                 }
@@ -1864,7 +4958,7 @@ impl TicketPaymentContract {
         if processed_count > 0 {
             crate::storage::set_event_balance(&env, event_id.clone(), balance);
             subtract_from_active_escrow_total(&env, total_refunded);
-            subtract_from_active_escrow_by_token(&env, token_address, total_refunded);
+            subtract_from_active_escrow_by_token(&env, token_address, add_ons_refunded);
         }
 
         set_bulk_refund_index(&env, event_id.clone(), end_index);
@@ -1895,9 +4989,7 @@ impl TicketPaymentContract {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
-        }
+        require_not_paused(&env, PauseScope::Refunds, Some(&event_id))?;
         if percentage_bps > MAX_BPS {
             panic!("Percentage cannot exceed 100%");
         }
@@ -1937,8 +5029,6 @@ impl TicketPaymentContract {
         let mut total_refunded = 0;
         let mut balance = get_event_balance(&env, event_id.clone());
 
-        let token_address = crate::storage::get_usdc_token(&env);
-        let token_client = token::Client::new(&env, &token_address);
         let contract_address = env.current_contract_address();
 
         for i in start_index..end_index {
@@ -1952,11 +5042,16 @@ impl TicketPaymentContract {
                         / (MAX_BPS as i128);
 
                     if refund_amount > 0 && payment.organizer_amount >= refund_amount {
-                        token_client.transfer(
+                        token::Client::new(&env, &payment.token).transfer(
                             &contract_address,
                             &payment.buyer_address,
                             &refund_amount,
                         );
+                        subtract_from_active_escrow_by_token(
+                            &env,
+                            payment.token.clone(),
+                            refund_amount,
+                        );
 
                         payment.refunded_amount += refund_amount;
                         payment.organizer_amount -= refund_amount;
@@ -1965,6 +5060,20 @@ impl TicketPaymentContract {
                         balance.organizer_amount -= refund_amount;
                         total_refunded += refund_amount;
                         processed_count += 1;
+
+                        let multiplier = event_info
+                            .tiers
+                            .get(payment.ticket_tier_id.clone())
+                            .map(|t| t.loyalty_multiplier)
+                            .unwrap_or(1);
+                        reverse_loyalty(
+                            &env,
+                            &registry_client,
+                            &payment,
+                            multiplier,
+                            refund_amount,
+                            active_pct,
+                        );
                     }
                 }
             }
@@ -1973,7 +5082,6 @@ impl TicketPaymentContract {
         if processed_count > 0 {
             crate::storage::set_event_balance(&env, event_id.clone(), balance);
             subtract_from_active_escrow_total(&env, total_refunded);
-            subtract_from_active_escrow_by_token(&env, token_address, total_refunded);
         }
 
         set_partial_refund_index(&env, event_id.clone(), end_index);
@@ -2020,13 +5128,24 @@ impl TicketPaymentContract {
         crate::storage::get_active_escrow_by_token(&env, token_address)
     }
 
+    /// Returns the cumulative revenue paid to `payee` from an event's revenue split.
+    pub fn get_payee_earnings(env: Env, event_id: String, payee: Address) -> i128 {
+        get_payee_earnings(&env, event_id, payee)
+    }
+
     pub fn get_withdrawal_cap(env: Env, token: Address) -> i128 {
         crate::storage::get_withdrawal_cap(&env, token)
     }
 
+    /// Platform fees withdrawn in `token` within the current rolling window.
     pub fn get_daily_withdrawn_amount(env: Env, token: Address) -> i128 {
-        let current_day = env.ledger().timestamp() / 86400;
-        crate::storage::get_daily_withdrawn_amount(&env, token, current_day)
+        let config = get_withdrawal_limit_config(&env);
+        get_window_withdrawn_amount(
+            &env,
+            env.current_contract_address(),
+            token,
+            config.window_secs,
+        )
     }
 
     /// Places a bid for an auction tier. Escrows funds and refunds the previous highest bidder.
@@ -2041,9 +5160,7 @@ impl TicketPaymentContract {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
-        }
+        require_not_paused(&env, PauseScope::Auctions, Some(&event_id))?;
         bidder_address.require_auth();
 
         if !is_token_whitelisted(&env, &token_address) {
@@ -2153,9 +5270,7 @@ impl TicketPaymentContract {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
-        if is_paused(&env) {
-            return Err(TicketPaymentError::ContractPaused);
-        }
+        require_not_paused(&env, PauseScope::Auctions, Some(&event_id))?;
 
         let event_registry_addr = get_event_registry(&env);
         let registry_client = event_registry::Client::new(&env, &event_registry_addr);
@@ -2225,6 +5340,12 @@ impl TicketPaymentContract {
         );
         add_to_total_volume_processed(&env, amount);
         add_to_total_fees_collected_by_token(&env, token_address.clone(), total_platform_fee);
+        add_to_event_fees_by_token(
+            &env,
+            event_id.clone(),
+            token_address.clone(),
+            total_platform_fee,
+        );
 
         // Increment inventory
         registry_client.increment_inventory(&event_id, &ticket_tier_id, &1);
@@ -2236,6 +5357,7 @@ impl TicketPaymentContract {
             event_id: event_id.clone(),
             buyer_address: bidder_address.clone(),
             ticket_tier_id: ticket_tier_id.clone(),
+            token: token_address.clone(),
             amount,
             platform_fee: total_platform_fee,
             organizer_amount: total_organizer_amount,
@@ -2306,6 +5428,433 @@ impl TicketPaymentContract {
     }
 }
 
+/// Returns the configured treasury wallets, or the platform wallet at 100% by default.
+fn effective_treasury_wallets(env: &Env) -> Vec<TreasuryWallet> {
+    let wallets = get_treasury_wallets(env);
+    if !wallets.is_empty() {
+        return wallets;
+    }
+    soroban_sdk::vec![
+        env,
+        TreasuryWallet {
+            address: get_platform_wallet(env),
+            share_bps: MAX_BPS,
+        }
+    ]
+}
+
+/// Cancels a pending queued withdrawal and returns its amount to where it was
+/// taken from: released fees, or the event's escrow balance.
+#[allow(deprecated)]
+fn cancel_withdrawal(env: &Env, queued: &mut QueuedWithdrawal) -> Result<(), TicketPaymentError> {
+    match (&queued.kind, queued.event_id.clone()) {
+        (WithdrawalKind::PlatformFees, None) => {
+            add_to_released_fees(env, queued.token.clone(), queued.amount);
+        }
+        (WithdrawalKind::PlatformFees, Some(event_id)) => {
+            let mut balance = get_event_balance(env, event_id.clone());
+            balance.platform_fee = balance
+                .platform_fee
+                .checked_add(queued.amount)
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            crate::storage::set_event_balance(env, event_id.clone(), balance);
+            add_to_event_fees_by_token(env, event_id, queued.token.clone(), queued.amount);
+        }
+        (WithdrawalKind::OrganizerRevenue, Some(event_id)) => {
+            let mut balance = get_event_balance(env, event_id.clone());
+            balance.organizer_amount = balance
+                .organizer_amount
+                .checked_add(queued.amount)
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            balance.total_withdrawn = balance
+                .total_withdrawn
+                .checked_sub(queued.amount)
+                .ok_or(TicketPaymentError::ArithmeticError)?;
+            crate::storage::set_event_balance(env, event_id, balance);
+        }
+        (WithdrawalKind::OrganizerRevenue, None) => {
+            return Err(TicketPaymentError::EventNotFound);
+        }
+    }
+
+    queued.status = QueuedWithdrawalStatus::Cancelled;
+    set_queued_withdrawal(env, queued);
+
+    env.events().publish(
+        (AgoraEvent::QueuedWithdrawalCancelled,),
+        QueuedWithdrawalCancelledEvent {
+            id: queued.id,
+            amount: queued.amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Checks a treasury split: at most `MAX_TREASURY_WALLETS` distinct valid wallets
+/// with non-zero shares summing to 10000 bps. An empty list is allowed.
+fn validate_treasury_wallets(
+    env: &Env,
+    wallets: &Vec<TreasuryWallet>,
+) -> Result<(), TicketPaymentError> {
+    if !wallets.is_empty() {
+        if wallets.len() > MAX_TREASURY_WALLETS {
+            return Err(TicketPaymentError::InvalidTreasurySplit);
+        }
+        let mut total_bps: u32 = 0;
+        for (i, wallet) in wallets.iter().enumerate() {
+            validate_recipient(env, &wallet.address)?;
+            if wallet.share_bps == 0 {
+                return Err(TicketPaymentError::InvalidTreasurySplit);
+            }
+            for other in wallets.iter().skip(i + 1) {
+                if other.address == wallet.address {
+                    return Err(TicketPaymentError::InvalidTreasurySplit);
+                }
+            }
+            total_bps = total_bps
+                .checked_add(wallet.share_bps)
+                .ok_or(TicketPaymentError::InvalidTreasurySplit)?;
+        }
+        if total_bps != MAX_BPS {
+            return Err(TicketPaymentError::InvalidTreasurySplit);
+        }
+    }
+    Ok(())
+}
+
+/// Releases `amount` of collected platform fees, paying it out immediately when it
+/// fits under the token's rolling-window cap and queueing it otherwise.
+///
+/// Returns the queue id when the payout was deferred.
+fn release_platform_fees(
+    env: &Env,
+    token_address: &Address,
+    amount: i128,
+    event_id: Option<String>,
+) -> Result<Option<u64>, TicketPaymentError> {
+    if amount > get_total_fees_collected_by_token(env, token_address.clone()) {
+        return Err(TicketPaymentError::InsufficientFees);
+    }
+
+    let config = get_withdrawal_limit_config(env);
+    let subject = env.current_contract_address();
+    let cap = get_withdrawal_cap(env, token_address.clone());
+    if exceeds_window_cap(env, &subject, token_address, cap, amount, &config)? {
+        let id = queue_withdrawal(
+            env,
+            &config,
+            WithdrawalKind::PlatformFees,
+            event_id,
+            None,
+            token_address,
+            amount,
+        );
+        return Ok(Some(id));
+    }
+
+    pay_out_platform_fees(env, token_address, amount)?;
+    record_window_withdrawal(
+        env,
+        subject,
+        token_address.clone(),
+        config.window_secs,
+        amount,
+    );
+    Ok(None)
+}
+
+/// Checks an organizer withdrawal against the organizer's rolling-window cap.
+///
+/// Withdrawals within the cap are recorded and `None` is returned so the caller can pay
+/// out; otherwise the withdrawal is queued and its id returned.
+fn check_organizer_withdrawal(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    organizer: &Address,
+    event_id: &String,
+    token_address: &Address,
+    amount: i128,
+) -> Result<Option<u64>, TicketPaymentError> {
+    if amount <= 0 {
+        return Ok(None);
+    }
+
+    let config = get_withdrawal_limit_config(env);
+    let cap = effective_organizer_cap(env, registry_client, organizer, token_address);
+    if exceeds_window_cap(env, organizer, token_address, cap, amount, &config)? {
+        let id = queue_withdrawal(
+            env,
+            &config,
+            WithdrawalKind::OrganizerRevenue,
+            Some(event_id.clone()),
+            Some(organizer.clone()),
+            token_address,
+            amount,
+        );
+        return Ok(Some(id));
+    }
+
+    record_window_withdrawal(
+        env,
+        organizer.clone(),
+        token_address.clone(),
+        config.window_secs,
+        amount,
+    );
+    Ok(None)
+}
+
+/// Returns the organizer's cap for `token_address`: their override if one is set,
+/// otherwise the policy cap for verified or unverified organizers (0 = unlimited).
+fn effective_organizer_cap(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    organizer: &Address,
+    token_address: &Address,
+) -> i128 {
+    if let Some(cap) = get_organizer_withdrawal_cap(env, organizer.clone(), token_address.clone()) {
+        return cap;
+    }
+
+    let policy = get_organizer_cap_policy(env, token_address.clone());
+    let verified = matches!(
+        registry_client.try_is_organizer_verified(organizer),
+        Ok(Ok(true))
+    );
+    if verified {
+        policy.verified_cap
+    } else {
+        policy.default_cap
+    }
+}
+
+/// Returns true if withdrawing `amount` would push `subject` over `cap` (0 = unlimited)
+/// within the rolling window.
+fn exceeds_window_cap(
+    env: &Env,
+    subject: &Address,
+    token_address: &Address,
+    cap: i128,
+    amount: i128,
+    config: &WithdrawalLimitConfig,
+) -> Result<bool, TicketPaymentError> {
+    if cap <= 0 {
+        return Ok(false);
+    }
+    let withdrawn = get_window_withdrawn_amount(
+        env,
+        subject.clone(),
+        token_address.clone(),
+        config.window_secs,
+    );
+    Ok(withdrawn
+        .checked_add(amount)
+        .ok_or(TicketPaymentError::ArithmeticError)?
+        > cap)
+}
+
+/// Stores an over-cap withdrawal that becomes executable after the queue delay.
+fn queue_withdrawal(
+    env: &Env,
+    config: &WithdrawalLimitConfig,
+    kind: WithdrawalKind,
+    event_id: Option<String>,
+    organizer: Option<Address>,
+    token_address: &Address,
+    amount: i128,
+) -> u64 {
+    let id = increment_queued_withdrawal_count(env);
+    let queued_at = env.ledger().timestamp();
+    let available_at = queued_at.saturating_add(config.queue_delay_secs);
+
+    set_queued_withdrawal(
+        env,
+        &QueuedWithdrawal {
+            id,
+            kind: kind.clone(),
+            event_id: event_id.clone(),
+            organizer,
+            token: token_address.clone(),
+            amount,
+            queued_at,
+            available_at,
+            status: QueuedWithdrawalStatus::Pending,
+        },
+    );
+
+    #[allow(deprecated)]
+    env.events().publish(
+        (AgoraEvent::WithdrawalQueued,),
+        WithdrawalQueuedEvent {
+            id,
+            kind,
+            event_id,
+            token: token_address.clone(),
+            amount,
+            available_at,
+            timestamp: queued_at,
+        },
+    );
+
+    id
+}
+
+/// Pays `amount` of collected platform fees in `token_address` out to the treasury.
+///
+/// This is the single exit path for platform fees: it splits the amount across the
+/// treasury wallets and reconciles `TotalFeesCollected` and the active escrow totals.
+/// Rate limits are applied by the callers.
+fn pay_out_platform_fees(
+    env: &Env,
+    token_address: &Address,
+    amount: i128,
+) -> Result<(), TicketPaymentError> {
+    if amount > get_total_fees_collected_by_token(env, token_address.clone()) {
+        return Err(TicketPaymentError::InsufficientFees);
+    }
+
+    let wallets = effective_treasury_wallets(env);
+    let token_client = token::Client::new(env, token_address);
+    let contract_address = env.current_contract_address();
+    let timestamp = env.ledger().timestamp();
+    let last_index = wallets.len() - 1;
+    let mut paid: i128 = 0;
+
+    for (i, wallet) in wallets.iter().enumerate() {
+        let share = if i as u32 == last_index {
+            amount
+                .checked_sub(paid)
+                .ok_or(TicketPaymentError::ArithmeticError)?
+        } else {
+            amount
+                .checked_mul(wallet.share_bps as i128)
+                .and_then(|v| v.checked_div(MAX_BPS as i128))
+                .ok_or(TicketPaymentError::ArithmeticError)?
+        };
+        if share <= 0 {
+            continue;
+        }
+        paid = paid
+            .checked_add(share)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        token_client.transfer(&contract_address, &wallet.address, &share);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::TreasuryPayout,),
+            TreasuryPayoutEvent {
+                wallet: wallet.address.clone(),
+                token: token_address.clone(),
+                share_bps: wallet.share_bps,
+                amount: share,
+                timestamp,
+            },
+        );
+    }
+
+    subtract_from_total_fees_collected_by_token(env, token_address.clone(), amount);
+    subtract_from_active_escrow_total(env, amount);
+    subtract_from_active_escrow_by_token(env, token_address.clone(), amount);
+
+    Ok(())
+}
+
+/// Takes up to `fee` of a refund's platform fee off the fees the event still holds in
+/// `token`, never more than `held` in total, so they are no longer collectable by the
+/// platform. Returns the amount taken off.
+fn return_unsettled_fee(
+    env: &Env,
+    event_id: &String,
+    token: &Address,
+    fee: i128,
+    held: i128,
+) -> i128 {
+    let collected = get_event_fees_by_token(env, event_id.clone())
+        .get(token.clone())
+        .unwrap_or(0);
+    let unsettled = fee.min(held).min(collected).max(0);
+    if unsettled > 0 {
+        subtract_from_total_fees_collected_by_token(env, token.clone(), unsettled);
+        subtract_from_event_fees_by_token(env, event_id.clone(), token.clone(), unsettled);
+    }
+    unsettled
+}
+
+/// Splits `amount` across the event's configured payees and transfers each share.
+///
+/// The split is read from the registry; registries without split support (or events
+/// without a configured split) pay everything to `default_payee`. Rounding dust is
+/// assigned to the last payee so the full amount always leaves escrow.
+fn distribute_to_payees(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    event_id: &String,
+    default_payee: &Address,
+    token_address: &Address,
+    amount: i128,
+) -> Result<(), TicketPaymentError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+
+    let payees = match registry_client.try_get_revenue_split(event_id) {
+        Ok(Ok(split)) if !split.is_empty() => split,
+        _ => soroban_sdk::vec![
+            env,
+            event_registry::Payee {
+                address: default_payee.clone(),
+                share_bps: MAX_BPS,
+            }
+        ],
+    };
+
+    let token_client = token::Client::new(env, token_address);
+    let contract_address = env.current_contract_address();
+    let timestamp = env.ledger().timestamp();
+    let last_index = payees.len() - 1;
+    let mut distributed: i128 = 0;
+
+    for (i, payee) in payees.iter().enumerate() {
+        let share = if i as u32 == last_index {
+            amount
+                .checked_sub(distributed)
+                .ok_or(TicketPaymentError::ArithmeticError)?
+        } else {
+            amount
+                .checked_mul(payee.share_bps as i128)
+                .and_then(|v| v.checked_div(MAX_BPS as i128))
+                .ok_or(TicketPaymentError::ArithmeticError)?
+        };
+        if share <= 0 {
+            continue;
+        }
+        distributed = distributed
+            .checked_add(share)
+            .ok_or(TicketPaymentError::ArithmeticError)?;
+
+        token_client.transfer(&contract_address, &payee.address, &share);
+        add_to_payee_earnings(env, event_id.clone(), payee.address.clone(), share);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (AgoraEvent::PayeePaid,),
+            PayeePaidEvent {
+                event_id: event_id.clone(),
+                payee: payee.address.clone(),
+                token: token_address.clone(),
+                share_bps: payee.share_bps,
+                amount: share,
+                timestamp,
+            },
+        );
+    }
+
+    Ok(())
+}
+
 fn validate_address(env: &Env, address: &Address) -> Result<(), TicketPaymentError> {
     if address == &env.current_contract_address() || is_zero_address(env, address) {
         return Err(TicketPaymentError::InvalidAddress);
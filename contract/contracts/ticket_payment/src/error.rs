@@ -53,6 +53,45 @@ pub enum TicketPaymentError {
     CannotRemoveLastGovernor = 57,
     InvalidFeePercent = 58,
     EventEnded = 59,
+    InvalidTreasurySplit = 60,
+    WithdrawalNotReady = 61,
+    QueuedWithdrawalNotFound = 62,
+    QueuedWithdrawalNotPending = 63,
+    InvalidWithdrawalLimit = 64,
+    UpgradeNotScheduled = 65,
+    UpgradeTimelockActive = 66,
+    AlreadyMigrated = 67,
+    PointsRedemptionDisabled = 68,
+    InsufficientLoyaltyPoints = 69,
+    EventNotSettled = 70,
+    InvalidTtlPolicy = 71,
+    NotOnAllowlist = 72,
+    AllowlistAllowanceExceeded = 73,
+    AttestationMissing = 74,
+    InvalidTierChange = 75,
+    ExchangeNotAllowed = 76,
+    CreditNotOffered = 77,
+    InsufficientCredit = 78,
+    InvalidCreditOffer = 79,
+    CreditTokenMismatch = 80,
+    DonationsNotEnabled = 81,
+    InvalidDonation = 82,
+    AddOnNotFound = 83,
+    AddOnSoldOut = 84,
+    AddOnNotAvailable = 85,
+    AddOnFullyRedeemed = 86,
+    InvalidQuantity = 87,
+    DepositNotRequired = 88,
+    InstallmentsNotOffered = 89,
+    InstallmentOverdue = 90,
+    InstallmentNotOverdue = 91,
+    PaymentNotFullyPaid = 92,
+    PaymentAlreadyExists = 93,
+    GroupOrderNotFound = 94,
+    GroupOrderClosed = 95,
+    AlreadyInGroupOrder = 96,
+    GroupOrderNotExpired = 97,
+    InvalidGroupOrder = 98,
 }
 
 impl From<TicketPaymentError> for soroban_sdk::Error {
@@ -122,6 +161,45 @@ impl From<soroban_sdk::Error> for TicketPaymentError {
             57 => TicketPaymentError::CannotRemoveLastGovernor,
             58 => TicketPaymentError::InvalidFeePercent,
             59 => TicketPaymentError::EventEnded,
+            60 => TicketPaymentError::InvalidTreasurySplit,
+            61 => TicketPaymentError::WithdrawalNotReady,
+            62 => TicketPaymentError::QueuedWithdrawalNotFound,
+            63 => TicketPaymentError::QueuedWithdrawalNotPending,
+            64 => TicketPaymentError::InvalidWithdrawalLimit,
+            65 => TicketPaymentError::UpgradeNotScheduled,
+            66 => TicketPaymentError::UpgradeTimelockActive,
+            67 => TicketPaymentError::AlreadyMigrated,
+            68 => TicketPaymentError::PointsRedemptionDisabled,
+            69 => TicketPaymentError::InsufficientLoyaltyPoints,
+            70 => TicketPaymentError::EventNotSettled,
+            71 => TicketPaymentError::InvalidTtlPolicy,
+            72 => TicketPaymentError::NotOnAllowlist,
+            73 => TicketPaymentError::AllowlistAllowanceExceeded,
+            74 => TicketPaymentError::AttestationMissing,
+            75 => TicketPaymentError::InvalidTierChange,
+            76 => TicketPaymentError::ExchangeNotAllowed,
+            77 => TicketPaymentError::CreditNotOffered,
+            78 => TicketPaymentError::InsufficientCredit,
+            79 => TicketPaymentError::InvalidCreditOffer,
+            80 => TicketPaymentError::CreditTokenMismatch,
+            81 => TicketPaymentError::DonationsNotEnabled,
+            82 => TicketPaymentError::InvalidDonation,
+            83 => TicketPaymentError::AddOnNotFound,
+            84 => TicketPaymentError::AddOnSoldOut,
+            85 => TicketPaymentError::AddOnNotAvailable,
+            86 => TicketPaymentError::AddOnFullyRedeemed,
+            87 => TicketPaymentError::InvalidQuantity,
+            88 => TicketPaymentError::DepositNotRequired,
+            89 => TicketPaymentError::InstallmentsNotOffered,
+            90 => TicketPaymentError::InstallmentOverdue,
+            91 => TicketPaymentError::InstallmentNotOverdue,
+            92 => TicketPaymentError::PaymentNotFullyPaid,
+            93 => TicketPaymentError::PaymentAlreadyExists,
+            94 => TicketPaymentError::GroupOrderNotFound,
+            95 => TicketPaymentError::GroupOrderClosed,
+            96 => TicketPaymentError::AlreadyInGroupOrder,
+            97 => TicketPaymentError::GroupOrderNotExpired,
+            98 => TicketPaymentError::InvalidGroupOrder,
             _ => TicketPaymentError::ArithmeticError,
         }
     }
@@ -1,5 +1,5 @@
-use crate::types::PaymentStatus;
-use soroban_sdk::{contracttype, Address, BytesN, String};
+use crate::types::{ArchivedPayment, PauseScope, PaymentStatus, TreasuryWallet, WithdrawalKind};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -21,10 +21,47 @@ pub enum AgoraEvent {
     TicketCheckedIn,
     BidPlaced,
     AuctionClosed,
-    ProposalCreated,
-    ProposalVoted,
     GovernanceActionExecuted,
     ContractVerificationFailed,
+    PayeePaid,
+    TreasuryPayout,
+    TreasuryWalletsUpdated,
+    WithdrawalQueued,
+    QueuedWithdrawalExecuted,
+    QueuedWithdrawalCancelled,
+    PauseScopeChanged,
+    UpgradeScheduled,
+    UpgradeCancelled,
+    ContractMigrated,
+    LoyaltyPointsApplied,
+    OrganizerLoyaltyDiscountApplied,
+    ProtectionPoolDrawn,
+    PaymentArchived,
+    TicketUpgraded,
+    TicketExchanged,
+    CreditIssued,
+    CreditRedeemed,
+    CreditReleased,
+    DonationReceived,
+    AddOnPurchased,
+    AddOnRedeemed,
+    AddOnRefunded,
+    DepositHeld,
+    DepositReturned,
+    DepositsSwept,
+}
+
+/// Event topics added after `AgoraEvent` neared Soroban's enum variant limit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AgoraEventExt {
+    InstallmentPaid,
+
+    GroupOrderCreated,
+    GroupOrderJoined,
+    GroupOrderFilled,
+    GroupOrderRefunded,
+    InstallmentForfeited,
 }
 
 #[contracttype]
@@ -100,6 +137,30 @@ pub struct DiscountCodeAppliedEvent {
     pub timestamp: u64,
 }
 
+/// Redeemable loyalty points spent as a price reduction on a purchase.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyPointsAppliedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub buyer_address: Address,
+    pub points: u64,
+    pub discount_amount: i128,
+    pub timestamp: u64,
+}
+
+/// Price reduction granted by the organizer's own loyalty ladder.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrganizerLoyaltyDiscountAppliedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub buyer_address: Address,
+    pub discount_bps: u32,
+    pub discount_amount: i128,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RevenueClaimedEvent {
@@ -136,6 +197,23 @@ pub struct ContractPausedEvent {
     pub timestamp: u64,
 }
 
+/// What a `PauseScopeChanged` event applies to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PauseTarget {
+    Scope(PauseScope),
+    Event(String),
+}
+
+/// Emitted when a pause scope or a single event is paused or resumed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseScopeChangedEvent {
+    pub target: PauseTarget,
+    pub paused: bool,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DisputeStatusChangedEvent {
@@ -144,6 +222,26 @@ pub struct DisputeStatusChangedEvent {
     pub timestamp: u64,
 }
 
+/// Refund shortfall covered by the registry's guest protection pool.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtectionPoolDrawnEvent {
+    pub event_id: String,
+    pub token: Address,
+    pub shortfall: i128,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// One leaf of an archived event's payments Merkle tree, in tree order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentArchivedEvent {
+    pub event_id: String,
+    pub index: u32,
+    pub leaf: ArchivedPayment,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PartialRefundProcessedEvent {
@@ -185,32 +283,295 @@ pub struct AuctionClosedEvent {
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ProposalCreatedEvent {
+pub struct GovernanceActionExecutedEvent {
     pub proposal_id: u64,
-    pub proposer: Address,
     pub change: crate::types::ParameterChange,
     pub timestamp: u64,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ProposalVotedEvent {
-    pub proposal_id: u64,
-    pub voter: Address,
+pub struct ContractVerificationFailedEvent {
+    pub missing_key: String,
     pub timestamp: u64,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct GovernanceActionExecutedEvent {
+pub struct PayeePaidEvent {
+    pub event_id: String,
+    pub payee: Address,
+    pub token: Address,
+    pub share_bps: u32,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryPayoutEvent {
+    pub wallet: Address,
+    pub token: Address,
+    pub share_bps: u32,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryWalletsUpdatedEvent {
+    pub wallets: Vec<TreasuryWallet>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalQueuedEvent {
+    pub id: u64,
+    pub kind: WithdrawalKind,
+    pub event_id: Option<String>,
+    pub token: Address,
+    pub amount: i128,
+    pub available_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedWithdrawalExecutedEvent {
+    pub id: u64,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedWithdrawalCancelledEvent {
+    pub id: u64,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeScheduledEvent {
     pub proposal_id: u64,
-    pub change: crate::types::ParameterChange,
+    pub wasm_hash: BytesN<32>,
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeCancelledEvent {
+    pub wasm_hash: BytesN<32>,
     pub timestamp: u64,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ContractVerificationFailedEvent {
-    pub missing_key: String,
+pub struct ContractMigratedEvent {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub records_migrated: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketUpgradedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub from_tier_id: String,
+    pub to_tier_id: String,
+    pub amount_charged: i128,
+    pub amount_refunded: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketExchangedEvent {
+    pub payment_id: String,
+    pub from_event_id: String,
+    pub from_tier_id: String,
+    pub to_event_id: String,
+    pub to_tier_id: String,
+    pub amount_charged: i128,
+    pub amount_refunded: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditIssuedEvent {
+    pub payment_id: String,
+    pub guest: Address,
+    pub organizer: Address,
+    pub refund_amount: i128,
+    pub credit_amount: i128,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditRedeemedEvent {
+    pub payment_id: String,
+    pub guest: Address,
+    pub organizer: Address,
+    pub amount: i128,
+    pub remaining: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditReleasedEvent {
+    pub guest: Address,
+    pub organizer: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationReceivedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub donor: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub platform_fee: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddOnPurchasedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub addon_id: String,
+    pub quantity: u32,
+    pub amount: i128,
+    pub platform_fee: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddOnRedeemedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub addon_id: String,
+    pub quantity: u32,
+    pub scanner: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddOnRefundedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub addon_id: String,
+    pub quantity: u32,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositHeldEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub guest: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositReturnedEvent {
+    pub payment_id: String,
+    pub guest: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositsSweptEvent {
+    pub event_id: String,
+    pub count: u32,
+    pub total: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentPaidEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub buyer_address: Address,
+    pub amount: i128,
+    pub installments_paid: u32,
+    pub installments: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentForfeitedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub buyer_address: Address,
+    pub refunded: i128,
+    pub penalty: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupOrderCreatedEvent {
+    pub order_id: String,
+    pub event_id: String,
+    pub tier_id: String,
+    pub initiator: Address,
+    pub tickets: u32,
+    pub price: i128,
+    pub deadline: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupOrderJoinedEvent {
+    pub order_id: String,
+    pub participant: Address,
+    pub payment_id: String,
+    pub amount: i128,
+    pub joined: u32,
+    pub tickets: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupOrderFilledEvent {
+    pub order_id: String,
+    pub event_id: String,
+    pub tickets: u32,
+    pub total: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupOrderRefundedEvent {
+    pub order_id: String,
+    pub event_id: String,
+    pub participants: u32,
+    pub total_refunded: i128,
     pub timestamp: u64,
 }
@@ -2,7 +2,9 @@
 pub mod contract;
 pub mod error;
 pub mod events;
+pub mod migration;
 pub mod storage;
+pub mod ttl;
 pub mod types;
 
 #[cfg(test)]
@@ -0,0 +1,90 @@
+//! # Migration Module
+//!
+//! Rewrites stored records after an upgrade changes their layout. Each schema
+//! version that adds fields to a stored type keeps a frozen copy of the previous
+//! layout here, together with the step that converts it to the next version.
+//!
+//! | Version | Change |
+//! |---------|--------|
+//! | 1 | Initial layout |
+//! | 2 | `Payment.token` (token the ticket was paid in) |
+
+use crate::storage::get_usdc_token;
+use crate::types::{DataKey, Payment, PaymentStatus};
+use soroban_sdk::{contracttype, Address, Env, Map, String, Symbol, Val, Vec};
+
+/// Layout version of stored records written by this build of the contract.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// `Payment` as stored by schema version 1, before it recorded its token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentV1 {
+    pub payment_id: String,
+    pub event_id: String,
+    pub buyer_address: Address,
+    pub ticket_tier_id: String,
+    pub amount: i128,
+    pub platform_fee: i128,
+    pub organizer_amount: i128,
+    pub status: PaymentStatus,
+    pub transaction_hash: String,
+    pub created_at: u64,
+    pub confirmed_at: Option<u64>,
+    pub refunded_amount: i128,
+}
+
+impl PaymentV1 {
+    fn into_v2(self, token: Address) -> Payment {
+        Payment {
+            payment_id: self.payment_id,
+            event_id: self.event_id,
+            buyer_address: self.buyer_address,
+            ticket_tier_id: self.ticket_tier_id,
+            token,
+            amount: self.amount,
+            platform_fee: self.platform_fee,
+            organizer_amount: self.organizer_amount,
+            status: self.status,
+            transaction_hash: self.transaction_hash,
+            created_at: self.created_at,
+            confirmed_at: self.confirmed_at,
+            refunded_amount: self.refunded_amount,
+        }
+    }
+}
+
+/// Applies the step that moves stored records from `from_version` to the next
+/// version, returning how many records were rewritten.
+pub fn migrate_step(env: &Env, from_version: u32, payment_ids: &Vec<String>) -> u32 {
+    match from_version {
+        1 => migrate_payments_v1_to_v2(env, payment_ids),
+        _ => 0,
+    }
+}
+
+/// Rewrites version 1 `Payment` records as paid in the refund token, which is
+/// what version 1 refunded them in. Records already in the current layout, and
+/// unknown ids, are skipped.
+fn migrate_payments_v1_to_v2(env: &Env, payment_ids: &Vec<String>) -> u32 {
+    let token = get_usdc_token(env);
+    let mut migrated = 0u32;
+    for payment_id in payment_ids.iter() {
+        let key = DataKey::Payment(payment_id);
+        // Decoding a record into the wrong struct traps, so check its field names first.
+        let raw: Option<Map<Symbol, Val>> = env.storage().persistent().get(&key);
+        let Some(raw) = raw else {
+            continue;
+        };
+        if raw.contains_key(Symbol::new(env, "token")) {
+            continue;
+        }
+        let legacy: PaymentV1 = env.storage().persistent().get(&key).unwrap();
+        // Written directly: `storage::get_payment` would try to decode the old record.
+        env.storage()
+            .persistent()
+            .set(&key, &legacy.into_v2(token.clone()));
+        migrated += 1;
+    }
+    migrated
+}
@@ -1,8 +1,16 @@
 use crate::{
     error::TicketPaymentError,
-    types::{DataKey, EventBalance, HighestBid, ParameterProposal, Payment, PaymentStatus},
+    ttl,
+    types::{
+        AddOnKey, AddOnPurchase, AllowlistKey, CreditBalance, CreditKey, CreditOffer, DataKey,
+        DepositKey, DonationConfig, DonationKey, EventBalance, FeeKey, GroupOrder, GroupOrderKey,
+        HeldDeposit, HighestBid, InstallmentKey, InstallmentSchedule, OrganizerCapPolicy,
+        PauseScope, Payment, PaymentStatus, PendingUpgrade, QueuedWithdrawal, TierChangeKey,
+        TreasuryWallet, TtlFamily, TtlKey, TtlPolicy, UpgradeKey, WithdrawalLimitConfig,
+        WithdrawalRecord, DEFAULT_WITHDRAWAL_QUEUE_DELAY_SECS, DEFAULT_WITHDRAWAL_WINDOW_SECS,
+    },
 };
-use soroban_sdk::{vec, Address, Env, String, Vec};
+use soroban_sdk::{vec, Address, Env, Map, String, Vec};
 
 const SHARD_SIZE: u32 = 100;
 
@@ -41,9 +49,14 @@ pub fn store_payment(env: &Env, payment: Payment) {
     }
 }
 
+/// Reading a payment extends its TTL.
 pub fn get_payment(env: &Env, payment_id: String) -> Option<Payment> {
     let key = DataKey::Payment(payment_id);
-    env.storage().persistent().get(&key)
+    let payment = env.storage().persistent().get(&key);
+    if payment.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Payment);
+    }
+    payment
 }
 
 pub fn update_payment_status(
@@ -101,6 +114,157 @@ pub fn get_event_payments(env: &Env, event_id: String) -> Vec<String> {
     all_payments
 }
 
+/// Deletes an event's payment records together with their event, buyer and
+/// status index entries.
+pub fn remove_event_payments(env: &Env, event_id: String, payment_ids: &Vec<String>) {
+    for payment_id in payment_ids.iter() {
+        if let Some(payment) = get_payment(env, payment_id.clone()) {
+            remove_payment_from_buyer_index(env, payment.buyer_address, payment_id.clone());
+            env.storage()
+                .persistent()
+                .remove(&DataKey::EventPaymentStatusEntry(
+                    event_id.clone(),
+                    payment.status,
+                    payment_id.clone(),
+                ));
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::EventPayment(event_id.clone(), payment_id.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Payment(payment_id));
+    }
+
+    let count = get_event_payment_count(env, event_id.clone());
+    for shard_id in 0..count.div_ceil(SHARD_SIZE) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::EventPaymentShard(event_id.clone(), shard_id));
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::EventPaymentCount(event_id.clone()));
+    for status in [
+        PaymentStatus::Pending,
+        PaymentStatus::Confirmed,
+        PaymentStatus::Refunded,
+        PaymentStatus::Failed,
+        PaymentStatus::CheckedIn,
+        PaymentStatus::PartiallyPaid,
+    ] {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::EventPaymentStatus(event_id.clone(), status));
+    }
+}
+
+/// Extends an event's balance, settings and payment indices, and every payment
+/// record together with its index entries.
+pub fn extend_event_payment_entries(env: &Env, event_id: String) {
+    let event = TtlFamily::Event;
+    for key in [
+        DataKey::Balances(event_id.clone()),
+        DataKey::TransferFee(event_id.clone()),
+        DataKey::BulkRefundIndex(event_id.clone()),
+        DataKey::DisputeStatus(event_id.clone()),
+        DataKey::PartialRefundIndex(event_id.clone()),
+        DataKey::PartialRefundPercentage(event_id.clone()),
+        DataKey::EventPaymentCount(event_id.clone()),
+    ] {
+        ttl::extend(env, &key, &event);
+    }
+    for key in [
+        TierChangeKey::DowngradeRefunds(event_id.clone()),
+        TierChangeKey::ExchangesEnabled(event_id.clone()),
+    ] {
+        ttl::extend(env, &key, &event);
+    }
+    for key in [
+        DonationKey::Config(event_id.clone()),
+        DonationKey::Totals(event_id.clone()),
+    ] {
+        ttl::extend(env, &key, &event);
+    }
+    ttl::extend(env, &AddOnKey::Redemptions(event_id.clone()), &event);
+    ttl::extend(env, &DepositKey::SweepIndex(event_id.clone()), &event);
+    ttl::extend(env, &GroupOrderKey::EventOrders(event_id.clone()), &event);
+    for order_id in get_event_group_orders(env, event_id.clone()).iter() {
+        ttl::extend(env, &GroupOrderKey::Order(order_id), &event);
+    }
+    for status in [
+        PaymentStatus::Pending,
+        PaymentStatus::Confirmed,
+        PaymentStatus::Refunded,
+        PaymentStatus::Failed,
+        PaymentStatus::CheckedIn,
+        PaymentStatus::PartiallyPaid,
+    ] {
+        ttl::extend(
+            env,
+            &DataKey::EventPaymentStatus(event_id.clone(), status),
+            &event,
+        );
+    }
+    ttl::extend(env, &FeeKey::EventFees(event_id.clone()), &event);
+    let count = get_event_payment_count(env, event_id.clone());
+    for shard_id in 0..count.div_ceil(SHARD_SIZE) {
+        ttl::extend(
+            env,
+            &DataKey::EventPaymentShard(event_id.clone(), shard_id),
+            &event,
+        );
+    }
+
+    let family = TtlFamily::Payment;
+    for payment_id in get_event_payments(env, event_id.clone()).iter() {
+        // `get_payment` extends the record itself.
+        if let Some(payment) = get_payment(env, payment_id.clone()) {
+            ttl::extend(
+                env,
+                &DataKey::EventPaymentStatusEntry(
+                    event_id.clone(),
+                    payment.status,
+                    payment_id.clone(),
+                ),
+                &family,
+            );
+            ttl::extend(
+                env,
+                &DataKey::BuyerPayment(payment.buyer_address, payment_id.clone()),
+                &family,
+            );
+        }
+        for addon_id in get_payment_add_ons(env, payment_id.clone()).iter() {
+            ttl::extend(
+                env,
+                &AddOnKey::Purchase(payment_id.clone(), addon_id),
+                &family,
+            );
+        }
+        ttl::extend(env, &AddOnKey::PaymentAddOns(payment_id.clone()), &family);
+        ttl::extend(env, &DepositKey::Held(payment_id.clone()), &family);
+        ttl::extend(env, &InstallmentKey::Schedule(payment_id.clone()), &family);
+        ttl::extend(
+            env,
+            &DataKey::EventPayment(event_id.clone(), payment_id),
+            &family,
+        );
+    }
+}
+
+pub fn get_ttl_policy(env: &Env, family: &TtlFamily) -> Option<TtlPolicy> {
+    env.storage()
+        .persistent()
+        .get(&TtlKey::Policy(family.clone()))
+}
+
+pub fn set_ttl_policy(env: &Env, family: &TtlFamily, policy: &TtlPolicy) {
+    env.storage()
+        .persistent()
+        .set(&TtlKey::Policy(family.clone()), policy);
+}
+
 pub fn get_buyer_payment_count(env: &Env, buyer_address: Address) -> u32 {
     env.storage()
         .persistent()
@@ -194,6 +358,32 @@ pub fn is_paused(env: &Env) -> bool {
         .unwrap_or(false)
 }
 
+pub fn get_paused_scopes(env: &Env) -> Vec<PauseScope> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PausedScopes)
+        .unwrap_or_else(|| vec![env])
+}
+
+pub fn set_paused_scopes(env: &Env, scopes: &Vec<PauseScope>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PausedScopes, scopes);
+}
+
+pub fn get_paused_events(env: &Env) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PausedEvents)
+        .unwrap_or_else(|| vec![env])
+}
+
+pub fn set_paused_events(env: &Env, event_ids: &Vec<String>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PausedEvents, event_ids);
+}
+
 pub fn add_token_to_whitelist(env: &Env, token: &Address) {
     env.storage()
         .persistent()
@@ -213,15 +403,18 @@ pub fn is_token_whitelisted(env: &Env, token: &Address) -> bool {
         .unwrap_or(false)
 }
 
+/// Reading an event's balance extends its TTL.
 pub fn get_event_balance(env: &Env, event_id: String) -> EventBalance {
-    env.storage()
-        .persistent()
-        .get(&DataKey::Balances(event_id))
-        .unwrap_or(EventBalance {
-            organizer_amount: 0,
-            total_withdrawn: 0,
-            platform_fee: 0,
-        })
+    let key = DataKey::Balances(event_id);
+    let balance = env.storage().persistent().get(&key);
+    if balance.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Event);
+    }
+    balance.unwrap_or(EventBalance {
+        organizer_amount: 0,
+        total_withdrawn: 0,
+        platform_fee: 0,
+    })
 }
 
 pub fn update_event_balance(
@@ -292,6 +485,59 @@ pub fn add_payment_to_event_index(env: &Env, event_id: String, payment_id: Strin
         .set(&DataKey::EventPayment(event_id, payment_id), &true);
 }
 
+pub fn remove_payment_from_event_index(env: &Env, event_id: String, payment_id: String) {
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::EventPayment(event_id.clone(), payment_id.clone()))
+    {
+        return;
+    }
+
+    let count = get_event_payment_count(env, event_id.clone());
+    for i in 0..count.div_ceil(SHARD_SIZE) {
+        let shard: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EventPaymentShard(event_id.clone(), i))
+            .unwrap_or_else(|| vec![env]);
+
+        if let Some(index) = shard.first_index_of(&payment_id) {
+            let mut new_shard = shard;
+            new_shard.remove(index);
+            env.storage()
+                .persistent()
+                .set(&DataKey::EventPaymentShard(event_id.clone(), i), &new_shard);
+            break;
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::EventPaymentCount(event_id.clone()), &(count - 1));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::EventPayment(event_id, payment_id));
+}
+
+/// Re-indexes a payment under another event, keeping its status.
+pub fn move_payment_to_event(env: &Env, payment: &Payment, to_event_id: String) {
+    remove_payment_from_event_index(env, payment.event_id.clone(), payment.payment_id.clone());
+    remove_payment_from_status_index(
+        env,
+        payment.event_id.clone(),
+        payment.status.clone(),
+        payment.payment_id.clone(),
+    );
+    add_payment_to_event_index(env, to_event_id.clone(), payment.payment_id.clone());
+    add_payment_to_status_index(
+        env,
+        to_event_id,
+        payment.status.clone(),
+        payment.payment_id.clone(),
+    );
+}
+
 pub fn add_payment_to_buyer_index(env: &Env, buyer_address: Address, payment_id: String) {
     if env.storage().persistent().has(&DataKey::BuyerPayment(
         buyer_address.clone(),
@@ -478,19 +724,129 @@ pub fn get_withdrawal_cap(env: &Env, token: Address) -> i128 {
         .unwrap_or(0)
 }
 
-pub fn get_daily_withdrawn_amount(env: &Env, token: Address, day: u64) -> i128 {
+/// Sums the withdrawals by `subject` in `token` within the last `window_secs`.
+pub fn get_window_withdrawn_amount(
+    env: &Env,
+    subject: Address,
+    token: Address,
+    window_secs: u64,
+) -> i128 {
+    let now = env.ledger().timestamp();
+    let records: Vec<WithdrawalRecord> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::WithdrawalWindow(subject, token))
+        .unwrap_or_else(|| vec![env]);
+    let mut total: i128 = 0;
+    for record in records.iter() {
+        if record.timestamp.saturating_add(window_secs) > now {
+            total = total.checked_add(record.amount).unwrap();
+        }
+    }
+    total
+}
+
+/// Records a withdrawal for `subject` and prunes records that fell out of the window.
+pub fn record_window_withdrawal(
+    env: &Env,
+    subject: Address,
+    token: Address,
+    window_secs: u64,
+    amount: i128,
+) {
+    let now = env.ledger().timestamp();
+    let key = DataKey::WithdrawalWindow(subject, token);
+    let records: Vec<WithdrawalRecord> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| vec![env]);
+    let mut kept: Vec<WithdrawalRecord> = vec![env];
+    for record in records.iter() {
+        if record.timestamp.saturating_add(window_secs) > now {
+            kept.push_back(record);
+        }
+    }
+    kept.push_back(WithdrawalRecord {
+        timestamp: now,
+        amount,
+    });
+    env.storage().persistent().set(&key, &kept);
+}
+
+pub fn get_withdrawal_limit_config(env: &Env) -> WithdrawalLimitConfig {
     env.storage()
         .persistent()
-        .get(&DataKey::DailyWithdrawalAmount(token, day))
-        .unwrap_or(0)
+        .get(&DataKey::WithdrawalLimitConfig)
+        .unwrap_or(WithdrawalLimitConfig {
+            window_secs: DEFAULT_WITHDRAWAL_WINDOW_SECS,
+            queue_delay_secs: DEFAULT_WITHDRAWAL_QUEUE_DELAY_SECS,
+        })
 }
 
-pub fn add_to_daily_withdrawn_amount(env: &Env, token: Address, day: u64, amount: i128) {
-    let current = get_daily_withdrawn_amount(env, token.clone(), day);
-    env.storage().persistent().set(
-        &DataKey::DailyWithdrawalAmount(token, day),
-        &current.checked_add(amount).unwrap(),
-    );
+pub fn set_withdrawal_limit_config(env: &Env, config: &WithdrawalLimitConfig) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::WithdrawalLimitConfig, config);
+}
+
+pub fn get_organizer_cap_policy(env: &Env, token: Address) -> OrganizerCapPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OrganizerCapPolicy(token))
+        .unwrap_or(OrganizerCapPolicy {
+            default_cap: 0,
+            verified_cap: 0,
+        })
+}
+
+pub fn set_organizer_cap_policy(env: &Env, token: Address, policy: &OrganizerCapPolicy) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::OrganizerCapPolicy(token), policy);
+}
+
+pub fn get_organizer_withdrawal_cap(env: &Env, organizer: Address, token: Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OrganizerWithdrawalCap(organizer, token))
+}
+
+pub fn set_organizer_withdrawal_cap(env: &Env, organizer: Address, token: Address, cap: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::OrganizerWithdrawalCap(organizer, token), &cap);
+}
+
+pub fn remove_organizer_withdrawal_cap(env: &Env, organizer: Address, token: Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::OrganizerWithdrawalCap(organizer, token));
+}
+
+pub fn get_queued_withdrawal(env: &Env, id: u64) -> Option<QueuedWithdrawal> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::QueuedWithdrawal(id))
+}
+
+pub fn set_queued_withdrawal(env: &Env, queued: &QueuedWithdrawal) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::QueuedWithdrawal(queued.id), queued);
+}
+
+pub fn increment_queued_withdrawal_count(env: &Env) -> u64 {
+    let count: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::QueuedWithdrawalCount)
+        .unwrap_or(0);
+    let next = count + 1;
+    env.storage()
+        .persistent()
+        .set(&DataKey::QueuedWithdrawalCount, &next);
+    next
 }
 
 pub fn get_active_escrow_total(env: &Env) -> i128 {
@@ -637,57 +993,350 @@ pub fn is_auction_closed(env: &Env, event_id: String, tier_id: String) -> bool {
         .unwrap_or(false)
 }
 
-// ── Governance functions ──────────────────────────────────────────────────────
+pub fn set_pending_upgrade(env: &Env, pending: &PendingUpgrade) {
+    env.storage()
+        .persistent()
+        .set(&UpgradeKey::PendingUpgrade, pending);
+}
+
+pub fn get_pending_upgrade(env: &Env) -> Option<PendingUpgrade> {
+    env.storage().persistent().get(&UpgradeKey::PendingUpgrade)
+}
 
-pub fn is_governor(env: &Env, address: &Address) -> bool {
+pub fn remove_pending_upgrade(env: &Env) {
     env.storage()
         .persistent()
-        .get(&DataKey::Governor(address.clone()))
-        .unwrap_or(false)
+        .remove(&UpgradeKey::PendingUpgrade);
+}
+
+/// Layout version of stored records; deployments that predate versioning report 1.
+pub fn get_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&UpgradeKey::SchemaVersion)
+        .unwrap_or(1)
+}
+
+pub fn set_schema_version(env: &Env, version: u32) {
+    env.storage()
+        .persistent()
+        .set(&UpgradeKey::SchemaVersion, &version);
+}
+
+// ── Event fees by token ───────────────────────────────────────────────────────
+
+/// Returns the platform fees an event has collected and not yet settled, per token.
+pub fn get_event_fees_by_token(env: &Env, event_id: String) -> Map<Address, i128> {
+    env.storage()
+        .persistent()
+        .get(&FeeKey::EventFees(event_id))
+        .unwrap_or_else(|| Map::new(env))
 }
 
-pub fn set_governor(env: &Env, address: &Address, status: bool) {
+pub fn add_to_event_fees_by_token(env: &Env, event_id: String, token: Address, amount: i128) {
+    let mut fees = get_event_fees_by_token(env, event_id.clone());
+    let total = fees.get(token.clone()).unwrap_or(0).saturating_add(amount);
+    fees.set(token, total);
     env.storage()
         .persistent()
-        .set(&DataKey::Governor(address.clone()), &status);
+        .set(&FeeKey::EventFees(event_id), &fees);
 }
 
-pub fn get_total_governors(env: &Env) -> u32 {
+pub fn subtract_from_event_fees_by_token(
+    env: &Env,
+    event_id: String,
+    token: Address,
+    amount: i128,
+) {
+    let mut fees = get_event_fees_by_token(env, event_id.clone());
+    let remaining = fees.get(token.clone()).unwrap_or(0).saturating_sub(amount);
+    if remaining > 0 {
+        fees.set(token, remaining);
+    } else {
+        fees.remove(token);
+    }
     env.storage()
         .persistent()
-        .get(&DataKey::TotalGovernors)
+        .set(&FeeKey::EventFees(event_id), &fees);
+}
+
+/// Tickets a buyer has bought against their allowlist entry. `tier_id` is the
+/// allowance's tier scope, or None for an event-wide allowlist.
+pub fn get_allowlist_purchases(
+    env: &Env,
+    event_id: String,
+    tier_id: Option<String>,
+    buyer: &Address,
+) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&AllowlistKey::Purchased(event_id, tier_id, buyer.clone()))
         .unwrap_or(0)
 }
 
-pub fn set_total_governors(env: &Env, total: u32) {
+pub fn set_allowlist_purchases(
+    env: &Env,
+    event_id: String,
+    tier_id: Option<String>,
+    buyer: &Address,
+    count: u32,
+) {
+    env.storage().persistent().set(
+        &AllowlistKey::Purchased(event_id, tier_id, buyer.clone()),
+        &count,
+    );
+}
+
+pub fn set_downgrade_refunds(env: &Env, event_id: String, enabled: bool) {
     env.storage()
         .persistent()
-        .set(&DataKey::TotalGovernors, &total);
+        .set(&TierChangeKey::DowngradeRefunds(event_id), &enabled);
 }
 
-pub fn get_proposal(env: &Env, id: u64) -> Option<ParameterProposal> {
-    env.storage().persistent().get(&DataKey::Proposal(id))
+pub fn get_downgrade_refunds(env: &Env, event_id: String) -> bool {
+    env.storage()
+        .persistent()
+        .get(&TierChangeKey::DowngradeRefunds(event_id))
+        .unwrap_or(false)
 }
 
-pub fn set_proposal(env: &Env, proposal: &ParameterProposal) {
+pub fn set_exchanges_enabled(env: &Env, event_id: String, enabled: bool) {
     env.storage()
         .persistent()
-        .set(&DataKey::Proposal(proposal.id), proposal);
+        .set(&TierChangeKey::ExchangesEnabled(event_id), &enabled);
 }
 
-pub fn get_proposal_count(env: &Env) -> u64 {
+pub fn get_exchanges_enabled(env: &Env, event_id: String) -> bool {
     env.storage()
         .persistent()
-        .get(&DataKey::ProposalCount)
+        .get(&TierChangeKey::ExchangesEnabled(event_id))
+        .unwrap_or(false)
+}
+
+// ── Refund credit ─────────────────────────────────────────────────────────────
+
+pub fn set_credit_offer(env: &Env, organizer: &Address, offer: Option<CreditOffer>) {
+    let key = CreditKey::Offer(organizer.clone());
+    match offer {
+        Some(offer) => env.storage().persistent().set(&key, &offer),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_credit_offer(env: &Env, organizer: &Address) -> Option<CreditOffer> {
+    env.storage()
+        .persistent()
+        .get(&CreditKey::Offer(organizer.clone()))
+}
+
+/// Returns the stored credit balance, expired or not. Extends the entry on read.
+pub fn get_credit_balance(env: &Env, guest: &Address, organizer: &Address) -> CreditBalance {
+    let key = CreditKey::Balance(guest.clone(), organizer.clone());
+    let balance = env.storage().persistent().get(&key);
+    if balance.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Payment);
+    }
+    balance.unwrap_or(CreditBalance {
+        amount: 0,
+        expires_at: 0,
+    })
+}
+
+pub fn set_credit_balance(
+    env: &Env,
+    guest: &Address,
+    organizer: &Address,
+    balance: &CreditBalance,
+) {
+    let key = CreditKey::Balance(guest.clone(), organizer.clone());
+    if balance.amount > 0 {
+        env.storage().persistent().set(&key, balance);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+// ── Add-ons ───────────────────────────────────────────────────────────────────
+
+pub fn get_add_on_purchase(
+    env: &Env,
+    payment_id: String,
+    addon_id: String,
+) -> Option<AddOnPurchase> {
+    let key = AddOnKey::Purchase(payment_id, addon_id);
+    let purchase = env.storage().persistent().get(&key);
+    if purchase.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Payment);
+    }
+    purchase
+}
+
+pub fn set_add_on_purchase(
+    env: &Env,
+    payment_id: String,
+    addon_id: String,
+    purchase: &AddOnPurchase,
+) {
+    let key = AddOnKey::Purchase(payment_id.clone(), addon_id.clone());
+    if !env.storage().persistent().has(&key) {
+        let mut ids = get_payment_add_ons(env, payment_id.clone());
+        ids.push_back(addon_id);
+        env.storage()
+            .persistent()
+            .set(&AddOnKey::PaymentAddOns(payment_id), &ids);
+    }
+    env.storage().persistent().set(&key, purchase);
+}
+
+pub fn get_payment_add_ons(env: &Env, payment_id: String) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&AddOnKey::PaymentAddOns(payment_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn get_add_on_redemptions(env: &Env, event_id: String) -> Map<String, u32> {
+    env.storage()
+        .persistent()
+        .get(&AddOnKey::Redemptions(event_id))
+        .unwrap_or_else(|| Map::new(env))
+}
+
+pub fn add_add_on_redemptions(env: &Env, event_id: String, addon_id: String, quantity: u32) {
+    let mut redemptions = get_add_on_redemptions(env, event_id.clone());
+    let total = redemptions
+        .get(addon_id.clone())
         .unwrap_or(0)
+        .saturating_add(quantity);
+    redemptions.set(addon_id, total);
+    env.storage()
+        .persistent()
+        .set(&AddOnKey::Redemptions(event_id), &redemptions);
 }
 
-pub fn increment_proposal_count(env: &Env) -> u64 {
-    let count = get_proposal_count(env) + 1;
+// ── RSVP deposits ─────────────────────────────────────────────────────────────
+
+pub fn get_held_deposit(env: &Env, payment_id: String) -> Option<HeldDeposit> {
+    let key = DepositKey::Held(payment_id);
+    let deposit = env.storage().persistent().get(&key);
+    if deposit.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Payment);
+    }
+    deposit
+}
+
+pub fn set_held_deposit(env: &Env, payment_id: String, deposit: &HeldDeposit) {
+    env.storage()
+        .persistent()
+        .set(&DepositKey::Held(payment_id), deposit);
+}
+
+pub fn remove_held_deposit(env: &Env, payment_id: String) {
     env.storage()
         .persistent()
-        .set(&DataKey::ProposalCount, &count);
-    count
+        .remove(&DepositKey::Held(payment_id));
+}
+
+pub fn get_deposit_sweep_index(env: &Env, event_id: String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DepositKey::SweepIndex(event_id))
+        .unwrap_or(0)
+}
+
+pub fn set_deposit_sweep_index(env: &Env, event_id: String, index: u32) {
+    env.storage()
+        .persistent()
+        .set(&DepositKey::SweepIndex(event_id), &index);
+}
+
+// ── Installments ──────────────────────────────────────────────────────────────
+
+pub fn get_installment_schedule(env: &Env, payment_id: String) -> Option<InstallmentSchedule> {
+    let key = InstallmentKey::Schedule(payment_id);
+    let schedule = env.storage().persistent().get(&key);
+    if schedule.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Payment);
+    }
+    schedule
+}
+
+pub fn set_installment_schedule(env: &Env, payment_id: String, schedule: &InstallmentSchedule) {
+    env.storage()
+        .persistent()
+        .set(&InstallmentKey::Schedule(payment_id), schedule);
+}
+
+pub fn remove_installment_schedule(env: &Env, payment_id: String) {
+    env.storage()
+        .persistent()
+        .remove(&InstallmentKey::Schedule(payment_id));
+}
+
+// ── Group orders ──────────────────────────────────────────────────────────────
+
+pub fn get_group_order(env: &Env, order_id: String) -> Option<GroupOrder> {
+    let key = GroupOrderKey::Order(order_id);
+    let order = env.storage().persistent().get(&key);
+    if order.is_some() {
+        ttl::extend(env, &key, &TtlFamily::Event);
+    }
+    order
+}
+
+/// Stores a group order, indexing it under its event the first time.
+pub fn set_group_order(env: &Env, order: &GroupOrder) {
+    let key = GroupOrderKey::Order(order.order_id.clone());
+    if !env.storage().persistent().has(&key) {
+        let mut ids = get_event_group_orders(env, order.event_id.clone());
+        ids.push_back(order.order_id.clone());
+        env.storage()
+            .persistent()
+            .set(&GroupOrderKey::EventOrders(order.event_id.clone()), &ids);
+    }
+    env.storage().persistent().set(&key, order);
+}
+
+pub fn get_event_group_orders(env: &Env, event_id: String) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&GroupOrderKey::EventOrders(event_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+// ── Donations ─────────────────────────────────────────────────────────────────
+
+pub fn set_donation_config(env: &Env, event_id: String, config: Option<DonationConfig>) {
+    let key = DonationKey::Config(event_id);
+    match config {
+        Some(config) => env.storage().persistent().set(&key, &config),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_donation_config(env: &Env, event_id: String) -> Option<DonationConfig> {
+    env.storage()
+        .persistent()
+        .get(&DonationKey::Config(event_id))
+}
+
+pub fn get_donation_totals(env: &Env, event_id: String) -> Map<Address, i128> {
+    env.storage()
+        .persistent()
+        .get(&DonationKey::Totals(event_id))
+        .unwrap_or_else(|| Map::new(env))
+}
+
+pub fn add_to_donation_total(env: &Env, event_id: String, token: Address, amount: i128) {
+    let mut totals = get_donation_totals(env, event_id.clone());
+    let total = totals
+        .get(token.clone())
+        .unwrap_or(0)
+        .saturating_add(amount);
+    totals.set(token, total);
+    env.storage()
+        .persistent()
+        .set(&DonationKey::Totals(event_id), &totals);
 }
 
 // ── Payment Status Index ──────────────────────────────────────────────────────
@@ -799,3 +1448,61 @@ pub fn get_payments_by_status(env: &Env, event_id: String, status: PaymentStatus
         .get(&DataKey::EventPaymentStatus(event_id, status))
         .unwrap_or_else(|| vec![env])
 }
+
+// ── Revenue split accounting ──────────────────────────────────────────────────
+
+/// Returns the cumulative amount paid out to `payee` for an event's revenue split.
+pub fn get_payee_earnings(env: &Env, event_id: String, payee: Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PayeeEarnings(event_id, payee))
+        .unwrap_or(0)
+}
+
+pub fn add_to_payee_earnings(env: &Env, event_id: String, payee: Address, amount: i128) {
+    let current = get_payee_earnings(env, event_id.clone(), payee.clone());
+    env.storage().persistent().set(
+        &DataKey::PayeeEarnings(event_id, payee),
+        &current.checked_add(amount).unwrap(),
+    );
+}
+
+// ── Platform fee treasury ─────────────────────────────────────────────────────
+
+pub fn set_treasury_wallets(env: &Env, wallets: &Vec<TreasuryWallet>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TreasuryWallets, wallets);
+}
+
+pub fn get_treasury_wallets(env: &Env) -> Vec<TreasuryWallet> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TreasuryWallets)
+        .unwrap_or_else(|| vec![env])
+}
+
+/// Platform fees that have left event escrow (e.g. on `claim_revenue`) and are
+/// waiting to be paid out through `withdraw_platform_fees`.
+pub fn get_released_fees(env: &Env, token: Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReleasedFees(token))
+        .unwrap_or(0)
+}
+
+pub fn add_to_released_fees(env: &Env, token: Address, amount: i128) {
+    let current = get_released_fees(env, token.clone());
+    env.storage().persistent().set(
+        &DataKey::ReleasedFees(token),
+        &current.checked_add(amount).unwrap(),
+    );
+}
+
+pub fn subtract_from_released_fees(env: &Env, token: Address, amount: i128) {
+    let current = get_released_fees(env, token.clone());
+    env.storage().persistent().set(
+        &DataKey::ReleasedFees(token),
+        &current.checked_sub(amount).unwrap(),
+    );
+}
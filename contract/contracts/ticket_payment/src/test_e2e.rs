@@ -1,6 +1,9 @@
 use super::contract::{event_registry, TicketPaymentContract, TicketPaymentContractClient};
 use super::storage::*;
-use super::types::{PaymentStatus, MAX_BPS, TRANSFER_FEE_BPS};
+use super::types::{
+    OrganizerCapPolicy, ParameterChange, PaymentStatus, QueuedWithdrawalStatus, WithdrawalKind,
+    MAX_BPS, TRANSFER_FEE_BPS,
+};
 use crate::error::TicketPaymentError;
 use soroban_sdk::{
     testutils::Address as _, testutils::Ledger, token, Address, Env, String, Symbol,
@@ -50,6 +53,13 @@ impl MockRegistryE2E {
         let end_time_key = Symbol::new(&env, "end_time");
         let stored_end_time: u64 = env.storage().instance().get(&end_time_key).unwrap_or(0);
 
+        let cancelled_key = Symbol::new(&env, "cancelled");
+        let cancelled: bool = env
+            .storage()
+            .instance()
+            .get(&cancelled_key)
+            .unwrap_or(false);
+
         Some(event_registry::EventInfo {
             event_id,
             name: String::from_str(&env, "Test Event"),
@@ -57,8 +67,12 @@ impl MockRegistryE2E {
             payment_address,
             platform_fee_percent: 500,
             custom_fee_bps: None,
-            is_active: true,
-            status: event_registry::EventStatus::Active,
+            is_active: !cancelled,
+            status: if cancelled {
+                event_registry::EventStatus::Cancelled
+            } else {
+                event_registry::EventStatus::Active
+            },
             created_at: 0,
             metadata_cid: String::from_str(
                 &env,
@@ -147,6 +161,37 @@ impl MockRegistryE2E {
         let key = Symbol::new(&env, "end_time");
         env.storage().instance().set(&key, &end_time);
     }
+
+    pub fn set_cancelled(env: Env, cancelled: bool) {
+        let key = Symbol::new(&env, "cancelled");
+        env.storage().instance().set(&key, &cancelled);
+    }
+
+    pub fn get_revenue_split(
+        env: Env,
+        _event_id: String,
+    ) -> soroban_sdk::Vec<event_registry::Payee> {
+        let key = Symbol::new(&env, "payees");
+        env.storage()
+            .instance()
+            .get(&key)
+            .unwrap_or_else(|| soroban_sdk::vec![&env])
+    }
+
+    pub fn set_payees(env: Env, payees: soroban_sdk::Vec<event_registry::Payee>) {
+        let key = Symbol::new(&env, "payees");
+        env.storage().instance().set(&key, &payees);
+    }
+
+    pub fn is_organizer_verified(env: Env, _organizer: Address) -> bool {
+        let key = Symbol::new(&env, "verified");
+        env.storage().instance().get(&key).unwrap_or(false)
+    }
+
+    pub fn set_verified(env: Env, verified: bool) {
+        let key = Symbol::new(&env, "verified");
+        env.storage().instance().set(&key, &verified);
+    }
 }
 
 /// Mock registry returning a cancelled event — for auto-refund tests.
@@ -420,6 +465,7 @@ fn buy_ticket(
         &1,
         &None,
         &None,
+        &None,
     )
 }
 
@@ -494,7 +540,7 @@ fn test_e2e_purchase_and_refund_flow() {
     assert_eq!(buyer_balance_after_buy, 0); // all spent
 
     // Request guest refund
-    client.request_guest_refund(&pay_id);
+    client.request_guest_refund(&pay_id, &false);
 
     let payment = client.get_payment_status(&pay_id).unwrap();
     assert_eq!(payment.status, PaymentStatus::Refunded);
@@ -509,6 +555,9 @@ fn test_e2e_purchase_and_refund_flow() {
     // After refund, organizer_amount is reduced; the restocking fee portion remains.
     // The original platform_fee is zeroed out (refunded from escrow).
     assert_eq!(escrow.platform_fee, 0);
+    // Refunded fees are no longer counted as collectable platform revenue.
+    assert_eq!(client.get_total_fees_collected(&usdc_id), 0);
+    assert_eq!(client.get_active_escrow_total(), restocking_fee);
 }
 
 // =============================================================================
@@ -638,6 +687,7 @@ fn test_e2e_duplicate_payment_id_rejected() {
         &1,
         &None,
         &None,
+        &None,
     );
 
     // The second call should succeed (no explicit duplicate rejection in the contract),
@@ -688,6 +738,7 @@ fn test_e2e_state_consistent_after_failed_payment() {
         &1,
         &None,
         &None,
+        &None,
     );
     assert_eq!(result, Err(Ok(TicketPaymentError::TokenNotWhitelisted)));
 
@@ -735,6 +786,7 @@ fn test_e2e_batch_purchase_then_partial_refund() {
         &quantity,
         &None,
         &None,
+        &None,
     );
 
     // Verify 3 sub-payments exist (p-0, p-1, p-2)
@@ -752,7 +804,7 @@ fn test_e2e_batch_purchase_then_partial_refund() {
     assert_eq!(p2.amount, amount_per_ticket);
 
     // Refund one ticket (p-1)
-    client.request_guest_refund(&String::from_str(&env, "p-1"));
+    client.request_guest_refund(&String::from_str(&env, "p-1"), &false);
 
     let p1_after = client
         .get_payment_status(&String::from_str(&env, "p-1"))
@@ -818,9 +870,6 @@ fn test_e2e_organizer_withdrawal_after_sales() {
     let settled = client.settle_platform_fees(&event_id, &usdc_id);
     assert_eq!(settled, expected_fee);
 
-    // Withdraw platform fees
-    client.withdraw_platform_fees(&expected_fee, &usdc_id);
-
     let platform_balance = token::Client::new(&env, &usdc_id).balance(&platform_wallet);
     assert_eq!(platform_balance, expected_fee);
 
@@ -830,6 +879,343 @@ fn test_e2e_organizer_withdrawal_after_sales() {
     assert_eq!(final_escrow.platform_fee, 0);
 }
 
+#[test]
+fn test_e2e_withdrawal_distributed_across_revenue_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _pw, registry_id) = setup_e2e(&env);
+    let organizer = Address::generate(&env);
+    let venue = Address::generate(&env);
+    let performer = Address::generate(&env);
+    let co_host = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+
+    env.as_contract(&registry_id, || {
+        MockRegistryE2E::set_organizer(env.clone(), organizer.clone());
+        MockRegistryE2E::set_payees(
+            env.clone(),
+            soroban_sdk::vec![
+                &env,
+                event_registry::Payee {
+                    address: venue.clone(),
+                    share_bps: 5000,
+                },
+                event_registry::Payee {
+                    address: performer.clone(),
+                    share_bps: 3333,
+                },
+                event_registry::Payee {
+                    address: co_host.clone(),
+                    share_bps: 1667,
+                },
+            ],
+        );
+    });
+
+    // Two sales, one of which is refunded with the restocking fee retained.
+    fund_buyer(&env, &usdc_id, &buyer, &client.address, amount * 2);
+    buy_ticket(&client, &env, "pay_s1", "event_1", &buyer, &usdc_id, amount);
+    let refunded = buy_ticket(&client, &env, "pay_s2", "event_1", &buyer, &usdc_id, amount);
+    client.request_guest_refund(&refunded, &false);
+
+    let event_id = String::from_str(&env, "event_1");
+    let organizer_amount = client.get_event_escrow_balance(&event_id).organizer_amount;
+    let restocking_fee = 50_0000000i128;
+    let fee = (amount * 500) / 10000;
+    assert_eq!(organizer_amount, amount - fee + restocking_fee);
+
+    let withdrawn = client.withdraw_organizer_funds(&event_id, &usdc_id);
+    assert_eq!(withdrawn, organizer_amount);
+
+    let usdc = token::Client::new(&env, &usdc_id);
+    let venue_share = organizer_amount * 5000 / 10000;
+    let performer_share = organizer_amount * 3333 / 10000;
+    let co_host_share = organizer_amount - venue_share - performer_share;
+    assert_eq!(usdc.balance(&venue), venue_share);
+    assert_eq!(usdc.balance(&performer), performer_share);
+    assert_eq!(usdc.balance(&co_host), co_host_share);
+    assert_eq!(usdc.balance(&organizer), 0);
+
+    assert_eq!(client.get_payee_earnings(&event_id, &venue), venue_share);
+    assert_eq!(
+        client.get_payee_earnings(&event_id, &performer),
+        performer_share
+    );
+    assert_eq!(
+        client.get_payee_earnings(&event_id, &co_host),
+        co_host_share
+    );
+}
+
+#[test]
+fn test_e2e_withdrawal_defaults_to_payment_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _pw, registry_id) = setup_e2e(&env);
+    let organizer = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+
+    env.as_contract(&registry_id, || {
+        MockRegistryE2E::set_organizer(env.clone(), organizer.clone());
+    });
+
+    fund_buyer(&env, &usdc_id, &buyer, &client.address, amount);
+    buy_ticket(&client, &env, "pay_d1", "event_1", &buyer, &usdc_id, amount);
+
+    let event_id = String::from_str(&env, "event_1");
+    let withdrawn = client.withdraw_organizer_funds(&event_id, &usdc_id);
+
+    // The mock routes payments to the organizer, which is the single default payee.
+    assert_eq!(
+        token::Client::new(&env, &usdc_id).balance(&organizer),
+        withdrawn
+    );
+    assert_eq!(client.get_payee_earnings(&event_id, &organizer), withdrawn);
+}
+
+#[test]
+fn test_e2e_organizer_withdrawal_over_cap_is_queued() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let (client, _admin, usdc_id, _pw, registry_id) = setup_e2e(&env);
+    let organizer = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+
+    env.as_contract(&registry_id, || {
+        MockRegistryE2E::set_organizer(env.clone(), organizer.clone());
+    });
+    client.apply_governance_change(
+        &0,
+        &ParameterChange::SetOrganizerCapPolicy(
+            usdc_id.clone(),
+            OrganizerCapPolicy {
+                default_cap: 100_0000000i128,
+                verified_cap: 2000_0000000i128,
+            },
+        ),
+    );
+
+    fund_buyer(&env, &usdc_id, &buyer, &client.address, amount);
+    buy_ticket(&client, &env, "pay_q1", "event_1", &buyer, &usdc_id, amount);
+
+    let event_id = String::from_str(&env, "event_1");
+    let organizer_amount = client.get_event_escrow_balance(&event_id).organizer_amount;
+    let escrow_before = client.get_active_escrow_total();
+
+    // Over the default cap: nothing is paid now and the amount leaves the balance.
+    assert_eq!(client.withdraw_organizer_funds(&event_id, &usdc_id), 0);
+    let balance = client.get_event_escrow_balance(&event_id);
+    assert_eq!(balance.organizer_amount, 0);
+    assert_eq!(balance.total_withdrawn, organizer_amount);
+    assert_eq!(client.get_active_escrow_total(), escrow_before);
+
+    let queued = client.get_queued_withdrawal(&1).unwrap();
+    assert_eq!(queued.kind, WithdrawalKind::OrganizerRevenue);
+    assert_eq!(queued.amount, organizer_amount);
+    assert_eq!(queued.organizer, Some(organizer.clone()));
+    assert_eq!(queued.available_at, 1_000 + 86_400);
+
+    let res = client.try_execute_queued_withdrawal(&1);
+    assert_eq!(res, Err(Ok(TicketPaymentError::WithdrawalNotReady)));
+
+    env.ledger().set_timestamp(1_000 + 86_400);
+    assert_eq!(client.execute_queued_withdrawal(&1), organizer_amount);
+    assert_eq!(
+        token::Client::new(&env, &usdc_id).balance(&organizer),
+        organizer_amount
+    );
+    assert_eq!(
+        client.get_active_escrow_total(),
+        escrow_before - organizer_amount
+    );
+    assert_eq!(
+        client.get_organizer_withdrawn_amount(&organizer, &usdc_id),
+        organizer_amount
+    );
+    assert_eq!(
+        client.get_queued_withdrawal(&1).unwrap().status,
+        QueuedWithdrawalStatus::Executed
+    );
+
+    let res = client.try_execute_queued_withdrawal(&1);
+    assert_eq!(res, Err(Ok(TicketPaymentError::QueuedWithdrawalNotPending)));
+}
+
+#[test]
+fn test_e2e_cancel_queued_organizer_withdrawal_restores_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _pw, registry_id) = setup_e2e(&env);
+    let organizer = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+
+    env.as_contract(&registry_id, || {
+        MockRegistryE2E::set_organizer(env.clone(), organizer.clone());
+    });
+    client.apply_governance_change(
+        &0,
+        &ParameterChange::SetOrganizerWithdrawalCap(
+            organizer.clone(),
+            usdc_id.clone(),
+            Some(1i128),
+        ),
+    );
+
+    fund_buyer(&env, &usdc_id, &buyer, &client.address, amount);
+    buy_ticket(&client, &env, "pay_c1", "event_1", &buyer, &usdc_id, amount);
+
+    let event_id = String::from_str(&env, "event_1");
+    let before = client.get_event_escrow_balance(&event_id);
+    assert_eq!(client.withdraw_organizer_funds(&event_id, &usdc_id), 0);
+
+    client.cancel_queued_withdrawal(&1);
+    assert_eq!(client.get_event_escrow_balance(&event_id), before);
+    assert_eq!(
+        client.get_queued_withdrawal(&1).unwrap().status,
+        QueuedWithdrawalStatus::Cancelled
+    );
+    let res = client.try_execute_queued_withdrawal(&1);
+    assert_eq!(res, Err(Ok(TicketPaymentError::QueuedWithdrawalNotPending)));
+
+    // Lifting the override lets the organizer withdraw directly.
+    client.apply_governance_change(
+        &0,
+        &ParameterChange::SetOrganizerWithdrawalCap(organizer.clone(), usdc_id.clone(), None),
+    );
+    assert_eq!(
+        client.withdraw_organizer_funds(&event_id, &usdc_id),
+        before.organizer_amount
+    );
+}
+
+#[test]
+fn test_e2e_queued_organizer_withdrawal_not_paid_after_cancellation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let (client, _admin, usdc_id, _pw, registry_id) = setup_e2e(&env);
+    let organizer = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+
+    env.as_contract(&registry_id, || {
+        MockRegistryE2E::set_organizer(env.clone(), organizer.clone());
+    });
+    client.apply_governance_change(
+        &0,
+        &ParameterChange::SetOrganizerWithdrawalCap(
+            organizer.clone(),
+            usdc_id.clone(),
+            Some(1i128),
+        ),
+    );
+
+    fund_buyer(&env, &usdc_id, &buyer, &client.address, amount);
+    buy_ticket(&client, &env, "pay_x1", "event_1", &buyer, &usdc_id, amount);
+
+    let event_id = String::from_str(&env, "event_1");
+    let before = client.get_event_escrow_balance(&event_id);
+    assert_eq!(client.withdraw_organizer_funds(&event_id, &usdc_id), 0);
+
+    // The event is cancelled while the withdrawal waits out its delay.
+    env.as_contract(&registry_id, || {
+        MockRegistryE2E::set_cancelled(env.clone(), true);
+    });
+    env.ledger().set_timestamp(1_000 + 86_400);
+    assert_eq!(client.execute_queued_withdrawal(&1), 0);
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&organizer), 0);
+    assert_eq!(
+        client.get_queued_withdrawal(&1).unwrap().status,
+        QueuedWithdrawalStatus::Cancelled
+    );
+    assert_eq!(client.get_event_escrow_balance(&event_id), before);
+    let res = client.try_execute_queued_withdrawal(&1);
+    assert_eq!(res, Err(Ok(TicketPaymentError::QueuedWithdrawalNotPending)));
+}
+
+#[test]
+fn test_e2e_verified_organizer_gets_larger_withdrawal_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _pw, registry_id) = setup_e2e(&env);
+    let organizer = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    let default_cap = 100_0000000i128;
+    let verified_cap = 2000_0000000i128;
+
+    env.as_contract(&registry_id, || {
+        MockRegistryE2E::set_organizer(env.clone(), organizer.clone());
+        MockRegistryE2E::set_verified(env.clone(), true);
+    });
+    client.apply_governance_change(
+        &0,
+        &ParameterChange::SetOrganizerCapPolicy(
+            usdc_id.clone(),
+            OrganizerCapPolicy {
+                default_cap,
+                verified_cap,
+            },
+        ),
+    );
+    assert_eq!(
+        client.get_organizer_withdrawal_cap(&organizer, &usdc_id),
+        verified_cap
+    );
+
+    fund_buyer(&env, &usdc_id, &buyer, &client.address, amount);
+    buy_ticket(&client, &env, "pay_v1", "event_1", &buyer, &usdc_id, amount);
+
+    let event_id = String::from_str(&env, "event_1");
+    let organizer_amount = client.get_event_escrow_balance(&event_id).organizer_amount;
+    assert!(organizer_amount > default_cap);
+    assert_eq!(
+        client.withdraw_organizer_funds(&event_id, &usdc_id),
+        organizer_amount
+    );
+    assert_eq!(
+        client.get_organizer_withdrawn_amount(&organizer, &usdc_id),
+        organizer_amount
+    );
+
+    // A per-organizer override takes precedence over the policy.
+    client.apply_governance_change(
+        &0,
+        &ParameterChange::SetOrganizerWithdrawalCap(
+            organizer.clone(),
+            usdc_id.clone(),
+            Some(default_cap / 2),
+        ),
+    );
+    assert_eq!(
+        client.get_organizer_withdrawal_cap(&organizer, &usdc_id),
+        default_cap / 2
+    );
+
+    let res = client.try_apply_governance_change(
+        &0,
+        &ParameterChange::SetOrganizerCapPolicy(
+            usdc_id.clone(),
+            OrganizerCapPolicy {
+                default_cap: -1i128,
+                verified_cap: 0i128,
+            },
+        ),
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidWithdrawalLimit)));
+}
+
 // =============================================================================
 // 9. Pause blocks operations, resume allows
 // =============================================================================
@@ -863,6 +1249,7 @@ fn test_e2e_pause_blocks_operations_resume_allows() {
         &1,
         &None,
         &None,
+        &None,
     );
     assert_eq!(result, Err(Ok(TicketPaymentError::ContractPaused)));
 
@@ -881,6 +1268,7 @@ fn test_e2e_pause_blocks_operations_resume_allows() {
         &1,
         &None,
         &None,
+        &None,
     );
     assert!(result.is_ok());
 }
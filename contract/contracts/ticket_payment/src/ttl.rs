@@ -0,0 +1,86 @@
+//! TTL policy for persistent entries.
+//!
+//! Payments and event balances are extended whenever they are read, and
+//! `bump_event` extends everything kept for an event in one call. Each
+//! [`TtlFamily`] has a default policy the admin may override.
+
+use crate::storage::get_ttl_policy;
+use crate::types::{DataKey, TtlFamily, TtlKey, TtlPolicy, UpgradeKey};
+use soroban_sdk::{Env, IntoVal, Val};
+
+/// Ledgers closed per day at a five second close time.
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+pub const CONFIG_TTL_THRESHOLD: u32 = 60 * DAY_IN_LEDGERS;
+pub const CONFIG_TTL_EXTEND_TO: u32 = 180 * DAY_IN_LEDGERS;
+pub const RECORD_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+pub const RECORD_TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+
+pub fn default_policy(family: &TtlFamily) -> TtlPolicy {
+    let (threshold, extend_to) = match family {
+        TtlFamily::Config => (CONFIG_TTL_THRESHOLD, CONFIG_TTL_EXTEND_TO),
+        TtlFamily::Payment | TtlFamily::Event => (RECORD_TTL_THRESHOLD, RECORD_TTL_EXTEND_TO),
+    };
+    TtlPolicy {
+        threshold,
+        extend_to,
+    }
+}
+
+pub fn get_policy(env: &Env, family: &TtlFamily) -> TtlPolicy {
+    get_ttl_policy(env, family).unwrap_or_else(|| default_policy(family))
+}
+
+pub fn is_valid_policy(env: &Env, policy: &TtlPolicy) -> bool {
+    policy.threshold > 0
+        && policy.threshold < policy.extend_to
+        && policy.extend_to <= env.storage().max_ttl()
+}
+
+/// Extends a persistent entry under its family's policy; missing keys are skipped.
+pub fn extend<K>(env: &Env, key: &K, family: &TtlFamily)
+where
+    K: IntoVal<Env, Val>,
+{
+    if env.storage().persistent().has(key) {
+        let policy = get_policy(env, family);
+        env.storage().persistent().extend_ttl(
+            key,
+            policy.threshold,
+            policy.extend_to.min(env.storage().max_ttl()),
+        );
+    }
+}
+
+/// Extends the contract instance and every contract-wide setting.
+pub fn extend_config(env: &Env) {
+    let policy = get_policy(env, &TtlFamily::Config);
+    env.storage().instance().extend_ttl(
+        policy.threshold,
+        policy.extend_to.min(env.storage().max_ttl()),
+    );
+
+    for key in [
+        DataKey::Admin,
+        DataKey::UsdcToken,
+        DataKey::PlatformWallet,
+        DataKey::EventRegistry,
+        DataKey::Initialized,
+        DataKey::OracleAddress,
+        DataKey::SlippageBps,
+        DataKey::IsPaused,
+        DataKey::PausedScopes,
+        DataKey::PausedEvents,
+        DataKey::TreasuryWallets,
+        DataKey::WithdrawalLimitConfig,
+        DataKey::TotalVolumeProcessed,
+        DataKey::ActiveEscrowTotal,
+        DataKey::QueuedWithdrawalCount,
+    ] {
+        extend(env, &key, &TtlFamily::Config);
+    }
+    extend(env, &UpgradeKey::SchemaVersion, &TtlFamily::Config);
+    for family in [TtlFamily::Config, TtlFamily::Payment, TtlFamily::Event] {
+        extend(env, &TtlKey::Policy(family), &TtlFamily::Config);
+    }
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, String};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Vec};
 
 pub const TRANSFER_FEE_BPS: u32 = 100;
 pub const MAX_BPS: u32 = 10000;
@@ -14,34 +14,24 @@ pub struct AuctionConfig {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParameterChange {
-    AddGovernor(Address),
-    RemoveGovernor(Address),
     AddTokenToWhitelist(Address),
     RemoveTokenFromWhitelist(Address),
     UpdateWithdrawalCap(Address, i128), // This is still i128 amount
     UpdateSlippage(u32),
     UpdateTransferFee(String, u32), // Changed from i128 to u32 basis points
+    UpgradeContract(BytesN<32>),    // Scheduled behind the upgrade timelock
+    SetTreasuryWallets(Vec<TreasuryWallet>), // Empty routes all fees to the platform wallet
+    SetWithdrawalLimitConfig(WithdrawalLimitConfig),
+    SetOrganizerCapPolicy(Address, OrganizerCapPolicy), // Default organizer caps for a token
+    SetOrganizerWithdrawalCap(Address, Address, Option<i128>), // (organizer, token); None restores the policy cap
 }
 
+/// A governance-approved upgrade waiting for its timelock to elapse.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub enum ProposalStatus {
-    Pending,
-    Executed,
-    Rejected,
-}
-
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ParameterProposal {
-    pub id: u64,
-    pub proposer: Address,
-    pub change: ParameterChange,
-    pub status: ProposalStatus,
-    pub created_at: u64,
-    pub expires_at: u64,
-    pub vote_count: u32,
-    pub voters: soroban_sdk::Vec<Address>,
+pub struct PendingUpgrade {
+    pub wasm_hash: BytesN<32>,
+    pub eta: u64,
 }
 
 #[contracttype]
@@ -52,6 +42,8 @@ pub enum PaymentStatus {
     Refunded,
     Failed,
     CheckedIn,
+    /// Installments are still due; the ticket cannot be checked in or transferred.
+    PartiallyPaid,
 }
 
 #[contracttype]
@@ -61,7 +53,8 @@ pub struct Payment {
     pub event_id: String,
     pub buyer_address: Address,
     pub ticket_tier_id: String,
-    pub amount: i128, // USDC amount in stroops
+    pub token: Address, // token the ticket was paid in
+    pub amount: i128,   // USDC amount in stroops
     pub platform_fee: i128,
     pub organizer_amount: i128,
     pub status: PaymentStatus,
@@ -71,6 +64,26 @@ pub struct Payment {
     pub refunded_amount: i128,
 }
 
+/// Leaf of an archived event's payments Merkle tree.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedPayment {
+    pub payment_id: String,
+    pub buyer_address: Address,
+    pub ticket_tier_id: String,
+    pub amount: i128,
+    pub refunded_amount: i128,
+    pub status: PaymentStatus,
+}
+
+/// Merkle root over an event's payments, computed when the event is archived.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentsCommitment {
+    pub root: BytesN<32>,
+    pub payment_count: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EventBalance {
@@ -79,6 +92,240 @@ pub struct EventBalance {
     pub platform_fee: i128,
 }
 
+/// A treasury destination for settled platform fees with its share in basis points.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryWallet {
+    pub address: Address,
+    pub share_bps: u32,
+}
+
+/// Default length of the rolling withdrawal window (24 hours).
+pub const DEFAULT_WITHDRAWAL_WINDOW_SECS: u64 = 86_400;
+/// Default delay before an over-cap withdrawal can be executed (24 hours).
+pub const DEFAULT_WITHDRAWAL_QUEUE_DELAY_SECS: u64 = 86_400;
+
+/// A buyer's allowlist entry; the SHA-256 hash of its XDR encoding is a leaf of
+/// the organizer's allowlist Merkle tree.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowlistEntry {
+    pub buyer: Address,
+    pub max_quantity: u32,
+}
+
+/// Optional extras a buyer can attach to `process_payment`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurchaseOptions {
+    /// Redeemable loyalty points to spend as a price reduction (0 = none). Only
+    /// honoured on events whose organizer enabled point redemption; points beyond
+    /// the event's discount cap are left unspent.
+    pub redeem_points: u64,
+    /// Total tickets the buyer's allowlist entry allows (invite-only tiers only).
+    pub allowlist_max_quantity: u32,
+    /// Sibling hashes from the buyer's allowlist leaf up to the root.
+    pub allowlist_proof: Vec<BytesN<32>>,
+    /// Store credit with the event's organizer to pay with (0 = none). Credit is
+    /// held in the refund token and only covers purchases made in it.
+    pub credit: i128,
+    /// Donation added on top of the tickets (0 = none), paid to the recipient of
+    /// the event's `DonationConfig`. Donations are paid out at once and not refunded.
+    pub donation: i128,
+    /// Add-ons to buy along with the tickets, attached to the first ticket of the
+    /// purchase and paid in the refund token.
+    pub add_ons: Vec<AddOnOrder>,
+}
+
+/// Add-on items requested in a purchase.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddOnOrder {
+    pub addon_id: String,
+    pub quantity: u32,
+}
+
+/// Add-on items bought against a ticket, with what was paid for them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddOnPurchase {
+    /// Items bought in total.
+    pub quantity: u32,
+    /// Items handed out by scanners.
+    pub redeemed: u32,
+    /// Items refunded with the ticket.
+    pub refunded: u32,
+    /// Total paid for the items, platform fee included.
+    pub amount: i128,
+    /// Platform fee taken from `amount`.
+    pub platform_fee: i128,
+}
+
+/// Deposit escrowed for an RSVP to a free tier, returned at check-in.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeldDeposit {
+    pub amount: i128,
+    /// Where the deposit goes if unclaimed after the event, fixed at RSVP time.
+    pub forfeit_to: Address,
+}
+
+/// Installments of a ticket bought on its tier's plan, with the terms fixed at purchase.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentSchedule {
+    /// Full ticket price across all installments.
+    pub total: i128,
+    /// Amount paid so far.
+    pub paid: i128,
+    pub installments: u32,
+    pub installments_paid: u32,
+    /// Deadlines of the installments after the first.
+    pub due_dates: Vec<u64>,
+    /// Share of the amount paid the organizer keeps on forfeiture.
+    pub penalty_bps: u32,
+    /// Platform fee applied to every installment.
+    pub fee_bps: u32,
+}
+
+/// Lifecycle of a group order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GroupOrderStatus {
+    Open,
+    Filled,
+    Refunded,
+}
+
+/// Tickets of one tier held for a group until a deadline, each paid for by its
+/// own participant.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupOrder {
+    pub order_id: String,
+    pub initiator: Address,
+    pub event_id: String,
+    pub tier_id: String,
+    /// Tickets held for the group.
+    pub tickets: u32,
+    /// Share each participant pays for their ticket.
+    pub price: i128,
+    /// Platform fee fixed when the order was created.
+    pub fee_bps: u32,
+    /// Time by which the group must fill, after which shares are refunded.
+    pub deadline: u64,
+    /// Participants who paid their share, with the payment ID of their ticket.
+    pub participants: Map<Address, String>,
+    pub status: GroupOrderStatus,
+    pub created_at: u64,
+}
+
+/// Where an event's donations go and whether they carry the platform fee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationConfig {
+    /// Organizer, charity or any other address receiving donations.
+    pub recipient: Address,
+    /// Whether the event's platform fee is taken from each donation.
+    pub apply_platform_fee: bool,
+}
+
+/// Terms an organizer offers guests who take a refund as store credit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditOffer {
+    /// Bonus on top of the refunded amount, funded from the organizer's share
+    /// (1000 = a voucher worth 110% of the refund).
+    pub bonus_bps: u32,
+    /// Seconds a voucher stays spendable after issue (0 = never expires).
+    pub validity_secs: u64,
+}
+
+/// Store credit a guest holds with one organizer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditBalance {
+    pub amount: i128,
+    /// Timestamp after which the credit can no longer be spent (0 = never).
+    pub expires_at: u64,
+}
+
+/// Rolling-window rate limit settings shared by platform and organizer withdrawals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalLimitConfig {
+    pub window_secs: u64,
+    pub queue_delay_secs: u64,
+}
+
+/// A single withdrawal counted against a rolling-window cap.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalRecord {
+    pub timestamp: u64,
+    pub amount: i128,
+}
+
+/// Default per-organizer withdrawal caps for a token (0 = unlimited).
+/// Verified (staked) organizers get `verified_cap` instead of `default_cap`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrganizerCapPolicy {
+    pub default_cap: i128,
+    pub verified_cap: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WithdrawalKind {
+    PlatformFees,
+    OrganizerRevenue,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QueuedWithdrawalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+/// A withdrawal that exceeded its rate limit and is held until `available_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedWithdrawal {
+    pub id: u64,
+    pub kind: WithdrawalKind,
+    pub event_id: Option<String>,
+    pub organizer: Option<Address>,
+    pub token: Address,
+    pub amount: i128,
+    pub queued_at: u64,
+    pub available_at: u64,
+    pub status: QueuedWithdrawalStatus,
+}
+
+/// A category of entrypoints that can be paused independently of the global switch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PauseScope {
+    Payments,
+    Transfers,
+    Refunds,
+    Auctions,
+    Withdrawals,
+    CheckIn,
+}
+
+/// Snapshot of every active circuit breaker.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseState {
+    pub global: bool,
+    pub scopes: Vec<PauseScope>,
+    pub event_ids: Vec<String>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct HighestBid {
@@ -101,38 +348,144 @@ pub enum DataKey {
     BuyerPaymentShard(Address, u32),
     /// Total number of payments for a buyer (Persistent)
     BuyerPaymentCount(Address),
-    Admin,                               // Contract administrator address
-    UsdcToken,                           // USDC token address
-    PlatformWallet,                      // Platform wallet address
-    EventRegistry,                       // Event Registry contract address
-    Initialized,                         // Initialization flag
-    TokenWhitelist(Address),             // token_address -> bool
-    Balances(String),                    // event_id -> EventBalance (escrow tracking)
-    TransferFee(String),                 // event_id -> transfer_fee_bps (u32)
-    BulkRefundIndex(String),             // event_id -> last processed payment index
-    PriceSwitched(String, String),       // (event_id, tier_id) -> bool
-    TotalVolumeProcessed,                // protocol-wide gross volume from all ticket sales
-    TotalFeesCollected(Address),         // cumulative platform fees collected by token
-    ActiveEscrowTotal,                   // protocol-wide active escrow across all tokens
-    ActiveEscrowByToken(Address),        // active escrow amount per token
-    DiscountCodeHash(BytesN<32>),        // sha256_hash -> bool (registered)
-    DiscountCodeUsed(BytesN<32>),        // sha256_hash -> bool (spent)
-    WithdrawalCap(Address),              // token_address -> max amount per day
-    DailyWithdrawalAmount(Address, u64), // (token_address, day_timestamp) -> amount withdrawn
-    IsPaused,                            // bool – global circuit breaker flag
-    DisputeStatus(String),               // event_id -> bool
-    PartialRefundIndex(String),          // event_id -> last processed payment index
-    PartialRefundPercentage(String),     // event_id -> active refund percentage in bps
-    OracleAddress,                       // Address of oracle contract
-    SlippageBps,                         // u32 — slippage tolerance in bps (default 200 = 2%)
-    HighestBid(String, String),          // (event_id, tier_id) -> HighestBid
-    AuctionClosed(String, String),       // (event_id, tier_id) -> bool
-    Governor(Address),                   // Address -> bool (is authorized governor)
-    TotalGovernors,                      // u32
-    Proposal(u64),                       // id -> ParameterProposal
-    ProposalCount,                       // u64
+    Admin,                              // Contract administrator address
+    UsdcToken,                          // USDC token address
+    PlatformWallet,                     // Platform wallet address
+    EventRegistry,                      // Event Registry contract address
+    Initialized,                        // Initialization flag
+    TokenWhitelist(Address),            // token_address -> bool
+    Balances(String),                   // event_id -> EventBalance (escrow tracking)
+    TransferFee(String),                // event_id -> transfer_fee_bps (u32)
+    BulkRefundIndex(String),            // event_id -> last processed payment index
+    PriceSwitched(String, String),      // (event_id, tier_id) -> bool
+    TotalVolumeProcessed,               // protocol-wide gross volume from all ticket sales
+    TotalFeesCollected(Address),        // cumulative platform fees collected by token
+    ActiveEscrowTotal,                  // protocol-wide active escrow across all tokens
+    ActiveEscrowByToken(Address),       // active escrow amount per token
+    DiscountCodeHash(BytesN<32>),       // sha256_hash -> bool (registered)
+    DiscountCodeUsed(BytesN<32>),       // sha256_hash -> bool (spent)
+    WithdrawalCap(Address),             // token_address -> max platform amount per window
+    WithdrawalWindow(Address, Address), // (subject, token) -> Vec<WithdrawalRecord>
+    IsPaused,                           // bool – global circuit breaker flag
+    PausedScopes,                       // Vec<PauseScope> – paused entrypoint categories
+    PausedEvents,                       // Vec<String> – event_ids with all activity paused
+    DisputeStatus(String),              // event_id -> bool
+    PartialRefundIndex(String),         // event_id -> last processed payment index
+    PartialRefundPercentage(String),    // event_id -> active refund percentage in bps
+    OracleAddress,                      // Address of oracle contract
+    SlippageBps,                        // u32 — slippage tolerance in bps (default 200 = 2%)
+    HighestBid(String, String),         // (event_id, tier_id) -> HighestBid
+    AuctionClosed(String, String),      // (event_id, tier_id) -> bool
     /// Status index for payments: (event_id, status) -> Vec<payment_id>
     EventPaymentStatus(String, PaymentStatus),
     /// Individual entry for status index: (event_id, status, payment_id) -> bool
     EventPaymentStatusEntry(String, PaymentStatus, String),
+    /// Cumulative revenue paid to a payee of an event: (event_id, payee) -> i128
+    PayeeEarnings(String, Address),
+    /// Treasury wallets receiving settled platform fees -> Vec<TreasuryWallet>
+    TreasuryWallets,
+    /// Platform fees released from event escrow but not yet paid out: token -> i128
+    ReleasedFees(Address),
+    /// Rolling window length and over-cap queue delay -> WithdrawalLimitConfig
+    WithdrawalLimitConfig,
+    /// Default organizer caps per token -> OrganizerCapPolicy
+    OrganizerCapPolicy(Address),
+    /// Per-organizer cap override: (organizer, token) -> i128
+    OrganizerWithdrawalCap(Address, Address),
+    /// Over-cap withdrawal held for later execution: id -> QueuedWithdrawal
+    QueuedWithdrawal(u64),
+    /// Number of withdrawals ever queued (u64)
+    QueuedWithdrawalCount,
+}
+
+/// Families of persistent entries that share a TTL policy
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TtlFamily {
+    Config,  // contract-wide settings, counters and TTL policies
+    Payment, // payment records and their index entries
+    Event,   // per-event balances, settings and payment indices
+}
+
+/// TTL thresholds in ledgers: entries below `threshold` are extended to `extend_to`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlPolicy {
+    pub threshold: u32,
+    pub extend_to: u32,
+}
+
+/// Storage keys for TTL management, kept apart from `DataKey` like `UpgradeKey`.
+#[contracttype]
+pub enum TtlKey {
+    Policy(TtlFamily), // TtlPolicy override for a family
+}
+
+/// Storage keys for allowlist allowances, kept apart from `DataKey` like `UpgradeKey`.
+#[contracttype]
+pub enum AllowlistKey {
+    Purchased(String, Option<String>, Address), // u32 tickets bought per (event, tier scope, buyer)
+}
+
+/// Storage keys for tier changes on existing tickets, kept apart from `DataKey` like `UpgradeKey`.
+#[contracttype]
+pub enum TierChangeKey {
+    DowngradeRefunds(String), // bool: refund the price difference on downgrades for an event
+    ExchangesEnabled(String), // bool: holders may exchange tickets for another event of the organizer
+}
+
+/// Storage keys for refund credit vouchers, kept apart from `DataKey` like `UpgradeKey`.
+#[contracttype]
+pub enum CreditKey {
+    Offer(Address),            // CreditOffer made by an organizer
+    Balance(Address, Address), // CreditBalance per (guest, organizer)
+}
+
+/// Storage keys for donations, kept apart from `DataKey` like `UpgradeKey`.
+#[contracttype]
+pub enum DonationKey {
+    Config(String), // DonationConfig for an event
+    Totals(String), // Map<Address, i128> gross donations per token for an event
+}
+
+/// Storage keys for add-on purchases, kept apart from `DataKey` like `UpgradeKey`.
+#[contracttype]
+pub enum AddOnKey {
+    Purchase(String, String), // AddOnPurchase per (payment_id, addon_id)
+    PaymentAddOns(String),    // Vec<String> add-on IDs bought against a payment
+    Redemptions(String),      // Map<String, u32> items redeemed per add-on for an event
+}
+
+/// Storage keys for RSVP deposits, kept apart from `DataKey` like `UpgradeKey`.
+#[contracttype]
+pub enum DepositKey {
+    Held(String),       // HeldDeposit per payment
+    SweepIndex(String), // u32 next event payment to sweep for unclaimed deposits
+}
+
+/// Storage keys for installment plans, kept apart from `DataKey` like `UpgradeKey`.
+#[contracttype]
+pub enum InstallmentKey {
+    Schedule(String), // InstallmentSchedule per payment
+}
+
+/// Storage keys for group orders, kept apart from `DataKey` like `UpgradeKey`.
+#[contracttype]
+pub enum GroupOrderKey {
+    Order(String),       // GroupOrder per order ID
+    EventOrders(String), // Vec<String> group order IDs for an event
+}
+
+/// Storage keys for upgrade and migration state, kept apart from `DataKey`
+/// so that enum stays within Soroban's variant limit.
+#[contracttype]
+pub enum UpgradeKey {
+    PendingUpgrade, // PendingUpgrade awaiting its timelock
+    SchemaVersion,  // u32 layout version of stored records
+}
+
+/// Storage keys for per-event fee accounting, kept apart from `DataKey`.
+#[contracttype]
+pub enum FeeKey {
+    EventFees(String), // Map<Address, i128> unsettled platform fees per token for an event
 }